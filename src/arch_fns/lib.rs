@@ -12,7 +12,21 @@
 extern crate lrs_cty_base as cty_base;
 
 #[cfg(not(no_libc))] #[path = "libc.rs"] mod imp;
-#[cfg(no_libc)] #[path = "no_libc.rs"] pub mod imp;
+#[cfg(no_libc)] #[path = "no_libc/mod.rs"] pub mod imp;
+
+/// Selects the implementations of the functions in this crate.
+///
+/// [argument, avx2]
+/// Whether the CPU and the kernel support AVX2.
+///
+/// = Remarks
+///
+/// This is called by the runtime before `main`. It must not be called once other
+/// threads have been created. Until it is called, only baseline instructions are used.
+/// If the functions of libc are used, this does nothing.
+pub unsafe fn select_kernels(avx2: bool) {
+    imp::select_kernels(avx2)
+}
 
 /// Returns the first occurrence of a byte in a byte slice if any.
 ///
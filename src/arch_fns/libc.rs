@@ -3,3 +3,5 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 pub use libc::{memchr, memrchr, memcmp, memset, strlen};
+
+pub unsafe fn select_kernels(_avx2: bool) { }
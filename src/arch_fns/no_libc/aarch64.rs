@@ -0,0 +1,142 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! NEON kernels.
+//!
+//! = Remarks
+//!
+//! NEON is part of the AArch64 baseline. The kernels operate on whole blocks of
+//! `block_size()` bytes. Block pointers passed to the search kernels are aligned to the
+//! block size.
+
+/// Returns the size of the blocks processed by the kernels.
+pub fn block_size() -> usize {
+    16
+}
+
+/// Returns the first block in `[p, end)` that contains `c` or null.
+pub unsafe fn find_byte(mut p: *const u8, end: *const u8, c: u8) -> *const u8 {
+    let mask: u64;
+    asm!("dup v0.16b, ${3:w}
+          1:
+          cmp $0, $2
+          b.eq 2f
+          ldr q1, [$0]
+          cmeq v1.16b, v1.16b, v0.16b
+          umaxp v1.16b, v1.16b, v1.16b
+          fmov $1, d1
+          cbnz $1, 3f
+          add $0, $0, #16
+          b 1b
+          2:
+          mov $1, xzr
+          3:"
+         : "+r"(p), "=&r"(mask)
+         : "r"(end), "r"(c as u32)
+         : "v0", "v1", "cc", "memory");
+    if mask != 0 { p } else { 0 as *const u8 }
+}
+
+/// Returns the last block in `[start, p)` that contains `c` or null.
+pub unsafe fn rfind_byte(start: *const u8, mut p: *const u8, c: u8) -> *const u8 {
+    let mask: u64;
+    asm!("dup v0.16b, ${3:w}
+          1:
+          cmp $0, $2
+          b.eq 2f
+          sub $0, $0, #16
+          ldr q1, [$0]
+          cmeq v1.16b, v1.16b, v0.16b
+          umaxp v1.16b, v1.16b, v1.16b
+          fmov $1, d1
+          cbnz $1, 3f
+          b 1b
+          2:
+          mov $1, xzr
+          3:"
+         : "+r"(p), "=&r"(mask)
+         : "r"(start), "r"(c as u32)
+         : "v0", "v1", "cc", "memory");
+    if mask != 0 { p } else { 0 as *const u8 }
+}
+
+/// Returns the first block starting at `p` that contains a null byte.
+pub unsafe fn find_zero(mut p: *const u8) -> *const u8 {
+    let _mask: u64;
+    asm!("1:
+          ldr q0, [$0]
+          cmeq v0.16b, v0.16b, #0
+          umaxp v0.16b, v0.16b, v0.16b
+          fmov $1, d0
+          cbnz $1, 2f
+          add $0, $0, #16
+          b 1b
+          2:"
+         : "+r"(p), "=&r"(_mask)
+         :
+         : "v0", "cc", "memory");
+    p
+}
+
+/// Returns the offset of the first block in which `a` and `b` differ or `n`.
+pub unsafe fn first_diff(a: *const u8, b: *const u8, n: usize) -> usize {
+    let off: usize;
+    let _mask: u64;
+    asm!("mov $0, xzr
+          1:
+          cmp $0, $4
+          b.eq 2f
+          ldr q0, [$2, $0]
+          ldr q1, [$3, $0]
+          eor v0.16b, v0.16b, v1.16b
+          umaxp v0.16b, v0.16b, v0.16b
+          fmov $1, d0
+          cbnz $1, 2f
+          add $0, $0, #16
+          b 1b
+          2:"
+         : "=&r"(off), "=&r"(_mask)
+         : "r"(a), "r"(b), "r"(n)
+         : "v0", "v1", "cc", "memory");
+    off
+}
+
+/// Copies `n` bytes from the front to the back.
+///
+/// = Remarks
+///
+/// Each block is read before it is written. This can be used for overlapping buffers
+/// if `dst` lies before `src`.
+pub unsafe fn copy(dst: *mut u8, src: *const u8, n: usize) {
+    let _off: usize;
+    asm!("mov $0, xzr
+          1:
+          cmp $0, $3
+          b.eq 2f
+          ldr q0, [$2, $0]
+          str q0, [$1, $0]
+          add $0, $0, #16
+          b 1b
+          2:"
+         : "=&r"(_off)
+         : "r"(dst), "r"(src), "r"(n)
+         : "v0", "cc", "memory");
+}
+
+/// Sets `n` bytes to `c`.
+pub unsafe fn set(dst: *mut u8, c: u8, n: usize) {
+    let _off: usize;
+    asm!("dup v0.16b, ${3:w}
+          mov $0, xzr
+          1:
+          cmp $0, $2
+          b.eq 2f
+          str q0, [$1, $0]
+          add $0, $0, #16
+          b 1b
+          2:"
+         : "=&r"(_off)
+         : "r"(dst), "r"(n), "r"(c as u32)
+         : "v0", "cc", "memory");
+}
@@ -0,0 +1,101 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Implementations that use the SIMD kernels for long buffers.
+//!
+//! = Remarks
+//!
+//! The unaligned head and the tail that doesn't fill a block are handled by the
+//! word-at-a-time implementations.
+
+use super::{word, simd};
+
+pub use super::word::{copy_backward};
+
+/// Buffers shorter than this are handled by the word-at-a-time implementations.
+const MIN: usize = 64;
+
+fn align_up(p: usize, b: usize) -> usize {
+    (p + b - 1) & !(b - 1)
+}
+
+pub unsafe fn memchr(s: *const u8, c: u8, n: usize) -> *const u8 {
+    if n < MIN {
+        return word::memchr(s, c, n);
+    }
+    let b = simd::block_size();
+    let head = align_up(s as usize, b) - s as usize;
+    let found = word::memchr(s, c, head);
+    if !found.is_null() {
+        return found;
+    }
+    let start = s.add(head);
+    let len = (n - head) & !(b - 1);
+    let end = start.add(len);
+    let block = simd::find_byte(start, end, c);
+    if !block.is_null() {
+        return word::memchr(block, c, b);
+    }
+    word::memchr(end, c, n - head - len)
+}
+
+pub unsafe fn memrchr(s: *const u8, c: u8, n: usize) -> *const u8 {
+    if n < MIN {
+        return word::memrchr(s, c, n);
+    }
+    let b = simd::block_size();
+    let end = s.add(n);
+    let tail = end as usize & (b - 1);
+    let found = word::memrchr(end.sub(tail), c, tail);
+    if !found.is_null() {
+        return found;
+    }
+    let end = end.sub(tail);
+    let len = (n - tail) & !(b - 1);
+    let start = end.sub(len);
+    let block = simd::rfind_byte(start, end, c);
+    if !block.is_null() {
+        return word::memrchr(block, c, b);
+    }
+    word::memrchr(s, c, n - tail - len)
+}
+
+pub unsafe fn memcmp(s1: *const u8, s2: *const u8, n: usize) -> i32 {
+    if n < MIN {
+        return word::memcmp(s1, s2, n);
+    }
+    let len = n & !(simd::block_size() - 1);
+    let off = simd::first_diff(s1, s2, len);
+    word::memcmp(s1.add(off), s2.add(off), n - off)
+}
+
+pub unsafe fn copy_forward(dst: *mut u8, src: *const u8, n: usize) {
+    if n < MIN {
+        return word::copy_forward(dst, src, n);
+    }
+    let len = n & !(simd::block_size() - 1);
+    simd::copy(dst, src, len);
+    word::copy_forward(dst.add(len), src.add(len), n - len);
+}
+
+pub unsafe fn memset(s: *mut u8, c: u8, n: usize) {
+    if n < MIN {
+        return word::memset(s, c, n);
+    }
+    let len = n & !(simd::block_size() - 1);
+    simd::set(s, c, len);
+    word::memset(s.add(len), c, n - len);
+}
+
+pub unsafe fn strlen(s: *const u8) -> usize {
+    let b = simd::block_size();
+    let head = align_up(s as usize, b) - s as usize;
+    for i in 0..head {
+        if *s.add(i) == 0 {
+            return i;
+        }
+    }
+    let block = simd::find_zero(s.add(head));
+    block as usize - s as usize + word::strlen(block)
+}
@@ -0,0 +1,131 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Memory functions for builds without libc.
+//!
+//! = Remarks
+//!
+//! The word-at-a-time implementations in `word` are the portable baseline. On x86_64
+//! and aarch64, long buffers are handled by SIMD kernels. On x86_64, the kernels are
+//! selected by the runtime depending on the features of the CPU.
+
+pub use cty_base::types::{c_int};
+
+mod word;
+
+#[cfg(target_arch = "x86_64")] #[path = "x86_64.rs"] mod simd;
+#[cfg(target_arch = "aarch64")] #[path = "aarch64.rs"] mod simd;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))] mod fast;
+
+#[cfg(target_arch = "x86_64")]
+pub unsafe fn select_kernels(avx2: bool) {
+    simd::set_avx2(avx2);
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub unsafe fn select_kernels(_avx2: bool) { }
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))] use self::word as fast;
+
+#[no_mangle]
+pub unsafe extern fn memchr(s: *const u8, c: c_int, n: usize) -> *const u8 {
+    fast::memchr(s, c as u8, n)
+}
+
+#[no_mangle]
+pub unsafe extern fn memrchr(s: *const u8, c: c_int, n: usize) -> *const u8 {
+    fast::memrchr(s, c as u8, n)
+}
+
+#[no_mangle]
+pub unsafe extern fn memcmp(s1: *const u8, s2: *const u8, n: usize) -> c_int {
+    fast::memcmp(s1, s2, n)
+}
+
+#[no_mangle]
+pub unsafe extern fn memcpy(dst: *mut u8, src: *const u8, n: usize) -> *const u8 {
+    fast::copy_forward(dst, src, n);
+    dst
+}
+
+#[no_mangle]
+pub unsafe extern fn memmove(dst: *mut u8, src: *const u8, n: usize) -> *const u8 {
+    let (d, s) = (dst as usize, src as usize);
+    if d <= s || d >= s + n {
+        fast::copy_forward(dst, src, n);
+    } else {
+        fast::copy_backward(dst, src, n);
+    }
+    dst
+}
+
+#[no_mangle]
+pub unsafe extern fn strlen(s: *const u8) -> usize {
+    fast::strlen(s)
+}
+
+#[no_mangle]
+pub unsafe extern fn memset(s: *mut u8, c: c_int, n: usize) -> *mut u8 {
+    fast::memset(s, c as u8, n);
+    s
+}
+
+#[cfg(target_arch = "arm")]
+pub mod arch {
+    use cty_base::types::{c_int};
+
+    macro_rules! memclr {
+        ($name:ident) => {
+            #[no_mangle]
+            pub unsafe extern fn $name(dst: *mut u8, n: usize) {
+                super::memset(dst, 0, n);
+            }
+        }
+    }
+    memclr!(__aeabi_memclr);
+    memclr!(__aeabi_memclr4);
+    memclr!(__aeabi_memclr8);
+
+
+    macro_rules! memcpy {
+        ($name:ident) => {
+            #[no_mangle]
+            pub unsafe extern fn $name(dst: *mut u8, src: *const u8, n: usize) {
+                super::memcpy(dst, src, n);
+            }
+        }
+    }
+    memcpy!(__aeabi_memcpy);
+    memcpy!(__aeabi_memcpy4);
+    memcpy!(__aeabi_memcpy8);
+
+
+    macro_rules! memmove {
+        ($name:ident) => {
+            #[no_mangle]
+            pub unsafe extern fn $name(dst: *mut u8, src: *const u8, n: usize) {
+                super::memmove(dst, src, n);
+            }
+        }
+    }
+    memmove!(__aeabi_memmove);
+    memmove!(__aeabi_memmove4);
+    memmove!(__aeabi_memmove8);
+
+
+    macro_rules! memset {
+        ($name:ident) => {
+            #[no_mangle]
+            pub unsafe extern fn $name(dst: *mut u8, n: usize, val: c_int) {
+                super::memset(dst, val, n);
+            }
+        }
+    }
+    memset!(__aeabi_memset);
+    memset!(__aeabi_memset4);
+    memset!(__aeabi_memset8);
+
+    #[no_mangle] pub fn __aeabi_unwind_cpp_pr1() { }
+    #[no_mangle] pub fn __aeabi_unwind_cpp_pr0() { }
+}
@@ -0,0 +1,261 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Portable word-at-a-time implementations.
+//!
+//! = Remarks
+//!
+//! Words are only read from aligned addresses. Aligned words never cross a page
+//! boundary, so reading a word that contains at least one byte of the buffer never
+//! faults even if the other bytes of the word lie outside of the buffer.
+
+/// The size of a word.
+#[cfg(target_pointer_width = "64")] pub const W: usize = 8;
+#[cfg(target_pointer_width = "32")] pub const W: usize = 4;
+
+/// A word with every byte set to `0x01`.
+const LO: usize = !0 / 255;
+
+/// A word with every byte set to `0x80`.
+const HI: usize = LO << 7;
+
+/// Returns whether any byte in the word is zero.
+fn has_zero(v: usize) -> bool {
+    v.wrapping_sub(LO) & !v & HI != 0
+}
+
+/// Returns a word with every byte set to `c`.
+pub fn splat(c: u8) -> usize {
+    LO * c as usize
+}
+
+fn is_aligned(p: usize) -> bool {
+    p & (W - 1) == 0
+}
+
+/// Combines the tail of `prev` and the head of `next` into one word.
+///
+/// `shift` is the offset of the first byte in `prev` in bits.
+#[cfg(target_endian = "little")]
+fn merge(prev: usize, next: usize, shift: usize) -> usize {
+    (prev >> shift) | (next << (W * 8 - shift))
+}
+
+#[cfg(target_endian = "big")]
+fn merge(prev: usize, next: usize, shift: usize) -> usize {
+    (prev << shift) | (next >> (W * 8 - shift))
+}
+
+pub unsafe fn memchr(mut s: *const u8, c: u8, mut n: usize) -> *const u8 {
+    while n > 0 && !is_aligned(s as usize) {
+        if *s == c {
+            return s;
+        }
+        s = s.add(1);
+        n -= 1;
+    }
+    let rep = splat(c);
+    while n >= W && !has_zero(*(s as *const usize) ^ rep) {
+        s = s.add(W);
+        n -= W;
+    }
+    while n > 0 {
+        if *s == c {
+            return s;
+        }
+        s = s.add(1);
+        n -= 1;
+    }
+    0 as *const u8
+}
+
+pub unsafe fn memrchr(s: *const u8, c: u8, mut n: usize) -> *const u8 {
+    let mut end = s.add(n);
+    while n > 0 && !is_aligned(end as usize) {
+        end = end.sub(1);
+        n -= 1;
+        if *end == c {
+            return end;
+        }
+    }
+    let rep = splat(c);
+    while n >= W && !has_zero(*(end.sub(W) as *const usize) ^ rep) {
+        end = end.sub(W);
+        n -= W;
+    }
+    while n > 0 {
+        end = end.sub(1);
+        n -= 1;
+        if *end == c {
+            return end;
+        }
+    }
+    0 as *const u8
+}
+
+pub unsafe fn memcmp(mut s1: *const u8, mut s2: *const u8, mut n: usize) -> i32 {
+    while n > 0 && !is_aligned(s1 as usize) {
+        if *s1 != *s2 {
+            return if *s1 < *s2 { -1 } else { 1 };
+        }
+        s1 = s1.add(1);
+        s2 = s2.add(1);
+        n -= 1;
+    }
+    if n >= W {
+        let off = s2 as usize & (W - 1);
+        let mut a = s1 as *const usize;
+        if off == 0 {
+            let mut b = s2 as *const usize;
+            while n >= W && *a == *b {
+                a = a.add(1);
+                b = b.add(1);
+                n -= W;
+            }
+        } else {
+            let shift = off * 8;
+            let mut b = (s2 as usize - off) as *const usize;
+            let mut prev = *b;
+            while n >= W {
+                let next = *b.add(1);
+                if *a != merge(prev, next, shift) {
+                    break;
+                }
+                prev = next;
+                a = a.add(1);
+                b = b.add(1);
+                n -= W;
+            }
+        }
+        let done = a as usize - s1 as usize;
+        s1 = s1.add(done);
+        s2 = s2.add(done);
+    }
+    while n > 0 {
+        if *s1 != *s2 {
+            return if *s1 < *s2 { -1 } else { 1 };
+        }
+        s1 = s1.add(1);
+        s2 = s2.add(1);
+        n -= 1;
+    }
+    0
+}
+
+/// Copies from the front to the back.
+///
+/// = Remarks
+///
+/// This can be used for overlapping buffers if `dst` lies before `src`.
+pub unsafe fn copy_forward(mut dst: *mut u8, mut src: *const u8, mut n: usize) {
+    if n >= 2 * W {
+        while !is_aligned(dst as usize) {
+            *dst = *src;
+            dst = dst.add(1);
+            src = src.add(1);
+            n -= 1;
+        }
+        let words = n / W;
+        let mut d = dst as *mut usize;
+        let off = src as usize & (W - 1);
+        if off == 0 {
+            let mut s = src as *const usize;
+            for _ in 0..words {
+                *d = *s;
+                d = d.add(1);
+                s = s.add(1);
+            }
+        } else {
+            // Read aligned words from the source and shift them into place. The next
+            // word is always read before the current one is written.
+            let shift = off * 8;
+            let mut s = (src as usize - off) as *const usize;
+            let mut prev = *s;
+            for _ in 0..words {
+                s = s.add(1);
+                let next = *s;
+                *d = merge(prev, next, shift);
+                prev = next;
+                d = d.add(1);
+            }
+        }
+        dst = dst.add(words * W);
+        src = src.add(words * W);
+        n -= words * W;
+    }
+    while n > 0 {
+        *dst = *src;
+        dst = dst.add(1);
+        src = src.add(1);
+        n -= 1;
+    }
+}
+
+/// Copies from the back to the front.
+///
+/// = Remarks
+///
+/// This can be used for overlapping buffers if `dst` lies after `src`.
+pub unsafe fn copy_backward(dst: *mut u8, src: *const u8, mut n: usize) {
+    let mut dst = dst.add(n);
+    let mut src = src.add(n);
+    if n >= 2 * W && (dst as usize ^ src as usize) & (W - 1) == 0 {
+        while !is_aligned(dst as usize) {
+            dst = dst.sub(1);
+            src = src.sub(1);
+            *dst = *src;
+            n -= 1;
+        }
+        while n >= W {
+            dst = dst.sub(W);
+            src = src.sub(W);
+            *(dst as *mut usize) = *(src as *const usize);
+            n -= W;
+        }
+    }
+    while n > 0 {
+        dst = dst.sub(1);
+        src = src.sub(1);
+        *dst = *src;
+        n -= 1;
+    }
+}
+
+pub unsafe fn memset(mut s: *mut u8, c: u8, mut n: usize) {
+    while n > 0 && !is_aligned(s as usize) {
+        *s = c;
+        s = s.add(1);
+        n -= 1;
+    }
+    let rep = splat(c);
+    while n >= W {
+        *(s as *mut usize) = rep;
+        s = s.add(W);
+        n -= W;
+    }
+    while n > 0 {
+        *s = c;
+        s = s.add(1);
+        n -= 1;
+    }
+}
+
+pub unsafe fn strlen(s: *const u8) -> usize {
+    let mut p = s;
+    while !is_aligned(p as usize) {
+        if *p == 0 {
+            return p as usize - s as usize;
+        }
+        p = p.add(1);
+    }
+    let mut w = p as *const usize;
+    while !has_zero(*w) {
+        w = w.add(1);
+    }
+    p = w as *const u8;
+    while *p != 0 {
+        p = p.add(1);
+    }
+    p as usize - s as usize
+}
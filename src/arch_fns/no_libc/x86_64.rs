@@ -0,0 +1,276 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! SSE2 and AVX2 kernels.
+//!
+//! = Remarks
+//!
+//! SSE2 is part of the x86_64 baseline. AVX2 is used once the runtime has found that
+//! the CPU and the kernel support it. The kernels operate on whole blocks of
+//! `block_size()` bytes. Block pointers passed to the search kernels are aligned to the
+//! block size.
+
+static mut AVX2: bool = false;
+
+/// Enables or disables the AVX2 kernels.
+///
+/// = Remarks
+///
+/// This must only be called before any other threads have been created.
+pub unsafe fn set_avx2(enabled: bool) {
+    AVX2 = enabled;
+}
+
+/// Returns whether AVX2 kernels are used.
+fn avx2() -> bool {
+    unsafe { AVX2 }
+}
+
+/// Returns the size of the blocks processed by the kernels.
+pub fn block_size() -> usize {
+    if avx2() { 32 } else { 16 }
+}
+
+/// Returns the first block in `[p, end)` that contains `c` or null.
+pub unsafe fn find_byte(mut p: *const u8, end: *const u8, c: u8) -> *const u8 {
+    let rep = c as u32 * 0x01010101;
+    let mask: u32;
+    if avx2() {
+        asm!("vmovd $3, %xmm0
+              vpbroadcastb %xmm0, %ymm0
+              1:
+              cmp $2, $0
+              je 2f
+              vpcmpeqb ($0), %ymm0, %ymm1
+              vpmovmskb %ymm1, $1
+              test $1, $1
+              jnz 3f
+              add $$32, $0
+              jmp 1b
+              2:
+              xor $1, $1
+              3:
+              vzeroupper"
+             : "+r"(p), "=&r"(mask)
+             : "r"(end), "r"(rep)
+             : "xmm0", "xmm1", "cc", "memory");
+    } else {
+        asm!("movd $3, %xmm0
+              pshufd $$0, %xmm0, %xmm0
+              1:
+              cmp $2, $0
+              je 2f
+              movdqa ($0), %xmm1
+              pcmpeqb %xmm0, %xmm1
+              pmovmskb %xmm1, $1
+              test $1, $1
+              jnz 3f
+              add $$16, $0
+              jmp 1b
+              2:
+              xor $1, $1
+              3:"
+             : "+r"(p), "=&r"(mask)
+             : "r"(end), "r"(rep)
+             : "xmm0", "xmm1", "cc", "memory");
+    }
+    if mask != 0 { p } else { 0 as *const u8 }
+}
+
+/// Returns the last block in `[start, p)` that contains `c` or null.
+pub unsafe fn rfind_byte(start: *const u8, mut p: *const u8, c: u8) -> *const u8 {
+    let rep = c as u32 * 0x01010101;
+    let mask: u32;
+    if avx2() {
+        asm!("vmovd $3, %xmm0
+              vpbroadcastb %xmm0, %ymm0
+              1:
+              cmp $2, $0
+              je 2f
+              sub $$32, $0
+              vpcmpeqb ($0), %ymm0, %ymm1
+              vpmovmskb %ymm1, $1
+              test $1, $1
+              jnz 3f
+              jmp 1b
+              2:
+              xor $1, $1
+              3:
+              vzeroupper"
+             : "+r"(p), "=&r"(mask)
+             : "r"(start), "r"(rep)
+             : "xmm0", "xmm1", "cc", "memory");
+    } else {
+        asm!("movd $3, %xmm0
+              pshufd $$0, %xmm0, %xmm0
+              1:
+              cmp $2, $0
+              je 2f
+              sub $$16, $0
+              movdqa ($0), %xmm1
+              pcmpeqb %xmm0, %xmm1
+              pmovmskb %xmm1, $1
+              test $1, $1
+              jnz 3f
+              jmp 1b
+              2:
+              xor $1, $1
+              3:"
+             : "+r"(p), "=&r"(mask)
+             : "r"(start), "r"(rep)
+             : "xmm0", "xmm1", "cc", "memory");
+    }
+    if mask != 0 { p } else { 0 as *const u8 }
+}
+
+/// Returns the first block starting at `p` that contains a null byte.
+pub unsafe fn find_zero(mut p: *const u8) -> *const u8 {
+    let _mask: u32;
+    if avx2() {
+        asm!("vpxor %xmm0, %xmm0, %xmm0
+              1:
+              vpcmpeqb ($0), %ymm0, %ymm1
+              vpmovmskb %ymm1, $1
+              test $1, $1
+              jnz 2f
+              add $$32, $0
+              jmp 1b
+              2:
+              vzeroupper"
+             : "+r"(p), "=&r"(_mask)
+             :
+             : "xmm0", "xmm1", "cc", "memory");
+    } else {
+        asm!("pxor %xmm0, %xmm0
+              1:
+              movdqa ($0), %xmm1
+              pcmpeqb %xmm0, %xmm1
+              pmovmskb %xmm1, $1
+              test $1, $1
+              jnz 2f
+              add $$16, $0
+              jmp 1b
+              2:"
+             : "+r"(p), "=&r"(_mask)
+             :
+             : "xmm0", "xmm1", "cc", "memory");
+    }
+    p
+}
+
+/// Returns the offset of the first block in which `a` and `b` differ or `n`.
+pub unsafe fn first_diff(a: *const u8, b: *const u8, n: usize) -> usize {
+    let off: usize;
+    let _mask: u32;
+    if avx2() {
+        asm!("xor $0, $0
+              1:
+              cmp $4, $0
+              je 2f
+              vmovdqu ($2,$0), %ymm0
+              vpcmpeqb ($3,$0), %ymm0, %ymm0
+              vpmovmskb %ymm0, $1
+              cmp $$-1, $1
+              jne 2f
+              add $$32, $0
+              jmp 1b
+              2:
+              vzeroupper"
+             : "=&r"(off), "=&r"(_mask)
+             : "r"(a), "r"(b), "r"(n)
+             : "xmm0", "cc", "memory");
+    } else {
+        asm!("xor $0, $0
+              1:
+              cmp $4, $0
+              je 2f
+              movdqu ($2,$0), %xmm0
+              movdqu ($3,$0), %xmm1
+              pcmpeqb %xmm1, %xmm0
+              pmovmskb %xmm0, $1
+              cmp $$0xffff, $1
+              jne 2f
+              add $$16, $0
+              jmp 1b
+              2:"
+             : "=&r"(off), "=&r"(_mask)
+             : "r"(a), "r"(b), "r"(n)
+             : "xmm0", "xmm1", "cc", "memory");
+    }
+    off
+}
+
+/// Copies `n` bytes from the front to the back.
+///
+/// = Remarks
+///
+/// Each block is read before it is written. This can be used for overlapping buffers
+/// if `dst` lies before `src`.
+pub unsafe fn copy(dst: *mut u8, src: *const u8, n: usize) {
+    let _off: usize;
+    if avx2() {
+        asm!("xor $0, $0
+              1:
+              cmp $3, $0
+              je 2f
+              vmovdqu ($2,$0), %ymm0
+              vmovdqu %ymm0, ($1,$0)
+              add $$32, $0
+              jmp 1b
+              2:
+              vzeroupper"
+             : "=&r"(_off)
+             : "r"(dst), "r"(src), "r"(n)
+             : "xmm0", "cc", "memory");
+    } else {
+        asm!("xor $0, $0
+              1:
+              cmp $3, $0
+              je 2f
+              movdqu ($2,$0), %xmm0
+              movdqu %xmm0, ($1,$0)
+              add $$16, $0
+              jmp 1b
+              2:"
+             : "=&r"(_off)
+             : "r"(dst), "r"(src), "r"(n)
+             : "xmm0", "cc", "memory");
+    }
+}
+
+/// Sets `n` bytes to `c`.
+pub unsafe fn set(dst: *mut u8, c: u8, n: usize) {
+    let rep = c as u32 * 0x01010101;
+    let _off: usize;
+    if avx2() {
+        asm!("vmovd $3, %xmm0
+              vpbroadcastb %xmm0, %ymm0
+              xor $0, $0
+              1:
+              cmp $2, $0
+              je 2f
+              vmovdqu %ymm0, ($1,$0)
+              add $$32, $0
+              jmp 1b
+              2:
+              vzeroupper"
+             : "=&r"(_off)
+             : "r"(dst), "r"(n), "r"(rep)
+             : "xmm0", "cc", "memory");
+    } else {
+        asm!("movd $3, %xmm0
+              pshufd $$0, %xmm0, %xmm0
+              xor $0, $0
+              1:
+              cmp $2, $0
+              je 2f
+              movdqu %xmm0, ($1,$0)
+              add $$16, $0
+              jmp 1b
+              2:"
+             : "=&r"(_off)
+             : "r"(dst), "r"(n), "r"(rep)
+             : "xmm0", "cc", "memory");
+    }
+}
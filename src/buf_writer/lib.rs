@@ -0,0 +1,442 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+#![crate_name = "lrs_buf_writer"]
+#![crate_type = "lib"]
+#![no_std]
+
+extern crate lrs_arch_fns as arch_fns;
+extern crate lrs_base as base;
+extern crate lrs_io as io;
+extern crate lrs_alloc as alloc;
+#[cfg(not(freestanding))] extern crate lrs_fd as fd;
+#[cfg(not(freestanding))] extern crate lrs_tty as tty;
+#[cfg(not(freestanding))] extern crate lrs_lock as lock;
+
+use base::prelude::*;
+use core::{slice, ptr, mem, cmp};
+use core::ptr::{NoAliasMemPtr};
+use base::{error};
+use alloc::{MemPool};
+use io::{Read, Write, BufWrite};
+use arch_fns::{memrchr};
+
+pub mod std { pub use base::std::*; }
+
+#[cfg(not(freestanding))] pub mod stdio;
+
+/// The maximum number of slices passed to one `gather_write` of the wrapped writer.
+const MAX_SLICES: usize = 16;
+
+/// A buffered writer.
+///
+/// = Remarks
+///
+/// The buffer is flushed when it is full, when `flush` or `into_inner` is called, and
+/// when the writer is dropped. If a write does not fit into the buffer, the buffered
+/// bytes and the new bytes are passed to the wrapped writer in a single `gather_write`.
+/// Errors that occur while flushing in the destructor are ignored. Use `into_inner` or
+/// `flush` to observe them.
+pub struct BufWriter<W, Heap = alloc::Heap>
+    where W: Write,
+          Heap: MemPool,
+{
+    data: NoAliasMemPtr<u8>,
+    cap: usize,
+    len: usize,
+    write: W,
+    pool: Heap,
+}
+
+impl<W, H = alloc::Heap> BufWriter<W, H>
+    where W: Write,
+          H: MemPool,
+{
+    /// Allocates a new buffered writer.
+    ///
+    /// [argument, write]
+    /// The writer that will be wrapped in the buffered writer.
+    ///
+    /// [argument, size]
+    /// The buffer-size of the buffered writer.
+    pub fn new(write: W, size: usize) -> Result<Self>
+        where H: OutOf,
+    {
+        Self::with_pool(write, size, H::out_of(()))
+    }
+
+    /// Allocates a new buffered writer.
+    ///
+    /// [argument, write]
+    /// The writer that will be wrapped in the buffered writer.
+    ///
+    /// [argument, size]
+    /// The buffer-size of the buffered writer.
+    ///
+    /// [argument, pool]
+    /// The pool from which the buffer will be allocated.
+    pub fn with_pool(write: W, size: usize, mut pool: H) -> Result<Self> {
+        let size = cmp::max(size, 1);
+        let ptr = unsafe { try!(alloc::alloc_array(&mut pool, size)).0 };
+        let ptr = unsafe { NoAliasMemPtr::new(ptr) };
+        Ok(BufWriter {
+            data: ptr,
+            cap: size,
+            len: 0,
+            write: write,
+            pool: pool,
+        })
+    }
+
+    /// Returns the number of currently buffered bytes.
+    pub fn buffered(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the total buffer capacity.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Returns a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        &self.write
+    }
+
+    /// Returns a mutable reference to the wrapped writer.
+    ///
+    /// = Remarks
+    ///
+    /// Writing directly to the wrapped writer bypasses the buffer and can reorder the
+    /// output.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.write
+    }
+
+    /// Writes all buffered bytes to the wrapped writer.
+    ///
+    /// = Remarks
+    ///
+    /// If an error occurs, the bytes that have not been written remain in the buffer.
+    pub fn flush(&mut self) -> Result {
+        let buf = unsafe { slice::from_ptr(self.data.get(), self.len) };
+        let mut pos = 0;
+        let mut res = Ok(());
+        while pos < buf.len() {
+            match self.write.write(&buf[pos..]) {
+                Ok(0) => { res = Err(error::DeviceFull); break; },
+                Ok(n) => pos += n,
+                Err(e) => { res = Err(e); break; },
+            }
+        }
+        if pos > 0 {
+            unsafe {
+                let data = self.data.get();
+                ptr::memmove(data, data.add(pos), self.len - pos);
+            }
+            self.len -= pos;
+        }
+        res
+    }
+
+    /// Flushes the buffer and returns the wrapped writer.
+    ///
+    /// [return_value]
+    /// Returns the wrapped writer or the wrapped writer and the error that occurred
+    /// while flushing.
+    ///
+    /// = Remarks
+    ///
+    /// If an error occurs, the remaining buffered bytes are lost.
+    pub fn into_inner(mut self) -> Result<W, (W, error::Errno)> {
+        let res = self.flush();
+        unsafe {
+            alloc::free_array(&mut self.pool, self.data.get(), self.cap);
+            let write = ptr::read(&self.write);
+            drop(ptr::read(&self.pool));
+            mem::unsafe_forget(self);
+            match res {
+                Ok(()) => Ok(write),
+                Err(e) => Err((write, e)),
+            }
+        }
+    }
+
+    fn unused(&mut self) -> &mut [u8] {
+        unsafe { slice::from_ptr(self.data.get().add(self.len), self.cap - self.len) }
+    }
+
+    fn push(&mut self, buf: &[u8]) -> usize {
+        let n = mem::copy(self.unused(), buf);
+        self.len += n;
+        n
+    }
+
+    /// Writes the buffered bytes followed by `bufs` with a single call to the wrapped
+    /// writer.
+    ///
+    /// Returns the number of bytes of `bufs` that have been consumed. Bytes that have not
+    /// been written are buffered as far as possible.
+    fn write_through(&mut self, bufs: &[&[u8]]) -> Result<usize> {
+        let n = {
+            let mut all: [&[u8]; MAX_SLICES] = [&[]; MAX_SLICES];
+            all[0] = unsafe { slice::from_ptr(self.data.get(), self.len) };
+            for i in 0..bufs.len() {
+                all[i + 1] = bufs[i];
+            }
+            try!(self.write.gather_write(&all[..bufs.len() + 1]))
+        };
+        if n == 0 {
+            return Err(error::DeviceFull);
+        }
+        let mut skip = 0;
+        if n < self.len {
+            unsafe {
+                let data = self.data.get();
+                ptr::memmove(data, data.add(n), self.len - n);
+            }
+            self.len -= n;
+        } else {
+            skip = n - self.len;
+            self.len = 0;
+        }
+        let mut total = skip;
+        for buf in bufs {
+            if skip >= buf.len() {
+                skip -= buf.len();
+                continue;
+            }
+            let rest = &buf[skip..];
+            skip = 0;
+            let pushed = self.push(rest);
+            total += pushed;
+            if pushed < rest.len() {
+                break;
+            }
+        }
+        Ok(total)
+    }
+}
+
+impl<W, H> Write for BufWriter<W, H>
+    where W: Write,
+          H: MemPool,
+{
+    fn gather_write(&mut self, bufs: &[&[u8]]) -> Result<usize> {
+        let mut total = 0;
+        for buf in bufs {
+            total += buf.len();
+        }
+        if total <= self.cap - self.len {
+            for buf in bufs {
+                self.push(buf);
+            }
+            return Ok(total);
+        }
+        if bufs.len() < MAX_SLICES {
+            return self.write_through(bufs);
+        }
+        try!(self.flush());
+        if total >= self.cap {
+            return self.write.gather_write(bufs);
+        }
+        for buf in bufs {
+            self.push(buf);
+        }
+        Ok(total)
+    }
+
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if buf.len() <= self.cap - self.len {
+            return Ok(self.push(buf));
+        }
+        self.write_through(&[buf])
+    }
+}
+
+impl<W, H> BufWrite for BufWriter<W, H>
+    where W: Write,
+          H: MemPool,
+{
+    fn read_to_eof<R>(&mut self, mut r: R) -> Result<usize>
+        where R: Read,
+    {
+        let mut sum = 0;
+        loop {
+            if self.len == self.cap {
+                try!(self.flush());
+            }
+            let n = try!(r.read(self.unused().as_mut()));
+            if n == 0 {
+                return Ok(sum);
+            }
+            self.len += n;
+            sum += n;
+        }
+    }
+
+    fn read<R>(&mut self, mut r: R, n: usize) -> Result<usize>
+        where R: Read,
+    {
+        if self.len == self.cap {
+            try!(self.flush());
+        }
+        let n = cmp::min(n, self.cap - self.len);
+        let n = try!(r.read(self.unused()[..n].as_mut()));
+        self.len += n;
+        Ok(n)
+    }
+}
+
+unsafe impl<W, H> Send for BufWriter<W, H> where W: Write+Send, H: MemPool+Send { }
+
+impl<W, H> Drop for BufWriter<W, H>
+    where W: Write,
+          H: MemPool,
+{
+    fn drop(&mut self) {
+        let _ = self.flush();
+        unsafe { alloc::free_array(&mut self.pool, self.data.get(), self.cap); }
+    }
+}
+
+/// A buffered writer that flushes after every newline.
+///
+/// = Remarks
+///
+/// This is the usual mode for writers connected to a terminal. Line buffering can be
+/// turned off with `set_line_buffered`, in which case the writer behaves like a
+/// `BufWriter`.
+pub struct LineWriter<W, Heap = alloc::Heap>
+    where W: Write,
+          Heap: MemPool,
+{
+    buf: BufWriter<W, Heap>,
+    line: bool,
+}
+
+impl<W, H = alloc::Heap> LineWriter<W, H>
+    where W: Write,
+          H: MemPool,
+{
+    /// Allocates a new line writer.
+    ///
+    /// [argument, write]
+    /// The writer that will be wrapped in the line writer.
+    ///
+    /// [argument, size]
+    /// The buffer-size of the line writer.
+    pub fn new(write: W, size: usize) -> Result<Self>
+        where H: OutOf,
+    {
+        Self::with_pool(write, size, H::out_of(()))
+    }
+
+    /// Allocates a new line writer.
+    ///
+    /// [argument, write]
+    /// The writer that will be wrapped in the line writer.
+    ///
+    /// [argument, size]
+    /// The buffer-size of the line writer.
+    ///
+    /// [argument, pool]
+    /// The pool from which the buffer will be allocated.
+    pub fn with_pool(write: W, size: usize, pool: H) -> Result<Self> {
+        Ok(LineWriter {
+            buf: try!(BufWriter::with_pool(write, size, pool)),
+            line: true,
+        })
+    }
+
+    /// Returns whether the writer flushes after every newline.
+    pub fn is_line_buffered(&self) -> bool {
+        self.line
+    }
+
+    /// Enables or disables line buffering.
+    ///
+    /// [argument, line]
+    /// Whether the writer flushes after every newline.
+    pub fn set_line_buffered(&mut self, line: bool) {
+        self.line = line;
+    }
+
+    /// Returns a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        self.buf.get_ref()
+    }
+
+    /// Returns a mutable reference to the wrapped writer.
+    ///
+    /// = Remarks
+    ///
+    /// Writing directly to the wrapped writer bypasses the buffer and can reorder the
+    /// output.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.buf.get_mut()
+    }
+
+    /// Writes all buffered bytes to the wrapped writer.
+    pub fn flush(&mut self) -> Result {
+        self.buf.flush()
+    }
+
+    /// Flushes the buffer and returns the wrapped writer.
+    ///
+    /// [return_value]
+    /// Returns the wrapped writer or the wrapped writer and the error that occurred
+    /// while flushing.
+    pub fn into_inner(self) -> Result<W, (W, error::Errno)> {
+        self.buf.into_inner()
+    }
+}
+
+impl<W, H> Write for LineWriter<W, H>
+    where W: Write,
+          H: MemPool,
+{
+    fn gather_write(&mut self, bufs: &[&[u8]]) -> Result<usize> {
+        if !self.line {
+            return self.buf.gather_write(bufs);
+        }
+        let mut sum = 0;
+        for buf in bufs {
+            match self.write(buf) {
+                Ok(n) => {
+                    sum += n;
+                    if n < buf.len() {
+                        break;
+                    }
+                },
+                Err(e) => {
+                    if sum == 0 {
+                        return Err(e);
+                    }
+                    break;
+                },
+            }
+        }
+        Ok(sum)
+    }
+
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if !self.line {
+            return self.buf.write(buf);
+        }
+        let pos = match memrchr(buf, b'\n') {
+            Some(pos) => pos + 1,
+            _ => return self.buf.write(buf),
+        };
+        let n = try!(self.buf.write(&buf[..pos]));
+        if n < pos || self.buf.flush().is_err() {
+            // The error will be reported by the next flush.
+            return Ok(n);
+        }
+        match self.buf.write(&buf[pos..]) {
+            Ok(m) => Ok(n + m),
+            _ => Ok(n),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use io::{Write};
+use fd::{FdIo, STDOUT, STDERR};
+use tty::{is_a_tty};
+use lock::{Mutex, MutexGuard};
+use {LineWriter};
+
+/// The buffer-size of the standard output handles.
+pub const BUF_SIZE: usize = 4096;
+
+type Shared = Mutex<Option<LineWriter<FdIo>>>;
+
+static STDOUT_BUF: Shared = Mutex::new(None);
+static STDERR_BUF: Shared = Mutex::new(None);
+
+/// A handle to a buffered standard output stream.
+///
+/// = Remarks
+///
+/// All handles to the same stream share one buffer that is protected by a lock. The
+/// buffer is flushed when a handle is dropped.
+pub struct StdioWriter {
+    shared: &'static Shared,
+}
+
+/// A buffered handle to the standard output.
+pub type Stdout = StdioWriter;
+
+/// A buffered handle to the standard error output.
+pub type Stderr = StdioWriter;
+
+impl StdioWriter {
+    fn init(shared: &'static Shared, fd: FdIo, line: bool) -> Result<StdioWriter> {
+        let mut guard = shared.lock();
+        if guard.is_none() {
+            let mut writer = try!(LineWriter::new(fd, BUF_SIZE));
+            writer.set_line_buffered(line);
+            *guard = Some(writer);
+        }
+        Ok(StdioWriter { shared: shared })
+    }
+
+    /// Locks the stream.
+    ///
+    /// [return_value]
+    /// Returns a guard through which the shared buffer can be accessed.
+    ///
+    /// = Remarks
+    ///
+    /// Writes through other handles of the stream block until the guard is dropped.
+    /// This can be used to write several pieces of output without other output in
+    /// between.
+    pub fn lock<'a>(&'a self) -> StdioGuard<'a> {
+        StdioGuard { guard: self.shared.lock() }
+    }
+
+    /// Writes all buffered bytes of the stream.
+    pub fn flush(&self) -> Result {
+        self.lock().flush()
+    }
+}
+
+impl Write for StdioWriter {
+    fn gather_write(&mut self, bufs: &[&[u8]]) -> Result<usize> {
+        self.lock().gather_write(bufs)
+    }
+}
+
+impl Drop for StdioWriter {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// A locked standard output stream.
+pub struct StdioGuard<'a> {
+    guard: MutexGuard<'a, Option<LineWriter<FdIo>>>,
+}
+
+impl<'a> Deref for StdioGuard<'a> {
+    type Target = LineWriter<FdIo>;
+    fn deref(&self) -> &LineWriter<FdIo> {
+        match *self.guard {
+            Some(ref writer) => writer,
+            // Handles are only created after the writer has been stored.
+            None => abort!(),
+        }
+    }
+}
+
+impl<'a> DerefMut for StdioGuard<'a> {
+    fn deref_mut(&mut self) -> &mut LineWriter<FdIo> {
+        match *self.guard {
+            Some(ref mut writer) => writer,
+            None => abort!(),
+        }
+    }
+}
+
+impl<'a> Write for StdioGuard<'a> {
+    fn gather_write(&mut self, bufs: &[&[u8]]) -> Result<usize> {
+        (**self).gather_write(bufs)
+    }
+}
+
+/// Returns a buffered handle to the standard output.
+///
+/// = Remarks
+///
+/// The stream is line buffered if the standard output refers to a terminal and fully
+/// buffered otherwise. The buffer is allocated by the first call. All handles share
+/// this buffer, so output written through different handles is not reordered.
+///
+/// = Examples
+///
+/// ----
+/// let mut out = stdio::stdout().unwrap();
+/// out.write_all(b"Hello World\n").unwrap();
+/// ----
+pub fn stdout() -> Result<Stdout> {
+    StdioWriter::init(&STDOUT_BUF, STDOUT, is_a_tty(&STDOUT))
+}
+
+/// Returns a buffered handle to the standard error output.
+///
+/// = Remarks
+///
+/// The stream is always line buffered so that diagnostics appear in a timely manner.
+/// All handles share one buffer.
+pub fn stderr() -> Result<Stderr> {
+    StdioWriter::init(&STDERR_BUF, STDERR, true)
+}
@@ -12,10 +12,13 @@ extern crate lrs_cty as cty;
 extern crate lrs_syscall as syscall;
 extern crate lrs_fmt as fmt;
 extern crate lrs_r_syscall as r_syscall;
+extern crate lrs_rv as rv;
 #[cfg(not(no_libc))] extern crate lrs_libc as libc;
 
 use base::prelude::*;
+use cty::{c_int};
 use cty::alias::{ProcessId};
+use syscall::{exit_group};
 
 mod std { pub use fmt::std::*; }
 
@@ -67,3 +70,61 @@ pub fn fork<F>(f: F) -> Result<ProcessId>
 pub fn fork_continue() -> Result<Option<ProcessId>> {
     imp::fork_continue()
 }
+
+/// Forks the process and executes a function in the child process.
+///
+/// [argument, f]
+/// The function that will be executed in the child process.
+///
+/// [return_value]
+/// Returns the process id of the child process and a process file descriptor
+/// referring to it.
+///
+/// = Remarks
+///
+/// The function `f` will only be executed in the child process. When the function
+/// returns, the child process automatically exits with exit code `0`.
+///
+/// The file descriptor has the close-on-exec flag set.
+///
+/// If lrs is linked against a libc, the descriptor is opened after the fork. If the
+/// child is reaped before this happens, e.g., because SIGCHLD is ignored or because
+/// another thread waits for any child, the descriptor can refer to an unrelated
+/// process.
+///
+/// = See also
+///
+/// * link:man:clone3(2) and CLONE_PIDFD therein
+pub fn fork_pidfd<F>(f: F) -> Result<(ProcessId, c_int)>
+    where F: FnOnce()
+{
+    match try!(imp::fork_pidfd_continue()) {
+        Some(child) => Ok(child),
+        None => {
+            f();
+            exit_group(0);
+        },
+    }
+}
+
+/// Forks the process and creates a process file descriptor for the child.
+///
+/// [return_value]
+/// Returns the process id of the child process and a process file descriptor
+/// referring to it or `None` if we're in the child process.
+///
+/// = Remarks
+///
+/// The file descriptor has the close-on-exec flag set.
+///
+/// If lrs is linked against a libc, the descriptor is opened after the fork. If the
+/// child is reaped before this happens, e.g., because SIGCHLD is ignored or because
+/// another thread waits for any child, the descriptor can refer to an unrelated
+/// process.
+///
+/// = See also
+///
+/// * link:man:clone3(2) and CLONE_PIDFD therein
+pub fn fork_pidfd_continue() -> Result<Option<(ProcessId, c_int)>> {
+    imp::fork_pidfd_continue()
+}
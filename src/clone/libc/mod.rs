@@ -5,8 +5,11 @@
 use {libc};
 use base::prelude::*;
 use base::{error};
+use core::{mem};
 use cty::alias::{ProcessId};
-use syscall::{exit_group};
+use cty::{c_int, P_PID, WEXITED, SIGKILL};
+use syscall::{exit_group, pidfd_open, kill, waitid};
+use rv::{retry};
 
 pub fn fork<F>(f: F) -> Result<ProcessId>
     where F: FnOnce()
@@ -34,3 +37,30 @@ pub fn fork_continue() -> Result<Option<ProcessId>> {
         n => Ok(Some(n)),
     }
 }
+
+pub fn fork_pidfd_continue() -> Result<Option<(ProcessId, c_int)>> {
+    // A raw clone3 would bypass the fork handlers of the libc, so the descriptor is
+    // opened after the fork. This races with anything that can reap the child before
+    // pidfd_open runs: if SIGCHLD is set to SIG_IGN, the kernel reaps the child as soon
+    // as it exits, and another thread waiting for any child (waitpid(-1) or P_ALL)
+    // can reap it as well. Once the child has been reaped, its process id can be
+    // reused and the descriptor may refer to an unrelated process.
+    let pid = match try!(fork_continue()) {
+        Some(pid) => pid,
+        None => return Ok(None),
+    };
+    match pidfd_open(pid, 0) {
+        fd if fd >= 0 => Ok(Some((pid, fd))),
+        e => {
+            let err = error::Errno(-e);
+            // If the child has already been reaped, its process id might have been
+            // reused and must not be signaled.
+            if err != error::NoSuchProcess {
+                kill(pid, SIGKILL);
+                let mut info = mem::zeroed();
+                let _ = retry(|| waitid(P_PID, pid, &mut info, WEXITED, None));
+            }
+            Err(err)
+        },
+    }
+}
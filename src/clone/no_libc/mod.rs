@@ -4,6 +4,7 @@
 
 use base::prelude::*;
 use base::{error};
+use core::{mem};
 use {r_syscall, cty};
 use cty::alias::{ProcessId};
 use syscall::{exit_group};
@@ -36,3 +37,20 @@ pub fn fork_continue() -> Result<Option<ProcessId>> {
         n => Ok(Some(n as ProcessId)),
     }
 }
+
+pub fn fork_pidfd_continue() -> Result<Option<(ProcessId, cty::c_int)>> {
+    let mut pidfd: cty::c_int = -1;
+    let pidfd_ptr: *mut cty::c_int = &mut pidfd;
+    let mut args: cty::clone_args = mem::zeroed();
+    args.flags = cty::CLONE_PIDFD as u64;
+    args.pidfd = pidfd_ptr as usize as u64;
+    args.exit_signal = cty::SIGCHLD as u64;
+    let rv = unsafe {
+        r_syscall::clone3(&mut args, mem::size_of::<cty::clone_args>() as cty::size_t)
+    };
+    match rv {
+        e if e < 0 => Err(error::Errno(-e as cty::c_int)),
+        0 => Ok(None),
+        n => Ok(Some((n as ProcessId, pidfd))),
+    }
+}
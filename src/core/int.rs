@@ -9,7 +9,7 @@ use ops::{
 };
 use marker::{Pod};
 use cmp::{Ord};
-use iter::{Iterator, IntoIterator};
+use iter::{Iterator, DoubleEndedIterator, IntoIterator};
 use option::{Option};
 use option::Option::{Some, None};
 use intrinsics::{self};
@@ -718,6 +718,17 @@ macro_rules! int_impls {
             }
         }
 
+        impl DoubleEndedIterator for Range<$t> {
+            fn next_back(&mut self) -> Option<$t> {
+                if self.start < self.end {
+                    self.end -= 1;
+                    Some(self.end)
+                } else {
+                    None
+                }
+            }
+        }
+
         impl Iterator for RangeFrom<$t> {
             type Item = $t;
             fn next(&mut self) -> Option<$t> {
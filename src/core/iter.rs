@@ -84,6 +84,23 @@ impl<'a, T: Iterator+?Sized> Iterator for &'a mut T {
     fn next(&mut self) -> Option<T::Item> { (**self).next() }
 }
 
+/// Iterators that can also yield values from the back.
+pub trait DoubleEndedIterator: Iterator {
+    /// Yields the next value from the back.
+    ///
+    /// [return_value]
+    /// Returns the next value from the back or `None` if no more values are available.
+    ///
+    /// = Remarks
+    ///
+    /// The values yielded from the front and the back never overlap.
+    fn next_back(&mut self) -> Option<Self::Item>;
+}
+
+impl<'a, T: DoubleEndedIterator+?Sized> DoubleEndedIterator for &'a mut T {
+    fn next_back(&mut self) -> Option<T::Item> { (**self).next_back() }
+}
+
 /// An iterator that yields no values.
 #[derive(Copy, Eq)]
 pub struct Empty<T> { _data: PhantomData<T> }
@@ -95,11 +112,19 @@ impl<T> Iterator for Empty<T> {
     fn next(&mut self) -> Option<T> { Option::None }
 }
 
+impl<T> DoubleEndedIterator for Empty<T> {
+    fn next_back(&mut self) -> Option<T> { Option::None }
+}
+
 impl<T> Iterator for Option<T> {
     type Item = T;
     fn next(&mut self) -> Option<T> { self.take() }
 }
 
+impl<T> DoubleEndedIterator for Option<T> {
+    fn next_back(&mut self) -> Option<T> { self.take() }
+}
+
 /// Objects that can be turned into iterators.
 ///
 /// = Remarks
@@ -35,7 +35,7 @@ pub mod non_zero;
 pub mod data;
 pub mod float;
 
-mod sort;
+pub mod sort;
 
 pub mod std {
     pub use ::{marker, ops, intrinsics, option, mem};
@@ -9,7 +9,7 @@ use ops::{Eq, Index, IndexMut, PartialOrd, Range, RangeTo, RangeFrom, RangeFull,
 use cmp::{self, Ord};
 use option::{Option};
 use option::Option::{None, Some};
-use iter::{Iterator};
+use iter::{Iterator, DoubleEndedIterator};
 use sort::{sort};
 use marker::{Pod};
 use data::{d8};
@@ -240,9 +240,18 @@ impl<T> [T] {
     pub fn sort_by<F>(&mut self, mut f: F)
         where F: FnMut(&T, &T) -> Ordering
     {
-        if mem::size_of::<T>() != 0 {
-            unsafe { sort(self, &mut f); }
-        }
+        sort(self, &mut f);
+    }
+
+    /// Sorts the slice in-place with a key extraction function.
+    ///
+    /// [argument, f]
+    /// The function that returns the key of an element.
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+        where K: Ord,
+              F: FnMut(&T) -> K,
+    {
+        self.sort_by(|one, two| f(one).cmp(&f(two)));
     }
 
     /// Creates a subslice without checking the range for correctness.
@@ -378,6 +387,12 @@ impl<'a, T> Iterator for Items<'a, T> {
     }
 }
 
+impl<'a, T> DoubleEndedIterator for Items<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.slice.next_back()
+    }
+}
+
 /// An iterator over a mutable immutable slice.
 pub struct MutItems<'a, T: 'a> {
     slice: &'a mut [T],
@@ -390,6 +405,12 @@ impl<'a, T> Iterator for MutItems<'a, T> {
     }
 }
 
+impl<'a, T> DoubleEndedIterator for MutItems<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        self.slice.next_back()
+    }
+}
+
 /// An iterator over subslices.
 pub struct Split<'a, T: 'a, F> {
     slice: &'a [T],
@@ -553,3 +574,29 @@ impl<'a, T> Iterator for &'a mut [T] {
         }
     }
 }
+
+impl<'a, T> DoubleEndedIterator for &'a [T] {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.len() > 0 {
+            let last = unsafe { &*self.as_ptr().add(self.len() - 1) };
+            *self = &self[..self.len() - 1];
+            Some(last)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for &'a mut [T] {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        if self.len() > 0 {
+            let len = self.len();
+            let last = unsafe { &mut *self.as_mut_ptr().add(len - 1) };
+            let slf = mem::replace(self, &mut []);
+            *self = &mut slf[..len - 1];
+            Some(last)
+        } else {
+            None
+        }
+    }
+}
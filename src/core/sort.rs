@@ -2,41 +2,527 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use {mem, slice};
+//! Sorting algorithms.
+//!
+//! = Remarks
+//!
+//! The unstable sort is a pattern-defeating quicksort. It uses insertion sort for short
+//! slices and falls back to heapsort when too many partitions are unbalanced. Hence
+//! it runs in `O(n log n)` time and `O(log n)` stack space.
+//!
+//! The stable sort is a merge sort that merges natural runs. It runs in `O(n log n)`
+//! time and needs a scratch buffer of half the length of the slice.
+//!
+//! Neither sort causes undefined behavior if the comparison function is not a total
+//! order but the order of the elements is unspecified in this case.
+
+use {cmp, mem, ptr, slice};
 use ops::{Ordering, FnMut};
-use ops::Ordering::{Greater};
+use ops::Ordering::{Less};
+
+/// Slices up to this length are sorted with insertion sort.
+const MAX_INSERTION: usize = 20;
+
+unsafe fn swap<T>(v: *mut T, a: usize, b: usize) {
+    if a != b {
+        mem::swap(&mut *v.add(a), &mut *v.add(b));
+    }
+}
+
+/// Moves the last element to the left until it's in place.
+unsafe fn shift_tail<T, F>(v: *mut T, len: usize, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool,
+{
+    if len < 2 || !is_less(&*v.add(len - 1), &*v.add(len - 2)) {
+        return;
+    }
+    let tmp = ptr::read(v.add(len - 1));
+    let mut hole = len - 1;
+    while hole > 0 && is_less(&tmp, &*v.add(hole - 1)) {
+        ptr::memcpy(v.add(hole), v.add(hole - 1), 1);
+        hole -= 1;
+    }
+    ptr::write(v.add(hole), tmp);
+}
+
+/// Moves the first element to the right until it's in place.
+unsafe fn shift_head<T, F>(v: *mut T, len: usize, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool,
+{
+    if len < 2 || !is_less(&*v.add(1), &*v) {
+        return;
+    }
+    let tmp = ptr::read(v);
+    let mut hole = 0;
+    while hole + 1 < len && is_less(&*v.add(hole + 1), &tmp) {
+        ptr::memcpy(v.add(hole), v.add(hole + 1), 1);
+        hole += 1;
+    }
+    ptr::write(v.add(hole), tmp);
+}
+
+unsafe fn insertion_sort<T, F>(v: *mut T, len: usize, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool,
+{
+    for i in 2..len + 1 {
+        shift_tail(v, i, is_less);
+    }
+}
+
+/// Sorts a slice that is already mostly sorted by shifting a few elements.
+///
+/// Returns whether the slice is sorted.
+unsafe fn partial_insertion_sort<T, F>(v: *mut T, len: usize, is_less: &mut F) -> bool
+    where F: FnMut(&T, &T) -> bool,
+{
+    const MAX_STEPS: usize = 5;
+    const SHORTEST_SHIFTING: usize = 50;
+
+    let mut i = 1;
+    for _ in 0..MAX_STEPS {
+        while i < len && !is_less(&*v.add(i), &*v.add(i - 1)) {
+            i += 1;
+        }
+        if i == len {
+            return true;
+        }
+        // Shifting elements of short slices is not worth it.
+        if len < SHORTEST_SHIFTING {
+            return false;
+        }
+        swap(v, i - 1, i);
+        shift_tail(v, i, is_less);
+        shift_head(v.add(i), len - i, is_less);
+    }
+    false
+}
+
+unsafe fn sift_down<T, F>(v: *mut T, len: usize, mut node: usize, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool,
+{
+    loop {
+        let left = 2 * node + 1;
+        let right = left + 1;
+        let greater = match right < len && is_less(&*v.add(left), &*v.add(right)) {
+            true => right,
+            false => left,
+        };
+        if greater >= len || !is_less(&*v.add(node), &*v.add(greater)) {
+            return;
+        }
+        swap(v, node, greater);
+        node = greater;
+    }
+}
+
+unsafe fn heapsort<T, F>(v: *mut T, len: usize, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool,
+{
+    let mut i = len / 2;
+    while i > 0 {
+        i -= 1;
+        sift_down(v, len, i, is_less);
+    }
+    let mut end = len;
+    while end > 1 {
+        end -= 1;
+        swap(v, 0, end);
+        sift_down(v, end, 0, is_less);
+    }
+}
+
+/// Partitions the slice into elements less than the pivot and elements greater than
+/// or equal to the pivot.
+///
+/// Returns the new position of the pivot and whether the slice was already
+/// partitioned.
+unsafe fn partition<T, F>(v: *mut T, len: usize, pivot: usize,
+                          is_less: &mut F) -> (usize, bool)
+    where F: FnMut(&T, &T) -> bool,
+{
+    swap(v, 0, pivot);
+    let pivot = &*v;
+    let rest = v.add(1);
+
+    let mut l = 0;
+    let mut r = len - 1;
+    while l < r && is_less(&*rest.add(l), pivot) {
+        l += 1;
+    }
+    while l < r && !is_less(&*rest.add(r - 1), pivot) {
+        r -= 1;
+    }
+    let was_partitioned = l >= r;
+
+    loop {
+        while l < r && is_less(&*rest.add(l), pivot) {
+            l += 1;
+        }
+        while l < r && !is_less(&*rest.add(r - 1), pivot) {
+            r -= 1;
+        }
+        if l >= r {
+            break;
+        }
+        r -= 1;
+        swap(rest, l, r);
+        l += 1;
+    }
+
+    swap(v, 0, l);
+    (l, was_partitioned)
+}
+
+/// Partitions the slice into elements equal to the pivot and elements greater than
+/// the pivot. The slice must not contain elements less than the pivot.
+///
+/// Returns the number of elements equal to the pivot.
+unsafe fn partition_equal<T, F>(v: *mut T, len: usize, pivot: usize,
+                                is_less: &mut F) -> usize
+    where F: FnMut(&T, &T) -> bool,
+{
+    swap(v, 0, pivot);
+    let pivot = &*v;
+    let rest = v.add(1);
+
+    let mut l = 0;
+    let mut r = len - 1;
+    loop {
+        while l < r && !is_less(pivot, &*rest.add(l)) {
+            l += 1;
+        }
+        while l < r && is_less(pivot, &*rest.add(r - 1)) {
+            r -= 1;
+        }
+        if l >= r {
+            break;
+        }
+        r -= 1;
+        swap(rest, l, r);
+        l += 1;
+    }
+    l + 1
+}
+
+/// Swaps some elements around to break patterns that cause unbalanced partitions.
+unsafe fn break_patterns<T>(v: *mut T, len: usize) {
+    // Xorshift. The sort must be deterministic so we don't use a random seed.
+    let mut random = len as u32;
+    let mut gen = || {
+        random ^= random << 13;
+        random ^= random >> 17;
+        random ^= random << 5;
+        random as usize
+    };
+
+    let mask = len.next_power_of_two() - 1;
+    let pos = len / 4 * 2;
+    for i in 0..3 {
+        let mut other = gen() & mask;
+        if other >= len {
+            other -= len;
+        }
+        swap(v, pos - 1 + i, other);
+    }
+}
+
+unsafe fn sort2<T, F>(v: *mut T, a: &mut usize, b: &mut usize, swaps: &mut usize,
+                      is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool,
+{
+    if is_less(&*v.add(*b), &*v.add(*a)) {
+        mem::swap(a, b);
+        *swaps += 1;
+    }
+}
+
+unsafe fn sort3<T, F>(v: *mut T, a: &mut usize, b: &mut usize, c: &mut usize,
+                      swaps: &mut usize, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool,
+{
+    sort2(v, a, b, swaps, is_less);
+    sort2(v, b, c, swaps, is_less);
+    sort2(v, a, b, swaps, is_less);
+}
+
+/// Replaces `m` by the index of the median of `m` and its neighbors.
+unsafe fn sort_adjacent<T, F>(v: *mut T, m: &mut usize, swaps: &mut usize,
+                              is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool,
+{
+    let (mut l, mut r) = (*m - 1, *m + 1);
+    sort3(v, &mut l, m, &mut r, swaps, is_less);
+}
+
+/// Chooses a pivot with the median of three or, for long slices, the median of
+/// medians.
+///
+/// Returns the index of the pivot and whether the slice is likely already sorted.
+unsafe fn choose_pivot<T, F>(v: *mut T, len: usize, is_less: &mut F) -> (usize, bool)
+    where F: FnMut(&T, &T) -> bool,
+{
+    const SHORTEST_MEDIAN_OF_MEDIANS: usize = 50;
+    const MAX_SWAPS: usize = 4 * 3;
+
+    let mut a = len / 4 * 1;
+    let mut b = len / 4 * 2;
+    let mut c = len / 4 * 3;
+    let mut swaps = 0;
+
+    if len >= SHORTEST_MEDIAN_OF_MEDIANS {
+        sort_adjacent(v, &mut a, &mut swaps, is_less);
+        sort_adjacent(v, &mut b, &mut swaps, is_less);
+        sort_adjacent(v, &mut c, &mut swaps, is_less);
+    }
+    sort3(v, &mut a, &mut b, &mut c, &mut swaps, is_less);
+
+    if swaps < MAX_SWAPS {
+        (b, swaps == 0)
+    } else {
+        // The slice is likely sorted in reverse order.
+        slice::from_ptr(v, len).reverse();
+        (len - 1 - b, true)
+    }
+}
+
+unsafe fn recurse<T, F>(mut v: *mut T, mut len: usize, is_less: &mut F,
+                        mut pred: Option<*const T>, mut limit: usize)
+    where F: FnMut(&T, &T) -> bool,
+{
+    let mut was_balanced = true;
+    let mut was_partitioned = true;
+
+    loop {
+        if len <= MAX_INSERTION {
+            insertion_sort(v, len, is_less);
+            return;
+        }
+        if limit == 0 {
+            heapsort(v, len, is_less);
+            return;
+        }
+        if !was_balanced {
+            break_patterns(v, len);
+            limit -= 1;
+        }
+
+        let (pivot, likely_sorted) = choose_pivot(v, len, is_less);
+        if was_balanced && was_partitioned && likely_sorted {
+            if partial_insertion_sort(v, len, is_less) {
+                return;
+            }
+        }
+
+        // If the pivot is equal to the predecessor of this slice, then it is the
+        // smallest element. Move all equal elements to the front and continue with
+        // the rest. This makes slices with many duplicates fast.
+        if let Some(p) = pred {
+            if !is_less(&*p, &*v.add(pivot)) {
+                let mid = partition_equal(v, len, pivot, is_less);
+                v = v.add(mid);
+                len -= mid;
+                continue;
+            }
+        }
+
+        let (mid, partitioned) = partition(v, len, pivot, is_less);
+        was_balanced = cmp::min(mid, len - mid) >= len / 8;
+        was_partitioned = partitioned;
+
+        // Recurse into the shorter side to bound the stack depth.
+        let right = v.add(mid + 1);
+        let right_len = len - mid - 1;
+        if mid < right_len {
+            recurse(v, mid, is_less, pred, limit);
+            pred = Some(v.add(mid) as *const T);
+            v = right;
+            len = right_len;
+        } else {
+            recurse(right, right_len, is_less, Some(v.add(mid) as *const T), limit);
+            len = mid;
+        }
+    }
+}
 
-pub unsafe fn sort<T, F>(slice: &mut [T], f: &mut F)
+/// Sorts a slice.
+///
+/// [argument, v]
+/// The slice to sort.
+///
+/// [argument, f]
+/// The comparison function.
+///
+/// = Remarks
+///
+/// The sort is not stable.
+pub fn sort<T, F>(v: &mut [T], f: &mut F)
     where F: FnMut(&T, &T) -> Ordering,
 {
-    if slice.len() < 2 {
+    if mem::size_of::<T>() == 0 || v.len() < 2 {
         return;
     }
+    let mut is_less = |a: &T, b: &T| f(a, b) == Less;
+    let limit = mem::size_of::<usize>() * 8 - v.len().leading_zeros();
+    unsafe { recurse(v.as_mut_ptr(), v.len(), &mut is_less, None, limit); }
+}
+
+/// Returns the number of elements of the scratch buffer of a stable sort.
+///
+/// [argument, len]
+/// The length of the slice that will be sorted.
+pub fn stable_buf_len(len: usize) -> usize {
+    match len <= MAX_INSERTION {
+        true => 0,
+        false => len / 2,
+    }
+}
 
-    let pivot = &mut slice[0] as *mut T;
-    let mut start = pivot.add(1);
-    let mut end = pivot.add(slice.len() - 1);
-    while start <= end {
-        while start <= end && f(&*start, &*pivot) != Greater {
-            start = start.offset(1);
+/// Merges the sorted runs `v[..mid]` and `v[mid..]`.
+unsafe fn merge<T, F>(v: *mut T, len: usize, mid: usize, buf: *mut T, is_less: &mut F)
+    where F: FnMut(&T, &T) -> bool,
+{
+    let v_mid = v.add(mid);
+    let v_end = v.add(len);
+
+    // Copy the shorter run into the buffer and merge from the corresponding end.
+    if mid <= len - mid {
+        ptr::memcpy(buf, v, mid);
+        let mut left = buf;
+        let left_end = buf.add(mid);
+        let mut right = v_mid;
+        let mut out = v;
+        while left < left_end && right < v_end {
+            if is_less(&*right, &*left) {
+                ptr::memcpy(out, right, 1);
+                right = right.add(1);
+            } else {
+                ptr::memcpy(out, left, 1);
+                left = left.add(1);
+            }
+            out = out.add(1);
         }
-        while start < end && f(&*end, &*pivot) == Greater {
-            end = end.offset(-1);
+        let rest = (left_end as usize - left as usize) / mem::size_of::<T>();
+        ptr::memcpy(out, left, rest);
+    } else {
+        ptr::memcpy(buf, v_mid, len - mid);
+        let mut left = v_mid;
+        let mut right = buf.add(len - mid);
+        let mut out = v_end;
+        while v < left && buf < right {
+            out = out.sub(1);
+            if is_less(&*right.sub(1), &*left.sub(1)) {
+                left = left.sub(1);
+                ptr::memcpy(out, left, 1);
+            } else {
+                right = right.sub(1);
+                ptr::memcpy(out, right, 1);
+            }
         }
-        if start < end {
-            mem::swap(&mut *start, &mut *end);
-            start = start.offset(1);
-            end = end.offset(-1);
+        let rest = (right as usize - buf as usize) / mem::size_of::<T>();
+        ptr::memcpy(left, buf, rest);
+    }
+}
+
+#[derive(Copy)]
+struct Run {
+    start: usize,
+    len: usize,
+}
+
+/// Returns the index of the run that should be merged with its successor.
+///
+/// The conditions ensure that the lengths of the runs on the stack grow at least as
+/// fast as the Fibonacci numbers.
+fn collapse(runs: &[Run]) -> Option<usize> {
+    let n = runs.len();
+    if n >= 2 && (runs[n - 1].start == 0
+                  || runs[n - 2].len <= runs[n - 1].len
+                  || (n >= 3 && runs[n - 3].len <= runs[n - 2].len + runs[n - 1].len)
+                  || (n >= 4 && runs[n - 4].len <= runs[n - 3].len + runs[n - 2].len)) {
+        if n >= 3 && runs[n - 3].len < runs[n - 1].len {
+            Some(n - 3)
         } else {
-            break;
+            Some(n - 2)
         }
+    } else {
+        None
     }
-    start = start.offset(-1);
-    if pivot < start {
-        mem::swap(&mut *pivot, &mut *start);
+}
+
+/// Sorts a slice with a stable sort.
+///
+/// [argument, v]
+/// The slice to sort.
+///
+/// [argument, buf]
+/// A scratch buffer.
+///
+/// [argument, f]
+/// The comparison function.
+///
+/// = Remarks
+///
+/// The buffer must have space for at least `stable_buf_len(v.len())` elements.
+/// Otherwise the behavior is undefined.
+pub unsafe fn sort_stable<T, F>(v: &mut [T], buf: *mut T, f: &mut F)
+    where F: FnMut(&T, &T) -> Ordering,
+{
+    // Runs shorter than this are extended with insertion sort.
+    const MIN_RUN: usize = 10;
+    // Enough for any slice since the lengths of the runs grow exponentially.
+    const MAX_RUNS: usize = 96;
+
+    if mem::size_of::<T>() == 0 || v.len() < 2 {
+        return;
+    }
+    let mut is_less = |a: &T, b: &T| f(a, b) == Less;
+    let len = v.len();
+    let v = v.as_mut_ptr();
+
+    if len <= MAX_INSERTION {
+        insertion_sort(v, len, &mut is_less);
+        return;
     }
-    let start_len = (start as usize - pivot as usize) / mem::size_of::<T>();
 
-    sort(slice::from_ptr(pivot          , start_len                  ), &mut *f);
-    sort(slice::from_ptr(start.offset(1), slice.len() - start_len - 1), &mut *f);
+    let mut runs = [Run { start: 0, len: 0 }; MAX_RUNS];
+    let mut num_runs = 0;
+
+    // Find the runs from right to left so that the merges mostly go forward.
+    let mut end = len;
+    while end > 0 {
+        let mut start = end - 1;
+        if start > 0 {
+            start -= 1;
+            if is_less(&*v.add(start + 1), &*v.add(start)) {
+                // Strictly descending runs can be reversed without losing stability.
+                while start > 0 && is_less(&*v.add(start), &*v.add(start - 1)) {
+                    start -= 1;
+                }
+                slice::from_ptr(v.add(start), end - start).reverse();
+            } else {
+                while start > 0 && !is_less(&*v.add(start), &*v.add(start - 1)) {
+                    start -= 1;
+                }
+            }
+        }
+        while start > 0 && end - start < MIN_RUN {
+            start -= 1;
+            shift_head(v.add(start), end - start, &mut is_less);
+        }
+
+        runs[num_runs] = Run { start: start, len: end - start };
+        num_runs += 1;
+        end = start;
+
+        while let Some(r) = collapse(&runs[..num_runs]) {
+            let left = runs[r + 1];
+            let right = runs[r];
+            merge(v.add(left.start), left.len + right.len, left.len, buf, &mut is_less);
+            runs[r] = Run { start: left.start, len: left.len + right.len };
+            for i in r + 1..num_runs - 1 {
+                runs[i] = runs[i + 1];
+            }
+            num_runs -= 1;
+        }
+    }
 }
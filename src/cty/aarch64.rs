@@ -37,8 +37,6 @@ pub const BYTES_PER_LONG  : usize = 8;
 pub const BYTES_PER_SHORT : usize = 2;
 pub const BYTES_PER_INT   : usize = 4;
 
-pub type timespec_tv_nsec_type = c_long;
-
 // bitfield manipulation
 
 #[cfg(target_endian = "little")]
@@ -411,6 +409,8 @@ pub use ::gen::{
     SCM_TIMESTAMPING, SO_PROTOCOL, SO_DOMAIN, SO_RXQ_OVFL, SO_WIFI_STATUS,
     SCM_WIFI_STATUS, SO_PEEK_OFF, SO_NOFCS, SO_LOCK_FILTER, SO_SELECT_ERR_QUEUE,
     SO_BUSY_POLL, SO_MAX_PACING_RATE, SO_BPF_EXTENSIONS,
+    SO_TIMESTAMP_NEW, SCM_TIMESTAMP_NEW, SO_TIMESTAMPNS_NEW, SCM_TIMESTAMPNS_NEW,
+    SO_TIMESTAMPING_NEW, SCM_TIMESTAMPING_NEW,
 };
 
 ////////////////////////////////////////
@@ -785,6 +785,14 @@ pub const __NR_getrandom              : usize = 278;
 pub const __NR_memfd_create           : usize = 279;
 pub const __NR_bpf                    : usize = 280;
 pub const __NR_execveat               : usize = 281;
+pub const __NR_statx                  : usize = 291;
+pub const __NR_pidfd_send_signal      : usize = 424;
+pub const __NR_io_uring_setup         : usize = 425;
+pub const __NR_io_uring_enter         : usize = 426;
+pub const __NR_io_uring_register      : usize = 427;
+pub const __NR_pidfd_open             : usize = 434;
+pub const __NR_clone3                 : usize = 435;
+pub const __NR_pidfd_getfd            : usize = 438;
 
 ///////////////////////////////////////
 // arch/arm64/include/uapi/asm/auxvec.h
@@ -793,7 +801,35 @@ pub const __NR_execveat               : usize = 281;
 pub const AT_SYSINFO_EHDR : usize = 33;
 
 /// The largest AT_* value plus one.
-pub const AUX_CNT : usize = 34;
+pub const AUX_CNT : usize = 52;
+
+//////////////////////////////////////
+// arch/arm64/include/uapi/asm/hwcap.h
+//////////////////////////////////////
+
+pub const HWCAP_FP       : usize = 1 << 0;
+pub const HWCAP_ASIMD    : usize = 1 << 1;
+pub const HWCAP_EVTSTRM  : usize = 1 << 2;
+pub const HWCAP_AES      : usize = 1 << 3;
+pub const HWCAP_PMULL    : usize = 1 << 4;
+pub const HWCAP_SHA1     : usize = 1 << 5;
+pub const HWCAP_SHA2     : usize = 1 << 6;
+pub const HWCAP_CRC32    : usize = 1 << 7;
+pub const HWCAP_ATOMICS  : usize = 1 << 8;
+pub const HWCAP_FPHP     : usize = 1 << 9;
+pub const HWCAP_ASIMDHP  : usize = 1 << 10;
+pub const HWCAP_CPUID    : usize = 1 << 11;
+pub const HWCAP_ASIMDRDM : usize = 1 << 12;
+pub const HWCAP_JSCVT    : usize = 1 << 13;
+pub const HWCAP_FCMA     : usize = 1 << 14;
+pub const HWCAP_LRCPC    : usize = 1 << 15;
+pub const HWCAP_DCPOP    : usize = 1 << 16;
+pub const HWCAP_SHA3     : usize = 1 << 17;
+pub const HWCAP_SM3      : usize = 1 << 18;
+pub const HWCAP_SM4      : usize = 1 << 19;
+pub const HWCAP_ASIMDDP  : usize = 1 << 20;
+pub const HWCAP_SHA512   : usize = 1 << 21;
+pub const HWCAP_SVE      : usize = 1 << 22;
 
 ///////////////////////////
 // include/uapi/linux/elf.h
@@ -44,8 +44,6 @@ pub const BYTES_PER_LONG  : usize = 4;
 pub const BYTES_PER_SHORT : usize = 2;
 pub const BYTES_PER_INT   : usize = 4;
 
-pub type timespec_tv_nsec_type = c_long;
-
 // bitfield manipulation
 
 #[cfg(target_endian = "little")]
@@ -482,6 +480,8 @@ pub use ::gen::{
     SCM_TIMESTAMPING, SO_PROTOCOL, SO_DOMAIN, SO_RXQ_OVFL, SO_WIFI_STATUS,
     SCM_WIFI_STATUS, SO_PEEK_OFF, SO_NOFCS, SO_LOCK_FILTER, SO_SELECT_ERR_QUEUE,
     SO_BUSY_POLL, SO_MAX_PACING_RATE, SO_BPF_EXTENSIONS,
+    SO_TIMESTAMP_NEW, SCM_TIMESTAMP_NEW, SO_TIMESTAMPNS_NEW, SCM_TIMESTAMPNS_NEW,
+    SO_TIMESTAMPING_NEW, SCM_TIMESTAMPING_NEW,
 };
 
 //////////////////////////////////////
@@ -1016,6 +1016,34 @@ pub const __NR_getrandom              : usize = 384;
 pub const __NR_memfd_create           : usize = 385;
 pub const __NR_bpf                    : usize = 386;
 pub const __NR_execveat               : usize = 387;
+pub const __NR_statx                  : usize = 397;
+pub const __NR_clock_gettime64        : usize = 403;
+pub const __NR_clock_settime64        : usize = 404;
+pub const __NR_clock_adjtime64        : usize = 405;
+pub const __NR_clock_getres_time64    : usize = 406;
+pub const __NR_clock_nanosleep_time64 : usize = 407;
+pub const __NR_timer_gettime64        : usize = 408;
+pub const __NR_timer_settime64        : usize = 409;
+pub const __NR_timerfd_gettime64      : usize = 410;
+pub const __NR_timerfd_settime64      : usize = 411;
+pub const __NR_utimensat_time64       : usize = 412;
+pub const __NR_pselect6_time64        : usize = 413;
+pub const __NR_ppoll_time64           : usize = 414;
+pub const __NR_io_pgetevents_time64   : usize = 416;
+pub const __NR_recvmmsg_time64        : usize = 417;
+pub const __NR_mq_timedsend_time64    : usize = 418;
+pub const __NR_mq_timedreceive_time64 : usize = 419;
+pub const __NR_semtimedop_time64      : usize = 420;
+pub const __NR_rt_sigtimedwait_time64 : usize = 421;
+pub const __NR_futex_time64           : usize = 422;
+pub const __NR_sched_rr_get_interval_time64: usize = 423;
+pub const __NR_pidfd_send_signal      : usize = 424;
+pub const __NR_io_uring_setup         : usize = 425;
+pub const __NR_io_uring_enter         : usize = 426;
+pub const __NR_io_uring_register      : usize = 427;
+pub const __NR_pidfd_open             : usize = 434;
+pub const __NR_clone3                 : usize = 435;
+pub const __NR_pidfd_getfd            : usize = 438;
 
 /////////////////////////////////////
 // arch/arm/include/uapi/asm/unistd.h
@@ -1032,8 +1060,43 @@ pub const __ARM_NR_set_tls    : usize = __ARM_NR_BASE + 5;
 // arch/arm/include/uapi/asm/auxvec.h
 /////////////////////////////////////
 
+pub const AT_SYSINFO_EHDR : usize = 33;
+
 /// The largest AT_* value plus one.
-pub const AUX_CNT : usize = 32;
+pub const AUX_CNT : usize = 52;
+
+////////////////////////////////////
+// arch/arm/include/uapi/asm/hwcap.h
+////////////////////////////////////
+
+pub const HWCAP_SWP       : usize = 1 << 0;
+pub const HWCAP_HALF      : usize = 1 << 1;
+pub const HWCAP_THUMB     : usize = 1 << 2;
+pub const HWCAP_26BIT     : usize = 1 << 3;
+pub const HWCAP_FAST_MULT : usize = 1 << 4;
+pub const HWCAP_FPA       : usize = 1 << 5;
+pub const HWCAP_VFP       : usize = 1 << 6;
+pub const HWCAP_EDSP      : usize = 1 << 7;
+pub const HWCAP_JAVA      : usize = 1 << 8;
+pub const HWCAP_IWMMXT    : usize = 1 << 9;
+pub const HWCAP_CRUNCH    : usize = 1 << 10;
+pub const HWCAP_THUMBEE   : usize = 1 << 11;
+pub const HWCAP_NEON      : usize = 1 << 12;
+pub const HWCAP_VFPv3     : usize = 1 << 13;
+pub const HWCAP_VFPv3D16  : usize = 1 << 14;
+pub const HWCAP_TLS       : usize = 1 << 15;
+pub const HWCAP_VFPv4     : usize = 1 << 16;
+pub const HWCAP_IDIVA     : usize = 1 << 17;
+pub const HWCAP_IDIVT     : usize = 1 << 18;
+pub const HWCAP_VFPD32    : usize = 1 << 19;
+pub const HWCAP_LPAE      : usize = 1 << 20;
+pub const HWCAP_EVTSTRM   : usize = 1 << 21;
+
+pub const HWCAP2_AES   : usize = 1 << 0;
+pub const HWCAP2_PMULL : usize = 1 << 1;
+pub const HWCAP2_SHA1  : usize = 1 << 2;
+pub const HWCAP2_SHA2  : usize = 1 << 3;
+pub const HWCAP2_CRC32 : usize = 1 << 4;
 
 ///////////////////////////
 // include/uapi/linux/elf.h
@@ -977,6 +977,12 @@ pub const SO_SELECT_ERR_QUEUE              : ::c_int = 45;
 pub const SO_BUSY_POLL                     : ::c_int = 46;
 pub const SO_MAX_PACING_RATE               : ::c_int = 47;
 pub const SO_BPF_EXTENSIONS                : ::c_int = 48;
+pub const SO_TIMESTAMP_NEW                 : ::c_int = 63;
+pub const SCM_TIMESTAMP_NEW                : ::c_int = ::SO_TIMESTAMP_NEW;
+pub const SO_TIMESTAMPNS_NEW               : ::c_int = 64;
+pub const SCM_TIMESTAMPNS_NEW              : ::c_int = ::SO_TIMESTAMPNS_NEW;
+pub const SO_TIMESTAMPING_NEW              : ::c_int = 65;
+pub const SCM_TIMESTAMPING_NEW             : ::c_int = ::SO_TIMESTAMPING_NEW;
 
 /////////////////////////////////////
 // include/uapi/asm-generic/sockios.h
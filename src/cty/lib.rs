@@ -127,12 +127,33 @@ pub type __wsum  = __u32;
 
 // time.h
 
-// TODO: Explain why the second type is not c_long.
+pub type __kernel_time64_t = c_longlong;
+
+/// The timespec used by the system calls.
+///
+/// This has 64 bit fields on all architectures. On 32 bit architectures, this is the
+/// type used by the `*_time64` system calls. The legacy system calls use
+/// `old_timespec32`.
 #[repr(C)]
 #[derive(Pod, Eq)]
 pub struct timespec {
+    pub tv_sec:  __kernel_time64_t,
+    pub tv_nsec: c_longlong,
+}
+
+/// The timespec used by `SO_TIMESTAMPNS` and `SIOCGSTAMPNS`.
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct __kernel_old_timespec {
     pub tv_sec:  __kernel_time_t,
-    pub tv_nsec: timespec_tv_nsec_type,
+    pub tv_nsec: k_long,
+}
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct old_timespec32 {
+    pub tv_sec:  __s32,
+    pub tv_nsec: __s32,
 }
 
 #[repr(C)]
@@ -142,6 +163,16 @@ pub struct timeval {
     pub tv_usec: __kernel_suseconds_t,
 }
 
+/// A timeval with 64 bit fields on all architectures.
+///
+/// On 64 bit architectures, this has the same layout as `timeval`.
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct __kernel_sock_timeval {
+    pub tv_sec:  __s64,
+    pub tv_usec: __s64,
+}
+
 #[repr(C)]
 #[derive(Pod, Eq)]
 pub struct timezone {
@@ -160,6 +191,13 @@ pub struct itimerspec {
     pub it_value:    timespec,
 }
 
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct old_itimerspec32 {
+    pub it_interval: old_timespec32,
+    pub it_value:    old_timespec32,
+}
+
 #[repr(C)]
 #[derive(Pod, Eq)]
 pub struct itimerval {
@@ -244,6 +282,9 @@ pub const AT_REMOVEDIR        : c_int = 0x200;
 pub const AT_SYMLINK_FOLLOW   : c_int = 0x400;
 pub const AT_NO_AUTOMOUNT     : c_int = 0x800;
 pub const AT_EMPTY_PATH       : c_int = 0x1000;
+pub const AT_STATX_SYNC_TYPE  : c_int = 0x6000;
+pub const AT_STATX_FORCE_SYNC : c_int = 0x2000;
+pub const AT_STATX_DONT_SYNC  : c_int = 0x4000;
 
 // bpf_common.h
 
@@ -291,6 +332,24 @@ pub const BPF_X    : u8 = 0x08;
 
 pub const BPF_MAXINSNS : usize = 4096;
 
+// filter.h
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct sock_filter {
+    pub code: __u16,
+    pub jt:   __u8,
+    pub jf:   __u8,
+    pub k:    __u32,
+}
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct sock_fprog {
+    pub len:    c_ushort,
+    pub filter: *mut sock_filter,
+}
+
 // bpf.h
 
 pub const BPF_ALU64   : u8 = 0x07;
@@ -859,6 +918,154 @@ pub struct iocb {
     pub aio_resfd:      __u32,
 }
 
+// io_uring.h
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct __kernel_timespec {
+    pub tv_sec:  __s64,
+    pub tv_nsec: c_longlong,
+}
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct io_uring_sqe {
+    pub opcode:       __u8,
+    pub flags:        __u8,
+    pub ioprio:       __u16,
+    pub fd:           __s32,
+    pub off:          __u64,
+    pub addr:         __u64,
+    pub len:          __u32,
+    pub op_flags:     __u32,
+    pub user_data:    __u64,
+    pub buf_index:    __u16,
+    pub personality:  __u16,
+    pub splice_fd_in: __s32,
+    pub __pad2:       [__u64; 2],
+}
+
+pub const IOSQE_FIXED_FILE  : __u8 = 1 << 0;
+pub const IOSQE_IO_DRAIN    : __u8 = 1 << 1;
+pub const IOSQE_IO_LINK     : __u8 = 1 << 2;
+pub const IOSQE_IO_HARDLINK : __u8 = 1 << 3;
+pub const IOSQE_ASYNC       : __u8 = 1 << 4;
+
+pub const IORING_SETUP_IOPOLL    : __u32 = 1 << 0;
+pub const IORING_SETUP_SQPOLL    : __u32 = 1 << 1;
+pub const IORING_SETUP_SQ_AFF    : __u32 = 1 << 2;
+pub const IORING_SETUP_CQSIZE    : __u32 = 1 << 3;
+pub const IORING_SETUP_CLAMP     : __u32 = 1 << 4;
+pub const IORING_SETUP_ATTACH_WQ : __u32 = 1 << 5;
+
+pub const IORING_OP_NOP             : __u8 = 0;
+pub const IORING_OP_READV           : __u8 = 1;
+pub const IORING_OP_WRITEV          : __u8 = 2;
+pub const IORING_OP_FSYNC           : __u8 = 3;
+pub const IORING_OP_READ_FIXED      : __u8 = 4;
+pub const IORING_OP_WRITE_FIXED     : __u8 = 5;
+pub const IORING_OP_POLL_ADD        : __u8 = 6;
+pub const IORING_OP_POLL_REMOVE     : __u8 = 7;
+pub const IORING_OP_SYNC_FILE_RANGE : __u8 = 8;
+pub const IORING_OP_SENDMSG         : __u8 = 9;
+pub const IORING_OP_RECVMSG         : __u8 = 10;
+pub const IORING_OP_TIMEOUT         : __u8 = 11;
+pub const IORING_OP_TIMEOUT_REMOVE  : __u8 = 12;
+pub const IORING_OP_ACCEPT          : __u8 = 13;
+pub const IORING_OP_ASYNC_CANCEL    : __u8 = 14;
+pub const IORING_OP_LINK_TIMEOUT    : __u8 = 15;
+pub const IORING_OP_CONNECT         : __u8 = 16;
+pub const IORING_OP_FALLOCATE       : __u8 = 17;
+pub const IORING_OP_OPENAT          : __u8 = 18;
+pub const IORING_OP_CLOSE           : __u8 = 19;
+pub const IORING_OP_FILES_UPDATE    : __u8 = 20;
+pub const IORING_OP_STATX           : __u8 = 21;
+pub const IORING_OP_READ            : __u8 = 22;
+pub const IORING_OP_WRITE           : __u8 = 23;
+pub const IORING_OP_FADVISE         : __u8 = 24;
+pub const IORING_OP_MADVISE         : __u8 = 25;
+pub const IORING_OP_SEND            : __u8 = 26;
+pub const IORING_OP_RECV            : __u8 = 27;
+pub const IORING_OP_OPENAT2         : __u8 = 28;
+pub const IORING_OP_EPOLL_CTL       : __u8 = 29;
+
+pub const IORING_FSYNC_DATASYNC : __u32 = 1 << 0;
+pub const IORING_TIMEOUT_ABS    : __u32 = 1 << 0;
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct io_uring_cqe {
+    pub user_data: __u64,
+    pub res:       __s32,
+    pub flags:     __u32,
+}
+
+pub const IORING_OFF_SQ_RING : u64 = 0;
+pub const IORING_OFF_CQ_RING : u64 = 0x8000000;
+pub const IORING_OFF_SQES    : u64 = 0x10000000;
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct io_sqring_offsets {
+    pub head:         __u32,
+    pub tail:         __u32,
+    pub ring_mask:    __u32,
+    pub ring_entries: __u32,
+    pub flags:        __u32,
+    pub dropped:      __u32,
+    pub array:        __u32,
+    pub resv1:        __u32,
+    pub resv2:        __u64,
+}
+
+pub const IORING_SQ_NEED_WAKEUP : __u32 = 1 << 0;
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct io_cqring_offsets {
+    pub head:         __u32,
+    pub tail:         __u32,
+    pub ring_mask:    __u32,
+    pub ring_entries: __u32,
+    pub overflow:     __u32,
+    pub cqes:         __u32,
+    pub flags:        __u32,
+    pub resv1:        __u32,
+    pub resv2:        __u64,
+}
+
+pub const IORING_ENTER_GETEVENTS : c_uint = 1 << 0;
+pub const IORING_ENTER_SQ_WAKEUP : c_uint = 1 << 1;
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct io_uring_params {
+    pub sq_entries:     __u32,
+    pub cq_entries:     __u32,
+    pub flags:          __u32,
+    pub sq_thread_cpu:  __u32,
+    pub sq_thread_idle: __u32,
+    pub features:       __u32,
+    pub wq_fd:          __u32,
+    pub resv:           [__u32; 3],
+    pub sq_off:         io_sqring_offsets,
+    pub cq_off:         io_cqring_offsets,
+}
+
+pub const IORING_FEAT_SINGLE_MMAP     : __u32 = 1 << 0;
+pub const IORING_FEAT_NODROP          : __u32 = 1 << 1;
+pub const IORING_FEAT_SUBMIT_STABLE   : __u32 = 1 << 2;
+pub const IORING_FEAT_RW_CUR_POS      : __u32 = 1 << 3;
+pub const IORING_FEAT_CUR_PERSONALITY : __u32 = 1 << 4;
+
+pub const IORING_REGISTER_BUFFERS       : c_uint = 0;
+pub const IORING_UNREGISTER_BUFFERS     : c_uint = 1;
+pub const IORING_REGISTER_FILES         : c_uint = 2;
+pub const IORING_UNREGISTER_FILES       : c_uint = 3;
+pub const IORING_REGISTER_EVENTFD       : c_uint = 4;
+pub const IORING_UNREGISTER_EVENTFD     : c_uint = 5;
+pub const IORING_REGISTER_FILES_UPDATE  : c_uint = 6;
+
 // fs.h
 
 #[repr(C)]
@@ -2692,8 +2899,57 @@ pub const S_IRUGO    : umode_t = S_IRUSR|S_IRGRP|S_IROTH;
 pub const S_IWUGO    : umode_t = S_IWUSR|S_IWGRP|S_IWOTH;
 pub const S_IXUGO    : umode_t = S_IXUSR|S_IXGRP|S_IXOTH;
 
-pub const UTIME_NOW  : k_long = (1 << 30) - 1;
-pub const UTIME_OMIT : k_long = (1 << 30) - 2;
+pub const UTIME_NOW  : c_longlong = (1 << 30) - 1;
+pub const UTIME_OMIT : c_longlong = (1 << 30) - 2;
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct statx_timestamp {
+    pub tv_sec:     __s64,
+    pub tv_nsec:    __u32,
+    pub __reserved: __s32,
+}
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct statx {
+    pub stx_mask:            __u32,
+    pub stx_blksize:         __u32,
+    pub stx_attributes:      __u64,
+    pub stx_nlink:           __u32,
+    pub stx_uid:             __u32,
+    pub stx_gid:             __u32,
+    pub stx_mode:            __u16,
+    pub __spare0:            [__u16; 1],
+    pub stx_ino:             __u64,
+    pub stx_size:            __u64,
+    pub stx_blocks:          __u64,
+    pub stx_attributes_mask: __u64,
+    pub stx_atime:           statx_timestamp,
+    pub stx_btime:           statx_timestamp,
+    pub stx_ctime:           statx_timestamp,
+    pub stx_mtime:           statx_timestamp,
+    pub stx_rdev_major:      __u32,
+    pub stx_rdev_minor:      __u32,
+    pub stx_dev_major:       __u32,
+    pub stx_dev_minor:       __u32,
+    pub __spare2:            [__u64; 14],
+}
+
+pub const STATX_TYPE        : c_uint = 0x00000001;
+pub const STATX_MODE        : c_uint = 0x00000002;
+pub const STATX_NLINK       : c_uint = 0x00000004;
+pub const STATX_UID         : c_uint = 0x00000008;
+pub const STATX_GID         : c_uint = 0x00000010;
+pub const STATX_ATIME       : c_uint = 0x00000020;
+pub const STATX_MTIME       : c_uint = 0x00000040;
+pub const STATX_CTIME       : c_uint = 0x00000080;
+pub const STATX_INO         : c_uint = 0x00000100;
+pub const STATX_SIZE        : c_uint = 0x00000200;
+pub const STATX_BLOCKS      : c_uint = 0x00000400;
+pub const STATX_BASIC_STATS : c_uint = 0x000007ff;
+pub const STATX_BTIME       : c_uint = 0x00000800;
+pub const STATX_ALL         : c_uint = 0x00000fff;
 
 // falloc.h
 
@@ -2736,6 +2992,7 @@ pub const __WCLONE    : c_int = 0x80000000;
 pub const P_ALL       : c_int = 0;
 pub const P_PID       : c_int = 1;
 pub const P_PGID      : c_int = 2;
+pub const P_PIDFD     : c_int = 3;
 
 // un.h
 
@@ -3177,6 +3434,8 @@ pub const IPV6_UNICAST_IF                : c_int = 76;
 
 pub const SIOCINQ                : c_int = FIONREAD as c_int;
 pub const SIOCOUTQ               : c_int = TIOCOUTQ as c_int;
+#[allow(overflowing_literals)]
+pub const SIOCGSTAMPNS_NEW       : c_int = 0x80108907;
 pub const SIOCADDRT              : c_int = 0x890B;
 pub const SIOCDELRT              : c_int = 0x890C;
 pub const SIOCRTMSG              : c_int = 0x890D;
@@ -4766,6 +5025,31 @@ pub struct ifmap {
     pub port:      c_uchar,
 }
 
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct ifreq {
+    pub ifr_name: [c_char; IFNAMSIZ],
+    //union {
+    //	struct sockaddr ifru_addr;
+    //	...
+    //	short	ifru_flags;
+    //	int	ifru_ivalue;
+    //	int	ifru_mtu;
+    //	struct  ifmap ifru_map;
+    //	...
+    //} ifr_ifru;
+    __union_one: ifmap,
+}
+
+impl ifreq {
+    pub fn ifr_ifindex(&self) -> c_int {
+        unsafe { *(&self.__union_one as *const _ as *const c_int) }
+    }
+    pub fn set_ifr_ifindex(&mut self, val: c_int) {
+        unsafe { *(&mut self.__union_one as *mut _ as *mut c_int) = val }
+    }
+}
+
 ////////////////////////////
 // include/uapi/linux/veth.h
 ////////////////////////////
@@ -4784,6 +5068,7 @@ pub const CLONE_VM             : c_int = 0x00000100;
 pub const CLONE_FS             : c_int = 0x00000200;
 pub const CLONE_FILES          : c_int = 0x00000400;
 pub const CLONE_SIGHAND        : c_int = 0x00000800;
+pub const CLONE_PIDFD          : c_int = 0x00001000;
 pub const CLONE_PTRACE         : c_int = 0x00002000;
 pub const CLONE_VFORK          : c_int = 0x00004000;
 pub const CLONE_PARENT         : c_int = 0x00008000;
@@ -4803,6 +5088,28 @@ pub const CLONE_NEWPID         : c_int = 0x20000000;
 pub const CLONE_NEWNET         : c_int = 0x40000000;
 pub const CLONE_IO             : c_int = 0x80000000;
 
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct clone_args {
+    pub flags: __u64,
+    pub pidfd: __u64,
+    pub child_tid: __u64,
+    pub parent_tid: __u64,
+    pub exit_signal: __u64,
+    pub stack: __u64,
+    pub stack_size: __u64,
+    pub tls: __u64,
+    pub set_tid: __u64,
+    pub set_tid_size: __u64,
+    pub cgroup: __u64,
+}
+
+/////////////////////////////
+// include/uapi/linux/pidfd.h
+/////////////////////////////
+
+pub const PIDFD_NONBLOCK : c_uint = O_NONBLOCK as c_uint;
+
 pub const SCHED_NORMAL   : c_int = 0;
 pub const SCHED_FIFO     : c_int = 1;
 pub const SCHED_RR       : c_int = 2;
@@ -4900,6 +5207,121 @@ pub const IN_ALL_EVENTS : u32 = IN_ACCESS | IN_MODIFY | IN_ATTRIB | IN_CLOSE_WRI
 pub const IN_CLOEXEC  : c_int = O_CLOEXEC;
 pub const IN_NONBLOCK : c_int = O_NONBLOCK;
 
+////////////////////////////////
+// include/uapi/linux/fanotify.h
+////////////////////////////////
+
+pub const FAN_ACCESS         : u64 = 0x00000001;
+pub const FAN_MODIFY         : u64 = 0x00000002;
+pub const FAN_ATTRIB         : u64 = 0x00000004;
+pub const FAN_CLOSE_WRITE    : u64 = 0x00000008;
+pub const FAN_CLOSE_NOWRITE  : u64 = 0x00000010;
+pub const FAN_OPEN           : u64 = 0x00000020;
+pub const FAN_MOVED_FROM     : u64 = 0x00000040;
+pub const FAN_MOVED_TO       : u64 = 0x00000080;
+pub const FAN_CREATE         : u64 = 0x00000100;
+pub const FAN_DELETE         : u64 = 0x00000200;
+pub const FAN_DELETE_SELF    : u64 = 0x00000400;
+pub const FAN_MOVE_SELF      : u64 = 0x00000800;
+pub const FAN_OPEN_EXEC      : u64 = 0x00001000;
+pub const FAN_Q_OVERFLOW     : u64 = 0x00004000;
+pub const FAN_FS_ERROR       : u64 = 0x00008000;
+pub const FAN_OPEN_PERM      : u64 = 0x00010000;
+pub const FAN_ACCESS_PERM    : u64 = 0x00020000;
+pub const FAN_OPEN_EXEC_PERM : u64 = 0x00040000;
+pub const FAN_EVENT_ON_CHILD : u64 = 0x08000000;
+pub const FAN_RENAME         : u64 = 0x10000000;
+pub const FAN_ONDIR          : u64 = 0x40000000;
+pub const FAN_CLOSE          : u64 = FAN_CLOSE_WRITE | FAN_CLOSE_NOWRITE;
+pub const FAN_MOVE           : u64 = FAN_MOVED_FROM | FAN_MOVED_TO;
+
+pub const FAN_CLOEXEC           : c_uint = 0x00000001;
+pub const FAN_NONBLOCK          : c_uint = 0x00000002;
+pub const FAN_CLASS_NOTIF       : c_uint = 0x00000000;
+pub const FAN_CLASS_CONTENT     : c_uint = 0x00000004;
+pub const FAN_CLASS_PRE_CONTENT : c_uint = 0x00000008;
+pub const FAN_UNLIMITED_QUEUE   : c_uint = 0x00000010;
+pub const FAN_UNLIMITED_MARKS   : c_uint = 0x00000020;
+pub const FAN_ENABLE_AUDIT      : c_uint = 0x00000040;
+pub const FAN_REPORT_PIDFD      : c_uint = 0x00000080;
+pub const FAN_REPORT_TID        : c_uint = 0x00000100;
+pub const FAN_REPORT_FID        : c_uint = 0x00000200;
+pub const FAN_REPORT_DIR_FID    : c_uint = 0x00000400;
+pub const FAN_REPORT_NAME       : c_uint = 0x00000800;
+pub const FAN_REPORT_TARGET_FID : c_uint = 0x00001000;
+pub const FAN_REPORT_DFID_NAME  : c_uint = FAN_REPORT_DIR_FID | FAN_REPORT_NAME;
+
+pub const FAN_MARK_ADD                 : c_uint = 0x00000001;
+pub const FAN_MARK_REMOVE              : c_uint = 0x00000002;
+pub const FAN_MARK_DONT_FOLLOW         : c_uint = 0x00000004;
+pub const FAN_MARK_ONLYDIR             : c_uint = 0x00000008;
+pub const FAN_MARK_IGNORED_MASK        : c_uint = 0x00000020;
+pub const FAN_MARK_IGNORED_SURV_MODIFY : c_uint = 0x00000040;
+pub const FAN_MARK_FLUSH               : c_uint = 0x00000080;
+pub const FAN_MARK_EVICTABLE           : c_uint = 0x00000200;
+pub const FAN_MARK_IGNORE              : c_uint = 0x00000400;
+pub const FAN_MARK_INODE               : c_uint = 0x00000000;
+pub const FAN_MARK_MOUNT               : c_uint = 0x00000010;
+pub const FAN_MARK_FILESYSTEM          : c_uint = 0x00000100;
+
+pub const FANOTIFY_METADATA_VERSION : u8 = 3;
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct fanotify_event_metadata {
+    pub event_len:    __u32,
+    pub vers:         __u8,
+    pub reserved:     __u8,
+    pub metadata_len: __u16,
+    pub mask:         __u64,
+    pub fd:           __s32,
+    pub pid:          __s32,
+}
+
+pub const FAN_EVENT_INFO_TYPE_FID           : u8 = 1;
+pub const FAN_EVENT_INFO_TYPE_DFID_NAME     : u8 = 2;
+pub const FAN_EVENT_INFO_TYPE_DFID          : u8 = 3;
+pub const FAN_EVENT_INFO_TYPE_PIDFD         : u8 = 4;
+pub const FAN_EVENT_INFO_TYPE_ERROR         : u8 = 5;
+pub const FAN_EVENT_INFO_TYPE_OLD_DFID_NAME : u8 = 10;
+pub const FAN_EVENT_INFO_TYPE_NEW_DFID_NAME : u8 = 12;
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct fanotify_event_info_header {
+    pub info_type: __u8,
+    pub pad:       __u8,
+    pub len:       __u16,
+}
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct fanotify_event_info_fid {
+    pub hdr:    fanotify_event_info_header,
+    pub fsid:   __kernel_fsid_t,
+    pub handle: [c_uchar; 0],
+}
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct fanotify_event_info_pidfd {
+    pub hdr:   fanotify_event_info_header,
+    pub pidfd: __s32,
+}
+
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct fanotify_response {
+    pub fd:       __s32,
+    pub response: __u32,
+}
+
+pub const FAN_ALLOW : u32 = 0x01;
+pub const FAN_DENY  : u32 = 0x02;
+pub const FAN_AUDIT : u32 = 0x10;
+
+pub const FAN_NOFD : c_int = -1;
+
 //////////////////////////
 // include/linux/eventfd.h
 //////////////////////////
@@ -5107,6 +5529,17 @@ pub struct seccomp_data {
     pub args:             [__u64; 6],
 }
 
+/////////////////////////////
+// include/uapi/linux/audit.h
+/////////////////////////////
+
+pub const __AUDIT_ARCH_64BIT : __u32 = 0x80000000;
+pub const __AUDIT_ARCH_LE    : __u32 = 0x40000000;
+pub const AUDIT_ARCH_AARCH64 : __u32 = 183 | __AUDIT_ARCH_64BIT | __AUDIT_ARCH_LE;
+pub const AUDIT_ARCH_ARM     : __u32 = 40 | __AUDIT_ARCH_LE;
+pub const AUDIT_ARCH_I386    : __u32 = 3 | __AUDIT_ARCH_LE;
+pub const AUDIT_ARCH_X86_64  : __u32 = 62 | __AUDIT_ARCH_64BIT | __AUDIT_ARCH_LE;
+
 ///////////////////////
 // include/linux/swap.h
 ///////////////////////
@@ -5145,6 +5578,7 @@ pub const AT_BASE_PLATFORM : usize = 24;
 pub const AT_RANDOM        : usize = 25;
 pub const AT_HWCAP2        : usize = 26;
 pub const AT_EXECFN        : usize = 31;
+pub const AT_MINSIGSTKSZ   : usize = 51;
 
 //////////////////////////////
 // include/uapi/linux/elf-em.h
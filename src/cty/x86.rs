@@ -37,8 +37,6 @@ pub const BYTES_PER_LONG  : usize = 4;
 pub const BYTES_PER_SHORT : usize = 2;
 pub const BYTES_PER_INT   : usize = 4;
 
-pub type timespec_tv_nsec_type = c_long;
-
 // bitfield manipulation
 
 pub fn bf32_get(f: u32, start: usize, width: usize) -> u32 {
@@ -433,6 +431,8 @@ pub use ::gen::{
     SCM_TIMESTAMPING, SO_PROTOCOL, SO_DOMAIN, SO_RXQ_OVFL, SO_WIFI_STATUS,
     SCM_WIFI_STATUS, SO_PEEK_OFF, SO_NOFCS, SO_LOCK_FILTER, SO_SELECT_ERR_QUEUE,
     SO_BUSY_POLL, SO_MAX_PACING_RATE, SO_BPF_EXTENSIONS,
+    SO_TIMESTAMP_NEW, SCM_TIMESTAMP_NEW, SO_TIMESTAMPNS_NEW, SCM_TIMESTAMPNS_NEW,
+    SO_TIMESTAMPING_NEW, SCM_TIMESTAMPING_NEW,
 };
 
 //////////////////////////////////////
@@ -920,6 +920,34 @@ pub const __NR_getrandom              : usize = 355;
 pub const __NR_memfd_create           : usize = 356;
 pub const __NR_bpf                    : usize = 357;
 pub const __NR_execveat               : usize = 358;
+pub const __NR_statx                  : usize = 383;
+pub const __NR_clock_gettime64        : usize = 403;
+pub const __NR_clock_settime64        : usize = 404;
+pub const __NR_clock_adjtime64        : usize = 405;
+pub const __NR_clock_getres_time64    : usize = 406;
+pub const __NR_clock_nanosleep_time64 : usize = 407;
+pub const __NR_timer_gettime64        : usize = 408;
+pub const __NR_timer_settime64        : usize = 409;
+pub const __NR_timerfd_gettime64      : usize = 410;
+pub const __NR_timerfd_settime64      : usize = 411;
+pub const __NR_utimensat_time64       : usize = 412;
+pub const __NR_pselect6_time64        : usize = 413;
+pub const __NR_ppoll_time64           : usize = 414;
+pub const __NR_io_pgetevents_time64   : usize = 416;
+pub const __NR_recvmmsg_time64        : usize = 417;
+pub const __NR_mq_timedsend_time64    : usize = 418;
+pub const __NR_mq_timedreceive_time64 : usize = 419;
+pub const __NR_semtimedop_time64      : usize = 420;
+pub const __NR_rt_sigtimedwait_time64 : usize = 421;
+pub const __NR_futex_time64           : usize = 422;
+pub const __NR_sched_rr_get_interval_time64: usize = 423;
+pub const __NR_pidfd_send_signal      : usize = 424;
+pub const __NR_io_uring_setup         : usize = 425;
+pub const __NR_io_uring_enter         : usize = 426;
+pub const __NR_io_uring_register      : usize = 427;
+pub const __NR_pidfd_open             : usize = 434;
+pub const __NR_clone3                 : usize = 435;
+pub const __NR_pidfd_getfd            : usize = 438;
 
 //////////////////////////////////
 // arch/x86/include/uapi/asm/ldt.h
@@ -967,7 +995,7 @@ pub const AT_SYSINFO      : usize = 32;
 pub const AT_SYSINFO_EHDR : usize = 33;
 
 /// The largest AT_* value plus one.
-pub const AUX_CNT : usize = 34;
+pub const AUX_CNT : usize = 52;
 
 ///////////////////////////
 // include/uapi/linux/elf.h
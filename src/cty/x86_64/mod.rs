@@ -47,9 +47,6 @@ pub const BYTES_PER_LONG  : usize = 8;
 pub const BYTES_PER_SHORT : usize = 2;
 pub const BYTES_PER_INT   : usize = 4;
 
-// We have to define this type because x32 doesn't use a compat layer.
-pub type timespec_tv_nsec_type = __kernel_long_t;
-
 // bitfield manipulation
 
 pub fn bf32_get(f: u32, start: usize, width: usize) -> u32 {
@@ -448,6 +445,8 @@ pub use ::gen::{
     SCM_TIMESTAMPING, SO_PROTOCOL, SO_DOMAIN, SO_RXQ_OVFL, SO_WIFI_STATUS,
     SCM_WIFI_STATUS, SO_PEEK_OFF, SO_NOFCS, SO_LOCK_FILTER, SO_SELECT_ERR_QUEUE,
     SO_BUSY_POLL, SO_MAX_PACING_RATE, SO_BPF_EXTENSIONS,
+    SO_TIMESTAMP_NEW, SCM_TIMESTAMP_NEW, SO_TIMESTAMPNS_NEW, SCM_TIMESTAMPNS_NEW,
+    SO_TIMESTAMPING_NEW, SCM_TIMESTAMPING_NEW,
 };
 
 //////////////////////////////////////
@@ -869,6 +868,14 @@ pub const __NR_getrandom              : usize = 318;
 pub const __NR_memfd_create           : usize = 319;
 pub const __NR_kexec_file_load        : usize = 320;
 pub const __NR_bpf                    : usize = 321;
+pub const __NR_statx                  : usize = 332;
+pub const __NR_pidfd_send_signal      : usize = 424;
+pub const __NR_io_uring_setup         : usize = 425;
+pub const __NR_io_uring_enter         : usize = 426;
+pub const __NR_io_uring_register      : usize = 427;
+pub const __NR_pidfd_open             : usize = 434;
+pub const __NR_clone3                 : usize = 435;
+pub const __NR_pidfd_getfd            : usize = 438;
 
 // bpf.h
 
@@ -939,7 +946,7 @@ pub const ARCH_GET_GS: c_int = 0x1004;
 pub const AT_SYSINFO_EHDR : usize = 33;
 
 /// The largest AT_* value plus one.
-pub const AUX_CNT : usize = 34;
+pub const AUX_CNT : usize = 52;
 
 ///////////////////////////
 // include/uapi/linux/elf.h
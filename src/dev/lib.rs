@@ -74,7 +74,7 @@ impl Device {
     pub fn from_major_minor(major: u32, minor: u32, ty: DeviceType) -> Device {
         let x = major as u64;
         let y = minor as u64;
-        let id = (((x & 0xfffff000) << 32) | ((y & 0x00000fff) << 8) |
+        let id = (((x & 0xfffff000) << 32) | ((x & 0x00000fff) << 8) |
                   ((y & 0xffffff00) << 12) | ((y & 0x000000ff))) as DeviceId;
         Device(id, ty)
     }
@@ -18,6 +18,7 @@ extern crate lrs_fmt       as fmt;
 extern crate lrs_file      as file;
 extern crate lrs_alloc     as alloc;
 extern crate lrs_vec       as vec;
+extern crate lrs_dev       as dev;
 
 use base::prelude::*;
 mod std { pub use fmt::std::*; }
@@ -43,6 +44,10 @@ pub const DEFAULT_BUF_SIZE: usize = 2048;
 
 pub type Pool<'a> = FcPool<OncePool<'a>, FbHeap>;
 
+pub use walk::{Walk, WalkEntry, WalkOptions, Order, Follow, walk};
+
+mod walk;
+
 fn rmo_cstr<'a, S>(s: &'a S,
                    buf: &'a mut [d8]) -> Result<Rmo<'a, CStr, CString<Pool<'a>>>>
     where S: for<'b> ToRmo<Pool<'b>, CStr, CString<Pool<'b>>>,
@@ -127,17 +132,54 @@ impl<'a> Iter<'a> {
 
     fn read(&mut self) -> Result {
         self.buf_pos = 0;
-        let cap = self.buf.capacity();
-        let res = unsafe {
-            self.buf.set_len(cap);
-            getdents(self.dir.borrow(), &mut self.buf)
-        };
-        if res < 0 {
-            self.buf.truncate(0);
-            Err(Errno(-res))
-        } else {
-            self.buf.truncate(res as usize);
-            Ok(())
+        read_dirents(&self.dir, &mut self.buf)
+    }
+}
+
+/// Fills the buffer with the next directory entries of a directory.
+fn read_dirents(dir: &File, buf: &mut Vec<d8>) -> Result {
+    let cap = buf.capacity();
+    let res = unsafe {
+        buf.set_len(cap);
+        getdents(dir.borrow(), buf)
+    };
+    if res < 0 {
+        buf.truncate(0);
+        Err(Errno(-res))
+    } else {
+        buf.truncate(res as usize);
+        Ok(())
+    }
+}
+
+/// Parses the directory entry at the start of the buffer.
+///
+/// Returns the length of the entry, its inode, type, and name.
+unsafe fn parse_dirent(buf: &[d8]) -> (usize, u64, Type, &CStr) {
+    let ent = &*(buf.as_ptr() as *const linux_dirent64);
+    let ty = file_type_from_mode((ent.d_type as umode_t) << MODE_TYPE_SHIFT);
+    (ent.d_reclen as usize, ent.d_ino, ty, CStr::from_ptr(ent.d_name.as_ptr()))
+}
+
+/// Reads all entries of a directory except `.` and `..`.
+fn read_entries(dir: &File, buf: &mut Vec<d8>, entries: &mut Vec<Entry>) -> Result {
+    loop {
+        try!(read_dirents(dir, buf));
+        if buf.len() == 0 {
+            return Ok(());
+        }
+        let mut pos = 0;
+        while pos < buf.len() {
+            let (len, inode, ty, name) = unsafe { parse_dirent(&buf[pos..]) };
+            pos += len;
+            if name == "." || name == ".." {
+                continue;
+            }
+            try!(entries.push(Entry {
+                inode: inode,
+                ty:    ty,
+                name:  try!(AsRef::<ByteStr>::as_ref(name).try_to()),
+            }));
         }
     }
 }
@@ -173,26 +215,23 @@ impl<'a> Iterator for Iter<'a> {
         if self.buf_pos == self.buf.len() {
             return None;
         }
-        unsafe {
-            let ent = &*(self.buf[self.buf_pos..].as_ptr() as *const linux_dirent64);
-            let ent_len = ent.d_reclen as usize;
-            self.buf_pos += ent_len;
-            let ty = file_type_from_mode((ent.d_type as umode_t) << MODE_TYPE_SHIFT);
-            let name = CStr::from_ptr(ent.d_name.as_ptr());
-            if name == "." || name == ".." {
-                self.next()
-            } else {
-                match AsRef::<ByteStr>::as_ref(name).try_to() {
-                    Ok(n) => Some(Entry {
-                        inode: ent.d_ino,
-                        ty:    ty,
-                        name:  n,
-                    }),
-                    Err(e) => {
-                        self.set_err(e);
-                        None
-                    },
-                }
+        let (ent_len, inode, ty, name) = unsafe {
+            parse_dirent(&self.buf[self.buf_pos..])
+        };
+        self.buf_pos += ent_len;
+        if name == "." || name == ".." {
+            self.next()
+        } else {
+            match AsRef::<ByteStr>::as_ref(name).try_to() {
+                Ok(n) => Some(Entry {
+                    inode: inode,
+                    ty:    ty,
+                    name:  n,
+                }),
+                Err(e) => {
+                    self.set_err(e);
+                    None
+                },
             }
         }
     }
@@ -0,0 +1,421 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use base::{error};
+use base::error::{Errno};
+use core::{mem};
+use core::cmp::{Ord};
+use cty::{PATH_MAX};
+use str_one::{CStr, ByteStr};
+use str_two::{CString};
+use vec::{Vec};
+use fmt::{Debug, Write};
+use rmo::{ToRmo};
+use dev::{Device};
+use file::{File};
+use file::flags::{FILE_ONLY_DIRECTORY, FILE_DONT_FOLLOW_LINKS, FileFlags, Mode};
+use file::info::{Type};
+use {Pool, Entry, DEFAULT_BUF_SIZE, rmo_cstr, read_entries};
+
+/// The order in which a directory and its contents are returned.
+#[derive(Copy, Eq)]
+pub enum Order {
+    /// A directory is returned before its contents.
+    Pre,
+    /// A directory is returned after its contents.
+    Post,
+}
+
+/// The policy for following symbolic links.
+#[derive(Copy, Eq)]
+pub enum Follow {
+    /// Symbolic links are never followed, not even the starting point.
+    Never,
+    /// Only the starting point is followed if it is a symbolic link.
+    Root,
+    /// All symbolic links to directories are followed.
+    Always,
+}
+
+/// Options of a directory walk.
+#[derive(Copy)]
+pub struct WalkOptions {
+    /// The order in which directories are returned. Default: `Order::Pre`.
+    pub order: Order,
+    /// The maximum depth of returned entries. Default: `None`.
+    ///
+    /// The entries of the starting point have depth `1`.
+    pub max_depth: Option<usize>,
+    /// The policy for following symbolic links. Default: `Follow::Root`.
+    pub follow: Follow,
+    /// Whether to stay on the filesystem of the starting point. Default: `false`.
+    pub same_fs: bool,
+    /// Whether the entries of each directory are sorted by name. Default: `false`.
+    pub sorted: bool,
+}
+
+impl WalkOptions {
+    /// Creates the default options.
+    pub fn new() -> WalkOptions {
+        WalkOptions {
+            order: Order::Pre,
+            max_depth: None,
+            follow: Follow::Root,
+            same_fs: false,
+            sorted: false,
+        }
+    }
+}
+
+/// Creates a recursive iterator over the entries in a directory tree.
+///
+/// [argument, path]
+/// The path of the directory at which the walk starts.
+///
+/// [argument, opts]
+/// The options of the walk.
+///
+/// [argument, error]
+/// Optional storage space for errors that occur during the walk.
+///
+/// = Remarks
+///
+/// The starting point itself is not returned. Subdirectories are opened relative to
+/// their parent so that the length of the paths is not limited by `PATH_MAX` and
+/// renaming a parent during the walk does not redirect it.
+///
+/// If the error argument is not `None`, errors will be stored in its place. An error
+/// that occurs while opening or reading a subdirectory does not end the walk. The
+/// subdirectory is skipped and the walk continues with the next entry. After the walk
+/// the error variable should be inspected for an error.
+///
+/// = Examples
+///
+/// ----
+/// let mut opts = WalkOptions::new();
+/// opts.sorted = true;
+/// let mut walk = dir::walk("/etc", opts, None);
+/// while let Some(entry) = walk.next() {
+///     if entry.name() == ".git" {
+///         walk.prune();
+///     }
+/// }
+/// ----
+pub fn walk<'a, S>(path: S, opts: WalkOptions, error: Option<&'a mut Result>) -> Walk<'a>
+    where S: for<'b> ToRmo<Pool<'b>, CStr, CString<Pool<'b>>>,
+{
+    let mut buf: [d8; PATH_MAX] = unsafe { mem::uninit() };
+    let mut walk = Walk {
+        stack: Vec::new(),
+        path: Vec::new(),
+        buf: Vec::new(),
+        pending: None,
+        root_dev: None,
+        opts: opts,
+        err: error,
+    };
+    let res = match rmo_cstr(&path, &mut buf) {
+        Ok(p) => walk.open_root(&p),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = res {
+        walk.set_err(e);
+        walk.stack.truncate(0);
+    }
+    walk
+}
+
+/// An entry in a directory tree.
+pub struct WalkEntry {
+    /// The inode of the entry.
+    pub inode: u64,
+    /// The type of the entry.
+    ///
+    /// = Remarks
+    ///
+    /// If symbolic links are followed, this is the type of the target of the link.
+    pub ty: Type,
+    /// The depth of the entry.
+    pub depth: usize,
+    /// The path of the entry relative to the starting point.
+    pub path: Vec<u8>,
+    name: usize,
+}
+
+impl WalkEntry {
+    /// Returns the name of the entry.
+    pub fn name(&self) -> &ByteStr {
+        self.path[self.name..].as_ref()
+    }
+}
+
+impl Debug for WalkEntry {
+    fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+        write!(w, "WalkEntry {{ inode: {}, ty: {:?}, depth: {}, path: {:?} }}",
+               self.inode, self.ty, self.depth, self.path.as_str())
+    }
+}
+
+struct Frame {
+    dir: File,
+    entries: Vec<Entry>,
+    pos: usize,
+    depth: usize,
+    parent_len: usize,
+    id: (Device, u64),
+    post: Option<WalkEntry>,
+}
+
+/// A recursive iterator over the entries in a directory tree.
+pub struct Walk<'a> {
+    stack: Vec<Frame>,
+    path: Vec<u8>,
+    buf: Vec<d8>,
+    pending: Option<Vec<u8>>,
+    root_dev: Option<Device>,
+    opts: WalkOptions,
+    err: Option<&'a mut Result>,
+}
+
+impl<'a> Walk<'a> {
+    fn set_err(&mut self, e: Errno) {
+        if let Some(ref mut err) = self.err {
+            **err = Err(e);
+        }
+    }
+
+    fn open_root(&mut self, path: &CStr) -> Result {
+        try!(self.buf.reserve(DEFAULT_BUF_SIZE));
+        let mut flags = FILE_ONLY_DIRECTORY;
+        if self.opts.follow == Follow::Never {
+            flags = flags | FILE_DONT_FOLLOW_LINKS;
+        }
+        let dir = try!(File::open(path, flags, Mode(0)));
+        let info = try!(dir.info());
+        if self.opts.same_fs {
+            self.root_dev = Some(info.device());
+        }
+        if let Some(0) = self.opts.max_depth {
+            return Ok(());
+        }
+        self.push_frame(dir, (info.device(), info.inode()), 1, 0)
+    }
+
+    fn push_frame(&mut self, dir: File, id: (Device, u64), depth: usize,
+                  parent_len: usize) -> Result {
+        let mut entries = Vec::new();
+        try!(read_entries(&dir, &mut self.buf, &mut entries));
+        if self.opts.sorted {
+            entries.sort_by(|a, b| a.name[..].cmp(&b.name[..]));
+        }
+        try!(self.stack.push(Frame {
+            dir: dir,
+            entries: entries,
+            pos: 0,
+            depth: depth,
+            parent_len: parent_len,
+            id: id,
+            post: None,
+        }));
+        Ok(())
+    }
+
+    /// Opens a subdirectory of the current directory and pushes it on the stack.
+    ///
+    /// Returns the post-order entry if the directory is not entered.
+    fn descend(&mut self, name: &[u8], post: Option<WalkEntry>) -> Option<WalkEntry> {
+        let depth = self.stack[self.stack.len() - 1].depth + 1;
+        let mut flags = FILE_ONLY_DIRECTORY;
+        if self.opts.follow != Follow::Always {
+            flags = flags | FILE_DONT_FOLLOW_LINKS;
+        }
+        let dir = match self.open_sub(name, flags) {
+            Ok(dir) => dir,
+            Err(e) => {
+                self.set_err(e);
+                return post;
+            },
+        };
+        let info = match dir.info() {
+            Ok(info) => info,
+            Err(e) => {
+                self.set_err(e);
+                return post;
+            },
+        };
+        let id = (info.device(), info.inode());
+        if let Some(dev) = self.root_dev {
+            if dev != id.0 {
+                return post;
+            }
+        }
+        if self.stack.iter().any(|f| f.id == id) {
+            self.set_err(error::TooManySymlinks);
+            return post;
+        }
+
+        let parent_len = self.path.len();
+        if let Err(e) = self.push_path(name) {
+            self.set_err(e);
+            return post;
+        }
+        match self.push_frame(dir, id, depth, parent_len) {
+            Ok(()) => {
+                self.stack.last_mut().unwrap().post = post;
+                None
+            },
+            Err(e) => {
+                self.set_err(e);
+                self.path.truncate(parent_len);
+                post
+            },
+        }
+    }
+
+    fn open_sub(&self, name: &[u8], flags: FileFlags) -> Result<File> {
+        let dir = &self.stack[self.stack.len() - 1].dir;
+        dir.rel_open(name, flags, Mode(0))
+    }
+
+    fn push_path(&mut self, name: &[u8]) -> Result {
+        if self.path.len() > 0 {
+            try!(self.path.push(b'/'));
+        }
+        self.path.push_all(name)
+    }
+
+    fn entry(&self, ent: &Entry, ty: Type, depth: usize) -> Result<WalkEntry> {
+        let sep = if self.path.len() > 0 { 1 } else { 0 };
+        let name = self.path.len() + sep;
+        let mut path = try!(Vec::with_capacity(name + ent.name.len()));
+        try!(path.push_all(&self.path));
+        if sep == 1 {
+            try!(path.push(b'/'));
+        }
+        try!(path.push_all(&ent.name));
+        Ok(WalkEntry {
+            inode: ent.inode,
+            ty: ty,
+            depth: depth,
+            path: path,
+            name: name,
+        })
+    }
+
+    /// Determines the type of an entry, following symbolic links if requested.
+    fn entry_type(&self, ent: &Entry) -> Type {
+        let dir = &self.stack[self.stack.len() - 1].dir;
+        let name: &[u8] = &ent.name;
+        match ent.ty {
+            Type::Unknown => match dir.rel_info_no_follow(name) {
+                Ok(info) => info.file_type(),
+                _ => Type::Unknown,
+            },
+            Type::SymLink if self.opts.follow == Follow::Always => {
+                match dir.rel_info(name) {
+                    Ok(info) => info.file_type(),
+                    _ => Type::SymLink,
+                }
+            },
+            ty => ty,
+        }
+    }
+
+    /// Skips the contents of the directory that was returned last.
+    ///
+    /// = Remarks
+    ///
+    /// This has no effect if the last entry was not a directory or if the walk returns
+    /// directories in post-order.
+    pub fn prune(&mut self) {
+        self.pending = None;
+    }
+
+    /// Returns the directory that contains the entry that was returned last.
+    ///
+    /// = Remarks
+    ///
+    /// This can be used to open the entry relative to its parent without constructing
+    /// an absolute path.
+    ///
+    /// = Examples
+    ///
+    /// ----
+    /// while let Some(entry) = walk.next() {
+    ///     if entry.ty == Type::File {
+    ///         let file = walk.dir().unwrap().rel_open_read(entry.name()).unwrap();
+    ///     }
+    /// }
+    /// ----
+    pub fn dir(&self) -> Option<&File> {
+        self.stack.last().map(|f| &f.dir)
+    }
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = WalkEntry;
+
+    fn next(&mut self) -> Option<WalkEntry> {
+        if let Some(name) = self.pending.take() {
+            self.descend(&name, None);
+        }
+        loop {
+            let next = match self.stack.last_mut() {
+                Some(frame) => {
+                    if frame.pos < frame.entries.len() {
+                        let ent = &mut frame.entries[frame.pos];
+                        frame.pos += 1;
+                        let ent = Entry {
+                            inode: ent.inode,
+                            ty: ent.ty,
+                            name: mem::replace(&mut ent.name, Vec::new()),
+                        };
+                        Some((ent, frame.depth))
+                    } else {
+                        None
+                    }
+                },
+                None => return None,
+            };
+            let (ent, depth) = match next {
+                Some(next) => next,
+                None => {
+                    let frame = self.stack.pop().unwrap();
+                    self.path.truncate(frame.parent_len);
+                    if frame.post.is_some() {
+                        return frame.post;
+                    }
+                    continue;
+                },
+            };
+
+            let ty = self.entry_type(&ent);
+            let entry = match self.entry(&ent, ty, depth) {
+                Ok(e) => e,
+                Err(e) => {
+                    self.set_err(e);
+                    self.stack.truncate(0);
+                    return None;
+                },
+            };
+            let descend = ty == Type::Directory &&
+                                self.opts.max_depth.map(|m| depth < m).unwrap_or(true);
+            if !descend {
+                return Some(entry);
+            }
+            match self.opts.order {
+                Order::Pre => {
+                    self.pending = Some(ent.name);
+                    return Some(entry);
+                },
+                Order::Post => {
+                    if let Some(entry) = self.descend(&ent.name, Some(entry)) {
+                        return Some(entry);
+                    }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use base::{error};
+use str_one::{CStr, NoNullStr};
+use str_two::{CString};
+use vec::{Vec};
+use alloc::{self, MemPool};
+use c_ptr_ptr::{CPtrPtr};
+use {LOCK, EnvpIter, envp, var_name};
+
+/// An owned set of environment variables.
+///
+/// = Remarks
+///
+/// This can be used to construct the environment of a child process without modifying
+/// the environment of this process.
+///
+/// = Examples
+///
+/// ----
+/// let mut env: Environment = Environment::current().unwrap();
+/// env.set("LANG", "C").unwrap();
+/// env.remove("DISPLAY").unwrap();
+/// let mut envp = env.to_envp().unwrap();
+/// exec_with_env("ls", &args, envp.finish().unwrap().as_ptr());
+/// ----
+pub struct Environment<Heap = alloc::Heap>
+    where Heap: MemPool,
+{
+    vars: Vec<CString<Heap>, Heap>,
+}
+
+impl<H = alloc::Heap> Environment<H>
+    where H: MemPool+OutOf,
+{
+    /// Creates a new, empty environment.
+    pub fn new() -> Environment<H> {
+        Environment { vars: Vec::new() }
+    }
+
+    /// Creates a copy of the environment of this process.
+    pub fn current() -> Result<Environment<H>> {
+        let mut vars = Vec::new();
+        let _guard = LOCK.lock();
+        for var in (EnvpIter { envp: envp() }) {
+            let var: &NoNullStr = var.as_ref();
+            try!(vars.push(try!(var.try_to())));
+        }
+        Ok(Environment { vars: vars })
+    }
+
+    /// Retrieves the value of a variable.
+    ///
+    /// [argument, name]
+    /// The name of the variable.
+    ///
+    /// [return_value]
+    /// Returns the value of the variable, if any.
+    ///
+    /// = Remarks
+    ///
+    /// The lookup works like the one performed by `lrs::env::var`.
+    pub fn get<S: ?Sized>(&self, name: &S) -> Option<&CStr>
+        where S: AsRef<[u8]>,
+    {
+        let name = name.as_ref();
+        for var in &self.vars[..] {
+            let var: &CStr = var;
+            let bytes: &[u8] = var.as_ref();
+            if var_name(bytes) == name {
+                return match bytes.len() > name.len() {
+                    true => Some(&var[name.len() + 1..]),
+                    _ => Some(CStr::empty()),
+                };
+            }
+        }
+        None
+    }
+
+    /// Sets a variable.
+    ///
+    /// [argument, name]
+    /// The name of the variable.
+    ///
+    /// [argument, val]
+    /// The value of the variable.
+    ///
+    /// = Remarks
+    ///
+    /// If the name contains a `=` character, an error is returned. An existing variable
+    /// with the same name is replaced.
+    pub fn set<S: ?Sized, V: ?Sized>(&mut self, name: &S, val: &V) -> Result
+        where S: TryAsRef<NoNullStr>,
+              V: TryAsRef<NoNullStr>,
+    {
+        let name: &[u8] = try!(name.try_as_ref()).as_ref();
+        let val: &[u8] = try!(val.try_as_ref()).as_ref();
+        if name.find(|&b| b == b'=').is_some() {
+            return Err(error::InvalidArgument);
+        }
+        let mut var: Vec<u8, H> = try!(Vec::with_capacity(name.len() + val.len() + 2));
+        var.push_all(name);
+        var.push(b'=');
+        var.push_all(val);
+        var.push(0);
+        let var = unsafe { CString::from_bytes_unchecked(var) };
+        self.remove_bytes(name);
+        self.vars.push(var)
+    }
+
+    /// Removes a variable.
+    ///
+    /// [argument, name]
+    /// The name of the variable.
+    pub fn remove<S: ?Sized>(&mut self, name: &S) -> Result
+        where S: TryAsRef<NoNullStr>,
+    {
+        let name: &[u8] = try!(name.try_as_ref()).as_ref();
+        self.remove_bytes(name);
+        Ok(())
+    }
+
+    fn remove_bytes(&mut self, name: &[u8]) {
+        let mut i = 0;
+        while i < self.vars.len() {
+            let matches = {
+                let var: &[u8] = self.vars[i].as_ref();
+                var_name(var) == name
+            };
+            if matches {
+                self.vars.drain(i..i+1);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    /// Removes all variables.
+    pub fn clear(&mut self) {
+        self.vars.truncate(0);
+    }
+
+    /// Returns the number of variables.
+    pub fn len(&self) -> usize {
+        self.vars.len()
+    }
+
+    /// Returns the variables in the `name=value` form.
+    pub fn vars(&self) -> &[CString<H>] {
+        &self.vars
+    }
+
+    /// Renders the environment into an array that can be passed to `execve`.
+    ///
+    /// = Remarks
+    ///
+    /// Call `finish` on the returned object to get the null-terminated array of
+    /// pointers.
+    ///
+    /// = See also
+    ///
+    /// * link:lrs::string::CPtrPtr
+    pub fn to_envp(&self) -> Result<CPtrPtr<H>> {
+        let mut envp = try!(CPtrPtr::new());
+        for var in &self.vars[..] {
+            try!(envp.push(var));
+        }
+        Ok(envp)
+    }
+}
@@ -15,6 +15,8 @@ extern crate lrs_rmo     as rmo;
 extern crate lrs_alloc   as alloc;
 extern crate lrs_vec     as vec;
 extern crate lrs_syscall as syscall;
+extern crate lrs_lock    as lock;
+extern crate lrs_c_ptr_ptr as c_ptr_ptr;
 
 use base::prelude::*;
 use core::slice::{Split};
@@ -22,14 +24,94 @@ use core::{mem};
 use str_one::{CStr, NoNullStr};
 use str_two::{CString};
 use alloc::{MemPool, FbHeap, FcPool, OncePool};
-use rt::{env};
 use vec::{Vec};
 use base::{error};
-use cty::{PATH_MAX};
+use cty::{PATH_MAX, c_char};
 use rmo::{Rmo, ToRmo};
+use lock::{Lock, LockGuard};
+
+pub use environment::{Environment};
 
 mod std { pub use base::std::*; pub use cty; }
 
+mod environment;
+
+/// Protects `ENVP` and `ENVP_CAP`.
+static LOCK: Lock = Lock::new();
+
+/// The environment of the process if it has been modified. Otherwise null.
+static mut ENVP: *mut *const c_char = 0 as *mut _;
+
+/// The capacity of the allocation `ENVP` points to.
+static mut ENVP_CAP: usize = 0;
+
+/// Returns the current environment. Must be called with `LOCK` held.
+fn envp() -> *const *const c_char {
+    unsafe {
+        match ENVP.is_null() {
+            true => rt::raw_env(),
+            _ => ENVP as *const _,
+        }
+    }
+}
+
+/// An iterator over a null-terminated array of environment variables.
+struct EnvpIter {
+    envp: *const *const c_char,
+}
+
+impl Iterator for EnvpIter {
+    type Item = &'static CStr;
+    fn next(&mut self) -> Option<&'static CStr> {
+        unsafe {
+            let var = *self.envp;
+            if var.is_null() {
+                None
+            } else {
+                self.envp = self.envp.add(1);
+                Some(CStr::from_ptr(var))
+            }
+        }
+    }
+}
+
+/// Returns the name of the variable `var`.
+fn var_name(var: &[u8]) -> &[u8] {
+    var.split(|&b| b == b'=').next().unwrap()
+}
+
+/// A locked view of the raw environment of the process.
+///
+/// = Remarks
+///
+/// The environment cannot be modified while this object exists.
+pub struct RawEnv {
+    _guard: LockGuard<'static>,
+    envp: *const *const c_char,
+}
+
+impl RawEnv {
+    /// Returns a pointer to the null-terminated array of variables.
+    ///
+    /// = Remarks
+    ///
+    /// The pointer becomes invalid when this object is dropped.
+    pub fn as_ptr(&self) -> *const *const c_char {
+        self.envp
+    }
+}
+
+/// Locks the environment and returns a raw view of it.
+///
+/// = Remarks
+///
+/// This can be used to pass the environment to `execve`. Other threads that try to
+/// read or modify the environment block until the returned object is dropped.
+pub fn raw() -> RawEnv {
+    let guard = LOCK.lock();
+    RawEnv { _guard: guard, envp: envp() }
+}
+
 /// Retrieves the value of an environment variable, if any.
 ///
 /// [argument, name]
@@ -41,6 +123,8 @@ mod std { pub use base::std::*; pub use cty; }
 ///
 /// = Remarks
 ///
+/// The returned string stays valid even if the variable is modified later.
+///
 /// This function iterates over all environment variables. If if finds a variable whose
 /// whole string is equal to `name`, the empty string is returned. Otherwise it tries to
 /// split the variable at the first `=` character and compares the part before the `=` to
@@ -49,7 +133,8 @@ pub fn var<S: ?Sized>(name: &S) -> Result<&'static CStr>
     where S: AsRef<[u8]>
 {
     let bytes = name.as_ref();
-    for var in env() {
+    let _guard = LOCK.lock();
+    for var in (EnvpIter { envp: envp() }) {
         if var == bytes {
             return Ok(CStr::empty());
         }
@@ -64,12 +149,110 @@ pub fn var<S: ?Sized>(name: &S) -> Result<&'static CStr>
     Err(error::DoesNotExist)
 }
 
+/// Sets an environment variable of the process.
+///
+/// [argument, name]
+/// The name of the variable.
+///
+/// [argument, val]
+/// The value of the variable.
+///
+/// = Remarks
+///
+/// If the name contains a `=` character, an error is returned. An existing variable
+/// with the same name is replaced. The new value is visible to `var` and is passed to
+/// programs started with `process::exec` and `process::Command`.
+///
+/// The memory used by the variable is never freed because other threads might still
+/// hold references returned by `var`.
+///
+/// = See also
+///
+/// * link:lrs::env::remove_var
+pub fn set_var<S: ?Sized, V: ?Sized>(name: &S, val: &V) -> Result
+    where S: TryAsRef<NoNullStr>,
+          V: TryAsRef<NoNullStr>,
+{
+    let name: &[u8] = try!(name.try_as_ref()).as_ref();
+    let val: &[u8] = try!(val.try_as_ref()).as_ref();
+    if name.find(|&b| b == b'=').is_some() {
+        return Err(error::InvalidArgument);
+    }
+    let mut var: Vec<u8> = try!(Vec::with_capacity(name.len() + val.len() + 2));
+    var.push_all(name);
+    var.push(b'=');
+    var.push_all(val);
+    var.push(0);
+    let _guard = LOCK.lock();
+    try!(unsafe { replace_var(name, Some(var.as_ptr() as *const c_char)) });
+    // The variable is now part of the environment and must never be freed.
+    var.leak();
+    Ok(())
+}
+
+/// Removes an environment variable from the process.
+///
+/// [argument, name]
+/// The name of the variable.
+///
+/// = Remarks
+///
+/// If the variable doesn't exist, this function does nothing.
+///
+/// = See also
+///
+/// * link:lrs::env::set_var
+pub fn remove_var<S: ?Sized>(name: &S) -> Result
+    where S: TryAsRef<NoNullStr>,
+{
+    let name: &[u8] = try!(name.try_as_ref()).as_ref();
+    let _guard = LOCK.lock();
+    unsafe { replace_var(name, None) }
+}
+
+/// Replaces all variables called `name` by `new`. Must be called with `LOCK` held.
+unsafe fn replace_var(name: &[u8], new: Option<*const c_char>) -> Result {
+    let old = envp();
+    let mut count = 0;
+    while !(*old.add(count)).is_null() {
+        count += 1;
+    }
+    let mut pool = alloc::Heap::out_of(());
+    let (buf, cap) = try!(alloc::alloc_array::<*const c_char, _>(&mut pool, count + 2));
+    let mut len = 0;
+    for var in (EnvpIter { envp: old }) {
+        if var_name(var.as_ref()) != name {
+            *buf.add(len) = var.as_ptr();
+            len += 1;
+        }
+    }
+    if let Some(new) = new {
+        *buf.add(len) = new;
+        len += 1;
+    }
+    *buf.add(len) = 0 as *const c_char;
+    if !ENVP.is_null() {
+        alloc::free_array(&mut pool, ENVP, ENVP_CAP);
+    }
+    ENVP = buf;
+    ENVP_CAP = cap;
+    Ok(())
+}
+
 fn path_split(b: &u8) -> bool { *b == b':' }
 
 /// Returns an iterator over the paths in the `PATH` environment variable.
 pub fn path() -> Result<PathIter> {
-    let bytes: &[u8] = try!(var("PATH")).as_ref();
-    Ok(PathIter { path: bytes.split(path_split) })
+    Ok(split_path(try!(var("PATH"))))
+}
+
+/// Returns an iterator over the paths in a `PATH`-like string.
+///
+/// [argument, path]
+/// A colon-separated list of paths.
+pub fn split_path(path: &'static CStr) -> PathIter {
+    let bytes: &[u8] = path.as_ref();
+    PathIter { path: bytes.split(path_split) }
 }
 
 /// An iterator over the paths in the `PATH` environment variable.
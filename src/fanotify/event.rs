@@ -0,0 +1,190 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use cty::{
+    FAN_ACCESS, FAN_MODIFY, FAN_ATTRIB, FAN_CLOSE_WRITE, FAN_CLOSE_NOWRITE, FAN_OPEN,
+    FAN_MOVED_FROM, FAN_MOVED_TO, FAN_CREATE, FAN_DELETE, FAN_DELETE_SELF,
+    FAN_MOVE_SELF, FAN_OPEN_EXEC, FAN_Q_OVERFLOW, FAN_FS_ERROR, FAN_OPEN_PERM,
+    FAN_ACCESS_PERM, FAN_OPEN_EXEC_PERM, FAN_EVENT_ON_CHILD, FAN_RENAME, FAN_ONDIR,
+    FAN_CLOSE, FAN_MOVE,
+};
+use fmt::{Debug, Write};
+use core::ops::{BitOr, BitAnd, Not};
+
+/// The type of a fanotify event.
+///
+/// [field, 1]
+/// The integer constant associated with the event type.
+///
+/// = Remarks
+///
+/// :events: link:lrs::fanotify::events
+///
+/// See {events} for pre-defined constants.
+///
+/// = See also
+///
+/// * {events}
+#[derive(Pod, Eq)]
+pub struct FanEvents(pub u64);
+
+impl BitAnd for FanEvents {
+    type Output = FanEvents;
+    fn bitand(self, rhs: FanEvents) -> FanEvents { FanEvents(self.0 & rhs.0) }
+}
+
+impl BitOr for FanEvents {
+    type Output = FanEvents;
+    fn bitor(self, rhs: FanEvents) -> FanEvents { FanEvents(self.0 | rhs.0) }
+}
+
+impl Not for FanEvents {
+    type Output = FanEvents;
+    fn not(self) -> FanEvents { FanEvents(!self.0) }
+}
+
+/// Dummy flag with all flags unset.
+pub const FANEV_NONE: FanEvents = FanEvents(0);
+
+/// Shortcut for `FANEV_CLOSE_WRITE | FANEV_CLOSE_READ`.
+pub const FANEV_CLOSE: FanEvents = FanEvents(FAN_CLOSE);
+
+/// Shortcut for `FANEV_MOVED_FROM | FANEV_MOVED_TO`.
+pub const FANEV_MOVE: FanEvents = FanEvents(FAN_MOVE);
+
+macro_rules! create {
+    ($($(#[$meta:meta])* flag $name:ident = $val:expr;)*) => {
+        $($(#[$meta])*  pub const $name: FanEvents = FanEvents($val);)*
+
+        impl Debug for FanEvents {
+            fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+                let raw = self.0;
+                const KNOWN_FLAGS: u64 = 0 $(| $val)*;
+                if raw & !KNOWN_FLAGS != 0 {
+                    return write!(w, "0x{:x}", raw);
+                }
+                let mut first = true;
+                $(
+                    if raw & $val != 0 {
+                        if !first { try!(w.write(b"|")); }
+                        first = false;
+                        try!(w.write_all(stringify!($name).as_bytes()));
+                    }
+                )*
+                if first {
+                    try!(w.write_all("FANEV_NONE".as_bytes()));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+create! {
+    #[doc = "A file was read.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_ACCESS therein"]
+    flag FANEV_ACCESS = FAN_ACCESS;
+
+    #[doc = "A file was modified.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_MODIFY therein"]
+    flag FANEV_MODIFY = FAN_MODIFY;
+
+    #[doc = "The metadata of a file changed.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_ATTRIB therein"]
+    flag FANEV_ATTRIB = FAN_ATTRIB;
+
+    #[doc = "A file that was opened for writing was closed.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_CLOSE_WRITE therein"]
+    flag FANEV_CLOSE_WRITE = FAN_CLOSE_WRITE;
+
+    #[doc = "A file that was not opened for writing was closed.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_CLOSE_NOWRITE therein"]
+    flag FANEV_CLOSE_READ = FAN_CLOSE_NOWRITE;
+
+    #[doc = "A file was opened.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_OPEN therein"]
+    flag FANEV_OPEN = FAN_OPEN;
+
+    #[doc = "A file was moved out of a marked directory.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_MOVED_FROM therein"]
+    flag FANEV_MOVED_FROM = FAN_MOVED_FROM;
+
+    #[doc = "A file was moved into a marked directory.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_MOVED_TO therein"]
+    flag FANEV_MOVED_TO = FAN_MOVED_TO;
+
+    #[doc = "A file was created in a marked directory.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_CREATE therein"]
+    flag FANEV_CREATE = FAN_CREATE;
+
+    #[doc = "A file was deleted from a marked directory.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_DELETE therein"]
+    flag FANEV_DELETE = FAN_DELETE;
+
+    #[doc = "A marked file was deleted.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_DELETE_SELF therein"]
+    flag FANEV_DELETE_SELF = FAN_DELETE_SELF;
+
+    #[doc = "A marked file was moved.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_MOVE_SELF therein"]
+    flag FANEV_MOVE_SELF = FAN_MOVE_SELF;
+
+    #[doc = "A file was opened for execution.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_OPEN_EXEC therein"]
+    flag FANEV_OPEN_EXEC = FAN_OPEN_EXEC;
+
+    #[doc = "The event queue overflowed.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_Q_OVERFLOW therein"]
+    flag FANEV_OVERFLOW = FAN_Q_OVERFLOW;
+
+    #[doc = "A filesystem error was detected.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_FS_ERROR therein"]
+    flag FANEV_FS_ERROR = FAN_FS_ERROR;
+
+    #[doc = "Permission to open a file is requested.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_OPEN_PERM therein"]
+    flag FANEV_OPEN_PERM = FAN_OPEN_PERM;
+
+    #[doc = "Permission to read a file is requested.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_ACCESS_PERM therein"]
+    flag FANEV_ACCESS_PERM = FAN_ACCESS_PERM;
+
+    #[doc = "Permission to open a file for execution is requested.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_OPEN_EXEC_PERM therein"]
+    flag FANEV_OPEN_EXEC_PERM = FAN_OPEN_EXEC_PERM;
+
+    #[doc = "Generate events for the children of a marked directory.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_EVENT_ON_CHILD therein"]
+    flag FANEV_ON_CHILD = FAN_EVENT_ON_CHILD;
+
+    #[doc = "A file was renamed.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_RENAME therein"]
+    flag FANEV_RENAME = FAN_RENAME;
+
+    #[doc = "The subject of the event is a directory.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify(7) and FAN_ONDIR therein"]
+    flag FANEV_DIR = FAN_ONDIR;
+}
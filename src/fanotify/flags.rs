@@ -0,0 +1,251 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use cty::{
+    c_uint,
+    FAN_NONBLOCK, FAN_CLOEXEC, FAN_CLASS_CONTENT, FAN_CLASS_PRE_CONTENT,
+    FAN_UNLIMITED_QUEUE, FAN_UNLIMITED_MARKS, FAN_ENABLE_AUDIT, FAN_REPORT_PIDFD,
+    FAN_REPORT_TID, FAN_REPORT_FID, FAN_REPORT_DIR_FID, FAN_REPORT_NAME,
+    FAN_REPORT_TARGET_FID, FAN_MARK_DONT_FOLLOW, FAN_MARK_ONLYDIR, FAN_MARK_MOUNT,
+    FAN_MARK_FILESYSTEM, FAN_MARK_EVICTABLE, FAN_MARK_IGNORE, FAN_MARK_IGNORED_MASK,
+    FAN_MARK_IGNORED_SURV_MODIFY,
+};
+use fmt::{Debug, Write};
+use core::ops::{BitOr, BitAnd, Not};
+
+/// Flags used when creating a fanotify object.
+///
+/// [field, 1]
+/// The integer constant associated with the flags.
+///
+/// = Remarks
+///
+/// :flags: link:lrs::fanotify::flags
+///
+/// See {flags} for pre-defined constants.
+///
+/// = See also
+///
+/// * {flags}
+#[derive(Pod, Eq)]
+pub struct FanotifyFlags(pub c_uint);
+
+impl BitAnd for FanotifyFlags {
+    type Output = FanotifyFlags;
+    fn bitand(self, rhs: FanotifyFlags) -> FanotifyFlags { FanotifyFlags(self.0 & rhs.0) }
+}
+
+impl BitOr for FanotifyFlags {
+    type Output = FanotifyFlags;
+    fn bitor(self, rhs: FanotifyFlags) -> FanotifyFlags { FanotifyFlags(self.0 | rhs.0) }
+}
+
+impl Not for FanotifyFlags {
+    type Output = FanotifyFlags;
+    fn not(self) -> FanotifyFlags { FanotifyFlags(!self.0) }
+}
+
+/// Dummy flag with all flags unset.
+pub const FANOTIFY_NONE: FanotifyFlags = FanotifyFlags(0);
+
+macro_rules! create {
+    ($($(#[$meta:meta])* flag $name:ident = $val:expr;)*) => {
+        $($(#[$meta])*  pub const $name: FanotifyFlags = FanotifyFlags($val);)*
+
+        impl Debug for FanotifyFlags {
+            fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+                let raw = self.0;
+                const KNOWN_FLAGS: c_uint = 0 $(| $val)*;
+                if raw & !KNOWN_FLAGS != 0 {
+                    return write!(w, "0x{:x}", raw as u64);
+                }
+                let mut first = true;
+                $(
+                    if raw & $val != 0 {
+                        if !first { try!(w.write(b"|")); }
+                        first = false;
+                        try!(w.write_all(stringify!($name).as_bytes()));
+                    }
+                )*
+                if first {
+                    try!(w.write_all("FANOTIFY_NONE".as_bytes()));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+create! {
+    #[doc = "Don't block when reading from the returned file descriptor.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_NONBLOCK therein"]
+    flag FANOTIFY_DONT_BLOCK = FAN_NONBLOCK;
+
+    #[doc = "Close the file descriptor when `exec` is called.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_CLOEXEC therein"]
+    flag FANOTIFY_CLOSE_ON_EXEC = FAN_CLOEXEC;
+
+    #[doc = "Receive permission events after the content of files is final.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_CLASS_CONTENT therein"]
+    flag FANOTIFY_CLASS_CONTENT = FAN_CLASS_CONTENT;
+
+    #[doc = "Receive permission events before the content of files is final.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_CLASS_PRE_CONTENT therein"]
+    flag FANOTIFY_CLASS_PRE_CONTENT = FAN_CLASS_PRE_CONTENT;
+
+    #[doc = "Remove the limit on the number of queued events.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_UNLIMITED_QUEUE therein"]
+    flag FANOTIFY_UNLIMITED_QUEUE = FAN_UNLIMITED_QUEUE;
+
+    #[doc = "Remove the limit on the number of marks.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_UNLIMITED_MARKS therein"]
+    flag FANOTIFY_UNLIMITED_MARKS = FAN_UNLIMITED_MARKS;
+
+    #[doc = "Allow permission responses to generate audit records.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_ENABLE_AUDIT therein"]
+    flag FANOTIFY_AUDIT = FAN_ENABLE_AUDIT;
+
+    #[doc = "Report a pidfd of the process that caused the event.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_REPORT_PIDFD therein"]
+    flag FANOTIFY_REPORT_PIDFD = FAN_REPORT_PIDFD;
+
+    #[doc = "Report the thread id instead of the process id.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_REPORT_TID therein"]
+    flag FANOTIFY_REPORT_TID = FAN_REPORT_TID;
+
+    #[doc = "Identify files by file handles instead of file descriptors.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_REPORT_FID therein"]
+    flag FANOTIFY_REPORT_FID = FAN_REPORT_FID;
+
+    #[doc = "Report the file handle of the directory containing the file.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_REPORT_DIR_FID therein"]
+    flag FANOTIFY_REPORT_DIR_FID = FAN_REPORT_DIR_FID;
+
+    #[doc = "Report the name of the file relative to its directory.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_REPORT_NAME therein"]
+    flag FANOTIFY_REPORT_NAME = FAN_REPORT_NAME;
+
+    #[doc = "Report the file handle of the child in directory events.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_init(2) and FAN_REPORT_TARGET_FID therein"]
+    flag FANOTIFY_REPORT_TARGET_FID = FAN_REPORT_TARGET_FID;
+}
+
+/// Flags used when adding or removing a mark.
+///
+/// [field, 1]
+/// The integer constant associated with the flags.
+///
+/// = Remarks
+///
+/// :flags: link:lrs::fanotify::flags
+///
+/// See {flags} for pre-defined constants.
+///
+/// = See also
+///
+/// * {flags}
+#[derive(Pod, Eq)]
+pub struct MarkFlags(pub c_uint);
+
+impl BitAnd for MarkFlags {
+    type Output = MarkFlags;
+    fn bitand(self, rhs: MarkFlags) -> MarkFlags { MarkFlags(self.0 & rhs.0) }
+}
+
+impl BitOr for MarkFlags {
+    type Output = MarkFlags;
+    fn bitor(self, rhs: MarkFlags) -> MarkFlags { MarkFlags(self.0 | rhs.0) }
+}
+
+impl Not for MarkFlags {
+    type Output = MarkFlags;
+    fn not(self) -> MarkFlags { MarkFlags(!self.0) }
+}
+
+/// Dummy flag with all flags unset.
+pub const MARK_NONE: MarkFlags = MarkFlags(0);
+
+macro_rules! create {
+    ($($(#[$meta:meta])* flag $name:ident = $val:expr;)*) => {
+        $($(#[$meta])*  pub const $name: MarkFlags = MarkFlags($val);)*
+
+        impl Debug for MarkFlags {
+            fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+                let raw = self.0;
+                const KNOWN_FLAGS: c_uint = 0 $(| $val)*;
+                if raw & !KNOWN_FLAGS != 0 {
+                    return write!(w, "0x{:x}", raw as u64);
+                }
+                let mut first = true;
+                $(
+                    if raw & $val != 0 {
+                        if !first { try!(w.write(b"|")); }
+                        first = false;
+                        try!(w.write_all(stringify!($name).as_bytes()));
+                    }
+                )*
+                if first {
+                    try!(w.write_all("MARK_NONE".as_bytes()));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+create! {
+    #[doc = "Don't follow symlinks when adding the mark.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_mark(2) and FAN_MARK_DONT_FOLLOW therein"]
+    flag MARK_DONT_FOLLOW_LINKS = FAN_MARK_DONT_FOLLOW;
+
+    #[doc = "Only add the mark if the path refers to a directory.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_mark(2) and FAN_MARK_ONLYDIR therein"]
+    flag MARK_ONLY_DIRECTORY = FAN_MARK_ONLYDIR;
+
+    #[doc = "Mark the mount containing the path instead of the inode.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_mark(2) and FAN_MARK_MOUNT therein"]
+    flag MARK_MOUNT = FAN_MARK_MOUNT;
+
+    #[doc = "Mark the filesystem containing the path instead of the inode.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_mark(2) and FAN_MARK_FILESYSTEM therein"]
+    flag MARK_FILESYSTEM = FAN_MARK_FILESYSTEM;
+
+    #[doc = "Don't pin the marked inode in the inode cache.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_mark(2) and FAN_MARK_EVICTABLE therein"]
+    flag MARK_EVICTABLE = FAN_MARK_EVICTABLE;
+
+    #[doc = "Interpret the events as events to be ignored.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_mark(2) and FAN_MARK_IGNORE therein"]
+    flag MARK_IGNORE = FAN_MARK_IGNORE;
+
+    #[doc = "Interpret the events as events to be ignored (legacy).\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_mark(2) and FAN_MARK_IGNORED_MASK therein"]
+    flag MARK_IGNORED_MASK = FAN_MARK_IGNORED_MASK;
+
+    #[doc = "Don't clear the ignored events when the file is modified.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:fanotify_mark(2) and FAN_MARK_IGNORED_SURV_MODIFY therein"]
+    flag MARK_IGNORED_SURVIVE_MODIFY = FAN_MARK_IGNORED_SURV_MODIFY;
+}
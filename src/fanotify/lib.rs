@@ -0,0 +1,476 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+#![crate_name = "lrs_fanotify"]
+#![crate_type = "lib"]
+#![feature(custom_derive, associated_consts)]
+#![no_std]
+
+extern crate lrs_base as base;
+extern crate lrs_cty as cty;
+extern crate lrs_fmt as fmt;
+extern crate lrs_syscall as syscall;
+extern crate lrs_fd as fd;
+extern crate lrs_rv as rv;
+extern crate lrs_str_one as str_one;
+extern crate lrs_str_two as str_two;
+extern crate lrs_io as io;
+extern crate lrs_alloc as alloc;
+extern crate lrs_rmo as rmo;
+extern crate lrs_file as file;
+extern crate lrs_process as process;
+
+use base::prelude::*;
+use syscall::{close, fanotify_init, fanotify_mark};
+use base::undef::{UndefState};
+use io::{Read, Write};
+use cty::{
+    c_int, c_uint, pid_t, PATH_MAX, AT_FDCWD, FAN_MARK_ADD, FAN_MARK_REMOVE,
+    FAN_MARK_FLUSH, FAN_ALLOW, FAN_DENY, FAN_AUDIT, fanotify_event_metadata,
+    fanotify_event_info_header, fanotify_response, __kernel_fsid_t,
+    FAN_EVENT_INFO_TYPE_FID, FAN_EVENT_INFO_TYPE_DFID_NAME, FAN_EVENT_INFO_TYPE_DFID,
+    FAN_EVENT_INFO_TYPE_PIDFD, FAN_EVENT_INFO_TYPE_OLD_DFID_NAME,
+    FAN_EVENT_INFO_TYPE_NEW_DFID_NAME, FANOTIFY_METADATA_VERSION,
+};
+use core::{mem};
+use alloc::{FbHeap, FcPool, OncePool};
+use rmo::{Rmo, ToRmo};
+use fd::{FdContainer};
+use file::{File};
+use file::flags::{FileFlags};
+use process::pidfd::{PidFd};
+use event::{FanEvents};
+use flags::{FanotifyFlags, MarkFlags};
+use str_one::{CStr};
+use str_two::{CString};
+
+mod std { pub use fmt::std::*; pub use cty; }
+
+pub mod flags;
+pub mod event;
+
+pub type Pool<'a> = FcPool<OncePool<'a>, FbHeap>;
+
+fn rmo_cstr<'a, S>(s: &'a S,
+                   buf: &'a mut [d8]) -> Result<Rmo<'a, CStr, CString<Pool<'a>>>>
+    where S: for<'b> ToRmo<Pool<'b>, CStr, CString<Pool<'b>>>,
+{
+    s.to_rmo_with(FcPool::new(OncePool::new(buf), FbHeap::out_of(())))
+}
+
+fn read<T: Pod>(data: &[u8]) -> Option<T> {
+    if data.len() < mem::size_of::<T>() {
+        return None;
+    }
+    let mut val: T = mem::zeroed();
+    mem::copy(val.as_mut(), &data[..mem::size_of::<T>()]);
+    Some(val)
+}
+
+/// A response to a permission event.
+#[derive(Copy, Eq)]
+pub enum Response {
+    /// Allow the operation.
+    Allow,
+    /// Deny the operation.
+    Deny,
+}
+
+/// A fanotify object.
+pub struct Fanotify {
+    fd: c_int,
+    owned: bool,
+}
+
+impl Fanotify {
+    /// Creates a new fanotify object.
+    ///
+    /// [argument, flags]
+    /// Flags to be used when creating the object.
+    ///
+    /// [argument, event_flags]
+    /// The flags of the file descriptors created for events, e.g., `FILE_READ_ONLY`.
+    ///
+    /// = Remarks
+    ///
+    /// This usually requires the CAP_SYS_ADMIN capability.
+    ///
+    /// = See also
+    ///
+    /// * link:man:fanotify_init(2)
+    pub fn new(flags: FanotifyFlags, event_flags: FileFlags) -> Result<Fanotify> {
+        let fd = try!(rv!(fanotify_init(flags.0, event_flags.0 as c_uint), -> c_int));
+        Ok(Fanotify::from_owned(fd))
+    }
+
+    fn mark<P>(&self, op: c_uint, path: Option<P>, events: FanEvents,
+               flags: MarkFlags) -> Result
+        where P: for<'a> ToRmo<Pool<'a>, CStr, CString<Pool<'a>>>,
+    {
+        let mut buf: [d8; PATH_MAX] = unsafe { mem::uninit() };
+        let path = match path {
+            Some(ref p) => Some(try!(rmo_cstr(p, &mut buf))),
+            _ => None,
+        };
+        let path = path.as_ref().map(|p| &**p);
+        rv!(fanotify_mark(self.fd, op | flags.0, events.0, AT_FDCWD, path))
+    }
+
+    /// Adds events to the mark of a path.
+    ///
+    /// [argument, path]
+    /// The path whose inode, mount, or filesystem will be marked.
+    ///
+    /// [argument, events]
+    /// The events to add to the mark.
+    ///
+    /// [argument, flags]
+    /// Flags that determine what kind of mark is modified.
+    ///
+    /// = Remarks
+    ///
+    /// By default the inode of the path is marked. The `MARK_MOUNT` and
+    /// `MARK_FILESYSTEM` flags can be used to mark a whole mount or filesystem.
+    ///
+    /// = See also
+    ///
+    /// * link:man:fanotify_mark(2)
+    pub fn add_mark<P>(&self, path: P, events: FanEvents, flags: MarkFlags) -> Result
+        where P: for<'a> ToRmo<Pool<'a>, CStr, CString<Pool<'a>>>,
+    {
+        self.mark(FAN_MARK_ADD, Some(path), events, flags)
+    }
+
+    /// Removes events from the mark of a path.
+    ///
+    /// [argument, path]
+    /// The path whose inode, mount, or filesystem is marked.
+    ///
+    /// [argument, events]
+    /// The events to remove from the mark.
+    ///
+    /// [argument, flags]
+    /// Flags that determine what kind of mark is modified.
+    ///
+    /// = See also
+    ///
+    /// * link:man:fanotify_mark(2)
+    pub fn remove_mark<P>(&self, path: P, events: FanEvents, flags: MarkFlags) -> Result
+        where P: for<'a> ToRmo<Pool<'a>, CStr, CString<Pool<'a>>>,
+    {
+        self.mark(FAN_MARK_REMOVE, Some(path), events, flags)
+    }
+
+    /// Removes all marks of a kind.
+    ///
+    /// [argument, flags]
+    /// `MARK_NONE` to remove all inode marks, `MARK_MOUNT` to remove all mount marks,
+    /// or `MARK_FILESYSTEM` to remove all filesystem marks.
+    ///
+    /// = See also
+    ///
+    /// * link:man:fanotify_mark(2) and FAN_MARK_FLUSH therein
+    pub fn flush_marks(&self, flags: MarkFlags) -> Result {
+        self.mark(FAN_MARK_FLUSH, None::<&CStr>, FanEvents(0), flags)
+    }
+
+    /// Reads events and creates an iterator over those events.
+    ///
+    /// [argument, buf]
+    /// The buffer in which the events will be stored.
+    ///
+    /// = Remarks
+    ///
+    /// Each event that carries a file descriptor owns it. The descriptors of events
+    /// that are not consumed by the iterator are closed when the iterator is dropped.
+    pub fn events<'a>(&self, buf: &'a mut [d8]) -> Result<FanEventIter<'a>> {
+        let len = try!(self.as_fdio().read(buf));
+        let buf: &'a [d8] = buf;
+        unsafe { Ok(FanEventIter { buf: buf[..len].as_bytes() }) }
+    }
+
+    /// Answers a permission event.
+    ///
+    /// [argument, file]
+    /// The file of the permission event.
+    ///
+    /// [argument, response]
+    /// Whether the operation is allowed.
+    ///
+    /// [argument, audit]
+    /// Whether an audit record is created for the decision.
+    ///
+    /// = Remarks
+    ///
+    /// Auditing requires the object to have been created with `FANOTIFY_AUDIT`.
+    pub fn respond(&self, file: &File, response: Response, audit: bool) -> Result {
+        let mut res = match response {
+            Response::Allow => FAN_ALLOW,
+            Response::Deny => FAN_DENY,
+        };
+        if audit {
+            res |= FAN_AUDIT;
+        }
+        let msg = fanotify_response { fd: file.borrow(), response: res };
+        try!(self.as_fdio().write_all(unsafe { mem::as_data(&msg).as_bytes() }));
+        Ok(())
+    }
+}
+
+unsafe impl UndefState for Fanotify {
+    fn num() -> usize { bool::num() }
+
+    unsafe fn set_undef(val: *mut Fanotify, n: usize) {
+        bool::set_undef(&mut (*val).owned, n);
+    }
+
+    unsafe fn is_undef(val: *const Fanotify, n: usize) -> bool {
+        bool::is_undef(&(*val).owned, n)
+    }
+}
+
+impl Drop for Fanotify {
+    fn drop(&mut self) {
+        if self.owned {
+            close(self.fd);
+        }
+    }
+}
+
+impl Into<c_int> for Fanotify {
+    fn into(self) -> c_int {
+        let fd = self.fd;
+        mem::forget(self);
+        fd
+    }
+}
+
+impl FdContainer for Fanotify {
+    fn is_owned(&self) -> bool {
+        self.owned
+    }
+
+    fn borrow(&self) -> c_int {
+        self.fd
+    }
+
+    fn from_owned(fd: c_int) -> Fanotify {
+        Fanotify { fd: fd, owned: true }
+    }
+
+    fn from_borrowed(fd: c_int) -> Fanotify {
+        Fanotify { fd: fd, owned: false }
+    }
+}
+
+/// A fanotify event.
+pub struct FanEvent<'a> {
+    /// The events that occurred.
+    pub events: FanEvents,
+    /// The process (or thread) that caused the event.
+    pub pid: pid_t,
+    /// The file of the event.
+    ///
+    /// = Remarks
+    ///
+    /// This is `None` if the object reports file handles or if the event is a queue
+    /// overflow.
+    pub file: Option<File>,
+    /// A pidfd of the process that caused the event.
+    ///
+    /// = Remarks
+    ///
+    /// This is `None` unless the object was created with `FANOTIFY_REPORT_PIDFD` and the
+    /// kernel could create the pidfd.
+    pub pidfd: Option<PidFd>,
+    info: &'a [u8],
+}
+
+impl<'a> FanEvent<'a> {
+    /// Returns an iterator over the information records of the event.
+    pub fn info(&self) -> EventInfoIter<'a> {
+        EventInfoIter { buf: self.info }
+    }
+}
+
+/// An iterator over fanotify events.
+pub struct FanEventIter<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Iterator for FanEventIter<'a> {
+    type Item = FanEvent<'a>;
+
+    fn next(&mut self) -> Option<FanEvent<'a>> {
+        let meta: fanotify_event_metadata = match read(self.buf) {
+            Some(m) => m,
+            _ => return None,
+        };
+        if meta.vers != FANOTIFY_METADATA_VERSION {
+            // The layout of the rest of the buffer is unknown.
+            self.buf = &[];
+            return None;
+        }
+        let len = meta.event_len as usize;
+        let meta_len = meta.metadata_len as usize;
+        if len > self.buf.len() || meta_len > len ||
+                meta_len < mem::size_of::<fanotify_event_metadata>() {
+            self.buf = &[];
+            return None;
+        }
+        let info = &self.buf[meta_len..len];
+        self.buf = &self.buf[len..];
+        let mut pidfd = None;
+        for record in (EventInfoIter { buf: info }) {
+            if let EventInfo::Pidfd(fd) = record {
+                if fd >= 0 {
+                    pidfd = Some(PidFd::from_owned(fd));
+                }
+            }
+        }
+        Some(FanEvent {
+            events: FanEvents(meta.mask),
+            pid: meta.pid,
+            file: if meta.fd >= 0 { Some(File::from_owned(meta.fd)) } else { None },
+            pidfd: pidfd,
+            info: info,
+        })
+    }
+}
+
+impl<'a> Drop for FanEventIter<'a> {
+    fn drop(&mut self) {
+        while let Some(_) = self.next() { }
+    }
+}
+
+/// The kind of a file handle record.
+#[derive(Copy, Eq)]
+pub enum FileIdKind {
+    /// The handle identifies the file of the event.
+    File,
+    /// The handle identifies the directory of the event.
+    Dir,
+    /// The handle identifies the directory containing the named file.
+    DirName,
+    /// The handle identifies the old directory of a rename.
+    OldDirName,
+    /// The handle identifies the new directory of a rename.
+    NewDirName,
+}
+
+/// A file handle record of a fanotify event.
+pub struct FileId<'a> {
+    /// The kind of the record.
+    pub kind: FileIdKind,
+    /// The id of the filesystem containing the file.
+    pub fsid: [c_int; 2],
+    /// The type of the handle.
+    pub handle_type: c_int,
+    /// The opaque handle.
+    pub handle: &'a [u8],
+    /// The name of the file relative to the directory, if any.
+    pub name: Option<&'a CStr>,
+}
+
+/// An information record of a fanotify event.
+pub enum EventInfo<'a> {
+    /// A file handle.
+    FileId(FileId<'a>),
+    /// A pidfd of the process that caused the event.
+    ///
+    /// = Remarks
+    ///
+    /// The descriptor is owned by the `pidfd` field of the event and is closed when the
+    /// event is dropped. Negative values are the error codes of the kernel.
+    Pidfd(c_int),
+    /// A record of an unknown type.
+    ///
+    /// [field, 1]
+    /// The type of the record.
+    ///
+    /// [field, 2]
+    /// The payload of the record after the header.
+    Unknown(u8, &'a [u8]),
+}
+
+/// An iterator over the information records of a fanotify event.
+pub struct EventInfoIter<'a> {
+    buf: &'a [u8],
+}
+
+fn parse_fid<'a>(kind: FileIdKind, data: &'a [u8]) -> Option<FileId<'a>> {
+    let fsid_len = mem::size_of::<__kernel_fsid_t>();
+    if data.len() < fsid_len + 8 {
+        return None;
+    }
+    let (fsid, bytes, handle_type) = match (read::<__kernel_fsid_t>(data),
+                                            read::<u32>(&data[fsid_len..]),
+                                            read::<c_int>(&data[fsid_len + 4..])) {
+        (Some(f), Some(b), Some(t)) => (f, b as usize, t),
+        _ => return None,
+    };
+    let data = &data[fsid_len + 8..];
+    if data.len() < bytes {
+        return None;
+    }
+    let (handle, rest) = data.split_at(bytes);
+    let name = match kind {
+        FileIdKind::DirName | FileIdKind::OldDirName | FileIdKind::NewDirName => {
+            // The name is padded with null bytes to the alignment of the records.
+            match rest.find(|&b| b == 0) {
+                Some(n) => Some(match rest[..n + 1].try_as_ref() {
+                    Ok(name) => name,
+                    _ => return None,
+                }),
+                _ => return None,
+            }
+        },
+        _ => None,
+    };
+    Some(FileId {
+        kind: kind,
+        fsid: fsid.val,
+        handle_type: handle_type,
+        handle: handle,
+        name: name,
+    })
+}
+
+impl<'a> Iterator for EventInfoIter<'a> {
+    type Item = EventInfo<'a>;
+
+    fn next(&mut self) -> Option<EventInfo<'a>> {
+        let hdr: fanotify_event_info_header = match read(self.buf) {
+            Some(hdr) => hdr,
+            _ => return None,
+        };
+        let len = hdr.len as usize;
+        let hdr_len = mem::size_of::<fanotify_event_info_header>();
+        if len < hdr_len || len > self.buf.len() {
+            self.buf = &[];
+            return None;
+        }
+        let data = &self.buf[hdr_len..len];
+        self.buf = &self.buf[len..];
+        let kind = match hdr.info_type {
+            FAN_EVENT_INFO_TYPE_FID => FileIdKind::File,
+            FAN_EVENT_INFO_TYPE_DFID => FileIdKind::Dir,
+            FAN_EVENT_INFO_TYPE_DFID_NAME => FileIdKind::DirName,
+            FAN_EVENT_INFO_TYPE_OLD_DFID_NAME => FileIdKind::OldDirName,
+            FAN_EVENT_INFO_TYPE_NEW_DFID_NAME => FileIdKind::NewDirName,
+            FAN_EVENT_INFO_TYPE_PIDFD => {
+                return match read(data) {
+                    Some(fd) => Some(EventInfo::Pidfd(fd)),
+                    _ => Some(EventInfo::Unknown(hdr.info_type, data)),
+                };
+            },
+            ty => return Some(EventInfo::Unknown(ty, data)),
+        };
+        match parse_fid(kind, data) {
+            Some(fid) => Some(EventInfo::FileId(fid)),
+            _ => Some(EventInfo::Unknown(hdr.info_type, data)),
+        }
+    }
+}
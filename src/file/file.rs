@@ -22,7 +22,7 @@ use int::{BoundedRange};
 use syscall::{
     openat, read, write, close, pread, lseek, pwrite, readv, writev, preadv, pwritev,
     ftruncate, fsync, fdatasync, syncfs, fadvise, fstatfs, fcntl_dupfd_cloexec,
-    fcntl_getfl, fstatat, faccessat,
+    fcntl_getfl, faccessat,
     linkat, utimensat, renameat2, mkdirat, unlinkat, symlinkat, readlinkat, fchownat,
     fchmodat, fchmod, mknodat, readahead, fallocate, fsetxattr,
     fgetxattr, fremovexattr,
@@ -46,7 +46,7 @@ use flags::{
     FileFlags, Mode, AccessMode, FILE_READ_ONLY,  MemfdFlags, FileSeals, FILE_PATH,
     FILE_DONT_BLOCK, FILE_CLOSE_ON_EXEC,
 };
-use info::{Info, info_from_stat, Type, file_type_to_mode};
+use info::{Info, info_at, Type, file_type_to_mode};
 
 /// An opened file in a filesystem.
 #[derive(Eq)]
@@ -315,11 +315,9 @@ impl File {
     ///
     /// = See also
     ///
-    /// * link:man:fstatat(2)
+    /// * link:man:statx(2)
     pub fn info(&self) -> Result<Info> {
-        let mut stat = mem::zeroed();
-        try!(rv!(fstatat(self.fd, CStr::empty(), &mut stat, AT_EMPTY_PATH)));
-        Ok(info_from_stat(stat))
+        info_at(self.fd, CStr::empty(), AT_EMPTY_PATH)
     }
 
     /// Changes the read/write position of the file.
@@ -1209,15 +1207,13 @@ impl File {
     ///
     /// = See also
     ///
-    /// * link:man:fstatat(2)
+    /// * link:man:statx(2)
     pub fn rel_info<P>(&self, path: P) -> Result<Info>
         where P: for<'a> ToRmo<Pool<'a>, CStr, CString<Pool<'a>>>,
     {
         let mut buf: [d8; PATH_MAX] = unsafe { mem::uninit() };
         let path = try!(rmo_cstr(&path, &mut buf));
-        let mut stat = mem::zeroed();
-        try!(rv!(fstatat(self.fd, &path, &mut stat, 0)));
-        Ok(info_from_stat(stat))
+        info_at(self.fd, &path, 0)
     }
 
     /// Returns information about a path relative to this file without following symlinks.
@@ -1235,15 +1231,13 @@ impl File {
     ///
     /// = See also
     ///
-    /// * link:man:fstatat(2)
+    /// * link:man:statx(2)
     pub fn rel_info_no_follow<P>(&self, path: P) -> Result<Info>
         where P: for<'a> ToRmo<Pool<'a>, CStr, CString<Pool<'a>>>,
     {
         let mut buf: [d8; PATH_MAX] = unsafe { mem::uninit() };
         let path = try!(rmo_cstr(&path, &mut buf));
-        let mut stat = mem::zeroed();
-        try!(rv!(fstatat(self.fd, &path, &mut stat, AT_SYMLINK_NOFOLLOW)));
-        Ok(info_from_stat(stat))
+        info_at(self.fd, &path, AT_SYMLINK_NOFOLLOW)
     }
 
     /// Returns whether a path relative to this file points to an existing file.
@@ -3,16 +3,67 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use base::prelude::*;
+use base::{error};
+use core::{mem};
 use fmt::{Debug, Write};
 use cty::{S_IFMT, S_IFDIR, S_IFCHR, S_IFBLK, S_IFREG, S_IFIFO, S_IFLNK,
-          S_IFSOCK, umode_t};
+          S_IFSOCK, umode_t, c_int, statx, statx_timestamp, STATX_BASIC_STATS};
 use cty::alias::{InodeId, UserId, GroupId, DeviceId};
 use time_base::{Time};
 use dev::{Device, DeviceType};
 use flags::{Mode};
-use syscall::{StatType};
+use str_one::{CStr};
+use syscall::{self, StatType};
+
+/// Retrieves information about a file relative to a directory.
+///
+/// = Remarks
+///
+/// This uses statx so that the timestamps have 64 bit seconds on all architectures. If
+/// the kernel doesn't support statx or a seccomp filter rejects it with EPERM, fstatat
+/// is used instead.
+pub fn info_at(dir: c_int, path: &CStr, flags: c_int) -> Result<Info> {
+    let mut x = mem::zeroed();
+    match rv!(syscall::statx(dir, path, flags, STATX_BASIC_STATS, &mut x)) {
+        Ok(()) => return Ok(Info(x)),
+        Err(error::NotImplemented) | Err(error::NotPermitted) => { },
+        Err(e) => return Err(e),
+    }
+    let mut stat = mem::zeroed();
+    try!(rv!(syscall::fstatat(dir, path, &mut stat, flags)));
+    Ok(info_from_stat(stat))
+}
+
+pub fn info_from_stat(s: StatType) -> Info {
+    let dev = Device::from_id(s.st_dev as DeviceId, DeviceType::Block);
+    let rdev = Device::from_id(s.st_rdev as DeviceId, DeviceType::Block);
+    let mut x: statx = mem::zeroed();
+    x.stx_mask = STATX_BASIC_STATS;
+    x.stx_blksize = s.st_blksize as u32;
+    x.stx_nlink = s.st_nlink as u32;
+    x.stx_uid = s.st_uid as u32;
+    x.stx_gid = s.st_gid as u32;
+    x.stx_mode = s.st_mode as u16;
+    x.stx_ino = s.st_ino as u64;
+    x.stx_size = s.st_size as u64;
+    x.stx_blocks = s.st_blocks as u64;
+    x.stx_atime = timestamp(s.st_atime as i64, s.st_atime_nsec as u32);
+    x.stx_mtime = timestamp(s.st_mtime as i64, s.st_mtime_nsec as u32);
+    x.stx_ctime = timestamp(s.st_ctime as i64, s.st_ctime_nsec as u32);
+    x.stx_rdev_major = rdev.major();
+    x.stx_rdev_minor = rdev.minor();
+    x.stx_dev_major = dev.major();
+    x.stx_dev_minor = dev.minor();
+    Info(x)
+}
 
-pub fn info_from_stat(s: StatType) -> Info { Info(s) }
+fn timestamp(sec: i64, nsec: u32) -> statx_timestamp {
+    statx_timestamp { tv_sec: sec, tv_nsec: nsec, __reserved: 0 }
+}
+
+fn time_from_timestamp(t: &statx_timestamp) -> Time {
+    Time { seconds: t.tv_sec, nanoseconds: t.tv_nsec as i64 }
+}
 
 pub fn file_type_from_mode(i: umode_t) -> Type {
     match i & S_IFMT {
@@ -79,84 +130,86 @@ impl Debug for Type {
 
 /// Information about a file.
 #[derive(Pod, Eq)]
-pub struct Info(StatType);
+pub struct Info(statx);
 
 impl Info {
     /// Returns the device on which the file is stored.
     pub fn device(&self) -> Device {
-        Device::from_id(self.0.st_dev as DeviceId, DeviceType::Block)
+        Device::from_major_minor(self.0.stx_dev_major, self.0.stx_dev_minor,
+                                 DeviceType::Block)
     }
 
     /// Returns the inode of the file.
     pub fn inode(&self) -> InodeId {
-        self.0.st_ino as InodeId
+        self.0.stx_ino as InodeId
     }
 
     /// Returns the number of hard links to the file.
     pub fn nr_hard_links(&self) -> u64 {
-        self.0.st_nlink as u64
+        self.0.stx_nlink as u64
     }
 
     /// Returns the mode of the file.
     pub fn mode(&self) -> Mode {
-        Mode(self.0.st_mode as umode_t)
+        Mode(self.0.stx_mode as umode_t)
     }
 
     /// Returns the user id of the owner.
     pub fn user(&self) -> UserId {
-        self.0.st_uid as UserId
+        self.0.stx_uid as UserId
     }
 
     /// Returns the group id of the owner.
     pub fn group(&self) -> GroupId {
-        self.0.st_gid as GroupId
+        self.0.stx_gid as GroupId
     }
 
     /// If `self` is a device special file, then this functions returns the device it
     /// represents.
     pub fn special_file(&self) -> Option<Device> {
+        let (major, minor) = (self.0.stx_rdev_major, self.0.stx_rdev_minor);
         match self.file_type() {
-            Type::BlockDevice => Some(Device::from_id(self.0.st_rdev as DeviceId,
-                                                      DeviceType::Block)),
-            Type::CharDevice => Some(Device::from_id(self.0.st_rdev as DeviceId,
-                                                     DeviceType::Character)),
+            Type::BlockDevice => Some(Device::from_major_minor(major, minor,
+                                                               DeviceType::Block)),
+            Type::CharDevice => Some(Device::from_major_minor(major, minor,
+                                                              DeviceType::Character)),
             _ => None,
         }
     }
 
     /// Returns he size of the file in bytes.
     pub fn size(&self) -> u64 {
-        self.0.st_size as u64
+        self.0.stx_size as u64
     }
 
     /// Returns the number of `512` byte blocks used by this file.
     pub fn blocks(&self) -> u64 {
-        self.0.st_blocks as u64
+        self.0.stx_blocks as u64
     }
 
     /// Returns the preferred size of writes to this file.
     pub fn preferred_write_size(&self) -> u64 {
-        self.0.st_blksize as u64
+        self.0.stx_blksize as u64
     }
 
     /// Returns the last time this file was accessed.
     pub fn last_access(&self) -> Time {
-        Time { seconds: self.0.st_atime as i64, nanoseconds: self.0.st_atime_nsec as i64 }
+        time_from_timestamp(&self.0.stx_atime)
     }
 
     /// Returns the last time this file was modified.
     pub fn last_modification(&self) -> Time {
-        Time { seconds: self.0.st_mtime as i64, nanoseconds: self.0.st_mtime_nsec as i64 }
+        time_from_timestamp(&self.0.stx_mtime)
     }
 
     /// Returns the last time the status of the inode was changed.
     pub fn last_status_change(&self) -> Time {
-        Time { seconds: self.0.st_ctime as i64, nanoseconds: self.0.st_ctime_nsec as i64 }
+        time_from_timestamp(&self.0.stx_ctime)
     }
 
     /// Returns the type of this file.
     pub fn file_type(&self) -> Type {
-        file_type_from_mode(self.0.st_mode as umode_t)
+        file_type_from_mode(self.0.stx_mode as umode_t)
     }
 }
 
@@ -11,6 +11,7 @@ extern crate lrs_base as base;
 extern crate lrs_alloc as alloc;
 extern crate lrs_hash as hash;
 extern crate lrs_fmt as fmt;
+extern crate lrs_iter as iter;
 
 use base::prelude::*;
 use core::ops::{Eq};
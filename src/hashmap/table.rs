@@ -11,6 +11,7 @@ use hash::{self, Hash};
 use alloc::{self};
 use bucket::{self, SetBucket, MutSetBucket};
 use fmt::{Debug, Write};
+use iter::{FromIter};
 
 #[derive(Eq)]
 enum SearchResult {
@@ -60,6 +61,26 @@ impl<K, V, B, H, S, A1: ?Sized, A2>
     }
 }
 
+impl<Key, Value, Bucket, Hasher, Allocator> FromIter<(Key, Value)>
+    for GenericMap<Key, Value, Bucket, Hasher, (), Allocator>
+    where Allocator: alloc::MemPool + OutOf,
+          Bucket: bucket::Bucket<Key, Value>,
+          Hasher: hash::Hasher,
+          (): Into<Hasher::Seed>,
+          Key: Eq + Hash,
+{
+    fn from_iter<I>(iter: I) -> Result<Self>
+        where I: IntoIterator<Item=(Key, Value)>,
+    {
+        let mut map = try!(Self::new());
+        for (key, value) in iter {
+            try!(map.reserve(1));
+            map.set(key, value);
+        }
+        Ok(map)
+    }
+}
+
 impl<Key, Value, Bucket, Hasher, Allocator>
     GenericMap<Key, Value, Bucket, Hasher, (), Allocator>
     where Allocator: alloc::MemPool + OutOf,
@@ -18,6 +18,9 @@ extern crate lrs_str_two as str_two;
 extern crate lrs_io as io;
 extern crate lrs_alloc as alloc;
 extern crate lrs_rmo as rmo;
+extern crate lrs_vec as vec;
+extern crate lrs_file as file;
+extern crate lrs_dir as dir;
 
 use base::prelude::*;
 use syscall::{
@@ -39,6 +42,7 @@ mod std { pub use fmt::std::*; pub use cty; }
 
 pub mod flags;
 pub mod event;
+pub mod tree;
 
 pub type Pool<'a> = FcPool<OncePool<'a>, FbHeap>;
 
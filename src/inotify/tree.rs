@@ -0,0 +1,363 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use base::{error};
+use core::{mem};
+use cty::{
+    c_int, IN_ATTRIB, IN_CREATE, IN_DELETE, IN_MODIFY, IN_MOVED_FROM, IN_MOVED_TO,
+    IN_ISDIR, IN_IGNORED, IN_Q_OVERFLOW,
+};
+use fmt::{Debug, Write};
+use vec::{Vec};
+use dir::{WalkOptions, walk};
+use file::info::{Type};
+use {Inotify, InodeWatch};
+use event::{InodeEvents};
+use flags::{
+    INOTIFY_CLOSE_ON_EXEC, WATCH_ONLY_DIRECTORY, WATCH_DONT_FOLLOW_LINKS,
+};
+
+/// The size of the buffer used to read raw events.
+const BUF_SIZE: usize = 4096;
+
+/// The events watched for in every directory of the tree.
+const TREE_EVENTS: InodeEvents = InodeEvents(IN_ATTRIB | IN_CREATE | IN_DELETE |
+                                             IN_MODIFY | IN_MOVED_FROM | IN_MOVED_TO);
+
+/// An event in a watched directory tree.
+///
+/// = Remarks
+///
+/// All paths are relative to the root of the tree. The root itself has the empty path.
+pub enum TreeEvent {
+    /// A file or directory was created or moved into the tree.
+    Created(Vec<u8>),
+    /// A file was modified.
+    Modified(Vec<u8>),
+    /// A file or directory was removed or moved out of the tree.
+    Removed(Vec<u8>),
+    /// A file or directory was renamed within the tree.
+    ///
+    /// [field, 1]
+    /// The old path.
+    ///
+    /// [field, 2]
+    /// The new path.
+    Renamed(Vec<u8>, Vec<u8>),
+    /// The metadata of a file or directory changed.
+    AttribChanged(Vec<u8>),
+    /// The kernel event queue overflowed and the tree was rescanned.
+    ///
+    /// = Remarks
+    ///
+    /// Events that occurred before the rescan might have been lost.
+    Rescanned,
+}
+
+impl Debug for TreeEvent {
+    fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+        match *self {
+            TreeEvent::Created(ref p) => write!(w, "Created({:?})", p.as_str()),
+            TreeEvent::Modified(ref p) => write!(w, "Modified({:?})", p.as_str()),
+            TreeEvent::Removed(ref p) => write!(w, "Removed({:?})", p.as_str()),
+            TreeEvent::Renamed(ref f, ref t) => write!(w, "Renamed({:?}, {:?})",
+                                                       f.as_str(), t.as_str()),
+            TreeEvent::AttribChanged(ref p) => write!(w, "AttribChanged({:?})",
+                                                      p.as_str()),
+            TreeEvent::Rescanned => w.write_all(b"Rescanned").ignore_ok(),
+        }
+    }
+}
+
+struct WatchedDir {
+    watch: c_int,
+    path: Vec<u8>,
+}
+
+struct PendingMove {
+    cookie: u32,
+    path: Vec<u8>,
+    is_dir: bool,
+}
+
+/// A watcher for a whole directory tree.
+///
+/// = Remarks
+///
+/// Watches are added for all directories in the tree, including directories that are
+/// created or moved into the tree while it is being watched. Pairs of `IN_MOVED_FROM`
+/// and `IN_MOVED_TO` events are combined into a single `Renamed` event.
+///
+/// Each directory consumes one inotify watch. The limit is configured in
+/// `/proc/sys/fs/inotify/max_user_watches`.
+pub struct TreeWatcher {
+    inotify: Inotify,
+    root: Vec<u8>,
+    dirs: Vec<WatchedDir>,
+    events: Vec<TreeEvent>,
+    pos: usize,
+    pending: Option<PendingMove>,
+}
+
+impl TreeWatcher {
+    /// Starts watching a directory tree.
+    ///
+    /// [argument, root]
+    /// The path of the root of the tree.
+    ///
+    /// = Examples
+    ///
+    /// ----
+    /// let mut watcher = TreeWatcher::new("/srv/data").unwrap();
+    /// loop {
+    ///     match watcher.next_event().unwrap() {
+    ///         TreeEvent::Renamed(from, to) => { },
+    ///         _ => { },
+    ///     }
+    /// }
+    /// ----
+    pub fn new<P: ?Sized>(root: &P) -> Result<TreeWatcher>
+        where P: AsRef<[u8]>,
+    {
+        let mut path = Vec::new();
+        try!(path.push_all(root.as_ref()));
+        let mut watcher = TreeWatcher {
+            inotify: try!(Inotify::new(INOTIFY_CLOSE_ON_EXEC)),
+            root: path,
+            dirs: Vec::new(),
+            events: Vec::new(),
+            pos: 0,
+            pending: None,
+        };
+        try!(watcher.add_tree(&[], false));
+        Ok(watcher)
+    }
+
+    /// Returns the underlying inotify object.
+    ///
+    /// = Remarks
+    ///
+    /// This can be used to wait for events with `poll` or `epoll`. Reading events
+    /// directly from the object will corrupt the state of the watcher.
+    pub fn inotify(&self) -> &Inotify {
+        &self.inotify
+    }
+
+    /// Returns the number of directories being watched.
+    pub fn num_watches(&self) -> usize {
+        self.dirs.len()
+    }
+
+    /// Waits for the next event.
+    ///
+    /// = Remarks
+    ///
+    /// If the inotify object was created non-blocking and no events are available, an
+    /// error is returned.
+    pub fn next_event(&mut self) -> Result<TreeEvent> {
+        loop {
+            if self.pos < self.events.len() {
+                let ev = mem::replace(&mut self.events[self.pos], TreeEvent::Rescanned);
+                self.pos += 1;
+                return Ok(ev);
+            }
+            self.events.truncate(0);
+            self.pos = 0;
+            try!(self.read_events());
+        }
+    }
+
+    fn read_events(&mut self) -> Result {
+        let mut buf: [d8; BUF_SIZE] = unsafe { mem::uninit() };
+        let mut raw = Vec::new();
+        for data in try!(self.inotify.events(&mut buf)) {
+            let name: &[u8] = data.name().as_ref();
+            let mut n = Vec::new();
+            try!(n.push_all(name));
+            try!(raw.push((data.watch.0, data.events.0, data.cookie, n)));
+        }
+        for &(watch, mask, cookie, ref name) in &raw {
+            try!(self.handle(watch, mask, cookie, name));
+        }
+        if self.pending.is_some() && try!(self.inotify.available()) == 0 {
+            // The matching IN_MOVED_TO would have been queued together with the
+            // IN_MOVED_FROM event. The file has been moved out of the tree.
+            let pending = self.pending.take().unwrap();
+            try!(self.moved_out(pending));
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, watch: c_int, mask: u32, cookie: u32, name: &[u8]) -> Result {
+        if mask & IN_Q_OVERFLOW != 0 {
+            return self.rescan();
+        }
+        if mask & IN_IGNORED != 0 {
+            if let Some(i) = self.find(watch) {
+                self.swap_remove(i);
+            }
+            return Ok(());
+        }
+        let path = match self.find(watch) {
+            Some(i) => try!(join(&self.dirs[i].path, name)),
+            _ => return Ok(()),
+        };
+        let is_dir = mask & IN_ISDIR != 0;
+
+        if let Some(pending) = self.pending.take() {
+            if mask & IN_MOVED_TO != 0 && pending.cookie == cookie {
+                if pending.is_dir {
+                    try!(self.rename_dirs(&pending.path, &path));
+                }
+                return self.events.push(TreeEvent::Renamed(pending.path, path));
+            }
+            try!(self.moved_out(pending));
+        }
+
+        if mask & IN_MOVED_FROM != 0 {
+            self.pending = Some(PendingMove {
+                cookie: cookie,
+                path: path,
+                is_dir: is_dir,
+            });
+        } else if mask & (IN_CREATE | IN_MOVED_TO) != 0 {
+            if is_dir {
+                // Files created before the watch was added would be lost otherwise.
+                try!(self.events.push(TreeEvent::Created(try!(join(&path, &[])))));
+                try!(self.add_tree(&path, true));
+            } else {
+                try!(self.events.push(TreeEvent::Created(path)));
+            }
+        } else if mask & IN_DELETE != 0 {
+            try!(self.events.push(TreeEvent::Removed(path)));
+        } else if mask & IN_MODIFY != 0 {
+            try!(self.events.push(TreeEvent::Modified(path)));
+        } else if mask & IN_ATTRIB != 0 {
+            try!(self.events.push(TreeEvent::AttribChanged(path)));
+        }
+        Ok(())
+    }
+
+    fn moved_out(&mut self, pending: PendingMove) -> Result {
+        if pending.is_dir {
+            let mut i = 0;
+            while i < self.dirs.len() {
+                if is_below(&self.dirs[i].path, &pending.path) {
+                    let _ = self.inotify.remove_watch(InodeWatch(self.dirs[i].watch));
+                    self.swap_remove(i);
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        self.events.push(TreeEvent::Removed(pending.path))
+    }
+
+    fn rename_dirs(&mut self, from: &[u8], to: &[u8]) -> Result {
+        for dir in &mut self.dirs {
+            if is_below(&dir.path, from) {
+                let path = try!(join(to, &dir.path[from.len()..]));
+                dir.path = path;
+            }
+        }
+        Ok(())
+    }
+
+    fn rescan(&mut self) -> Result {
+        for dir in &self.dirs {
+            let _ = self.inotify.remove_watch(InodeWatch(dir.watch));
+        }
+        self.dirs.truncate(0);
+        self.events.truncate(0);
+        self.pending = None;
+        try!(self.add_tree(&[], false));
+        self.events.push(TreeEvent::Rescanned)
+    }
+
+    /// Adds watches for a directory and all of its subdirectories.
+    fn add_tree(&mut self, rel: &[u8], created: bool) -> Result {
+        let full = try!(join(&self.root, rel));
+        match self.add_dir(&full, rel) {
+            // The directory might already be gone again.
+            Err(e) if rel.len() > 0 && vanished(e) => return Ok(()),
+            r => try!(r),
+        }
+        let mut err = Ok(());
+        {
+            let mut walk = walk(&full[..], WalkOptions::new(), Some(&mut err));
+            while let Some(entry) = walk.next() {
+                let path = try!(join(rel, &entry.path));
+                if entry.ty == Type::Directory {
+                    let full = try!(join(&self.root, &path));
+                    match self.add_dir(&full, &path) {
+                        Err(e) if !vanished(e) => return Err(e),
+                        _ => { },
+                    }
+                }
+                if created {
+                    try!(self.events.push(TreeEvent::Created(path)));
+                }
+            }
+        }
+        match err {
+            Err(e) if !vanished(e) => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn add_dir(&mut self, full: &[u8], rel: &[u8]) -> Result {
+        let flags = WATCH_ONLY_DIRECTORY | WATCH_DONT_FOLLOW_LINKS;
+        let watch = try!(self.inotify.set_watch(full, TREE_EVENTS, flags)).0;
+        let mut path = Vec::new();
+        try!(path.push_all(rel));
+        match self.find(watch) {
+            Some(i) => self.dirs[i].path = path,
+            _ => try!(self.dirs.push(WatchedDir { watch: watch, path: path })),
+        }
+        Ok(())
+    }
+
+    fn find(&self, watch: c_int) -> Option<usize> {
+        let mut i = 0;
+        while i < self.dirs.len() {
+            if self.dirs[i].watch == watch {
+                return Some(i);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    fn swap_remove(&mut self, i: usize) {
+        let last = self.dirs.len() - 1;
+        if i < last {
+            let (a, b) = self.dirs.split_at_mut(last);
+            mem::swap(&mut a[i], &mut b[0]);
+        }
+        self.dirs.pop();
+    }
+}
+
+/// Joins two relative paths.
+fn join(a: &[u8], b: &[u8]) -> Result<Vec<u8>> {
+    let mut path = try!(Vec::with_capacity(a.len() + b.len() + 1));
+    try!(path.push_all(a));
+    if a.len() > 0 && b.len() > 0 {
+        try!(path.push(b'/'));
+    }
+    try!(path.push_all(b));
+    Ok(path)
+}
+
+/// Returns whether `path` is `dir` or lies below `dir`.
+fn is_below(path: &[u8], dir: &[u8]) -> bool {
+    path.starts_with(dir) && (path.len() == dir.len() || path[dir.len()] == b'/')
+}
+
+/// Returns whether an error means that a directory was removed or replaced while the tree
+/// was being scanned.
+fn vanished(e: error::Errno) -> bool {
+    e == error::DoesNotExist || e == error::NotADirectory
+}
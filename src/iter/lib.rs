@@ -10,7 +10,9 @@
 extern crate lrs_base as base;
 
 use base::prelude::*;
-use core::ops::{Add};
+use core::ops::{Add, Ordering};
+use core::ops::Ordering::{Greater};
+use core::iter::{IntoIterator, DoubleEndedIterator};
 
 /// Returns an iterator which yields a value indefinitely.
 ///
@@ -32,6 +34,26 @@ impl<T> Iterator for Repeat<T>
     fn next(&mut self) -> Option<T> { Some(self.val) }
 }
 
+/// Objects that can be created from an iterator.
+///
+/// = Remarks
+///
+/// :collect: link:lrs::iter::IteratorExt::collect[collect]
+///
+/// This trait is used by {collect}.
+pub trait FromIter<T>: Sized {
+    /// Creates an object from the elements of an iterator.
+    ///
+    /// [argument, iter]
+    /// The iterator.
+    ///
+    /// [return_value]
+    /// Returns the new object or an error if the object could not be created, e.g.,
+    /// because no memory was available.
+    fn from_iter<I>(iter: I) -> Result<Self>
+        where I: IntoIterator<Item=T>;
+}
+
 impl<T: Iterator> IteratorExt for T { }
 
 /// Extensions for the `Iterator` trait.
@@ -93,6 +115,282 @@ pub trait IteratorExt : Iterator+Sized {
         self
     }
 
+    /// Returns a new iterator that yields pairs of the elements of two iterators.
+    ///
+    /// [argument, other]
+    /// The second iterator.
+    ///
+    /// = Remarks
+    ///
+    /// The iterator stops when either iterator is exhausted.
+    fn zip<U>(self, other: U) -> Zip<Self, U::IntoIter>
+        where U: IntoIterator,
+    {
+        Zip { one: self, two: other.into_iter() }
+    }
+
+    /// Returns a new iterator that yields the elements of another iterator after the
+    /// elements of this iterator.
+    ///
+    /// [argument, other]
+    /// The second iterator.
+    fn chain<U>(self, other: U) -> Chain<Self, U::IntoIter>
+        where U: IntoIterator<Item=Self::Item>,
+    {
+        Chain { one: Some(self), two: other.into_iter() }
+    }
+
+    /// Returns a new iterator that yields at most a number of elements.
+    ///
+    /// [argument, n]
+    /// The maximum number of elements.
+    fn take(self, n: usize) -> Take<Self> {
+        Take { iter: self, n: n }
+    }
+
+    /// Returns a new iterator that skips a number of elements.
+    ///
+    /// [argument, n]
+    /// The number of elements to skip.
+    fn skip(self, n: usize) -> Skip<Self> {
+        Skip { iter: self, n: n }
+    }
+
+    /// Returns a new iterator that yields elements while they satisfy a predicate.
+    ///
+    /// [argument, f]
+    /// The predicate.
+    ///
+    /// = Remarks
+    ///
+    /// The first element that doesn't satisfy the predicate is lost.
+    fn take_while<F>(self, f: F) -> TakeWhile<F, Self>
+        where F: FnMut(&Self::Item) -> bool,
+    {
+        TakeWhile { iter: Some(self), f: f }
+    }
+
+    /// Returns a new iterator that skips elements while they satisfy a predicate.
+    ///
+    /// [argument, f]
+    /// The predicate.
+    fn skip_while<F>(self, f: F) -> SkipWhile<F, Self>
+        where F: FnMut(&Self::Item) -> bool,
+    {
+        SkipWhile { iter: self, f: Some(f) }
+    }
+
+    /// Returns a new iterator that can look at the next element without consuming it.
+    fn peekable(self) -> Peekable<Self> {
+        Peekable { iter: self, peeked: None }
+    }
+
+    /// Returns a new iterator that maps every element to an iterator and yields the
+    /// elements of these iterators.
+    ///
+    /// [argument, f]
+    /// The function that will be applied.
+    fn flat_map<U, F>(self, f: F) -> FlatMap<F, Self, U>
+        where U: IntoIterator,
+              F: FnMut(Self::Item) -> U,
+    {
+        FlatMap { iter: self, f: f, cur: None }
+    }
+
+    /// Returns a new iterator that filters and maps elements via a function.
+    ///
+    /// [argument, f]
+    /// The function that will be applied.
+    ///
+    /// = Remarks
+    ///
+    /// An element is passed through if `f` returns `Some`.
+    fn filter_map<T, F>(self, f: F) -> FilterMap<T, F, Self>
+        where F: FnMut(Self::Item) -> Option<T>,
+    {
+        FilterMap { iter: self, f: f }
+    }
+
+    /// Returns a new iterator that yields the first element and then every `n`th
+    /// element.
+    ///
+    /// [argument, n]
+    /// The step size.
+    ///
+    /// = Remarks
+    ///
+    /// If `n` is `0`, the process is aborted.
+    fn step_by(self, n: usize) -> StepBy<Self> {
+        assert!(n > 0);
+        StepBy { iter: self, step: n - 1, first: true }
+    }
+
+    /// Returns a new iterator that yields the elements in reverse order.
+    fn rev(self) -> Rev<Self>
+        where Self: DoubleEndedIterator,
+    {
+        Rev { iter: self }
+    }
+
+    /// Returns a new iterator that repeats this iterator indefinitely.
+    ///
+    /// = Remarks
+    ///
+    /// If this iterator is empty, the new iterator is empty.
+    fn cycle(self) -> Cycle<Self>
+        where Self: Clone,
+    {
+        Cycle { cur: self.clone(), orig: self }
+    }
+
+    /// Returns a new iterator that calls a function on every element before yielding it.
+    ///
+    /// [argument, f]
+    /// The function that will be called.
+    fn inspect<F>(self, f: F) -> Inspect<F, Self>
+        where F: FnMut(&Self::Item),
+    {
+        Inspect { iter: self, f: f }
+    }
+
+    /// Returns a new iterator that maps the elements with a function that has access to
+    /// a state.
+    ///
+    /// [argument, state]
+    /// The initial state.
+    ///
+    /// [argument, f]
+    /// The function that will be applied.
+    ///
+    /// = Remarks
+    ///
+    /// The iterator stops when `f` returns `None`.
+    fn scan<S, T, F>(self, state: S, f: F) -> Scan<S, T, F, Self>
+        where F: FnMut(&mut S, Self::Item) -> Option<T>,
+    {
+        Scan { iter: Some(self), state: state, f: f }
+    }
+
+    /// Combines all elements with a function.
+    ///
+    /// [argument, init]
+    /// The initial value.
+    ///
+    /// [argument, f]
+    /// The function that combines the accumulated value with an element.
+    ///
+    /// [return_value]
+    /// Returns the accumulated value.
+    fn fold<T, F>(self, init: T, mut f: F) -> T
+        where F: FnMut(T, Self::Item) -> T,
+    {
+        let mut acc = init;
+        for e in self { acc = f(acc, e); }
+        acc
+    }
+
+    /// Returns whether all elements in the iterator satisfy a predicate.
+    ///
+    /// [argument, pred]
+    /// The predicate which returns `true` iff it is satisfied by a value.
+    ///
+    /// = Remarks
+    ///
+    /// The values before and including the first element that doesn't satisfy the
+    /// predicate are lost.
+    fn all<P>(&mut self, mut pred: P) -> bool
+        where P: FnMut(Self::Item) -> bool,
+    {
+        while let Some(e) = self.next() {
+            if !pred(e) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Returns the position of the first element that satisfies a predicate.
+    ///
+    /// [argument, pred]
+    /// The predicate which returns `true` iff it is satisfied by a value.
+    ///
+    /// = Remarks
+    ///
+    /// The values before and including the first element that satisfies the predicate
+    /// are lost.
+    fn position<P>(&mut self, mut pred: P) -> Option<usize>
+        where P: FnMut(Self::Item) -> bool,
+    {
+        let mut pos = 0;
+        while let Some(e) = self.next() {
+            if pred(e) {
+                return Some(pos);
+            }
+            pos += 1;
+        }
+        None
+    }
+
+    /// Returns the minimum element with respect to a comparison function.
+    ///
+    /// [argument, f]
+    /// The comparison function.
+    ///
+    /// = Remarks
+    ///
+    /// If several elements are minimal, the first one is returned.
+    fn min_by<F>(self, mut f: F) -> Option<Self::Item>
+        where F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        self.fold(None, |min, e| match min {
+            Some(min) => if f(&min, &e) == Greater { Some(e) } else { Some(min) },
+            _ => Some(e),
+        })
+    }
+
+    /// Returns the maximum element with respect to a comparison function.
+    ///
+    /// [argument, f]
+    /// The comparison function.
+    ///
+    /// = Remarks
+    ///
+    /// If several elements are maximal, the last one is returned.
+    fn max_by<F>(self, mut f: F) -> Option<Self::Item>
+        where F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        self.fold(None, |max, e| match max {
+            Some(max) => if f(&max, &e) == Greater { Some(max) } else { Some(e) },
+            _ => Some(e),
+        })
+    }
+
+    /// Returns the number of elements in the iterator.
+    fn count(self) -> usize {
+        self.fold(0, |n, _| n + 1)
+    }
+
+    /// Returns the last element of the iterator.
+    fn last(self) -> Option<Self::Item> {
+        self.fold(None, |_, e| Some(e))
+    }
+
+    /// Creates an object from the elements of the iterator.
+    ///
+    /// [return_value]
+    /// Returns the new object or an error if the object could not be created.
+    ///
+    /// = Examples
+    ///
+    /// ----
+    /// let squares: Vec<u32> = try!((0..10).map(|i| i * i).collect());
+    /// ----
+    fn collect<C>(self) -> Result<C>
+        where C: FromIter<Self::Item>,
+    {
+        C::from_iter(self)
+    }
+
     /// Places the elements of the iterator into a slice until the slice or the iterator
     /// are exhausted.
     ///
@@ -136,6 +434,15 @@ impl<T, F, I> Iterator for Map<T, F, I>
     }
 }
 
+impl<T, F, I> DoubleEndedIterator for Map<T, F, I>
+    where I: DoubleEndedIterator,
+          F: FnMut(I::Item) -> T,
+{
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back().map(|i| (self.f)(i))
+    }
+}
+
 /// An iterator that wraps another iterator and yields only those elements that satisfy a
 /// predicate.
 pub struct Filter<F, I>
@@ -163,6 +470,22 @@ impl<F, I> Iterator for Filter<F, I>
     }
 }
 
+impl<F, I> DoubleEndedIterator for Filter<F, I>
+    where I: DoubleEndedIterator,
+          F: FnMut(&I::Item) -> bool,
+{
+    fn next_back(&mut self) -> Option<I::Item> {
+        loop {
+            match self.iter.next_back() {
+                Some(t) => if (self.f)(&t) {
+                    return Some(t);
+                },
+                _ => return None,
+            }
+        }
+    }
+}
+
 /// An iterator that wrap another iterator and returns the number of the returned value in
 /// addition to the value.
 pub struct Enumerate<I>
@@ -189,3 +512,392 @@ impl<I> Iterator for Enumerate<I>
         }
     }
 }
+
+/// An iterator that yields pairs of the elements of two iterators.
+pub struct Zip<A, B> {
+    one: A,
+    two: B,
+}
+
+impl<A, B> Iterator for Zip<A, B>
+    where A: Iterator,
+          B: Iterator,
+{
+    type Item = (A::Item, B::Item);
+    fn next(&mut self) -> Option<(A::Item, B::Item)> {
+        match self.one.next() {
+            Some(a) => self.two.next().map(|b| (a, b)),
+            _ => None,
+        }
+    }
+}
+
+/// An iterator that yields the elements of two iterators one after the other.
+pub struct Chain<A, B> {
+    one: Option<A>,
+    two: B,
+}
+
+impl<A, B> Iterator for Chain<A, B>
+    where A: Iterator,
+          B: Iterator<Item=A::Item>,
+{
+    type Item = A::Item;
+    fn next(&mut self) -> Option<A::Item> {
+        if let Some(ref mut one) = self.one {
+            if let Some(e) = one.next() {
+                return Some(e);
+            }
+        }
+        self.one = None;
+        self.two.next()
+    }
+}
+
+impl<A, B> DoubleEndedIterator for Chain<A, B>
+    where A: DoubleEndedIterator,
+          B: DoubleEndedIterator<Item=A::Item>,
+{
+    fn next_back(&mut self) -> Option<A::Item> {
+        if let Some(e) = self.two.next_back() {
+            return Some(e);
+        }
+        match self.one {
+            Some(ref mut one) => one.next_back(),
+            _ => None,
+        }
+    }
+}
+
+/// An iterator that yields at most a number of elements.
+pub struct Take<I> {
+    iter: I,
+    n: usize,
+}
+
+impl<I> Iterator for Take<I>
+    where I: Iterator,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<I::Item> {
+        match self.n {
+            0 => None,
+            _ => {
+                self.n -= 1;
+                self.iter.next()
+            },
+        }
+    }
+}
+
+/// An iterator that skips a number of elements.
+pub struct Skip<I> {
+    iter: I,
+    n: usize,
+}
+
+impl<I> Iterator for Skip<I>
+    where I: Iterator,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<I::Item> {
+        while self.n > 0 {
+            self.n -= 1;
+            if self.iter.next().is_none() {
+                return None;
+            }
+        }
+        self.iter.next()
+    }
+}
+
+/// An iterator that yields elements while they satisfy a predicate.
+pub struct TakeWhile<F, I> {
+    /// `None` once an element didn't satisfy the predicate.
+    iter: Option<I>,
+    f: F,
+}
+
+impl<F, I> Iterator for TakeWhile<F, I>
+    where I: Iterator,
+          F: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<I::Item> {
+        let e = match self.iter {
+            Some(ref mut iter) => iter.next(),
+            _ => return None,
+        };
+        match e {
+            Some(e) => if (self.f)(&e) {
+                return Some(e);
+            },
+            _ => { },
+        }
+        self.iter = None;
+        None
+    }
+}
+
+/// An iterator that skips elements while they satisfy a predicate.
+pub struct SkipWhile<F, I> {
+    iter: I,
+    /// `None` once an element didn't satisfy the predicate.
+    f: Option<F>,
+}
+
+impl<F, I> Iterator for SkipWhile<F, I>
+    where I: Iterator,
+          F: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<I::Item> {
+        if let Some(mut f) = self.f.take() {
+            while let Some(e) = self.iter.next() {
+                if !f(&e) {
+                    return Some(e);
+                }
+            }
+            return None;
+        }
+        self.iter.next()
+    }
+}
+
+/// An iterator that can look at the next element without consuming it.
+pub struct Peekable<I>
+    where I: Iterator,
+{
+    iter: I,
+    peeked: Option<Option<I::Item>>,
+}
+
+impl<I> Peekable<I>
+    where I: Iterator,
+{
+    /// Returns a reference to the next element without consuming it.
+    pub fn peek(&mut self) -> Option<&I::Item> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.iter.next());
+        }
+        match self.peeked {
+            Some(Some(ref e)) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl<I> Iterator for Peekable<I>
+    where I: Iterator,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<I::Item> {
+        match self.peeked.take() {
+            Some(e) => e,
+            _ => self.iter.next(),
+        }
+    }
+}
+
+/// An iterator that maps every element to an iterator and yields the elements of these
+/// iterators.
+pub struct FlatMap<F, I, U>
+    where U: IntoIterator,
+{
+    iter: I,
+    f: F,
+    cur: Option<U::IntoIter>,
+}
+
+impl<F, I, U> Iterator for FlatMap<F, I, U>
+    where I: Iterator,
+          U: IntoIterator,
+          F: FnMut(I::Item) -> U,
+{
+    type Item = U::Item;
+    fn next(&mut self) -> Option<U::Item> {
+        loop {
+            if let Some(ref mut cur) = self.cur {
+                if let Some(e) = cur.next() {
+                    return Some(e);
+                }
+            }
+            match self.iter.next() {
+                Some(e) => self.cur = Some((self.f)(e).into_iter()),
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// An iterator that filters and maps elements via a function.
+pub struct FilterMap<T, F, I>
+    where I: Iterator,
+          F: FnMut(I::Item) -> Option<T>,
+{
+    iter: I,
+    f: F,
+}
+
+impl<T, F, I> Iterator for FilterMap<T, F, I>
+    where I: Iterator,
+          F: FnMut(I::Item) -> Option<T>,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        while let Some(e) = self.iter.next() {
+            if let Some(t) = (self.f)(e) {
+                return Some(t);
+            }
+        }
+        None
+    }
+}
+
+impl<T, F, I> DoubleEndedIterator for FilterMap<T, F, I>
+    where I: DoubleEndedIterator,
+          F: FnMut(I::Item) -> Option<T>,
+{
+    fn next_back(&mut self) -> Option<T> {
+        while let Some(e) = self.iter.next_back() {
+            if let Some(t) = (self.f)(e) {
+                return Some(t);
+            }
+        }
+        None
+    }
+}
+
+/// An iterator that yields the first element and then every `n`th element.
+pub struct StepBy<I> {
+    iter: I,
+    /// The number of elements skipped between two yielded elements.
+    step: usize,
+    first: bool,
+}
+
+impl<I> Iterator for StepBy<I>
+    where I: Iterator,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<I::Item> {
+        if self.first {
+            self.first = false;
+        } else {
+            for _ in 0..self.step {
+                if self.iter.next().is_none() {
+                    return None;
+                }
+            }
+        }
+        self.iter.next()
+    }
+}
+
+/// An iterator that yields the elements of a double-ended iterator in reverse order.
+pub struct Rev<I> {
+    iter: I,
+}
+
+impl<I> Iterator for Rev<I>
+    where I: DoubleEndedIterator,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<I> DoubleEndedIterator for Rev<I>
+    where I: DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<I::Item> {
+        self.iter.next()
+    }
+}
+
+/// An iterator that repeats another iterator indefinitely.
+pub struct Cycle<I> {
+    orig: I,
+    cur: I,
+}
+
+impl<I> Iterator for Cycle<I>
+    where I: Iterator+Clone,
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<I::Item> {
+        match self.cur.next() {
+            None => {
+                self.cur = self.orig.clone();
+                self.cur.next()
+            },
+            e => e,
+        }
+    }
+}
+
+/// An iterator that calls a function on every element before yielding it.
+pub struct Inspect<F, I> {
+    iter: I,
+    f: F,
+}
+
+impl<F, I> Iterator for Inspect<F, I>
+    where I: Iterator,
+          F: FnMut(&I::Item),
+{
+    type Item = I::Item;
+    fn next(&mut self) -> Option<I::Item> {
+        let e = self.iter.next();
+        if let Some(ref e) = e {
+            (self.f)(e);
+        }
+        e
+    }
+}
+
+impl<F, I> DoubleEndedIterator for Inspect<F, I>
+    where I: DoubleEndedIterator,
+          F: FnMut(&I::Item),
+{
+    fn next_back(&mut self) -> Option<I::Item> {
+        let e = self.iter.next_back();
+        if let Some(ref e) = e {
+            (self.f)(e);
+        }
+        e
+    }
+}
+
+/// An iterator that maps elements with a function that has access to a state.
+pub struct Scan<S, T, F, I>
+    where F: FnMut(&mut S, I::Item) -> Option<T>,
+          I: Iterator,
+{
+    /// `None` once the function returned `None`.
+    iter: Option<I>,
+    state: S,
+    f: F,
+}
+
+impl<S, T, F, I> Iterator for Scan<S, T, F, I>
+    where F: FnMut(&mut S, I::Item) -> Option<T>,
+          I: Iterator,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        let e = match self.iter {
+            Some(ref mut iter) => iter.next(),
+            _ => return None,
+        };
+        let rv = match e {
+            Some(e) => (self.f)(&mut self.state, e),
+            _ => None,
+        };
+        if rv.is_none() {
+            self.iter = None;
+        }
+        rv
+    }
+}
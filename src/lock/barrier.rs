@@ -0,0 +1,57 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use atomic::{Atomic};
+use syscall::{futex_wait, futex_wake};
+use cty::{c_int};
+
+/// A reusable barrier.
+///
+/// = Remarks
+///
+/// This barrier can be used for inter-process synchronization.
+#[repr(C)]
+pub struct Barrier {
+    count: c_int,
+    arrived: Atomic<c_int>,
+    /// Incremented every time all threads have arrived.
+    generation: Atomic<c_int>,
+}
+
+impl Barrier {
+    /// Creates a new barrier.
+    ///
+    /// [argument, count]
+    /// The number of threads that have to call `wait` before they are woken.
+    pub const fn new(count: u32) -> Barrier {
+        Barrier {
+            count: count as c_int,
+            arrived: Atomic::new(0),
+            generation: Atomic::new(0),
+        }
+    }
+
+    /// Sleeps until `count` threads are waiting on the barrier.
+    ///
+    /// [return_value]
+    /// Returns whether this thread was the last thread to arrive.
+    ///
+    /// = Remarks
+    ///
+    /// Once all threads have been woken, the barrier can be used again.
+    pub fn wait(&self) -> bool {
+        let generation = self.generation.load();
+        if self.arrived.add(1) + 1 >= self.count {
+            self.arrived.store(0);
+            self.generation.add(1);
+            futex_wake(&self.generation, usize::max());
+            return true;
+        }
+        while self.generation.load() == generation {
+            futex_wait(&self.generation, generation, None);
+        }
+        false
+    }
+}
@@ -2,6 +2,9 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use base::prelude::*;
+use time_base::{Time};
+use time_base::clock::{Clock};
 use mutex::{Mutex, MutexGuard};
 use raw_condvar::{RawCondvar};
 
@@ -67,6 +70,79 @@ impl Condvar {
         mutex.existing_lock(guard)
     }
 
+    /// Atomically unlocks a mutex guard and waits for a signal on this condvar or until
+    /// a certain amount of time has expired before re-locking the mutex.
+    ///
+    /// [argument, guard]
+    /// The mutex guard to be unlocked.
+    ///
+    /// [argument, time]
+    /// An upper bound for the amount of time until this function returns.
+    ///
+    /// [return_value]
+    /// Returns a guard created by re-locking the mutex of the guard argument and whether
+    /// the function returned because of a signal.
+    ///
+    /// = Remarks
+    ///
+    /// The time is measured on the `MONO_RAW` clock. If no signal was received in
+    /// time, the second element of the return value is `TimedOut`.
+    ///
+    /// See `wait` for the restrictions on the mutex that can be used.
+    pub fn wait_for<'a, T>(&self, guard: MutexGuard<'a, T>,
+                           time: Time) -> (MutexGuard<'a, T>, Result) {
+        let mutex = guard.as_mutex();
+        let (guard, res) = self.raw.wait_for(guard.into_lock_guard(), time);
+        (mutex.existing_lock(guard), res)
+    }
+
+    /// Atomically unlocks a mutex guard and waits for a signal on this condvar or until
+    /// a clock has reached a deadline before re-locking the mutex.
+    ///
+    /// [argument, guard]
+    /// The mutex guard to be unlocked.
+    ///
+    /// [argument, clock]
+    /// The clock on which the deadline is measured.
+    ///
+    /// [argument, deadline]
+    /// The point in time, on `clock`, until which to wait.
+    ///
+    /// [return_value]
+    /// Returns a guard created by re-locking the mutex of the guard argument and whether
+    /// the function returned because of a signal.
+    ///
+    /// = Remarks
+    ///
+    /// If no signal was received before the deadline, the second element of the return
+    /// value is `TimedOut`. The function may take longer to return than allowed by the
+    /// deadline.
+    ///
+    /// See `wait` for the restrictions on the mutex that can be used.
+    ///
+    /// = Examples
+    ///
+    /// ----
+    /// let mutex = Mutex::new(false);
+    /// let condvar = Condvar::new();
+    /// let deadline = clock::MONO.get_time().unwrap() + Time::seconds(1);
+    ///
+    /// let mut guard = mutex.lock();
+    /// while !*guard {
+    ///     let (g, res) = condvar.wait_until(guard, clock::MONO, deadline);
+    ///     guard = g;
+    ///     if res.is_err() {
+    ///         break;
+    ///     }
+    /// }
+    /// ----
+    pub fn wait_until<'a, T>(&self, guard: MutexGuard<'a, T>, clock: Clock,
+                             deadline: Time) -> (MutexGuard<'a, T>, Result) {
+        let mutex = guard.as_mutex();
+        let (guard, res) = self.raw.wait_until(guard.into_lock_guard(), clock, deadline);
+        (mutex.existing_lock(guard), res)
+    }
+
     /// Wakes a number of threads waiting on this condvar.
     ///
     /// [argument, n]
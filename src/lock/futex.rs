@@ -0,0 +1,61 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use base::{error};
+use atomic::{Atomic};
+use syscall::{futex_wait};
+use cty::{c_int};
+use time_base::{time_to_timespec, Time, clock};
+use time_base::clock::{Clock};
+
+/// Returns the point in time, on the monotonic clock, `time` from now.
+pub fn deadline(time: Time) -> Result<Time> {
+    let now = try!(clock::MONO_RAW.get_time());
+    Ok(now + time)
+}
+
+/// Sleeps on a futex while it contains `val`.
+///
+/// = Remarks
+///
+/// Like `futex_wait`, this function can return spuriously.
+pub fn wait(addr: &Atomic<c_int>, val: c_int) {
+    futex_wait(addr, val, None);
+}
+
+/// Sleeps on a futex while it contains `val` or until the deadline has passed.
+///
+/// [return_value]
+/// Returns `TimedOut` if the deadline has passed.
+///
+/// = Remarks
+///
+/// Like `futex_wait`, this function can return spuriously.
+pub fn wait_until(addr: &Atomic<c_int>, val: c_int, deadline: Time) -> Result {
+    wait_clock(addr, val, clock::MONO_RAW, deadline)
+}
+
+/// Sleeps on a futex while it contains `val` or until `clock` has reached the deadline.
+///
+/// [return_value]
+/// Returns `TimedOut` if the deadline has passed.
+///
+/// = Remarks
+///
+/// Like `futex_wait`, this function can return spuriously. The kernel measures the
+/// remaining time on the monotonic clock, so jumps of `clock` are only noticed when
+/// the function returns.
+pub fn wait_clock(addr: &Atomic<c_int>, val: c_int, clock: Clock,
+                  deadline: Time) -> Result {
+    let now = try!(clock.get_time());
+    if deadline <= now {
+        return Err(error::TimedOut);
+    }
+    let spec = time_to_timespec(deadline - now);
+    match rv!(futex_wait(addr, val, Some(&spec))) {
+        Err(error::TimedOut) => Err(error::TimedOut),
+        _ => Ok(()),
+    }
+}
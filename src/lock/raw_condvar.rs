@@ -8,7 +8,10 @@ use cell::cell::{Cell};
 use atomic::{Atomic};
 use syscall::{futex_wait, futex_wake};
 use cty::{c_int};
+use time_base::{Time};
+use time_base::clock::{self, Clock};
 use lock::{Lock, LockGuard};
+use futex::{self};
 
 const WAITING:  c_int = 0;
 const SIGNALED: c_int = 1;
@@ -89,11 +92,69 @@ impl RawCondvar {
     /// argument. The `guard` argument doesn't have to be related to the `lock` in any
     /// way.
     pub fn wait2<'a, 'b>(&self, lock: &'a Lock, guard: LockGuard<'b>) -> LockGuard<'a> {
-        unsafe { self.unsafe_wait(lock, guard) }
+        unsafe { self.unsafe_wait(lock, guard, None).0 }
     }
 
-    unsafe fn unsafe_wait<'a, 'b>(&self, user_lock: &'a Lock,
-                                  user_guard: LockGuard<'b>) -> LockGuard<'a> {
+    /// Atomically unlocks a lock guard and waits for a signal on this condvar or until
+    /// a certain amount of time has expired before re-locking the lock.
+    ///
+    /// [argument, guard]
+    /// The lock guard to be unlocked.
+    ///
+    /// [argument, time]
+    /// An upper bound for the amount of time until this function returns.
+    ///
+    /// [return_value]
+    /// Returns a guard created by re-locking the lock of the guard argument and whether
+    /// the function returned because of a signal.
+    ///
+    /// = Remarks
+    ///
+    /// The time is measured on the `MONO_RAW` clock. If no signal was received in
+    /// time, the second element of the return value is `TimedOut`.
+    ///
+    /// See `wait` for the restrictions on the lock that can be used.
+    pub fn wait_for<'a>(&self, guard: LockGuard<'a>,
+                        time: Time) -> (LockGuard<'a>, Result) {
+        let deadline = match futex::deadline(time) {
+            Ok(d) => d,
+            Err(e) => return (guard, Err(e)),
+        };
+        self.wait_until(guard, clock::MONO_RAW, deadline)
+    }
+
+    /// Atomically unlocks a lock guard and waits for a signal on this condvar or until
+    /// a clock has reached a deadline before re-locking the lock.
+    ///
+    /// [argument, guard]
+    /// The lock guard to be unlocked.
+    ///
+    /// [argument, clock]
+    /// The clock on which the deadline is measured.
+    ///
+    /// [argument, deadline]
+    /// The point in time, on `clock`, until which to wait.
+    ///
+    /// [return_value]
+    /// Returns a guard created by re-locking the lock of the guard argument and whether
+    /// the function returned because of a signal.
+    ///
+    /// = Remarks
+    ///
+    /// If no signal was received before the deadline, the second element of the return
+    /// value is `TimedOut`. The function may take longer to return than allowed by the
+    /// deadline.
+    ///
+    /// See `wait` for the restrictions on the lock that can be used.
+    pub fn wait_until<'a>(&self, guard: LockGuard<'a>, clock: Clock,
+                          deadline: Time) -> (LockGuard<'a>, Result) {
+        let lock = guard.as_lock();
+        unsafe { self.unsafe_wait(lock, guard, Some((clock, deadline))) }
+    }
+
+    unsafe fn unsafe_wait<'a, 'b>(&self, user_lock: &'a Lock, user_guard: LockGuard<'b>,
+                                  deadline: Option<(Clock, Time)>)
+                                  -> (LockGuard<'a>, Result) {
         let mut node: Node = mem::unsafe_zeroed();
 
         {
@@ -120,19 +181,73 @@ impl RawCondvar {
 
         drop(user_guard);
 
+        let mut res = Ok(());
+
         while node.lock.load() == WAITING {
-            futex_wait(&node.lock, WAITING, None);
+            let (clock, deadline) = match deadline {
+                Some(d) => d,
+                _ => {
+                    futex_wait(&node.lock, WAITING, None);
+                    continue;
+                },
+            };
+            if let Err(e) = futex::wait_clock(&node.lock, WAITING, clock, deadline) {
+                if self.remove(&mut node) {
+                    res = Err(e);
+                    break;
+                }
+            }
         }
 
         let user_guard = user_lock.lock();
 
+        if res.is_ok() {
+            // Pass the signal on to the next node in our group. See `_signal`.
+            let _cvguard = self.lock.lock();
+            if !node.right.is_null() {
+                let next = &mut *node.right;
+                next.lock.store(SIGNALED);
+                futex_wake(&next.lock, 1);
+            }
+        }
+
+        (user_guard, res)
+    }
+
+    /// Removes a node whose wait has timed out.
+    ///
+    /// [return_value]
+    /// Returns whether the node was removed. If it wasn't, then it has already been
+    /// signaled.
+    unsafe fn remove(&self, node: &mut Node) -> bool {
+        let _cvguard = self.lock.lock();
+        let inner = &mut *self.inner.ptr();
+
+        if node.lock.load() != WAITING {
+            return false;
+        }
+
+        // The node is either in the list of waiting nodes or in a group that has been
+        // signaled but whose predecessor hasn't passed the signal on yet. In the second
+        // case the node has a left neighbor. In both cases its neighbors are still
+        // alive because they pass the signal on with the condvar lock held.
+
+        if !node.left.is_null() {
+            (&mut *node.left).right = node.right;
+        } else {
+            inner.left_end = node.right;
+        }
         if !node.right.is_null() {
-            let next = &mut *node.right;
-            next.lock.store(SIGNALED);
-            futex_wake(&next.lock, 1);
+            (&mut *node.right).left = node.left;
+        } else if inner.right_end == node as *mut Node {
+            inner.right_end = node.left;
+        }
+        if inner.left_end.is_null() {
+            inner.right_end = 0 as *mut Node;
+            inner.user_lock = None;
         }
 
-        user_guard
+        true
     }
 
     /// Wakes a number of threads waiting on this condvar.
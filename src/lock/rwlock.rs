@@ -0,0 +1,308 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use base::{error};
+use io::{Write};
+use fmt::{Debug};
+use cell::cell::{Cell};
+use atomic::{Atomic};
+use syscall::{futex_wake};
+use cty::{c_int};
+use time_base::{Time};
+use futex::{self};
+
+const UNLOCKED:     c_int = 0;
+const WRITE_LOCKED: c_int = -1;
+const MAX_READERS:  c_int = c_int::max() - 1;
+
+/// A reader-writer lock protecting some data.
+///
+/// = Remarks
+///
+/// The data can either be borrowed immutably by any number of readers or mutably by a
+/// single writer. Waiting writers do not block new readers, so a steady stream of
+/// readers can starve writers.
+pub struct RwLock<T> {
+    /// The number of readers, `UNLOCKED`, or `WRITE_LOCKED`.
+    state: Atomic<c_int>,
+    /// The number of threads sleeping on `state`.
+    waiters: Atomic<c_int>,
+    data: Cell<T>,
+}
+
+impl<T> RwLock<T> {
+    /// Creates a new reader-writer lock.
+    ///
+    /// [argument, data]
+    /// The data to be protected by the lock.
+    pub const fn new(data: T) -> RwLock<T> {
+        RwLock {
+            state: Atomic::new(UNLOCKED),
+            waiters: Atomic::new(0),
+            data: Cell::new(data),
+        }
+    }
+
+    fn try_read_once(&self) -> Option<c_int> {
+        let state = self.state.load();
+        if state != WRITE_LOCKED && state < MAX_READERS {
+            if self.state.compare_exchange(state, state + 1) == state {
+                return None;
+            }
+        }
+        Some(state)
+    }
+
+    fn try_write_once(&self) -> Option<c_int> {
+        match self.state.compare_exchange(UNLOCKED, WRITE_LOCKED) {
+            UNLOCKED => None,
+            state => Some(state),
+        }
+    }
+
+    fn sleep(&self, state: c_int) {
+        self.waiters.add(1);
+        futex::wait(&self.state, state);
+        self.waiters.sub(1);
+    }
+
+    fn sleep_until(&self, state: c_int, deadline: Time) -> Result {
+        self.waiters.add(1);
+        let res = futex::wait_until(&self.state, state, deadline);
+        self.waiters.sub(1);
+        res
+    }
+
+    fn wake(&self) {
+        if self.waiters.load() > 0 {
+            futex_wake(&self.state, usize::max());
+        }
+    }
+
+    fn read_guard<'a>(&'a self) -> RwLockReadGuard<'a, T> {
+        RwLockReadGuard { lock: self, _marker: (NoSend, NoSync) }
+    }
+
+    fn write_guard<'a>(&'a self) -> RwLockWriteGuard<'a, T> {
+        RwLockWriteGuard { lock: self, _marker: (NoSend, NoSync) }
+    }
+
+    /// Tries to lock the lock for reading. Returns the state to sleep on if the lock is
+    /// locked for writing or has the maximum number of readers.
+    fn read_once(&self) -> Option<c_int> {
+        loop {
+            match self.try_read_once() {
+                None => return None,
+                Some(state) if state == WRITE_LOCKED || state == MAX_READERS => {
+                    return Some(state);
+                },
+                // Lost a race against another reader.
+                _ => { },
+            }
+        }
+    }
+
+    /// Tries to lock the lock for reading if it's currently not locked for writing.
+    ///
+    /// [return_value]
+    /// Returns a read guard if the operation succeeded.
+    pub fn try_read<'a>(&'a self) -> Result<RwLockReadGuard<'a, T>> {
+        match self.read_once() {
+            None => Ok(self.read_guard()),
+            _ => Err(error::ResourceBusy),
+        }
+    }
+
+    /// Locks the lock for reading by sleeping until the lock is not locked for writing.
+    ///
+    /// [return_value]
+    /// Returns a read guard.
+    pub fn read<'a>(&'a self) -> RwLockReadGuard<'a, T> {
+        while let Some(state) = self.read_once() {
+            self.sleep(state);
+        }
+        self.read_guard()
+    }
+
+    /// Locks the lock for reading by sleeping until the lock is not locked for writing
+    /// or until a certain amount of time has expired.
+    ///
+    /// [argument, time]
+    /// An upper bound for the amount of time until this function returns.
+    ///
+    /// [return_value]
+    /// Returns a read guard or an error.
+    ///
+    /// = Remarks
+    ///
+    /// The function may take longer to return than allowed by the `time` parameter.
+    pub fn try_read_until<'a>(&'a self, time: Time) -> Result<RwLockReadGuard<'a, T>> {
+        if let Ok(guard) = self.try_read() {
+            return Ok(guard);
+        }
+        let deadline = try!(futex::deadline(time));
+        while let Some(state) = self.read_once() {
+            try!(self.sleep_until(state, deadline));
+        }
+        Ok(self.read_guard())
+    }
+
+    /// Tries to lock the lock for writing if it's currently unlocked.
+    ///
+    /// [return_value]
+    /// Returns a write guard if the operation succeeded.
+    pub fn try_write<'a>(&'a self) -> Result<RwLockWriteGuard<'a, T>> {
+        match self.try_write_once() {
+            None => Ok(self.write_guard()),
+            _ => Err(error::ResourceBusy),
+        }
+    }
+
+    /// Locks the lock for writing by sleeping until the lock is unlocked.
+    ///
+    /// [return_value]
+    /// Returns a write guard.
+    pub fn write<'a>(&'a self) -> RwLockWriteGuard<'a, T> {
+        while let Some(state) = self.try_write_once() {
+            self.sleep(state);
+        }
+        self.write_guard()
+    }
+
+    /// Locks the lock for writing by sleeping until the lock is unlocked or until a
+    /// certain amount of time has expired.
+    ///
+    /// [argument, time]
+    /// An upper bound for the amount of time until this function returns.
+    ///
+    /// [return_value]
+    /// Returns a write guard or an error.
+    ///
+    /// = Remarks
+    ///
+    /// The function may take longer to return than allowed by the `time` parameter.
+    pub fn try_write_until<'a>(&'a self, time: Time) -> Result<RwLockWriteGuard<'a, T>> {
+        if let Ok(guard) = self.try_write() {
+            return Ok(guard);
+        }
+        let deadline = try!(futex::deadline(time));
+        while let Some(state) = self.try_write_once() {
+            try!(self.sleep_until(state, deadline));
+        }
+        Ok(self.write_guard())
+    }
+
+    /// Provides mutable access to the protected data without locking the lock.
+    ///
+    /// = Remarks
+    ///
+    /// This is safe because the availability of a mutable reference implies that there
+    /// are currently no guards borrowing the lock.
+    pub fn data(&mut self) -> &mut T {
+        unsafe { &mut *self.data.ptr() }
+    }
+}
+
+unsafe impl<T> Sync for RwLock<T> where T: Send+Sync { }
+unsafe impl<T> Send for RwLock<T> where T: Send { }
+
+/// A read guard.
+///
+/// = Remarks
+///
+/// This guard automatically releases the read lock when it goes out of scope.
+pub struct RwLockReadGuard<'a, T: 'a> {
+    lock: &'a RwLock<T>,
+    _marker: (NoSend, NoSync),
+}
+
+impl<'a, T> RwLockReadGuard<'a, T> {
+    /// Returns a reference to the underlying lock.
+    pub fn as_rw_lock(&self) -> &'a RwLock<T> {
+        self.lock
+    }
+
+    /// Releases the read lock and returns a reference to the lock.
+    pub fn unlock(self) -> &'a RwLock<T> {
+        self.lock
+    }
+}
+
+unsafe impl<'a, T> Sync for RwLockReadGuard<'a, T> where T: Sync { }
+
+impl<'a, T> Drop for RwLockReadGuard<'a, T> {
+    fn drop(&mut self) {
+        // Writers wait for the last reader and readers wait while the reader count is
+        // saturated.
+        match self.lock.state.sub(1) {
+            1 | MAX_READERS => self.lock.wake(),
+            _ => { },
+        }
+    }
+}
+
+impl<'a, T> Deref for RwLockReadGuard<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.data.ptr() }
+    }
+}
+
+impl<'a, T: Debug> Debug for RwLockReadGuard<'a, T> {
+    fn fmt<W: Write>(&self, w: &mut W) -> Result {
+        self.deref().fmt(w)
+    }
+}
+
+/// A write guard.
+///
+/// = Remarks
+///
+/// This guard automatically releases the write lock when it goes out of scope.
+pub struct RwLockWriteGuard<'a, T: 'a> {
+    lock: &'a RwLock<T>,
+    _marker: (NoSend, NoSync),
+}
+
+impl<'a, T> RwLockWriteGuard<'a, T> {
+    /// Returns a reference to the underlying lock.
+    pub fn as_rw_lock(&self) -> &'a RwLock<T> {
+        self.lock
+    }
+
+    /// Releases the write lock and returns a reference to the lock.
+    pub fn unlock(self) -> &'a RwLock<T> {
+        self.lock
+    }
+}
+
+unsafe impl<'a, T> Sync for RwLockWriteGuard<'a, T> where T: Sync { }
+unsafe impl<'a, T> Send for RwLockWriteGuard<'a, T> where T: Send { }
+
+impl<'a, T> Drop for RwLockWriteGuard<'a, T> {
+    fn drop(&mut self) {
+        self.lock.state.store(UNLOCKED);
+        self.lock.wake();
+    }
+}
+
+impl<'a, T> Deref for RwLockWriteGuard<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.data.ptr() }
+    }
+}
+
+impl<'a, T> DerefMut for RwLockWriteGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.data.ptr() }
+    }
+}
+
+impl<'a, T: Debug> Debug for RwLockWriteGuard<'a, T> {
+    fn fmt<W: Write>(&self, w: &mut W) -> Result {
+        self.deref().fmt(w)
+    }
+}
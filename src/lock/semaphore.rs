@@ -0,0 +1,115 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use base::{error};
+use atomic::{Atomic};
+use syscall::{futex_wake};
+use cty::{c_int};
+use time_base::{Time};
+use futex::{self};
+
+/// A counting semaphore.
+///
+/// = Remarks
+///
+/// This semaphore can be used for inter-process synchronization.
+#[repr(C)]
+pub struct Semaphore {
+    value: Atomic<c_int>,
+    /// The number of threads sleeping on `value`.
+    waiters: Atomic<c_int>,
+}
+
+impl Semaphore {
+    /// Creates a new semaphore.
+    ///
+    /// [argument, value]
+    /// The initial value of the semaphore.
+    pub const fn new(value: u32) -> Semaphore {
+        Semaphore {
+            value: Atomic::new(value as c_int),
+            waiters: Atomic::new(0),
+        }
+    }
+
+    /// Returns the current value of the semaphore.
+    pub fn value(&self) -> usize {
+        self.value.load_unordered() as usize
+    }
+
+    fn try_acquire_once(&self) -> Option<c_int> {
+        let value = self.value.load();
+        if value > 0 && self.value.compare_exchange(value, value - 1) == value {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Tries to decrement the value. Returns `false` if the value is zero.
+    fn acquire_once(&self) -> bool {
+        loop {
+            match self.try_acquire_once() {
+                None => return true,
+                Some(0) => return false,
+                _ => { },
+            }
+        }
+    }
+
+    /// Tries to decrement the value of the semaphore if it's currently positive.
+    ///
+    /// [return_value]
+    /// Returns whether the operation succeeded.
+    pub fn try_acquire(&self) -> Result {
+        match self.acquire_once() {
+            true => Ok(()),
+            false => Err(error::ResourceBusy),
+        }
+    }
+
+    /// Decrements the value of the semaphore by sleeping until the value is positive.
+    pub fn acquire(&self) {
+        while !self.acquire_once() {
+            self.waiters.add(1);
+            futex::wait(&self.value, 0);
+            self.waiters.sub(1);
+        }
+    }
+
+    /// Decrements the value of the semaphore by sleeping until the value is positive or
+    /// until a certain amount of time has expired.
+    ///
+    /// [argument, time]
+    /// An upper bound for the amount of time until this function returns.
+    ///
+    /// [return_value]
+    /// Returns whether the operation succeeded.
+    ///
+    /// = Remarks
+    ///
+    /// The function may take longer to return than allowed by the `time` parameter.
+    pub fn try_acquire_until(&self, time: Time) -> Result {
+        if self.try_acquire().is_ok() {
+            return Ok(());
+        }
+        let deadline = try!(futex::deadline(time));
+        while !self.acquire_once() {
+            self.waiters.add(1);
+            let res = futex::wait_until(&self.value, 0, deadline);
+            self.waiters.sub(1);
+            try!(res);
+        }
+        Ok(())
+    }
+
+    /// Increments the value of the semaphore and wakes a thread waiting for it.
+    pub fn release(&self) {
+        self.value.add(1);
+        if self.waiters.load() > 0 {
+            futex_wake(&self.value, 1);
+        }
+    }
+}
@@ -0,0 +1,11 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! CPU feature detection.
+
+pub use lrs_rt::cpu::{
+    Features, features, has, NONE, SSE2, SSE3, SSSE3, SSE4_1, SSE4_2, POPCNT, AVX, AVX2,
+    FMA, BMI1, BMI2, AVX512F, RDRAND, RDSEED, AES, CLMUL, SHA1, SHA2, CRC32, NEON, VFPV4,
+    ATOMICS, SHA512, SHA3, SVE,
+};
@@ -6,3 +6,4 @@
 //! tree.
 
 pub use lrs_dir::{Entry, Iter, DEFAULT_BUF_SIZE, iter};
+pub use lrs_dir::{Walk, WalkEntry, WalkOptions, Order, Follow, walk};
@@ -5,8 +5,14 @@
 //! Process environment.
 
 pub use lrs_rt::{args, arg_count, env};
-pub use lrs_env::{var, path, get_cwd, get_cwd_pool, set_cwd};
+pub use lrs_env::{
+    var, path, split_path, get_cwd, get_cwd_pool, set_cwd, set_var, remove_var, raw,
+    RawEnv, Environment, PathIter,
+};
 
 pub mod aux {
-    pub use lrs_rt::aux::{page_size};
+    pub use lrs_rt::aux::{
+        page_size, hwcap, hwcap2, platform, random_bytes, secure, min_signal_stack_size,
+        clock_ticks,
+    };
 }
@@ -0,0 +1,34 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+pub use lrs_fanotify::{
+    Fanotify, Response, FanEvent, FanEventIter, FileIdKind, FileId, EventInfo,
+    EventInfoIter,
+};
+pub use lrs_fanotify::flags::{FanotifyFlags, MarkFlags};
+pub use lrs_fanotify::event::{FanEvents};
+
+pub mod flags {
+    pub use lrs_fanotify::flags::{
+        FANOTIFY_NONE, FANOTIFY_DONT_BLOCK, FANOTIFY_CLOSE_ON_EXEC,
+        FANOTIFY_CLASS_CONTENT, FANOTIFY_CLASS_PRE_CONTENT, FANOTIFY_UNLIMITED_QUEUE,
+        FANOTIFY_UNLIMITED_MARKS, FANOTIFY_AUDIT, FANOTIFY_REPORT_PIDFD,
+        FANOTIFY_REPORT_TID, FANOTIFY_REPORT_FID, FANOTIFY_REPORT_DIR_FID,
+        FANOTIFY_REPORT_NAME, FANOTIFY_REPORT_TARGET_FID,
+        MARK_NONE, MARK_DONT_FOLLOW_LINKS, MARK_ONLY_DIRECTORY, MARK_MOUNT,
+        MARK_FILESYSTEM, MARK_EVICTABLE, MARK_IGNORE, MARK_IGNORED_MASK,
+        MARK_IGNORED_SURVIVE_MODIFY,
+    };
+}
+
+pub mod events {
+    pub use lrs_fanotify::event::{
+        FANEV_NONE, FANEV_CLOSE, FANEV_MOVE,
+        FANEV_ACCESS, FANEV_MODIFY, FANEV_ATTRIB, FANEV_CLOSE_WRITE, FANEV_CLOSE_READ,
+        FANEV_OPEN, FANEV_MOVED_FROM, FANEV_MOVED_TO, FANEV_CREATE, FANEV_DELETE,
+        FANEV_DELETE_SELF, FANEV_MOVE_SELF, FANEV_OPEN_EXEC, FANEV_OVERFLOW,
+        FANEV_FS_ERROR, FANEV_OPEN_PERM, FANEV_ACCESS_PERM, FANEV_OPEN_EXEC_PERM,
+        FANEV_ON_CHILD, FANEV_RENAME, FANEV_DIR,
+    };
+}
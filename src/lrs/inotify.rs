@@ -5,6 +5,7 @@
 pub use lrs_inotify::{Inotify, InodeWatch, InodeData, InodeDataIter};
 pub use lrs_inotify::flags::{WatchFlags, InotifyFlags};
 pub use lrs_inotify::event::{InodeEvents};
+pub use lrs_inotify::tree::{TreeWatcher, TreeEvent};
 
 pub mod flags {
     pub use lrs_inotify::flags::{
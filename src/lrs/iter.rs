@@ -5,8 +5,9 @@
 //! Iterators
 
 pub use lrs_core::iter::{
-    Iterator, Empty, IntoIterator,
+    Iterator, DoubleEndedIterator, Empty, IntoIterator,
 };
 pub use lrs_iter::{
-    repeat, Repeat, IteratorExt, Map,
+    repeat, Repeat, IteratorExt, FromIter, Map, Filter, Enumerate, Zip, Chain, Take, Skip,
+    TakeWhile, SkipWhile, Peekable, FlatMap, FilterMap, StepBy, Rev, Cycle, Inspect, Scan,
 };
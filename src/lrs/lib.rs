@@ -133,6 +133,7 @@ extern crate lrs_arch_fns;
 extern crate lrs_atomic;
 extern crate lrs_base;
 extern crate lrs_buf_reader;
+extern crate lrs_buf_writer;
 extern crate lrs_cell;
 extern crate lrs_cty;
 extern crate lrs_slice;
@@ -161,6 +162,7 @@ extern crate lrs_time_ext;
 extern crate lrs_vec;
 extern crate lrs_c_ptr_ptr;
 extern crate lrs_tree;
+extern crate lrs_treemap;
 extern crate lrs_lock;
 extern crate lrs_rand;
 
@@ -177,6 +179,7 @@ extern crate lrs_rand;
 #[cfg(not(freestanding))] extern crate lrs_queue;
 #[cfg(not(freestanding))] extern crate lrs_swap;
 #[cfg(not(freestanding))] extern crate lrs_inotify;
+#[cfg(not(freestanding))] extern crate lrs_fanotify;
 #[cfg(not(freestanding))] extern crate lrs_env;
 #[cfg(not(freestanding))] extern crate lrs_fs;
 #[cfg(not(freestanding))] extern crate lrs_socket;
@@ -190,6 +193,9 @@ extern crate lrs_rand;
 #[cfg(not(freestanding))] extern crate lrs_thread;
 #[cfg(not(freestanding))] extern crate lrs_process;
 #[cfg(not(freestanding))] extern crate lrs_dir;
+#[cfg(not(freestanding))] extern crate lrs_reactor;
+#[cfg(not(freestanding))] extern crate lrs_uring;
+#[cfg(not(freestanding))] extern crate lrs_resolve;
 // #[cfg(not(freestanding))] extern crate lrs_user_group;
 
 pub mod atomic;
@@ -218,6 +224,7 @@ pub mod marker;
 pub mod share;
 pub mod hash;
 pub mod hashmap;
+pub mod treemap;
 pub mod slice;
 pub mod string;
 pub mod conv;
@@ -234,6 +241,7 @@ pub mod rand;
 pub use hosted::{
     event, /* user, group, */ poll, sync, process, fd, file, dir, env, tty, fs, netlink,
     mem_map, signal, sys, thread, inotify, socket, syscall, msg_queue, pipe, swap,
+    reactor, uring, resolve, stdio, fanotify, cpu,
 };
 
 #[cfg(not(freestanding))]
@@ -262,6 +270,12 @@ mod hosted {
     pub mod msg_queue;
     pub mod pipe;
     pub mod swap;
+    pub mod reactor;
+    pub mod uring;
+    pub mod resolve;
+    pub mod stdio;
+    pub mod fanotify;
+    pub mod cpu;
 }
 
 /// The prelude.
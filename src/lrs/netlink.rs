@@ -11,7 +11,7 @@ pub use lrs_netlink::{MsgHeader, MsgError, Attr};
 
 pub mod route {
     pub use lrs_netlink::route::{
-        IfInfoMsg, IfAddrMsg, RouteMsg,
+        IfInfoMsg, IfAddrMsg, RouteMsg, NeighMsg,
     };
 
     pub mod op {
@@ -51,6 +51,25 @@ pub mod route {
             Unspec, Peer,
         };
     }
+
+    pub mod route_attr {
+        pub use lrs_netlink::route::route_attr::{
+            Unspec, Dst, Src, Iif, Oif, Gateway, Priority, PrefSrc, Metrics, Multipath,
+            Flow, CacheInfo, Table, Mark,
+        };
+    }
+
+    pub mod neigh_attr {
+        pub use lrs_netlink::route::neigh_attr::{
+            Unspec, Dst, LlAddr, CacheInfo, Probes, Vlan, Port, Vni, IfIndex, Master,
+        };
+    }
+
+    pub mod group {
+        pub use lrs_netlink::route::group::{
+            Link, Neigh, Ipv4Addr, Ipv4Route, Ipv6Addr, Ipv6Route,
+        };
+    }
 }
 
 pub mod fmt {
@@ -61,7 +80,13 @@ pub mod fmt {
 
 pub mod parse {
     pub use lrs_netlink::parse::{
-        MsgIter, MsgParser,
+        MsgIter, AttrIter, MsgParser,
+    };
+}
+
+pub mod rtnl {
+    pub use lrs_netlink::rtnl::{
+        RtNetlink, Link, LinkStats, OperState, Address, Route, Neighbour, Event,
     };
 }
 
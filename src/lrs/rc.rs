@@ -4,4 +4,4 @@
 
 //! Reference-counted objects.
 
-pub use lrs_rc::{Rc, RcBuf, Arc, ArcBuf};
+pub use lrs_rc::{Rc, RcBuf, RcWeak, Arc, ArcBuf, ArcWeak};
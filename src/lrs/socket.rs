@@ -30,6 +30,8 @@ pub mod ip {
     pub use lrs_socket::ip_proto::{Proto};
     pub use lrs_socket::addr::ipv4::{Ipv4Addr, Ipv4SockAddr};
     pub use lrs_socket::addr::ipv6::{Ipv6Addr, Ipv6SockAddr, Ipv6Scope};
+    pub use lrs_socket::addr::ip::{IpAddr};
+    pub use lrs_socket::addr::net::{Ipv4Net, Ipv6Net, Ipv4Subnets, Ipv6Subnets};
 
     /// IP protocol constants.
     ///
@@ -88,7 +90,7 @@ pub mod kind {
 /// Control messages.
 pub mod cmsg {
     pub use lrs_socket::cmsg::{
-        CMsgBuf, CMsgIter, CMsg, Credentials,
+        CMsgBuf, CMsgIter, CMsg, Credentials, Timestamp,
     };
 }
 
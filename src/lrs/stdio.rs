@@ -4,6 +4,10 @@
 
 //! Standard input/output.
 
+pub use lrs_buf_writer::stdio::{
+    BUF_SIZE, StdioWriter, StdioGuard, Stdout, Stderr, stdout, stderr,
+};
+
 pub mod raw {
-    pub use lrs_base::raw_stdio::*;
+    pub use lrs_fd::{STDIN, STDOUT, STDERR};
 }
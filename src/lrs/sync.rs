@@ -7,7 +7,8 @@
 pub use lrs_lock::{
     RawCondvar, Lock, LockGuard, DUMMY, Mutex,
     MutexGuard, Condvar, LockStatus, Once,
-    SpinLock, SpinLockGuard, SpinLockStatus,
+    SpinLock, SpinLockGuard, SpinLockStatus, RwLock, RwLockReadGuard,
+    RwLockWriteGuard, Semaphore, Barrier,
 };
 pub use lrs_queue::{
     Queue,
@@ -9,22 +9,25 @@ pub use lrs_syscall::{
     fcntl_setfd, ftruncate, getpid, getppid, setresuid, setresgid, fsync, fdatasync, sync,
     syncfs, fadvise, fchmod, fallocate, timerfd_create, epoll_create, flock, readahead,
     read, write, pread, pwrite, readv, writev, preadv, pwritev, getresuid, getresgid,
-    getgroups, setgroups, statfs, fstatfs, prlimit, getdents, fstatat, faccessat,
+    getgroups, setgroups, statfs, fstatfs, prlimit, getdents, fstatat, statx, faccessat,
     truncate, linkat, utimensat, renameat2, mkdirat, unlinkat, symlinkat, readlinkat,
     fchownat, fchmodat, mknodat, setxattr, lsetxattr, fsetxattr, getxattr, lgetxattr,
     fgetxattr, removexattr, lremovexattr, fremovexattr, listxattr, llistxattr, flistxattr,
-    clock_getres, clock_gettime, clock_settime, clock_nanosleep, timerfd_settime,
-    timerfd_gettime, epoll_ctl, epoll_pwait, sched_getaffinity, uname, sysinfo, getrandom,
+    clock_getres, clock_gettime, gettimeofday, time, clock_settime, clock_nanosleep,
+    timerfd_settime, timerfd_gettime, epoll_ctl, epoll_pwait, sched_getaffinity, uname,
+    sysinfo, getrandom,
     acct, mount, umount, sethostname, setdomainname, socket, connect, accept4, recvfrom,
     recvmsg, recvmmsg, sendto, sendmsg, sendmmsg, shutdown, bind, listen, getsockname,
     getpeername, socketpair, setsockopt, getsockopt, futex_wait, futex_wake, exit,
     exit_group, execveat, mmap, munmap, mremap, waitid, getcwd, chdir, ioctl_siocgstampns,
-    ioctl_siocinq, ioctl_siocoutq, rt_sigprocmask, rt_sigpending, rt_sigsuspend,
-    signalfd4, rt_sigtimedwait, rt_sigaction, pipe2, fcntl_setpipe_sz, fcntl_getpipe_sz,
+    ioctl_siocgifindex, ioctl_siocinq, ioctl_siocoutq, rt_sigprocmask, rt_sigpending,
+    rt_sigsuspend, signalfd4, rt_sigtimedwait, rt_sigaction, pipe2, fcntl_setpipe_sz, fcntl_getpipe_sz,
     ioctl_fionread, tee, splice, inotify_init1, inotify_add_watch, inotify_rm_watch, dup3,
+    fanotify_init, fanotify_mark,
     umask, eventfd2, times, reboot, memfd_create, fcntl_add_seals, fcntl_get_seals,
     madvise, mprotect, mlock, munlock, mlockall, munlockall, mincore, setsid, getsid,
-    fchdir, setpgid, getpgid, kill, tgkill, gettid, getrusage, ioctl_tiocgptn,
+    fchdir, setpgid, getpgid, kill, tgkill, pidfd_open, pidfd_send_signal, pidfd_getfd,
+    gettid, getrusage, ioctl_tiocgptn,
     ioctl_tiocsptlck, ioctl_tiocgptlck, ioctl_tiocsig, ioctl_tiocpkt, ioctl_tiocgpkt,
     ioctl_tiocsti, ioctl_tiocgwinsz, ioctl_tiocswinsz, ioctl_tioccons, ioctl_tiocexcl,
     ioctl_tiocnxcl, ioctl_tiocgexcl, ioctl_tiocnotty, ioctl_tiocsctty, ioctl_tiocgpgrp,
@@ -37,3 +40,7 @@ pub use lrs_syscall::{
     prctl_pr_get_keepcaps, prctl_pr_set_keepcaps, unshare, getcpu, setns,
     seccomp_seccomp_set_mode_strict, swapon, swapoff, chroot, pivot_root,
 };
+
+pub mod vdso {
+    pub use lrs_syscall::vdso::{Vdso, get};
+}
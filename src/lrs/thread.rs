@@ -67,3 +67,9 @@ pub mod capability {
         BlockSuspend, AuditRead,
     };
 }
+
+pub mod seccomp {
+    pub use lrs_thread::seccomp::{
+        Filter, Action, Cmp, ArgCmp, Instruction,
+    };
+}
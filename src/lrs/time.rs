@@ -66,7 +66,7 @@
 //! ----
 
 pub use lrs_time_base::{Time};
-pub use lrs_time_ext::{DateTime, Weekday, Zone};
+pub use lrs_time_ext::{DateTime, Weekday, Zone, RFC3339, RFC2822, HTTP_DATE};
 
 #[cfg(not(freestanding))] pub use lrs_time_base::timer::{Timer};
 #[cfg(not(freestanding))] pub use lrs_time_base::clock::{Clock};
@@ -2,4 +2,4 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-pub use lrs_tree::{Tree, Node, Entree, GetNode};
+pub use lrs_tree::{Tree, Node, Entree, GetNode, Cursor};
@@ -0,0 +1,9 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Ordered maps and sets.
+
+pub use lrs_treemap::{
+    TreeMap, TreeSet, Entry, VacantEntry, OccupiedEntry, Iter, RevIter, Range, Keys,
+};
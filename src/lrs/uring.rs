@@ -0,0 +1,15 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Asynchronous I/O via io_uring.
+
+pub use lrs_uring::{Uring, Completion, Completions};
+pub use lrs_uring::entry::{Entry, Timeout, CURRENT_POSITION};
+pub use lrs_uring::flags::{UringFlags};
+
+pub mod flags {
+    pub use lrs_uring::flags::{
+        URING_NONE, URING_IO_POLL, URING_SQ_POLL, URING_CLAMP,
+    };
+}
@@ -5,7 +5,7 @@
 use base::prelude::*;
 use core::{mem};
 use base::{error};
-use {MsgHeader};
+use {MsgHeader, Attr};
 
 pub struct MsgIter<'a> {
     data: &'a [u8],
@@ -55,6 +55,38 @@ impl<'a> Iterator for MsgIter<'a> {
     }
 }
 
+pub struct AttrIter<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> AttrIter<'a> {
+    pub fn new(data: &'a [u8]) -> AttrIter<'a> {
+        match data.as_ptr() as usize & 3 {
+            0 => AttrIter { data: data },
+            _ => AttrIter { data: &[] },
+        }
+    }
+}
+
+impl<'a> Iterator for AttrIter<'a> {
+    type Item = (u16, &'a [u8]);
+    fn next(&mut self) -> Option<(u16, &'a [u8])> {
+        let attr = match mem::from_bytes::<Attr>(self.data) {
+            Some(a) => a,
+            _ => return None,
+        };
+        let len = attr.len as usize;
+        if len > self.data.len() || len < mem::size_of::<Attr>() {
+            self.data = &[];
+            return None;
+        }
+        let payload = &self.data[mem::size_of::<Attr>()..len];
+        self.data = mem::align_for::<u32>(&self.data[len..]);
+        // The upper two bits are the NLA_F_NESTED and NLA_F_NET_BYTEORDER flags.
+        Some((attr.ty & 0x3fff, payload))
+    }
+}
+
 pub struct MsgParser<'a> {
     data: &'a [u8],
 }
@@ -73,6 +73,18 @@ pub struct RouteMsg {
     pub flags:    u32,
 }
 
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct NeighMsg {
+    pub family: u8,
+    pub pad1:   u8,
+    pub pad2:   u16,
+    pub index:  i32,
+    pub state:  u16,
+    pub flags:  u8,
+    pub ty:     u8,
+}
+
 pub mod link_attr {
     use cty::{
         IFLA_UNSPEC, IFLA_ADDRESS, IFLA_BROADCAST, IFLA_IFNAME, IFLA_MTU, IFLA_LINK,
@@ -162,3 +174,58 @@ pub mod veth_info {
     pub const Unspec : u16 = VETH_INFO_UNSPEC;
     pub const Peer   : u16 = VETH_INFO_PEER;
 }
+
+pub mod route_attr {
+    pub use cty::{
+        RTA_UNSPEC, RTA_DST, RTA_SRC, RTA_IIF, RTA_OIF, RTA_GATEWAY, RTA_PRIORITY,
+        RTA_PREFSRC, RTA_METRICS, RTA_MULTIPATH, RTA_FLOW, RTA_CACHEINFO, RTA_TABLE,
+        RTA_MARK,
+    };
+
+    pub const Unspec    : u16 = RTA_UNSPEC as u16;
+    pub const Dst       : u16 = RTA_DST as u16;
+    pub const Src       : u16 = RTA_SRC as u16;
+    pub const Iif       : u16 = RTA_IIF as u16;
+    pub const Oif       : u16 = RTA_OIF as u16;
+    pub const Gateway   : u16 = RTA_GATEWAY as u16;
+    pub const Priority  : u16 = RTA_PRIORITY as u16;
+    pub const PrefSrc   : u16 = RTA_PREFSRC as u16;
+    pub const Metrics   : u16 = RTA_METRICS as u16;
+    pub const Multipath : u16 = RTA_MULTIPATH as u16;
+    pub const Flow      : u16 = RTA_FLOW as u16;
+    pub const CacheInfo : u16 = RTA_CACHEINFO as u16;
+    pub const Table     : u16 = RTA_TABLE as u16;
+    pub const Mark      : u16 = RTA_MARK as u16;
+}
+
+pub mod neigh_attr {
+    pub use cty::{
+        NDA_UNSPEC, NDA_DST, NDA_LLADDR, NDA_CACHEINFO, NDA_PROBES, NDA_VLAN, NDA_PORT,
+        NDA_VNI, NDA_IFINDEX, NDA_MASTER,
+    };
+
+    pub const Unspec    : u16 = NDA_UNSPEC as u16;
+    pub const Dst       : u16 = NDA_DST as u16;
+    pub const LlAddr    : u16 = NDA_LLADDR as u16;
+    pub const CacheInfo : u16 = NDA_CACHEINFO as u16;
+    pub const Probes    : u16 = NDA_PROBES as u16;
+    pub const Vlan      : u16 = NDA_VLAN as u16;
+    pub const Port      : u16 = NDA_PORT as u16;
+    pub const Vni       : u16 = NDA_VNI as u16;
+    pub const IfIndex   : u16 = NDA_IFINDEX as u16;
+    pub const Master    : u16 = NDA_MASTER as u16;
+}
+
+pub mod group {
+    pub use cty::{
+        RTNLGRP_LINK, RTNLGRP_NEIGH, RTNLGRP_IPV4_IFADDR, RTNLGRP_IPV4_ROUTE,
+        RTNLGRP_IPV6_IFADDR, RTNLGRP_IPV6_ROUTE,
+    };
+
+    pub const Link      : u32 = RTNLGRP_LINK as u32;
+    pub const Neigh     : u32 = RTNLGRP_NEIGH as u32;
+    pub const Ipv4Addr  : u32 = RTNLGRP_IPV4_IFADDR as u32;
+    pub const Ipv4Route : u32 = RTNLGRP_IPV4_ROUTE as u32;
+    pub const Ipv6Addr  : u32 = RTNLGRP_IPV6_IFADDR as u32;
+    pub const Ipv6Route : u32 = RTNLGRP_IPV6_ROUTE as u32;
+}
@@ -0,0 +1,953 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A high-level interface to the routing netlink protocol.
+
+use base::prelude::*;
+use core::{mem};
+use base::{error};
+use cty::{
+    AF_UNSPEC, AF_INET, AF_INET6, AF_NETLINK, sockaddr_nl, rtnl_link_stats64, IFF_UP,
+    IFNAMSIZ, IF_OPER_NOTPRESENT, IF_OPER_DOWN, IF_OPER_LOWERLAYERDOWN, IF_OPER_TESTING,
+    IF_OPER_DORMANT, IF_OPER_UP, RT_TABLE_MAIN, RT_TABLE_UNSPEC, RTPROT_BOOT,
+    RT_SCOPE_UNIVERSE, RTN_UNICAST, NUD_PERMANENT,
+};
+use alloc::{self, AlignAlloc};
+use vec::{Vec};
+use socket::{Socket, IpAddr, Ipv4Addr, Ipv6Addr};
+use socket::nl_proto::{self};
+use socket::flags::{SOCK_CLOSE_ON_EXEC};
+use socket::msg::{MSG_NONE, MSG_REAL_SIZE};
+use fmt_::{Debug, Write};
+use kind::{self, Kind};
+use flags::{NlFlags, NLF_REQUEST, NLF_ACK, NLF_DUMP, NLF_CREATE, NLF_EXCL, NLF_REPLACE};
+use fmt::{NlBuf, NlData};
+use parse::{MsgIter, AttrIter};
+use route::{
+    self, IfInfoMsg, IfAddrMsg, RouteMsg, NeighMsg, link_attr, addr_attr, route_attr,
+    neigh_attr,
+};
+use {MsgError};
+
+/// The size of the buffer used to receive messages from the kernel.
+const RECV_SIZE: usize = 32 * 1024;
+
+/// The maximum length of a link-layer address.
+const MAX_ADDR_LEN: usize = 32;
+
+fn read<T: Pod>(data: &[u8]) -> Option<T> {
+    if data.len() < mem::size_of::<T>() {
+        return None;
+    }
+    let mut val: T = mem::zeroed();
+    mem::copy(val.as_mut(), &data[..mem::size_of::<T>()]);
+    Some(val)
+}
+
+fn family(addr: IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(..) => AF_INET as u8,
+        IpAddr::V6(..) => AF_INET6 as u8,
+    }
+}
+
+fn read_ip(family: u8, data: &[u8]) -> Option<IpAddr> {
+    if family == AF_INET as u8 && data.len() >= 4 {
+        Some(IpAddr::V4(Ipv4Addr(data[0], data[1], data[2], data[3])))
+    } else if family == AF_INET6 as u8 && data.len() >= 16 {
+        let mut segs = [0; 8];
+        for i in 0..8 {
+            segs[i] = (data[2 * i] as u16) << 8 | data[2 * i + 1] as u16;
+        }
+        Some(IpAddr::V6(Ipv6Addr::from_bytes(segs)))
+    } else {
+        None
+    }
+}
+
+fn add_ip(data: &mut NlData, ty: u16, addr: IpAddr) -> Result {
+    match addr {
+        IpAddr::V4(a) => data.add_data(ty, &a.to_bytes()),
+        IpAddr::V6(a) => {
+            let segs = a.to_bytes();
+            let mut octets = [0; 16];
+            for i in 0..8 {
+                octets[2 * i] = (segs[i] >> 8) as u8;
+                octets[2 * i + 1] = segs[i] as u8;
+            }
+            data.add_data(ty, &octets)
+        },
+    }
+}
+
+fn unspecified(family: u8) -> IpAddr {
+    match family == AF_INET6 as u8 {
+        true => IpAddr::V6(Ipv6Addr::from_bytes([0; 8])),
+        _ => IpAddr::V4(Ipv4Addr(0, 0, 0, 0)),
+    }
+}
+
+/// The operational state of a link.
+#[derive(Copy, Eq)]
+pub enum OperState {
+    /// The state is unknown.
+    Unknown,
+    /// Some component of the link is missing.
+    NotPresent,
+    /// The link is down.
+    Down,
+    /// The link is down because a link it depends on is down.
+    LowerLayerDown,
+    /// The link is in testing mode.
+    Testing,
+    /// The link is up but waiting for an external event.
+    Dormant,
+    /// The link is up.
+    Up,
+}
+
+impl OperState {
+    fn from_raw(val: u8) -> OperState {
+        match val as i32 {
+            IF_OPER_NOTPRESENT     => OperState::NotPresent,
+            IF_OPER_DOWN           => OperState::Down,
+            IF_OPER_LOWERLAYERDOWN => OperState::LowerLayerDown,
+            IF_OPER_TESTING        => OperState::Testing,
+            IF_OPER_DORMANT        => OperState::Dormant,
+            IF_OPER_UP             => OperState::Up,
+            _                      => OperState::Unknown,
+        }
+    }
+}
+
+impl Debug for OperState {
+    fn fmt<W: Write>(&self, w: &mut W) -> Result {
+        let s = match *self {
+            OperState::Unknown        => "Unknown",
+            OperState::NotPresent     => "NotPresent",
+            OperState::Down           => "Down",
+            OperState::LowerLayerDown => "LowerLayerDown",
+            OperState::Testing        => "Testing",
+            OperState::Dormant        => "Dormant",
+            OperState::Up             => "Up",
+        };
+        w.write_all(s.as_bytes()).ignore_ok()
+    }
+}
+
+/// Traffic statistics of a link.
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct LinkStats {
+    /// Number of packets received.
+    pub rx_packets: u64,
+    /// Number of packets sent.
+    pub tx_packets: u64,
+    /// Number of bytes received.
+    pub rx_bytes: u64,
+    /// Number of bytes sent.
+    pub tx_bytes: u64,
+    /// Number of receive errors.
+    pub rx_errors: u64,
+    /// Number of transmit errors.
+    pub tx_errors: u64,
+    /// Number of received packets that were dropped.
+    pub rx_dropped: u64,
+    /// Number of outgoing packets that were dropped.
+    pub tx_dropped: u64,
+}
+
+/// A network interface.
+#[derive(Copy)]
+pub struct Link {
+    /// The index of the interface.
+    pub index: i32,
+    /// The `IFF_*` flags of the interface.
+    pub flags: u32,
+    /// The maximum transmission unit.
+    pub mtu: u32,
+    /// The operational state.
+    pub state: OperState,
+    /// Traffic statistics.
+    pub stats: LinkStats,
+    name: [u8; IFNAMSIZ],
+    name_len: usize,
+    addr: [u8; MAX_ADDR_LEN],
+    addr_len: usize,
+}
+
+impl Link {
+    fn parse(payload: &[u8]) -> Option<Link> {
+        let msg: IfInfoMsg = match read(payload) {
+            Some(m) => m,
+            _ => return None,
+        };
+        let mut link = Link {
+            index: msg.index,
+            flags: msg.flags,
+            mtu: 0,
+            state: OperState::Unknown,
+            stats: mem::zeroed(),
+            name: [0; IFNAMSIZ],
+            name_len: 0,
+            addr: [0; MAX_ADDR_LEN],
+            addr_len: 0,
+        };
+        let attrs = mem::align_for::<u32>(&payload[mem::size_of::<IfInfoMsg>()..]);
+        for (ty, data) in AttrIter::new(attrs) {
+            match ty {
+                link_attr::IfName => {
+                    let len = data.find(|&b| b == 0).unwrap_or(data.len());
+                    link.name_len = mem::copy(&mut link.name[..], &data[..len]);
+                },
+                link_attr::Address => {
+                    link.addr_len = mem::copy(&mut link.addr[..], data);
+                },
+                link_attr::Mtu => {
+                    link.mtu = read(data).unwrap_or(0);
+                },
+                link_attr::Operstate => {
+                    link.state = OperState::from_raw(read(data).unwrap_or(0));
+                },
+                link_attr::Stats64 => {
+                    if let Some(s) = read::<rtnl_link_stats64>(data) {
+                        link.stats = LinkStats {
+                            rx_packets: s.rx_packets,
+                            tx_packets: s.tx_packets,
+                            rx_bytes:   s.rx_bytes,
+                            tx_bytes:   s.tx_bytes,
+                            rx_errors:  s.rx_errors,
+                            tx_errors:  s.tx_errors,
+                            rx_dropped: s.rx_dropped,
+                            tx_dropped: s.tx_dropped,
+                        };
+                    }
+                },
+                _ => { },
+            }
+        }
+        Some(link)
+    }
+
+    /// Returns the name of the interface.
+    pub fn name(&self) -> &[u8] {
+        &self.name[..self.name_len]
+    }
+
+    /// Returns the link-layer address of the interface, e.g., its MAC address.
+    pub fn address(&self) -> &[u8] {
+        &self.addr[..self.addr_len]
+    }
+
+    /// Returns whether the interface has been brought up.
+    pub fn is_up(&self) -> bool {
+        self.flags & IFF_UP as u32 != 0
+    }
+}
+
+/// An address assigned to a network interface.
+#[derive(Copy, Eq)]
+pub struct Address {
+    /// The index of the interface.
+    pub index: i32,
+    /// The address.
+    pub addr: IpAddr,
+    /// The length of the network prefix.
+    pub prefix: u8,
+    /// The scope of the address.
+    pub scope: u8,
+    /// The `IFA_F_*` flags of the address.
+    pub flags: u8,
+}
+
+impl Address {
+    fn parse(payload: &[u8]) -> Option<Address> {
+        let msg: IfAddrMsg = match read(payload) {
+            Some(m) => m,
+            _ => return None,
+        };
+        let mut local = None;
+        let mut address = None;
+        let attrs = mem::align_for::<u32>(&payload[mem::size_of::<IfAddrMsg>()..]);
+        for (ty, data) in AttrIter::new(attrs) {
+            match ty {
+                addr_attr::Local => local = read_ip(msg.family, data),
+                addr_attr::Address => address = read_ip(msg.family, data),
+                _ => { },
+            }
+        }
+        // On point-to-point links, IFA_ADDRESS is the address of the peer.
+        let addr = match local.or(address) {
+            Some(a) => a,
+            _ => return None,
+        };
+        Some(Address {
+            index: msg.index as i32,
+            addr: addr,
+            prefix: msg.prefix,
+            scope: msg.scope,
+            flags: msg.flags,
+        })
+    }
+}
+
+/// A route.
+#[derive(Copy)]
+pub struct Route {
+    /// The destination network.
+    pub dst: IpAddr,
+    /// The length of the destination prefix.
+    pub dst_len: u8,
+    /// The gateway.
+    pub gateway: Option<IpAddr>,
+    /// The index of the outgoing interface.
+    pub oif: Option<i32>,
+    /// The metric of the route.
+    pub priority: Option<u32>,
+    /// The preferred source address.
+    pub src: Option<IpAddr>,
+    /// The routing table.
+    pub table: u32,
+    /// The `RTPROT_*` protocol that installed the route.
+    pub protocol: u8,
+    /// The `RT_SCOPE_*` scope of the route.
+    pub scope: u8,
+    /// The `RTN_*` type of the route.
+    pub kind: u8,
+}
+
+impl Route {
+    /// Creates a new unicast route in the main table.
+    ///
+    /// [argument, dst]
+    /// The destination network.
+    ///
+    /// [argument, dst_len]
+    /// The length of the destination prefix.
+    ///
+    /// = Remarks
+    ///
+    /// The gateway, the outgoing interface, and the other optional fields are unset.
+    /// The scope is `RT_SCOPE_UNIVERSE` which is suitable for routes via a gateway.
+    /// Routes to directly attached networks usually use `RT_SCOPE_LINK`.
+    pub fn new(dst: IpAddr, dst_len: u8) -> Route {
+        Route {
+            dst: dst,
+            dst_len: dst_len,
+            gateway: None,
+            oif: None,
+            priority: None,
+            src: None,
+            table: RT_TABLE_MAIN as u32,
+            protocol: RTPROT_BOOT,
+            scope: RT_SCOPE_UNIVERSE,
+            kind: RTN_UNICAST as u8,
+        }
+    }
+
+    fn parse(payload: &[u8]) -> Option<Route> {
+        let msg: RouteMsg = match read(payload) {
+            Some(m) => m,
+            _ => return None,
+        };
+        let mut route = Route {
+            dst: unspecified(msg.family),
+            dst_len: msg.dst_len,
+            gateway: None,
+            oif: None,
+            priority: None,
+            src: None,
+            table: msg.table as u32,
+            protocol: msg.protocol,
+            scope: msg.scope,
+            kind: msg.ty,
+        };
+        let attrs = mem::align_for::<u32>(&payload[mem::size_of::<RouteMsg>()..]);
+        for (ty, data) in AttrIter::new(attrs) {
+            match ty {
+                route_attr::Dst => {
+                    route.dst = read_ip(msg.family, data).unwrap_or(route.dst);
+                },
+                route_attr::Gateway => route.gateway = read_ip(msg.family, data),
+                route_attr::PrefSrc => route.src = read_ip(msg.family, data),
+                route_attr::Oif => route.oif = read(data),
+                route_attr::Priority => route.priority = read(data),
+                route_attr::Table => route.table = read(data).unwrap_or(route.table),
+                _ => { },
+            }
+        }
+        Some(route)
+    }
+
+    fn write(&self, data: &mut NlData) -> Result {
+        let table = match self.table < 256 {
+            true => self.table as u8,
+            _ => RT_TABLE_UNSPEC,
+        };
+        let msg = RouteMsg {
+            family: family(self.dst),
+            dst_len: self.dst_len,
+            src_len: 0,
+            tos: 0,
+            table: table,
+            protocol: self.protocol,
+            scope: self.scope,
+            ty: self.kind,
+            flags: 0,
+        };
+        try!(data.add_raw(msg.as_ref()));
+        if self.dst_len > 0 {
+            try!(add_ip(data, route_attr::Dst, self.dst));
+        }
+        if let Some(gw) = self.gateway {
+            try!(add_ip(data, route_attr::Gateway, gw));
+        }
+        if let Some(src) = self.src {
+            try!(add_ip(data, route_attr::PrefSrc, src));
+        }
+        if let Some(oif) = self.oif {
+            try!(data.add_i32(route_attr::Oif, oif));
+        }
+        if let Some(prio) = self.priority {
+            try!(data.add_u32(route_attr::Priority, prio));
+        }
+        data.add_u32(route_attr::Table, self.table)
+    }
+}
+
+/// An entry of the neighbour table, e.g., an ARP entry.
+#[derive(Copy)]
+pub struct Neighbour {
+    /// The index of the interface.
+    pub index: i32,
+    /// The network-layer address of the neighbour.
+    pub addr: IpAddr,
+    /// The `NUD_*` state of the entry.
+    pub state: u16,
+    /// The `NTF_*` flags of the entry.
+    pub flags: u8,
+    lladdr: [u8; MAX_ADDR_LEN],
+    lladdr_len: usize,
+}
+
+impl Neighbour {
+    /// Creates a new permanent neighbour entry.
+    ///
+    /// [argument, index]
+    /// The index of the interface.
+    ///
+    /// [argument, addr]
+    /// The network-layer address of the neighbour.
+    ///
+    /// [argument, lladdr]
+    /// The link-layer address of the neighbour.
+    ///
+    /// = Remarks
+    ///
+    /// Link-layer addresses longer than 32 bytes are truncated.
+    pub fn new(index: i32, addr: IpAddr, lladdr: &[u8]) -> Neighbour {
+        let mut neigh = Neighbour {
+            index: index,
+            addr: addr,
+            state: NUD_PERMANENT as u16,
+            flags: 0,
+            lladdr: [0; MAX_ADDR_LEN],
+            lladdr_len: 0,
+        };
+        neigh.lladdr_len = mem::copy(&mut neigh.lladdr[..], lladdr);
+        neigh
+    }
+
+    /// Returns the link-layer address of the neighbour.
+    pub fn lladdr(&self) -> &[u8] {
+        &self.lladdr[..self.lladdr_len]
+    }
+
+    fn parse(payload: &[u8]) -> Option<Neighbour> {
+        let msg: NeighMsg = match read(payload) {
+            Some(m) => m,
+            _ => return None,
+        };
+        let mut addr = None;
+        let mut lladdr: &[u8] = &[];
+        let attrs = mem::align_for::<u32>(&payload[mem::size_of::<NeighMsg>()..]);
+        for (ty, data) in AttrIter::new(attrs) {
+            match ty {
+                neigh_attr::Dst => addr = read_ip(msg.family, data),
+                neigh_attr::LlAddr => lladdr = data,
+                _ => { },
+            }
+        }
+        let mut neigh = match addr {
+            Some(a) => Neighbour::new(msg.index, a, lladdr),
+            _ => return None,
+        };
+        neigh.state = msg.state;
+        neigh.flags = msg.flags;
+        Some(neigh)
+    }
+
+    fn write(&self, data: &mut NlData) -> Result {
+        let msg = NeighMsg {
+            family: family(self.addr),
+            pad1: 0,
+            pad2: 0,
+            index: self.index,
+            state: self.state,
+            flags: self.flags,
+            ty: 0,
+        };
+        try!(data.add_raw(msg.as_ref()));
+        try!(add_ip(data, neigh_attr::Dst, self.addr));
+        if self.lladdr_len > 0 {
+            try!(data.add_data(neigh_attr::LlAddr, self.lladdr()));
+        }
+        Ok(())
+    }
+}
+
+/// A change notification.
+///
+/// = See also
+///
+/// * link:lrs::netlink::rtnl::RtNetlink::next_event
+#[derive(Copy)]
+pub enum Event {
+    /// A link was created or changed.
+    NewLink(Link),
+    /// A link was removed.
+    DelLink(Link),
+    /// An address was added.
+    NewAddr(Address),
+    /// An address was removed.
+    DelAddr(Address),
+    /// A route was added.
+    NewRoute(Route),
+    /// A route was removed.
+    DelRoute(Route),
+    /// A neighbour entry was created or changed.
+    NewNeigh(Neighbour),
+    /// A neighbour entry was removed.
+    DelNeigh(Neighbour),
+}
+
+impl Event {
+    fn parse(ty: Kind, payload: &[u8]) -> Option<Event> {
+        match ty {
+            route::NewLink  => Link::parse(payload).map(Event::NewLink),
+            route::DelLink  => Link::parse(payload).map(Event::DelLink),
+            route::NewAddr  => Address::parse(payload).map(Event::NewAddr),
+            route::DelAddr  => Address::parse(payload).map(Event::DelAddr),
+            route::NewRoute => Route::parse(payload).map(Event::NewRoute),
+            route::DelRoute => Route::parse(payload).map(Event::DelRoute),
+            route::NewNeigh => Neighbour::parse(payload).map(Event::NewNeigh),
+            route::DelNeigh => Neighbour::parse(payload).map(Event::DelNeigh),
+            _ => None,
+        }
+    }
+}
+
+/// A routing netlink socket.
+///
+/// = Remarks
+///
+/// All requests are blocking. While a request is in progress, notifications received
+/// on the socket are discarded.
+///
+/// = Examples
+///
+/// ----
+/// let mut rtnl = RtNetlink::new().unwrap();
+/// for link in &rtnl.links().unwrap() {
+///     println!("{:?}: mtu {}", link.name().as_str(), link.mtu);
+/// }
+/// ----
+///
+/// = See also
+///
+/// * link:man:rtnetlink(7)
+pub struct RtNetlink {
+    sock: Socket,
+    seq: u32,
+    rbuf: Vec<u8, AlignAlloc<u32, alloc::Heap>>,
+    rpos: usize,
+    rlen: usize,
+}
+
+impl RtNetlink {
+    /// Opens a new routing netlink socket.
+    pub fn new() -> Result<RtNetlink> {
+        let sock = try!(Socket::netlink(nl_proto::Route, SOCK_CLOSE_ON_EXEC));
+        let mut addr: sockaddr_nl = mem::zeroed();
+        addr.nl_family = AF_NETLINK as u16;
+        // Binding to port 0 assigns a unique port. Without a port, notifications are
+        // not delivered to the socket.
+        try!(sock.bind(&addr));
+        let mut rbuf: Vec<_, _> = try!(Vec::with_capacity(RECV_SIZE));
+        unsafe { rbuf.set_len(RECV_SIZE); }
+        Ok(RtNetlink { sock: sock, seq: 0, rbuf: rbuf, rpos: 0, rlen: 0 })
+    }
+
+    /// Returns the underlying socket.
+    pub fn socket(&self) -> &Socket {
+        &self.sock
+    }
+
+    fn next_seq(&mut self) -> u32 {
+        self.seq = self.seq.wrapping_add(1);
+        self.seq
+    }
+
+    fn send(&mut self, buf: &NlBuf) -> Result {
+        self.rpos = 0;
+        self.rlen = 0;
+        let data = unsafe { buf.as_ref().as_bytes() };
+        try!(self.sock.send(data, MSG_NONE));
+        Ok(())
+    }
+
+    /// Receives a datagram into the receive buffer and returns its length.
+    ///
+    /// Datagrams that don't fit into the buffer cause the error `MessageSize` because
+    /// a truncated reply cannot be parsed.
+    fn recv(&mut self) -> Result<usize> {
+        let len = try!(self.sock.recv(&mut self.rbuf, MSG_REAL_SIZE));
+        if len > self.rbuf.len() {
+            return Err(error::MessageSize);
+        }
+        Ok(len)
+    }
+
+    /// Sends a request and collects the replies until the end of the dump.
+    fn dump<F>(&mut self, ty: Kind, hdr: &[u8], mut f: F) -> Result
+        where F: FnMut(Kind, &[u8]) -> Result,
+    {
+        let seq = self.next_seq();
+        let mut buf: NlBuf = NlBuf::new();
+        {
+            let mut msg = try!(buf.new_msg(ty, NLF_REQUEST | NLF_DUMP, seq, 0));
+            try!(msg.add_raw(hdr));
+        }
+        try!(self.send(&buf));
+        loop {
+            let len = try!(self.recv());
+            let mut res = Ok(());
+            for (header, payload) in MsgIter::new(&self.rbuf[..len], Some(&mut res)) {
+                if header.sequence != seq {
+                    continue;
+                }
+                if header.ty == kind::Done {
+                    return Ok(());
+                }
+                if header.ty == kind::ErrorAck {
+                    if let Some(err) = read::<MsgError>(payload) {
+                        if err.error != 0 {
+                            return Err(error::Errno(-err.error));
+                        }
+                    }
+                    continue;
+                }
+                try!(f(header.ty, payload));
+            }
+            try!(res);
+        }
+    }
+
+    /// Sends a request and waits for the acknowledgement.
+    fn request<F>(&mut self, ty: Kind, flags: NlFlags, f: F) -> Result
+        where F: FnOnce(&mut NlData) -> Result,
+    {
+        let seq = self.next_seq();
+        let mut buf: NlBuf = NlBuf::new();
+        {
+            let mut msg = try!(buf.new_msg(ty, NLF_REQUEST | NLF_ACK | flags, seq, 0));
+            try!(f(&mut msg));
+        }
+        try!(self.send(&buf));
+        loop {
+            let len = try!(self.recv());
+            let mut res = Ok(());
+            for (header, payload) in MsgIter::new(&self.rbuf[..len], Some(&mut res)) {
+                if header.sequence != seq || header.ty != kind::ErrorAck {
+                    continue;
+                }
+                return match read::<MsgError>(payload) {
+                    Some(err) if err.error != 0 => Err(error::Errno(-err.error)),
+                    Some(_) => Ok(()),
+                    _ => Err(error::InvalidSequence),
+                };
+            }
+            try!(res);
+        }
+    }
+
+    /// Retrieves all network interfaces.
+    ///
+    /// = See also
+    ///
+    /// * link:man:rtnetlink(7) and RTM_GETLINK therein
+    pub fn links(&mut self) -> Result<Vec<Link>> {
+        let msg: IfInfoMsg = mem::zeroed();
+        let mut links = Vec::new();
+        try!(self.dump(route::GetLink, msg.as_ref(), |ty, payload| {
+            match (ty == route::NewLink, Link::parse(payload)) {
+                (true, Some(link)) => links.push(link),
+                _ => Ok(()),
+            }
+        }));
+        Ok(links)
+    }
+
+    /// Retrieves a network interface by name.
+    ///
+    /// [argument, name]
+    /// The name of the interface.
+    ///
+    /// = Remarks
+    ///
+    /// If there is no such interface, an error is returned.
+    pub fn link_by_name<N: ?Sized>(&mut self, name: &N) -> Result<Link>
+        where N: AsRef<[u8]>,
+    {
+        let name = name.as_ref();
+        for link in &try!(self.links()) {
+            if link.name() == name {
+                return Ok(*link);
+            }
+        }
+        Err(error::DoesNotExist)
+    }
+
+    /// Brings a network interface up or down.
+    ///
+    /// [argument, index]
+    /// The index of the interface.
+    ///
+    /// [argument, up]
+    /// Whether the interface is brought up.
+    ///
+    /// = See also
+    ///
+    /// * link:man:rtnetlink(7) and RTM_NEWLINK therein
+    pub fn set_link_up(&mut self, index: i32, up: bool) -> Result {
+        let msg = IfInfoMsg {
+            family: AF_UNSPEC as u8,
+            ty: 0,
+            index: index,
+            flags: if up { IFF_UP as u32 } else { 0 },
+            change: IFF_UP as u32,
+        };
+        self.request(route::NewLink, NlFlags(0), |data| data.add_raw(msg.as_ref()))
+    }
+
+    /// Retrieves all addresses assigned to network interfaces.
+    ///
+    /// = See also
+    ///
+    /// * link:man:rtnetlink(7) and RTM_GETADDR therein
+    pub fn addrs(&mut self) -> Result<Vec<Address>> {
+        let msg: IfAddrMsg = mem::zeroed();
+        let mut addrs = Vec::new();
+        try!(self.dump(route::GetAddr, msg.as_ref(), |ty, payload| {
+            match (ty == route::NewAddr, Address::parse(payload)) {
+                (true, Some(addr)) => addrs.push(addr),
+                _ => Ok(()),
+            }
+        }));
+        Ok(addrs)
+    }
+
+    fn addr_common(&mut self, ty: Kind, flags: NlFlags, index: i32, addr: IpAddr,
+                   prefix: u8) -> Result {
+        let msg = IfAddrMsg {
+            family: family(addr),
+            prefix: prefix,
+            flags: 0,
+            scope: RT_SCOPE_UNIVERSE,
+            index: index as u32,
+        };
+        self.request(ty, flags, |data| {
+            try!(data.add_raw(msg.as_ref()));
+            try!(add_ip(data, addr_attr::Local, addr));
+            add_ip(data, addr_attr::Address, addr)
+        })
+    }
+
+    /// Assigns an address to a network interface.
+    ///
+    /// [argument, index]
+    /// The index of the interface.
+    ///
+    /// [argument, addr]
+    /// The address.
+    ///
+    /// [argument, prefix]
+    /// The length of the network prefix.
+    ///
+    /// = See also
+    ///
+    /// * link:man:rtnetlink(7) and RTM_NEWADDR therein
+    pub fn add_addr(&mut self, index: i32, addr: IpAddr, prefix: u8) -> Result {
+        self.addr_common(route::NewAddr, NLF_CREATE | NLF_EXCL, index, addr, prefix)
+    }
+
+    /// Removes an address from a network interface.
+    ///
+    /// [argument, index]
+    /// The index of the interface.
+    ///
+    /// [argument, addr]
+    /// The address.
+    ///
+    /// [argument, prefix]
+    /// The length of the network prefix.
+    ///
+    /// = See also
+    ///
+    /// * link:man:rtnetlink(7) and RTM_DELADDR therein
+    pub fn del_addr(&mut self, index: i32, addr: IpAddr, prefix: u8) -> Result {
+        self.addr_common(route::DelAddr, NlFlags(0), index, addr, prefix)
+    }
+
+    /// Retrieves all routes.
+    ///
+    /// = Remarks
+    ///
+    /// This includes the routes of all tables, e.g., the local table.
+    ///
+    /// = See also
+    ///
+    /// * link:man:rtnetlink(7) and RTM_GETROUTE therein
+    pub fn routes(&mut self) -> Result<Vec<Route>> {
+        let msg: RouteMsg = mem::zeroed();
+        let mut routes = Vec::new();
+        try!(self.dump(route::GetRoute, msg.as_ref(), |ty, payload| {
+            match (ty == route::NewRoute, Route::parse(payload)) {
+                (true, Some(route)) => routes.push(route),
+                _ => Ok(()),
+            }
+        }));
+        Ok(routes)
+    }
+
+    /// Adds a route.
+    ///
+    /// [argument, route]
+    /// The route.
+    ///
+    /// = See also
+    ///
+    /// * link:man:rtnetlink(7) and RTM_NEWROUTE therein
+    pub fn add_route(&mut self, route: &Route) -> Result {
+        self.request(route::NewRoute, NLF_CREATE | NLF_EXCL, |data| route.write(data))
+    }
+
+    /// Removes a route.
+    ///
+    /// [argument, route]
+    /// The route.
+    ///
+    /// = See also
+    ///
+    /// * link:man:rtnetlink(7) and RTM_DELROUTE therein
+    pub fn del_route(&mut self, route: &Route) -> Result {
+        self.request(route::DelRoute, NlFlags(0), |data| route.write(data))
+    }
+
+    /// Retrieves all neighbour entries.
+    ///
+    /// = See also
+    ///
+    /// * link:man:rtnetlink(7) and RTM_GETNEIGH therein
+    pub fn neighbours(&mut self) -> Result<Vec<Neighbour>> {
+        let msg: NeighMsg = mem::zeroed();
+        let mut neighs = Vec::new();
+        try!(self.dump(route::GetNeigh, msg.as_ref(), |ty, payload| {
+            match (ty == route::NewNeigh, Neighbour::parse(payload)) {
+                (true, Some(neigh)) => neighs.push(neigh),
+                _ => Ok(()),
+            }
+        }));
+        Ok(neighs)
+    }
+
+    /// Adds or replaces a neighbour entry.
+    ///
+    /// [argument, neigh]
+    /// The entry.
+    ///
+    /// = See also
+    ///
+    /// * link:man:rtnetlink(7) and RTM_NEWNEIGH therein
+    pub fn add_neigh(&mut self, neigh: &Neighbour) -> Result {
+        self.request(route::NewNeigh, NLF_CREATE | NLF_REPLACE, |data| neigh.write(data))
+    }
+
+    /// Removes a neighbour entry.
+    ///
+    /// [argument, neigh]
+    /// The entry.
+    ///
+    /// = See also
+    ///
+    /// * link:man:rtnetlink(7) and RTM_DELNEIGH therein
+    pub fn del_neigh(&mut self, neigh: &Neighbour) -> Result {
+        self.request(route::DelNeigh, NlFlags(0), |data| neigh.write(data))
+    }
+
+    /// Subscribes to a multicast group.
+    ///
+    /// [argument, group]
+    /// The group.
+    ///
+    /// = Remarks
+    ///
+    /// :groups: link:lrs::netlink::route::group
+    ///
+    /// See {groups} for pre-defined constants.
+    ///
+    /// = See also
+    ///
+    /// * link:lrs::netlink::rtnl::RtNetlink::next_event
+    pub fn subscribe(&mut self, group: u32) -> Result {
+        self.sock.netlink_join_group(group)
+    }
+
+    /// Unsubscribes from a multicast group.
+    ///
+    /// [argument, group]
+    /// The group.
+    pub fn unsubscribe(&mut self, group: u32) -> Result {
+        self.sock.netlink_leave_group(group)
+    }
+
+    /// Waits for the next notification.
+    ///
+    /// = Remarks
+    ///
+    /// Messages that don't describe a change of a link, address, route, or neighbour
+    /// entry are skipped.
+    pub fn next_event(&mut self) -> Result<Event> {
+        loop {
+            if self.rpos == self.rlen {
+                self.rpos = 0;
+                self.rlen = 0;
+                self.rlen = try!(self.recv());
+            }
+            let (event, used) = {
+                let data = &self.rbuf[self.rpos..self.rlen];
+                let mut iter = MsgIter::new(data, None);
+                match iter.next() {
+                    Some((header, payload)) => (Event::parse(header.ty, payload),
+                                                data.len() - iter.data().len()),
+                    _ => (None, data.len()),
+                }
+            };
+            self.rpos += used;
+            if let Some(event) = event {
+                return Ok(event);
+            }
+        }
+    }
+}
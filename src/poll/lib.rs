@@ -182,7 +182,20 @@ impl Event {
     pub fn is_hang_up(self) -> bool { self.data.events & cty::POLLHUP != 0 }
 
     /// Returns the associated file descriptor.
+    ///
+    /// = Remarks
+    ///
+    /// If the file descriptor was added with custom data, this returns the lower bits of
+    /// the data instead.
     pub fn fd(self) -> c_int { self.data.data as c_int }
+
+    /// Returns the data associated with the file descriptor.
+    ///
+    /// = Remarks
+    ///
+    /// Unless the file descriptor was added with custom data, this is the value of the
+    /// file descriptor.
+    pub fn data(self) -> u64 { self.data.data }
 }
 
 impl Debug for Event {
@@ -238,6 +251,41 @@ impl Epoll {
         rv!(epoll_ctl(self.fd, EPOLL_CTL_MOD, fd.borrow(), Some(&mut event)))
     }
 
+    /// Adds a file descriptor with custom data to the epoll instance.
+    ///
+    /// [argument, fd]
+    /// The file descriptor to add.
+    ///
+    /// [argument, flags]
+    /// The flags to be set.
+    ///
+    /// [argument, data]
+    /// The data that will be returned with events of this file descriptor.
+    ///
+    /// = See also
+    ///
+    /// * link:lrs::poll::Event::data
+    pub fn add_data<T: FdContainer>(&self, fd: &T, flags: PollFlags, data: u64) -> Result {
+        let mut event = epoll_event { events: flags.0, data: data };
+        rv!(epoll_ctl(self.fd, EPOLL_CTL_ADD, fd.borrow(), Some(&mut event)))
+    }
+
+    /// Modifies the flags and the custom data associated with an added file descriptor.
+    ///
+    /// [argument, fd]
+    /// The file descriptor to modify.
+    ///
+    /// [argument, flags]
+    /// The new flags.
+    ///
+    /// [argument, data]
+    /// The new data.
+    pub fn modify_data<T: FdContainer>(&self, fd: &T, flags: PollFlags,
+                                       data: u64) -> Result {
+        let mut event = epoll_event { events: flags.0, data: data };
+        rv!(epoll_ctl(self.fd, EPOLL_CTL_MOD, fd.borrow(), Some(&mut event)))
+    }
+
     /// Removes a file descriptor from an epoll instance.
     ///
     /// [argument, fd]
@@ -0,0 +1,616 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use core::{mem};
+use base::{error};
+use base::error::{Errno};
+use cty::{c_int, c_char, SIGKILL};
+use cty::alias::{ProcessId};
+use syscall::{dup3, fcntl_setfd, fcntl_dupfd_cloexec, chdir, kill, exit_group};
+use str_one::{NoNullStr, CStr};
+use str_two::{CString};
+use c_ptr_ptr::{CPtrPtr};
+use vec::{Vec};
+use alloc::{self, MemPool};
+use io::{Read, BufWrite};
+use fd::{FdContainer};
+use pipe::{Pipe};
+use pipe::flags::{PIPE_CLOSE_ON_EXEC};
+use file::{File};
+use file::flags::{FILE_READ_WRITE, Mode};
+use poll::{Epoll, Event, EMPTY_EVENT, POLL_READ};
+use clone::{fork_continue};
+use env::{Environment};
+use exec::{exec_with_path};
+use wait::{
+    ChildStatus, wait_id, WAIT_EXITED, WAIT_DONT_BLOCK,
+};
+
+/// The exit code of a child process whose `exec` call failed.
+///
+/// = Remarks
+///
+/// The parent never observes this exit code since the error is reported by `spawn`
+/// instead.
+const EXEC_FAILED: c_int = 127;
+
+/// The configuration of a standard stream of a child process.
+#[derive(Copy, Eq)]
+pub enum Stdio {
+    /// The child inherits the stream from the parent.
+    Inherit,
+
+    /// The stream is connected to `/dev/null`.
+    Null,
+
+    /// The stream is connected to a new pipe whose other end is available in the parent
+    /// via the `Child` object.
+    Piped,
+
+    /// The stream is connected to an existing file descriptor.
+    ///
+    /// [field, 1]
+    /// The file descriptor.
+    ///
+    /// = Remarks
+    ///
+    /// The file descriptor is borrowed and must stay open until `spawn` has returned.
+    Fd(c_int),
+}
+
+impl Stdio {
+    /// Creates a stream configuration that borrows a file descriptor container.
+    ///
+    /// [argument, fd]
+    /// The container whose file descriptor will be used, e.g., a `Pipe` or a `File`.
+    pub fn from_fd<F: FdContainer>(fd: &F) -> Stdio {
+        Stdio::Fd(fd.borrow())
+    }
+}
+
+/// A builder for child processes.
+///
+/// = Remarks
+///
+/// The first argument passed to the child is the path of the program. Further arguments
+/// can be added with `arg`. Unless the environment has been modified, the child
+/// inherits the environment of this process.
+///
+/// = Examples
+///
+/// ----
+/// let mut cmd = try!(Command::new("ls"));
+/// try!(cmd.arg("-l"));
+/// cmd.set_stdout(Stdio::Piped);
+/// let output = try!(cmd.output());
+/// ----
+pub struct Command<Heap = alloc::Heap>
+    where Heap: MemPool,
+{
+    path: CString<Heap>,
+    args: CPtrPtr<Heap>,
+    env: Option<Environment<Heap>>,
+    cwd: Option<CString<Heap>>,
+    stdin: Stdio,
+    stdout: Stdio,
+    stderr: Stdio,
+}
+
+impl<H = alloc::Heap> Command<H>
+    where H: MemPool+OutOf,
+{
+    /// Creates a new command.
+    ///
+    /// [argument, path]
+    /// The path of the program.
+    ///
+    /// = Remarks
+    ///
+    /// The path is interpreted like the `path` argument of `exec`. It is also passed to
+    /// the child as its first argument.
+    ///
+    /// = See also
+    ///
+    /// * link:lrs::process::exec
+    pub fn new<S: ?Sized>(path: &S) -> Result<Command<H>>
+        where S: TryAsRef<NoNullStr>,
+    {
+        let mut args = try!(CPtrPtr::new());
+        try!(args.push(path));
+        Ok(Command {
+            path: try!(path.try_to()),
+            args: args,
+            env: None,
+            cwd: None,
+            stdin: Stdio::Inherit,
+            stdout: Stdio::Inherit,
+            stderr: Stdio::Inherit,
+        })
+    }
+
+    /// Adds an argument.
+    ///
+    /// [argument, arg]
+    /// The argument to be passed to the child.
+    pub fn arg<S: ?Sized>(&mut self, arg: &S) -> Result
+        where S: TryAsRef<NoNullStr>,
+    {
+        self.args.push(arg)
+    }
+
+    fn env_mut(&mut self) -> Result<&mut Environment<H>> {
+        if self.env.is_none() {
+            self.env = Some(try!(Environment::current()));
+        }
+        Ok(self.env.as_mut().unwrap())
+    }
+
+    /// Removes all variables from the environment of the child.
+    pub fn clear_env(&mut self) {
+        match self.env {
+            Some(ref mut env) => env.clear(),
+            _ => self.env = Some(Environment::new()),
+        }
+    }
+
+    /// Sets a variable in the environment of the child.
+    ///
+    /// [argument, name]
+    /// The name of the variable.
+    ///
+    /// [argument, val]
+    /// The value of the variable.
+    ///
+    /// = Remarks
+    ///
+    /// If the name contains a `=` character, an error is returned. An existing variable
+    /// with the same name is replaced.
+    pub fn set_env<S: ?Sized, V: ?Sized>(&mut self, name: &S, val: &V) -> Result
+        where S: TryAsRef<NoNullStr>,
+              V: TryAsRef<NoNullStr>,
+    {
+        try!(self.env_mut()).set(name, val)
+    }
+
+    /// Removes a variable from the environment of the child.
+    ///
+    /// [argument, name]
+    /// The name of the variable.
+    pub fn remove_env<S: ?Sized>(&mut self, name: &S) -> Result
+        where S: TryAsRef<NoNullStr>,
+    {
+        try!(self.env_mut()).remove(name)
+    }
+
+    /// Replaces the environment of the child.
+    ///
+    /// [argument, env]
+    /// The new environment.
+    pub fn set_environment(&mut self, env: Environment<H>) {
+        self.env = Some(env);
+    }
+
+    /// Sets the working directory of the child.
+    ///
+    /// [argument, path]
+    /// The path of the working directory.
+    ///
+    /// = Remarks
+    ///
+    /// If the path is relative, it is interpreted relative to the current working
+    /// directory of this process. If no working directory is set, the child inherits
+    /// the working directory of this process.
+    pub fn set_cwd<S: ?Sized>(&mut self, path: &S) -> Result
+        where S: TryAsRef<NoNullStr>,
+    {
+        self.cwd = Some(try!(path.try_to()));
+        Ok(())
+    }
+
+    /// Sets the standard input of the child.
+    ///
+    /// [argument, stdin]
+    /// The configuration of the stream.
+    pub fn set_stdin(&mut self, stdin: Stdio) {
+        self.stdin = stdin;
+    }
+
+    /// Sets the standard output of the child.
+    ///
+    /// [argument, stdout]
+    /// The configuration of the stream.
+    pub fn set_stdout(&mut self, stdout: Stdio) {
+        self.stdout = stdout;
+    }
+
+    /// Sets the standard error of the child.
+    ///
+    /// [argument, stderr]
+    /// The configuration of the stream.
+    pub fn set_stderr(&mut self, stderr: Stdio) {
+        self.stderr = stderr;
+    }
+
+    /// Spawns the child process.
+    ///
+    /// [return_value]
+    /// Returns a handle to the child.
+    ///
+    /// = Remarks
+    ///
+    /// If the program cannot be executed, the child exits and the error that occurred
+    /// in the child is returned. In this case the child has already been reaped.
+    ///
+    /// = See also
+    ///
+    /// * link:man:fork(2)
+    /// * link:lrs::process::exec
+    pub fn spawn(&mut self) -> Result<Child> {
+        // The child must not access the environment of this process because another
+        // thread might hold the lock at the time of the fork.
+        let path_var = env::var("PATH");
+        let (mut envp_buf, raw_env) = match self.env {
+            Some(ref vars) => (Some(try!(vars.to_envp())), None),
+            _ => (None, Some(env::raw())),
+        };
+        let envp = match raw_env {
+            Some(ref raw) => raw.as_ptr(),
+            _ => try!(envp_buf.as_mut().unwrap().finish()).as_ptr(),
+        };
+
+        let null = match (self.stdin, self.stdout, self.stderr) {
+            (Stdio::Null, _, _) | (_, Stdio::Null, _) | (_, _, Stdio::Null) => {
+                Some(try!(File::open("/dev/null", FILE_READ_WRITE, Mode(0))))
+            },
+            _ => None,
+        };
+        let stdin = try!(ChildStream::new(self.stdin, &null, true));
+        let stdout = try!(ChildStream::new(self.stdout, &null, false));
+        let stderr = try!(ChildStream::new(self.stderr, &null, false));
+
+        let (err_write, mut err_read) = try!(Pipe::new(PIPE_CLOSE_ON_EXEC));
+
+        let argv = try!(self.args.finish());
+
+        let id = match try!(fork_continue()) {
+            Some(id) => id,
+            _ => {
+                let res = exec_child(&self.path, &self.cwd, argv, envp, path_var,
+                                     [&stdin, &stdout, &stderr]);
+                let errno = match res {
+                    Err(e) => e.0,
+                    _ => 0,
+                };
+                let _ = err_write.write(unsafe { mem::as_data(&errno).as_bytes() });
+                exit_group(EXEC_FAILED);
+            },
+        };
+
+        drop(raw_env);
+        drop(err_write);
+
+        let mut errno: c_int = 0;
+        let mut n = 0;
+        {
+            let buf = mem::as_mut_data(&mut errno);
+            while n < buf.len() {
+                match err_read.read(&mut buf[n..]) {
+                    Err(error::Interrupted) => { },
+                    Err(e) => return Err(e),
+                    Ok(0) => break,
+                    Ok(m) => n += m,
+                }
+            }
+        }
+        if n == mem::size_of::<c_int>() {
+            let _ = wait_id(id, WAIT_EXITED);
+            return Err(Errno(errno));
+        }
+
+        Ok(Child {
+            id: id,
+            status: None,
+            stdin: stdin.parent,
+            stdout: stdout.parent,
+            stderr: stderr.parent,
+        })
+    }
+
+    /// Spawns the child process and collects its output.
+    ///
+    /// [return_value]
+    /// Returns the exit status and the output of the child.
+    ///
+    /// = Remarks
+    ///
+    /// The standard output and standard error of the child are always connected to
+    /// pipes, regardless of the configuration of this command. If the standard input
+    /// has been configured as `Stdio::Piped`, it is closed before the output is
+    /// collected.
+    pub fn output(&mut self) -> Result<Output<H>> {
+        let (stdout, stderr) = (self.stdout, self.stderr);
+        self.stdout = Stdio::Piped;
+        self.stderr = Stdio::Piped;
+        let child = self.spawn();
+        self.stdout = stdout;
+        self.stderr = stderr;
+        try!(child).output()
+    }
+}
+
+fn exec_child<H>(path: &CString<H>, cwd: &Option<CString<H>>, argv: &[*const c_char],
+                 envp: *const *const c_char, path_var: Result<&'static CStr>,
+                 streams: [&ChildStream; 3]) -> Result
+    where H: MemPool,
+{
+    // Move all sources above the standard streams first. Otherwise redirecting one
+    // stream could overwrite the source of a later one.
+    let mut fds = [-1; 3];
+    for i in 0..streams.len() {
+        if streams[i].fd != -1 {
+            fds[i] = try!(rv!(fcntl_dupfd_cloexec(streams[i].fd, 3), -> c_int));
+        }
+    }
+    for i in 0..fds.len() {
+        try!(redirect(fds[i], i as c_int));
+    }
+    if let Some(ref cwd) = *cwd {
+        try!(rv!(chdir(cwd)));
+    }
+    let path: &CStr = path;
+    exec_with_path(path, argv, envp, path_var)
+}
+
+/// One of the standard streams of a child that is being spawned.
+struct ChildStream {
+    /// The end of the stream that is kept in the parent.
+    parent: Option<Pipe>,
+    /// The end of the stream that is passed to the child. It is closed in the parent
+    /// once the child has been spawned.
+    _child: Option<Pipe>,
+    /// The file descriptor that will be installed in the child.
+    fd: c_int,
+}
+
+impl ChildStream {
+    fn new(stdio: Stdio, null: &Option<File>, read: bool) -> Result<ChildStream> {
+        let (parent, child, fd) = match stdio {
+            Stdio::Inherit => (None, None, -1),
+            Stdio::Null => (None, None, null.as_ref().unwrap().borrow()),
+            Stdio::Fd(fd) => (None, None, fd),
+            Stdio::Piped => {
+                let (write, read_) = try!(Pipe::new(PIPE_CLOSE_ON_EXEC));
+                let (parent, child) = if read { (write, read_) } else { (read_, write) };
+                let fd = child.borrow();
+                (Some(parent), Some(child), fd)
+            },
+        };
+        Ok(ChildStream { parent: parent, _child: child, fd: fd })
+    }
+}
+
+/// Installs a file descriptor as one of the standard streams of the child.
+///
+/// [argument, fd]
+/// The file descriptor to install or `-1` to keep the inherited stream. It must not
+/// be one of the standard streams.
+///
+/// [argument, target]
+/// The standard stream to replace.
+fn redirect(fd: c_int, target: c_int) -> Result {
+    if fd == -1 {
+        return Ok(());
+    }
+    try!(rv!(dup3(fd, target, 0)));
+    // dup3 sets the close-on-exec flag unless lrs was compiled with the
+    // `no-auto-cloexec` flag.
+    rv!(fcntl_setfd(target, 0))
+}
+
+/// The collected output of a child process.
+pub struct Output<Heap = alloc::Heap>
+    where Heap: MemPool,
+{
+    /// The exit status of the child.
+    pub status: ChildStatus,
+    /// The bytes the child wrote to its standard output.
+    pub stdout: Vec<u8, Heap>,
+    /// The bytes the child wrote to its standard error.
+    pub stderr: Vec<u8, Heap>,
+}
+
+/// A handle to a child process created by `Command`.
+///
+/// = Remarks
+///
+/// Dropping the handle neither kills nor reaps the child.
+pub struct Child {
+    id: ProcessId,
+    status: Option<ChildStatus>,
+    stdin: Option<Pipe>,
+    stdout: Option<Pipe>,
+    stderr: Option<Pipe>,
+}
+
+impl Child {
+    /// Returns the process id of the child.
+    pub fn id(&self) -> ProcessId {
+        self.id
+    }
+
+    /// Returns the write end of the pipe connected to the standard input of the child.
+    ///
+    /// = Remarks
+    ///
+    /// This is only available if the standard input has been configured as
+    /// `Stdio::Piped`.
+    pub fn stdin(&self) -> Option<&Pipe> {
+        self.stdin.as_ref()
+    }
+
+    /// Returns the read end of the pipe connected to the standard output of the child.
+    ///
+    /// = Remarks
+    ///
+    /// This is only available if the standard output has been configured as
+    /// `Stdio::Piped`.
+    pub fn stdout(&self) -> Option<&Pipe> {
+        self.stdout.as_ref()
+    }
+
+    /// Returns the read end of the pipe connected to the standard error of the child.
+    ///
+    /// = Remarks
+    ///
+    /// This is only available if the standard error has been configured as
+    /// `Stdio::Piped`.
+    pub fn stderr(&self) -> Option<&Pipe> {
+        self.stderr.as_ref()
+    }
+
+    /// Takes ownership of the pipe connected to the standard input of the child.
+    pub fn take_stdin(&mut self) -> Option<Pipe> {
+        self.stdin.take()
+    }
+
+    /// Takes ownership of the pipe connected to the standard output of the child.
+    pub fn take_stdout(&mut self) -> Option<Pipe> {
+        self.stdout.take()
+    }
+
+    /// Takes ownership of the pipe connected to the standard error of the child.
+    pub fn take_stderr(&mut self) -> Option<Pipe> {
+        self.stderr.take()
+    }
+
+    /// Waits for the child to exit.
+    ///
+    /// [return_value]
+    /// Returns the exit status of the child.
+    ///
+    /// = Remarks
+    ///
+    /// The pipe connected to the standard input of the child, if any, is closed before
+    /// waiting so that the child does not block while reading from it. Once the child
+    /// has been reaped, subsequent calls return the same status.
+    pub fn wait(&mut self) -> Result<ChildStatus> {
+        if let Some(status) = self.status {
+            return Ok(status);
+        }
+        self.stdin = None;
+        let status = try!(wait_id(self.id, WAIT_EXITED));
+        self.status = Some(status);
+        Ok(status)
+    }
+
+    /// Checks whether the child has exited without blocking.
+    ///
+    /// [return_value]
+    /// Returns the exit status of the child or `None` if the child is still running.
+    pub fn try_wait(&mut self) -> Result<Option<ChildStatus>> {
+        if let Some(status) = self.status {
+            return Ok(Some(status));
+        }
+        match try!(wait_id(self.id, WAIT_EXITED | WAIT_DONT_BLOCK)) {
+            ChildStatus::None => Ok(None),
+            status => {
+                self.status = Some(status);
+                Ok(Some(status))
+            },
+        }
+    }
+
+    /// Kills the child.
+    ///
+    /// = Remarks
+    ///
+    /// This sends `SIGKILL` to the child. If the child has already been reaped, an
+    /// error is returned.
+    ///
+    /// = See also
+    ///
+    /// * link:man:kill(2)
+    pub fn kill(&self) -> Result {
+        if self.status.is_some() {
+            return Err(error::NoSuchProcess);
+        }
+        rv!(kill(self.id, SIGKILL))
+    }
+
+    /// Collects the output of the child and waits for it to exit.
+    ///
+    /// [return_value]
+    /// Returns the exit status and the output of the child.
+    ///
+    /// = Remarks
+    ///
+    /// Standard output and standard error are read concurrently so that the child
+    /// cannot block on a full pipe. Streams that are not connected to pipes produce
+    /// empty buffers.
+    pub fn output<H = alloc::Heap>(mut self) -> Result<Output<H>>
+        where H: MemPool+OutOf,
+    {
+        self.stdin = None;
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        try!(read_pipes(self.stdout.take(), self.stderr.take(), &mut stdout,
+                        &mut stderr));
+        let status = try!(self.wait());
+        Ok(Output {
+            status: status,
+            stdout: stdout,
+            stderr: stderr,
+        })
+    }
+}
+
+fn read_pipes<H>(mut out: Option<Pipe>, mut err: Option<Pipe>,
+                 out_buf: &mut Vec<u8, H>, err_buf: &mut Vec<u8, H>) -> Result
+    where H: MemPool,
+{
+    const READ_STEP_SIZE: usize = 4096;
+
+    let epoll = try!(Epoll::new());
+    if let Some(ref pipe) = out {
+        try!(epoll.add(pipe, POLL_READ));
+    }
+    if let Some(ref pipe) = err {
+        try!(epoll.add(pipe, POLL_READ));
+    }
+
+    let mut events = [EMPTY_EVENT; 2];
+    while out.is_some() || err.is_some() {
+        let ready = match epoll.wait(&mut events) {
+            Err(error::Interrupted) => continue,
+            Err(e) => return Err(e),
+            Ok(ready) => ready,
+        };
+        for event in ready.iter() {
+            let event: Event = *event;
+            let is_out = match out {
+                Some(ref p) => p.borrow() == event.fd(),
+                _ => false,
+            };
+            let (pipe, buf) = if is_out {
+                (&mut out, &mut *out_buf)
+            } else {
+                (&mut err, &mut *err_buf)
+            };
+            let done = match *pipe {
+                Some(ref mut p) => match BufWrite::read(buf, p, READ_STEP_SIZE) {
+                    Ok(0) => true,
+                    Ok(_) | Err(error::Interrupted) => false,
+                    Err(e) => return Err(e),
+                },
+                _ => false,
+            };
+            if done {
+                try!(epoll.remove(pipe.as_ref().unwrap()));
+                *pipe = None;
+            }
+        }
+    }
+    Ok(())
+}
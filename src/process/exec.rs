@@ -9,7 +9,6 @@ use syscall::{execveat};
 use cty::{AT_FDCWD, PATH_MAX, c_char};
 use str_one::{CStr};
 use str_two::{CString};
-use rt::{raw_env};
 use rmo::{Rmo, ToRmo};
 use alloc::{MemPool, FbHeap, FcPool, OncePool};
 use {env, file};
@@ -40,7 +39,8 @@ fn rmo_cstr<'a, S>(s: &'a S,
 /// :cptrptr: link:lrs::string::CPtrPtr
 ///
 /// The user must have permission to execute the program at `path`. The environment passed
-/// to the program will be the same as the environment passed to this program.
+/// to the program is the current environment of this program, including modifications
+/// made with `lrs::env::set_var`.
 ///
 /// The last element of the `argv` slice must be a null pointer. The other elements must
 /// be pointers to null-terminated strings. The {cptrptr}[`CPtrPtr`] structure can be used
@@ -57,23 +57,74 @@ fn rmo_cstr<'a, S>(s: &'a S,
 /// * {cptrptr}
 pub fn exec<P>(path: P, argv: &[*const c_char]) -> Result
     where P: for<'a> ToRmo<Pool<'a>, CStr, CString<Pool<'a>>>,
+{
+    // Look up PATH first since it cannot be read while the environment is locked.
+    let path_var = env::var("PATH");
+    let envp = env::raw();
+    exec_with_path(path, argv, envp.as_ptr(), path_var)
+}
+
+/// Executes a program with a custom environment in place of the current program.
+///
+/// [argument, path]
+/// The path of the file that is going to be executed.
+///
+/// [argument, argv]
+/// The arguments that will be passed to the new program in form of `argv` and `argc`.
+///
+/// [argument, envp]
+/// The environment that will be passed to the new program.
+///
+/// [return_value]
+/// On success, this function does not return.
+///
+/// = Remarks
+///
+/// :execve: link:man:execve(2)
+///
+/// `envp` must point to a null-terminated array of pointers to null-terminated
+/// strings. Apart from the environment, this function behaves like `exec`. In
+/// particular, the `PATH` search uses the `PATH` variable of the current process and not
+/// the one in `envp`.
+///
+/// = See also
+///
+/// * {execve}
+/// * link:lrs::process::exec
+pub fn exec_with_env<P>(path: P, argv: &[*const c_char],
+                        envp: *const *const c_char) -> Result
+    where P: for<'a> ToRmo<Pool<'a>, CStr, CString<Pool<'a>>>,
+{
+    exec_with_path(path, argv, envp, env::var("PATH"))
+}
+
+/// Like `exec_with_env` but with the value of the `PATH` variable already looked up.
+///
+/// = Remarks
+///
+/// This doesn't access the environment of the process and can therefore be called
+/// after `fork` in a multi-threaded program.
+pub fn exec_with_path<P>(path: P, argv: &[*const c_char], envp: *const *const c_char,
+                         path_var: Result<&'static CStr>) -> Result
+    where P: for<'a> ToRmo<Pool<'a>, CStr, CString<Pool<'a>>>,
 {
     let mut buf: [d8; PATH_MAX] = unsafe { mem::uninit() };
     let file = try!(rmo_cstr(&path, &mut buf));
     if file.len() == 0 {
         return Err(error::InvalidArgument);
     } else if file[0] == b'/' {
-        return rv!(execveat(-1, &file, argv.as_ptr(), raw_env(), 0));
+        return rv!(execveat(-1, &file, argv.as_ptr(), envp, 0));
     } else if file.len() > 1 && file[0] == b'.' {
         if file[1] == b'/' || (file.len() > 2 && file[1] == b'.' && file[2] == b'/') {
-            return rv!(execveat(AT_FDCWD, &file, argv.as_ptr(), raw_env(), 0));
+            return rv!(execveat(AT_FDCWD, &file, argv.as_ptr(), envp, 0));
         }
     }
 
     // Try first without allocating
 
+    let path_var = try!(path_var);
     let mut abs_buf: [d8; PATH_MAX] = unsafe { mem::uninit() };
-    match exec_rel(&file, OncePool::new(&mut abs_buf), argv) {
+    match exec_rel(&file, OncePool::new(&mut abs_buf), argv, envp, path_var) {
         Err(error::NoMemory) => { },
         x => return x,
     }
@@ -81,21 +132,22 @@ pub fn exec<P>(path: P, argv: &[*const c_char]) -> Result
     // NoMemory can come from our stuff or execve but we can't distinguish at this point.
     // Let's just try again with dynamic allocations.
 
-    exec_rel(&file, FbHeap::out_of(()), argv)
+    exec_rel(&file, FbHeap::out_of(()), argv, envp, path_var)
 }
 
-fn exec_rel<H>(rel: &CStr, pool: H, args: &[*const c_char]) -> Result
+fn exec_rel<H>(rel: &CStr, pool: H, args: &[*const c_char], envp: *const *const c_char,
+               path_var: &'static CStr) -> Result
     where H: MemPool,
 {
     let mut buf = try!(CString::with_pool(pool));
-    for path in try!(env::path()) {
+    for path in env::split_path(path_var) {
         try!(buf.set_path(path));
         try!(buf.push_file(rel));
         let cstr = try!(buf.try_as_ref());
         if file::exists(cstr) == Ok(true) {
             // Paths in PATH don't have to start with a /. We pass AT_FDCWD so that such
             // paths are interpreted relative to the cwd.
-            return rv!(execveat(AT_FDCWD, cstr, args.as_ptr(), raw_env(), 0));
+            return rv!(execveat(AT_FDCWD, cstr, args.as_ptr(), envp, 0));
         }
     }
     Err(error::DoesNotExist)
@@ -22,6 +22,13 @@ extern crate lrs_rmo as rmo;
 extern crate lrs_rv as rv;
 extern crate lrs_time_base as time_base;
 extern crate lrs_env as env;
+extern crate lrs_fd as fd;
+extern crate lrs_io as io;
+extern crate lrs_vec as vec;
+extern crate lrs_pipe as pipe;
+extern crate lrs_poll as poll;
+extern crate lrs_clone as clone;
+extern crate lrs_signal as signal;
 
 mod std { pub use fmt::std::*; pub use {cty}; }
 
@@ -41,8 +48,10 @@ use res::{Resource};
 
 pub mod exec;
 pub mod wait;
+pub mod command;
 pub mod res_user;
 pub mod res;
+pub mod pidfd;
 
 /// Returns the process id of this process.
 pub fn process_id() -> ProcessId {
@@ -0,0 +1,190 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use base::undef::{UndefState};
+use core::{mem};
+use cty::{c_int};
+use cty::alias::{ProcessId};
+use syscall::{close, pidfd_open, pidfd_send_signal, pidfd_getfd};
+use fd::{FdContainer};
+use signal::signals::{Signal};
+use wait::{ChildStatus, WaitFlags, wait_pidfd};
+use clone::{fork_pidfd, fork_pidfd_continue};
+
+/// A process file descriptor.
+///
+/// = Remarks
+///
+/// Unlike a process id, a process file descriptor always refers to the same process
+/// even if the process id of an exited process is reused.
+///
+/// The file descriptor becomes readable when the process exits. It can therefore be
+/// registered with an `Epoll` object to wait for the exit of many processes.
+///
+/// = See also
+///
+/// * link:man:pidfd_open(2)
+pub struct PidFd {
+    fd: c_int,
+    owned: bool,
+}
+
+impl PidFd {
+    /// Creates a process file descriptor for an existing process.
+    ///
+    /// [argument, process]
+    /// The process id of the process.
+    ///
+    /// = Remarks
+    ///
+    /// This is only race-free if the process is an unreaped child of this process.
+    ///
+    /// = See also
+    ///
+    /// * link:man:pidfd_open(2)
+    pub fn open(process: ProcessId) -> Result<PidFd> {
+        let fd = try!(rv!(pidfd_open(process, 0), -> c_int));
+        Ok(PidFd::from_owned(fd))
+    }
+
+    /// Forks the process and executes a function in the child process.
+    ///
+    /// [argument, f]
+    /// The function that will be executed in the child process.
+    ///
+    /// [return_value]
+    /// Returns the process id of the child process and a process file descriptor
+    /// referring to it.
+    ///
+    /// = Remarks
+    ///
+    /// The function `f` will only be executed in the child process. When the function
+    /// returns, the child process automatically exits with exit code `0`.
+    ///
+    /// = See also
+    ///
+    /// * link:man:clone3(2) and CLONE_PIDFD therein
+    pub fn fork<F>(f: F) -> Result<(ProcessId, PidFd)>
+        where F: FnOnce()
+    {
+        let (pid, fd) = try!(fork_pidfd(f));
+        Ok((pid, PidFd::from_owned(fd)))
+    }
+
+    /// Forks the process.
+    ///
+    /// [return_value]
+    /// Returns the process id of the child process and a process file descriptor
+    /// referring to it or `None` if we're in the child process.
+    ///
+    /// = See also
+    ///
+    /// * link:man:clone3(2) and CLONE_PIDFD therein
+    pub fn fork_continue() -> Result<Option<(ProcessId, PidFd)>> {
+        match try!(fork_pidfd_continue()) {
+            Some((pid, fd)) => Ok(Some((pid, PidFd::from_owned(fd)))),
+            None => Ok(None),
+        }
+    }
+
+    /// Sends a signal to the process.
+    ///
+    /// [argument, sig]
+    /// The signal to send.
+    ///
+    /// = See also
+    ///
+    /// * link:man:pidfd_send_signal(2)
+    pub fn send_signal(&self, sig: Signal) -> Result {
+        rv!(pidfd_send_signal(self.fd, sig.0 as c_int, None, 0))
+    }
+
+    /// Waits for the process.
+    ///
+    /// [argument, flags]
+    /// The flags used for this wait operation.
+    ///
+    /// [return_value]
+    /// Returns the status of the process.
+    ///
+    /// = Remarks
+    ///
+    /// The process must be a child of this process.
+    ///
+    /// = See also
+    ///
+    /// * link:man:waitid(2) and P_PIDFD therein
+    pub fn wait(&self, flags: WaitFlags) -> Result<ChildStatus> {
+        wait_pidfd(self.fd, flags)
+    }
+
+    /// Duplicates a file descriptor of the process.
+    ///
+    /// [argument, fd]
+    /// The file descriptor in the process.
+    ///
+    /// [return_value]
+    /// Returns an owned copy of the file descriptor.
+    ///
+    /// = Remarks
+    ///
+    /// This requires the permission to ptrace the process.
+    ///
+    /// = See also
+    ///
+    /// * link:man:pidfd_getfd(2)
+    pub fn get_fd<T>(&self, fd: c_int) -> Result<T>
+        where T: FdContainer,
+    {
+        let fd = try!(rv!(pidfd_getfd(self.fd, fd), -> c_int));
+        Ok(T::from_owned(fd))
+    }
+}
+
+unsafe impl UndefState for PidFd {
+    fn num() -> usize { bool::num() }
+
+    unsafe fn set_undef(val: *mut PidFd, n: usize) {
+        bool::set_undef(&mut (*val).owned, n);
+    }
+
+    unsafe fn is_undef(val: *const PidFd, n: usize) -> bool {
+        bool::is_undef(&(*val).owned, n)
+    }
+}
+
+impl Drop for PidFd {
+    fn drop(&mut self) {
+        if self.owned {
+            close(self.fd);
+        }
+    }
+}
+
+impl Into<c_int> for PidFd {
+    fn into(self) -> c_int {
+        let fd = self.fd;
+        mem::forget(self);
+        fd
+    }
+}
+
+impl FdContainer for PidFd {
+    fn is_owned(&self) -> bool {
+        self.owned
+    }
+
+    fn borrow(&self) -> c_int {
+        self.fd
+    }
+
+    fn from_owned(fd: c_int) -> PidFd {
+        PidFd { fd: fd, owned: true }
+    }
+
+    fn from_borrowed(fd: c_int) -> PidFd {
+        PidFd { fd: fd, owned: false }
+    }
+}
@@ -7,7 +7,7 @@ use core::{mem};
 use core::ops::{BitAnd, BitOr, Not};
 use cty::{
     c_int, WEXITED, WSTOPPED, WCONTINUED, WNOHANG, WNOWAIT, P_ALL, CLD_EXITED,
-    CLD_KILLED, CLD_DUMPED, CLD_STOPPED, CLD_TRAPPED, CLD_CONTINUED, P_PID,
+    CLD_KILLED, CLD_DUMPED, CLD_STOPPED, CLD_TRAPPED, CLD_CONTINUED, P_PID, P_PIDFD,
 };
 use cty::alias::{ProcessId};
 use syscall::{waitid};
@@ -155,6 +155,24 @@ pub fn wait_id(id: ProcessId, flags: WaitFlags) -> Result<ChildStatus> {
     wait_inner(P_PID, id, flags).map(|o| o.1)
 }
 
+/// Wait for the child process referred to by a process file descriptor.
+///
+/// [argument, fd]
+/// The process file descriptor of the child.
+///
+/// [argument, flags]
+/// The flags used for this wait operation.
+///
+/// [return_value]
+/// Returns the status of the child.
+///
+/// = See also
+///
+/// * link:man:waitid(2) and P_PIDFD therein
+pub fn wait_pidfd(fd: c_int, flags: WaitFlags) -> Result<ChildStatus> {
+    wait_inner(P_PIDFD, fd as ProcessId, flags).map(|o| o.1)
+}
+
 fn wait_inner(kind: c_int, id: ProcessId,
               flags: WaitFlags) -> Result<(ProcessId, ChildStatus)> {
     let mut info = mem::zeroed();
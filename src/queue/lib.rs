@@ -13,6 +13,7 @@ extern crate lrs_arch_fns as arch_fns;
 extern crate lrs_atomic as atomic;
 extern crate lrs_lock as lock;
 extern crate lrs_alloc as alloc;
+extern crate lrs_time_base as time_base;
 
 use base::prelude::*;
 use core::ptr::{NoAliasMemPtr};
@@ -23,14 +24,22 @@ use atomic::{Atomic};
 use cell::cell::{Cell};
 use core::{ptr, mem};
 use lock::{Lock, LockGuard, RawCondvar};
+use time_base::{Time};
+use time_base::clock::{self};
 
 pub mod std { pub use base::std::*; }
 
+const OPEN: usize = 0;
+const CLOSING: usize = 1;
+const CLOSED: usize = 2;
+
 /// A multi-threaded queue.
 ///
 /// = Remarks
 ///
-/// This queue can be used for sending messages between threads.
+/// This queue can be used for sending messages between threads. Once the queue has been
+/// closed, no more elements can be added and all threads waiting on the queue are
+/// woken.
 pub struct Queue<T, Heap = alloc::Heap>
     where Heap: MemPool,
 {
@@ -63,6 +72,13 @@ pub struct Queue<T, Heap = alloc::Heap>
     // Mutex that protects the two atomic variables above.
     sleep_lock: Lock,
 
+    // Number of threads that are currently adding an element.
+    pushing: Atomic<usize>,
+    // Whether the queue has been closed. Changes from OPEN to CLOSING when `close` is
+    // called and to CLOSED once all elements that were being added have been
+    // published.
+    closed: Atomic<usize>,
+
     pool: Heap,
 
     _marker: PhantomData<Cell<T>>,
@@ -123,6 +139,9 @@ impl<T, H> Queue<T, H>
 
             sleep_lock: Lock::new(),
 
+            pushing: Atomic::new(0),
+            closed: Atomic::new(OPEN),
+
             pool: pool,
 
             _marker: PhantomData,
@@ -170,12 +189,24 @@ impl<T, H> Queue<T, H>
     }
 
     fn push_int(&self, val: T, guard: Option<&LockGuard>) -> Option<T> {
+        // Announce the push before checking the flag. `close` stores the flag before it
+        // waits for `pushing` to drop to zero, so either we see the flag or the element
+        // is published before the queue counts as closed.
+        self.pushing.add(1);
+        if self.is_closed() {
+            self.pushing.sub(1);
+            return Some(val);
+        }
         let write_pos = match self.get_write_pos() {
             Some(w) => w,
-            _ => return Some(val),
+            _ => {
+                self.pushing.sub(1);
+                return Some(val);
+            },
         };
         self.set_mem(write_pos, val);
         self.set_write_end(write_pos);
+        self.pushing.sub(1);
 
         if self.sleeping_receivers.load() > 0 {
             let _guard = match guard.is_some() {
@@ -193,7 +224,7 @@ impl<T, H> Queue<T, H>
     /// The element to be added.
     ///
     /// [return_value]
-    /// Returns the element if the queue is full.
+    /// Returns the element if the queue is full or has been closed.
     pub fn push(&self, val: T) -> Option<T> {
         self.push_int(val, None)
     }
@@ -202,22 +233,72 @@ impl<T, H> Queue<T, H>
     ///
     /// [argument, val]
     /// The element to be added.
-    pub fn push_wait(&self, mut val: T) {
+    ///
+    /// [return_value]
+    /// Returns the element and `BrokenPipe` if the queue has been closed.
+    pub fn push_wait(&self, val: T) -> Result<(), (T, Errno)> {
+        self.push_until(val, None)
+    }
+
+    /// Blocks until it can add the element to the queue or until a certain amount of
+    /// time has expired.
+    ///
+    /// [argument, val]
+    /// The element to be added.
+    ///
+    /// [argument, time]
+    /// An upper bound for the amount of time until this function returns.
+    ///
+    /// [return_value]
+    /// Returns the element and `TimedOut` if the queue stayed full and `BrokenPipe` if
+    /// the queue has been closed.
+    ///
+    /// = Remarks
+    ///
+    /// The function may take longer to return than allowed by the `time` parameter.
+    pub fn push_wait_timeout(&self, val: T, time: Time) -> Result<(), (T, Errno)> {
+        match clock::MONO_RAW.get_time() {
+            Ok(now) => self.push_until(val, Some(now + time)),
+            Err(e) => Err((val, e)),
+        }
+    }
+
+    fn push_until(&self, mut val: T, deadline: Option<Time>) -> Result<(), (T, Errno)> {
         val = match self.push_int(val, None) {
             Some(v) => v,
-            _ => return,
+            _ => return Ok(()),
         };
 
         let mut guard = self.sleep_lock.lock();
         self.sleeping_senders.add(1);
+        let mut res = Ok(());
         loop {
-            val = match self.push_int(val, Some(&guard)) {
-                Some(v) => v,
-                _ => break,
+            // After a timeout we try one last time in case the timeout raced with a
+            // pop.
+            if self.is_closed() {
+                res = Err(error::BrokenPipe);
+            } else {
+                val = match self.push_int(val, Some(&guard)) {
+                    Some(v) => v,
+                    _ => {
+                        self.sleeping_senders.sub(1);
+                        return Ok(());
+                    },
+                };
+            }
+            if let Err(e) = res {
+                self.sleeping_senders.sub(1);
+                return Err((val, e));
+            }
+            guard = match deadline {
+                Some(d) => {
+                    let (g, r) = self.send_condvar.wait_until(guard, clock::MONO_RAW, d);
+                    res = r;
+                    g
+                },
+                _ => self.send_condvar.wait(guard),
             };
-            guard = self.send_condvar.wait(guard);
         }
-        self.sleeping_senders.sub(1);
     }
 
     /// Get a position to read from if the queue isn't empty
@@ -287,6 +368,10 @@ impl<T, H> Queue<T, H>
     ///
     /// [return_value]
     /// Returns the removed element.
+    ///
+    /// = Remarks
+    ///
+    /// Elements that were added before the queue was closed can still be removed.
     pub fn pop(&self) -> Option<T> {
         self.pop_int(None)
     }
@@ -294,24 +379,93 @@ impl<T, H> Queue<T, H>
     /// Blocks until there is an element in the queue.
     ///
     /// [return_value]
-    /// Returns the removed element.
-    pub fn pop_wait(&self) -> T {
-        let mut rv = self.pop_int(None);
-
-        if rv.is_none() {
-            let mut guard = self.sleep_lock.lock();
-            self.sleeping_receivers.add(1);
-            loop {
-                rv = self.pop_int(Some(&guard));
-                if rv.is_some() {
-                    break;
-                }
-                guard = self.recv_condvar.wait(guard);
+    /// Returns the removed element or `BrokenPipe` if the queue is empty and has been
+    /// closed.
+    pub fn pop_wait(&self) -> Result<T> {
+        self.pop_until(None)
+    }
+
+    /// Blocks until there is an element in the queue or until a certain amount of time
+    /// has expired.
+    ///
+    /// [argument, time]
+    /// An upper bound for the amount of time until this function returns.
+    ///
+    /// [return_value]
+    /// Returns the removed element, `TimedOut` if the queue stayed empty, or
+    /// `BrokenPipe` if the queue is empty and has been closed.
+    ///
+    /// = Remarks
+    ///
+    /// The function may take longer to return than allowed by the `time` parameter.
+    pub fn pop_wait_timeout(&self, time: Time) -> Result<T> {
+        let now = try!(clock::MONO_RAW.get_time());
+        self.pop_until(Some(now + time))
+    }
+
+    fn pop_until(&self, deadline: Option<Time>) -> Result<T> {
+        if let Some(val) = self.pop_int(None) {
+            return Ok(val);
+        }
+
+        let mut guard = self.sleep_lock.lock();
+        self.sleeping_receivers.add(1);
+        let mut res = Ok(());
+        loop {
+            // Check for closing first so that elements added before the queue was
+            // closed are still returned by the `pop_int` below.
+            let closed = self.closed.load() == CLOSED;
+            if let Some(val) = self.pop_int(Some(&guard)) {
+                self.sleeping_receivers.sub(1);
+                return Ok(val);
             }
-            self.sleeping_receivers.sub(1);
+            if closed {
+                res = Err(error::BrokenPipe);
+            }
+            if let Err(e) = res {
+                self.sleeping_receivers.sub(1);
+                return Err(e);
+            }
+            guard = match deadline {
+                Some(d) => {
+                    let (g, r) = self.recv_condvar.wait_until(guard, clock::MONO_RAW, d);
+                    res = r;
+                    g
+                },
+                _ => self.recv_condvar.wait(guard),
+            };
         }
+    }
+
+    /// Closes the queue.
+    ///
+    /// = Remarks
+    ///
+    /// After this function has been called, no more elements can be added to the
+    /// queue. All threads waiting to add an element are woken and receive the
+    /// `BrokenPipe` error. Threads waiting to remove an element are woken and receive
+    /// the same error once the queue is empty.
+    pub fn close(&self) {
+        if self.closed.compare_exchange(OPEN, CLOSING) != OPEN {
+            // Another thread is closing the queue. Wait until it has finished so that
+            // the queue is closed when we return.
+            while self.closed.load() != CLOSED {
+                spin();
+            }
+            return;
+        }
+        while self.pushing.load() != 0 {
+            spin();
+        }
+        self.closed.store(CLOSED);
+        let _guard = self.sleep_lock.lock();
+        self.send_condvar.signal(usize::max());
+        self.recv_condvar.signal(usize::max());
+    }
 
-        rv.unwrap()
+    /// Returns whether the queue has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load() != OPEN
     }
 }
 
@@ -6,45 +6,54 @@ use core::{mem};
 
 pub use ::common::{
     accept, accept4, acct, add_key, adjtimex, bind, bpf, brk, capget,
-    capset, chdir, chroot, clock_adjtime, clock_getres, clock_gettime,
-    clock_nanosleep, clock_settime, close, connect, delete_module, dup, dup3,
+    capset, chdir, chroot, clock_adjtime,
+    clone3, close, connect, delete_module, dup, dup3,
     epoll_create1, epoll_ctl, epoll_pwait, eventfd2,
     execve, execveat, exit, exit_group, faccessat, fanotify_init,
     fchdir, fchmod, fchmodat, fchown, fchownat, fdatasync,
     fgetxattr, finit_module, flistxattr, flock, fremovexattr, fsetxattr,
-    fsync, futex, getcpu, getcwd, getegid, geteuid, getgid,
+    fsync, getcpu, getcwd, getegid, geteuid, getgid,
     getgroups, getitimer, get_mempolicy, getpeername, getpgid, getpid, getppid,
     getpriority, getrandom, getresgid, getresuid, getrlimit, get_robust_list, getrusage,
     getsid, getsockname, getsockopt, gettid, gettimeofday, getuid, getxattr, init_module,
     inotify_add_watch, inotify_init1, inotify_rm_watch, io_cancel, ioctl,
-    io_destroy, io_getevents, ioprio_get, ioprio_set, io_setup, io_submit, kcmp,
+    io_destroy, ioprio_get, ioprio_set, io_setup, io_submit,
+    io_uring_enter, io_uring_register, io_uring_setup, kcmp,
     kexec_load, keyctl, kill, lgetxattr, linkat, listen,
     listxattr, llistxattr, lremovexattr, lsetxattr, madvise, mbind,
     memfd_create, mincore, mkdirat, mknodat, mlock, mlockall,
-    mount, move_pages, mprotect, mq_getsetattr, mq_open, mq_timedreceive, mq_timedsend,
+    mount, move_pages, mprotect, mq_getsetattr, mq_open,
     mq_unlink, mremap, msgctl, msgget, msgrcv, msgsnd, msync, munlock, munlockall, munmap,
-    name_to_handle_at, nanosleep, openat, open_by_handle_at, perf_event_open,
-    personality, pipe2, pivot_root, ppoll, prctl, preadv, process_vm_readv,
-    process_vm_writev, pselect6, ptrace, pwritev, quotactl, read,
-    readlinkat, readv, reboot, recvfrom, recvmmsg, recvmsg, remap_file_pages, removexattr,
+    name_to_handle_at, openat, open_by_handle_at, perf_event_open,
+    personality, pidfd_getfd, pidfd_open, pidfd_send_signal, pipe2, pivot_root, prctl,
+    preadv, process_vm_readv,
+    process_vm_writev, ptrace, pwritev, quotactl, read,
+    readlinkat, readv, reboot, recvfrom, recvmsg, remap_file_pages, removexattr,
     renameat, renameat2, request_key, restart_syscall, rt_sigaction,
     rt_sigpending, rt_sigprocmask, rt_sigqueueinfo, rt_sigsuspend, rt_sigreturn,
-    rt_sigtimedwait, rt_tgsigqueueinfo, sched_getaffinity, sched_getattr, sched_getparam,
+    rt_tgsigqueueinfo, sched_getaffinity, sched_getattr, sched_getparam,
     sched_get_priority_max, sched_get_priority_min, sched_getscheduler,
-    sched_rr_get_interval, sched_setaffinity, sched_setattr, sched_setparam,
-    sched_setscheduler, sched_yield, seccomp, semget, semop, semtimedop, sendmmsg,
+    sched_setaffinity, sched_setattr, sched_setparam,
+    sched_setscheduler, sched_yield, seccomp, semget, semop, sendmmsg,
     sendmsg, sendto, setdomainname, setfsgid, setfsuid, setgid, setgroups, sethostname,
     setitimer, set_mempolicy, setns, setpgid, setpriority, setregid, setresgid, setresuid,
     setreuid, setrlimit, set_robust_list, setsid, setsockopt, set_tid_address,
     settimeofday, setuid, setxattr, shmat, shmctl, shmdt, shmget, shutdown, sigaltstack,
-    signalfd4, socket, socketpair, splice, swapoff, swapon,
+    signalfd4, socket, socketpair, splice, swapoff, swapon, statx,
     symlinkat, sync, syncfs, sysinfo, syslog, tee, tgkill,
-    timer_delete, timerfd_create, timerfd_gettime, timerfd_settime, timer_getoverrun,
-    timer_gettime, timer_settime, times, tkill, umask, umount, unlinkat,
-    unshare, utimensat, vhangup, vmsplice, waitid,
+    timer_delete, timerfd_create, timer_getoverrun,
+    times, tkill, umask, umount, unlinkat,
+    unshare, vhangup, vmsplice, waitid,
     write, writev,
 };
 
+pub use ::time32::{
+    clock_getres, clock_gettime, clock_nanosleep, clock_settime, futex, io_getevents,
+    mq_timedreceive, mq_timedsend, nanosleep, ppoll, pselect6, recvmmsg, rt_sigtimedwait,
+    sched_rr_get_interval, semtimedop, timerfd_gettime, timerfd_settime, timer_gettime,
+    timer_settime, utimensat,
+};
+
 use cty::{
     self,
     k_int, k_long, k_ulong, c_char, k_uint, linux_dirent64, loff_t,
@@ -7,14 +7,15 @@
 
 use cty::{
     self,
-    bpf_attr, cap_user_data_t, cap_user_header_t, clockid_t, c_void, epoll_event, gid_t,
-    key_serial_t, k_int, k_uint, k_ulong, loff_t, sigset_t, size_t, timespec, timex,
+    bpf_attr, cap_user_data_t, cap_user_header_t, clockid_t, clone_args, c_void,
+    epoll_event, gid_t, key_serial_t, k_int, k_uint, k_ulong, loff_t, sigset_t, size_t,
+    timespec, timex,
     uid_t, umode_t, c_char, aio_context_t, clock_t, fd_set, file_handle, getcpu_cache,
-    iocb, io_event, iovec, itimerspec, itimerval, kexec_segment, k_long,
+    iocb, io_event, io_uring_params, iovec, itimerspec, itimerval, kexec_segment, k_long,
     linux_dirent64, mq_attr, mqd_t, new_utsname, off_t,
     perf_event_attr, pid_t, pollfd, qid_t, rlimit, rlimit64,
     robust_list_head, rusage, __s32, sched_attr, sched_param, sigaction, siginfo_t,
-    ssize_t, stack_t, statfs, sysinfo, timer_t,
+    ssize_t, stack_t, statfs, statx, sysinfo, timer_t,
     timeval, timezone, tms, k_uchar,
 };
 
@@ -79,6 +80,10 @@ pub unsafe fn clock_settime(which_clock: clockid_t, tp: *const timespec) -> k_in
     call!(cty::__NR_clock_settime, which_clock, tp) as k_int
 }
 
+pub unsafe fn clone3(uargs: *mut clone_args, size: size_t) -> k_long {
+    call!(cty::__NR_clone3, uargs, size) as k_long
+}
+
 pub unsafe fn close(fd: k_uint) -> k_int {
     call!(cty::__NR_close, fd) as k_int
 }
@@ -369,6 +374,21 @@ pub unsafe fn io_submit(ctx_id: aio_context_t, nr: k_long,
     call!(cty::__NR_io_submit, ctx_id, nr, iocbpp) as k_int
 }
 
+pub unsafe fn io_uring_setup(entries: u32, params: *mut io_uring_params) -> k_int {
+    call!(cty::__NR_io_uring_setup, entries, params) as k_int
+}
+
+pub unsafe fn io_uring_enter(fd: k_uint, to_submit: u32, min_complete: u32, flags: u32,
+                             sig: *const sigset_t, sigsz: size_t) -> k_int {
+    call!(cty::__NR_io_uring_enter, fd, to_submit, min_complete, flags, sig,
+          sigsz) as k_int
+}
+
+pub unsafe fn io_uring_register(fd: k_uint, opcode: k_uint, arg: *mut c_void,
+                                nr_args: k_uint) -> k_int {
+    call!(cty::__NR_io_uring_register, fd, opcode, arg, nr_args) as k_int
+}
+
 pub unsafe fn kcmp(pid1: pid_t, pid2: pid_t, ty: k_int, idx1: k_ulong,
                    idx2: k_ulong) -> k_int {
     call!(cty::__NR_kcmp, pid1, pid2, ty, idx1, idx2) as k_int
@@ -581,6 +601,19 @@ pub unsafe fn personality(personality: k_uint) -> k_int {
     call!(cty::__NR_personality, personality) as k_int
 }
 
+pub unsafe fn pidfd_getfd(pidfd: k_int, fd: k_int, flags: k_uint) -> k_int {
+    call!(cty::__NR_pidfd_getfd, pidfd, fd, flags) as k_int
+}
+
+pub unsafe fn pidfd_open(pid: pid_t, flags: k_uint) -> k_int {
+    call!(cty::__NR_pidfd_open, pid, flags) as k_int
+}
+
+pub unsafe fn pidfd_send_signal(pidfd: k_int, sig: k_int, info: *mut siginfo_t,
+                                flags: k_uint) -> k_int {
+    call!(cty::__NR_pidfd_send_signal, pidfd, sig, info, flags) as k_int
+}
+
 pub unsafe fn pipe2(fildes: *mut k_int, flags: k_int) -> k_int {
     call!(cty::__NR_pipe2, fildes, flags) as k_int
 }
@@ -902,6 +935,11 @@ pub unsafe fn statfs(pathname: *const c_char, buf: *mut statfs) -> k_int {
     call!(cty::__NR_statfs, pathname, buf) as k_int
 }
 
+pub unsafe fn statx(dfd: k_int, filename: *const c_char, flags: k_uint, mask: k_uint,
+                    buffer: *mut statx) -> k_int {
+    call!(cty::__NR_statx, dfd, filename, flags, mask, buffer) as k_int
+}
+
 pub unsafe fn swapoff(specialfile: *const c_char) -> k_int {
     call!(cty::__NR_swapoff, specialfile) as k_int
 }
@@ -1075,6 +1113,122 @@ mod _64_calls {
     }
 }
 
+// 64 bit time calls for 32 bit systems
+#[cfg(any(target_arch = "x86", target_arch = "arm"))]
+pub use self::time64_calls::*;
+
+#[cfg(any(target_arch = "x86", target_arch = "arm"))]
+mod time64_calls {
+    use cty::{
+        self, k_int, k_uint, k_long, c_char, c_void, clockid_t, timespec, itimerspec,
+        timer_t, aio_context_t, io_event, mqd_t, size_t, ssize_t, pollfd, sigset_t,
+        fd_set, siginfo_t, pid_t, sembuf, mmsghdr,
+    };
+    use ::arch::{SCT};
+
+    pub unsafe fn clock_gettime64(which_clock: clockid_t, tp: *mut timespec) -> k_int {
+        call!(cty::__NR_clock_gettime64, which_clock, tp) as k_int
+    }
+
+    pub unsafe fn clock_settime64(which_clock: clockid_t, tp: *const timespec) -> k_int {
+        call!(cty::__NR_clock_settime64, which_clock, tp) as k_int
+    }
+
+    pub unsafe fn clock_getres_time64(which_clock: clockid_t,
+                                      tp: *mut timespec) -> k_int {
+        call!(cty::__NR_clock_getres_time64, which_clock, tp) as k_int
+    }
+
+    pub unsafe fn clock_nanosleep_time64(which_clock: clockid_t, flags: k_int,
+                                         rqtp: *const timespec,
+                                         rmtp: *mut timespec) -> k_int {
+        call!(cty::__NR_clock_nanosleep_time64, which_clock, flags, rqtp, rmtp) as k_int
+    }
+
+    pub unsafe fn timer_gettime64(timer_id: timer_t, setting: *mut itimerspec) -> k_int {
+        call!(cty::__NR_timer_gettime64, timer_id, setting) as k_int
+    }
+
+    pub unsafe fn timer_settime64(timer_id: timer_t, flags: k_int,
+                                  new_setting: *const itimerspec,
+                                  old_setting: *mut itimerspec) -> k_int {
+        call!(cty::__NR_timer_settime64, timer_id, flags, new_setting,
+              old_setting) as k_int
+    }
+
+    pub unsafe fn timerfd_gettime64(ufd: k_int, otmr: *mut itimerspec) -> k_int {
+        call!(cty::__NR_timerfd_gettime64, ufd, otmr) as k_int
+    }
+
+    pub unsafe fn timerfd_settime64(ufd: k_int, flags: k_int, utmr: *const itimerspec,
+                                    otmr: *mut itimerspec) -> k_int {
+        call!(cty::__NR_timerfd_settime64, ufd, flags, utmr, otmr) as k_int
+    }
+
+    pub unsafe fn utimensat_time64(dfd: k_int, filename: *const c_char,
+                                   utimes: *const timespec, flags: k_int) -> k_int {
+        call!(cty::__NR_utimensat_time64, dfd, filename, utimes, flags) as k_int
+    }
+
+    pub unsafe fn pselect6_time64(n: k_int, inp: *mut fd_set, outp: *mut fd_set,
+                                  exp: *mut fd_set, tsp: *mut timespec,
+                                  sig: *mut c_void) -> k_int {
+        call!(cty::__NR_pselect6_time64, n, inp, outp, exp, tsp, sig) as k_int
+    }
+
+    pub unsafe fn ppoll_time64(ufds: *mut pollfd, nfds: k_uint, tsp: *mut timespec,
+                               sigmask: *const sigset_t, sigsetsize: size_t) -> k_int {
+        call!(cty::__NR_ppoll_time64, ufds, nfds, tsp, sigmask, sigsetsize) as k_int
+    }
+
+    pub unsafe fn io_pgetevents_time64(ctx_id: aio_context_t, min_nr: k_long, nr: k_long,
+                                       events: *mut io_event, timeout: *mut timespec,
+                                       usig: *const c_void) -> k_int {
+        call!(cty::__NR_io_pgetevents_time64, ctx_id, min_nr, nr, events, timeout,
+              usig) as k_int
+    }
+
+    pub unsafe fn recvmmsg_time64(fd: k_int, mmsg: *mut mmsghdr, vlen: k_uint,
+                                  flags: k_uint, timeout: *mut timespec) -> ssize_t {
+        call!(cty::__NR_recvmmsg_time64, fd, mmsg, vlen, flags, timeout) as ssize_t
+    }
+
+    pub unsafe fn mq_timedsend_time64(mqdes: mqd_t, u_msg_ptr: *const c_char,
+                                      msg_len: size_t, msg_prio: k_uint,
+                                      u_abs_timeout: *const timespec) -> k_int {
+        call!(cty::__NR_mq_timedsend_time64, mqdes, u_msg_ptr, msg_len, msg_prio,
+              u_abs_timeout) as k_int
+    }
+
+    pub unsafe fn mq_timedreceive_time64(mqdes: mqd_t, u_msg_ptr: *mut c_char,
+                                         msg_len: size_t, u_msg_prio: *mut k_uint,
+                                         u_abs_timeout: *const timespec) -> ssize_t {
+        call!(cty::__NR_mq_timedreceive_time64, mqdes, u_msg_ptr, msg_len, u_msg_prio,
+              u_abs_timeout) as ssize_t
+    }
+
+    pub unsafe fn semtimedop_time64(semid: k_int, tsops: *mut sembuf, nsops: k_uint,
+                                    timeout: *const timespec) -> k_int {
+        call!(cty::__NR_semtimedop_time64, semid, tsops, nsops, timeout) as k_int
+    }
+
+    pub unsafe fn rt_sigtimedwait_time64(uthese: *const sigset_t, uinfo: *mut siginfo_t,
+                                         uts: *const timespec,
+                                         sigsetsize: size_t) -> k_int {
+        call!(cty::__NR_rt_sigtimedwait_time64, uthese, uinfo, uts, sigsetsize) as k_int
+    }
+
+    pub unsafe fn futex_time64(uaddr: *mut u32, op: k_int, val: u32, utime: *mut timespec,
+                               uaddr2: *mut u32, val3: u32) -> k_int {
+        call!(cty::__NR_futex_time64, uaddr, op, val, utime, uaddr2, val3) as k_int
+    }
+
+    pub unsafe fn sched_rr_get_interval_time64(pid: pid_t,
+                                               k_interval: *mut timespec) -> k_int {
+        call!(cty::__NR_sched_rr_get_interval_time64, pid, k_interval) as k_int
+    }
+}
+
 // new calls for 64 bit systems
 #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
 pub use self::new_calls::*;
@@ -13,7 +13,8 @@ extern crate lrs_cty as cty;
 pub use ::arch::{
     accept, accept4, acct, add_key, adjtimex, bind, bpf, brk, capget,
     capset, chdir, chroot, clock_adjtime, clock_getres, clock_gettime,
-    clock_nanosleep, clock_settime, clone, close, connect, delete_module, dup, dup3,
+    clock_nanosleep, clock_settime, clone, clone3, close, connect, delete_module, dup,
+    dup3,
     epoll_create1, epoll_ctl, epoll_pwait, eventfd2,
     execve, execveat, exit, exit_group, faccessat, fadvise, fallocate, fanotify_init,
     fanotify_mark, fchdir, fchmod, fchmodat, fchown, fchownat, fcntl, fdatasync,
@@ -24,14 +25,16 @@ pub use ::arch::{
     get_robust_list, getrusage, getsid, getsockname, getsockopt, gettid, gettimeofday,
     getuid, getxattr, init_module, inotify_add_watch, inotify_init1,
     inotify_rm_watch, io_cancel, ioctl, io_destroy, io_getevents, ioprio_get,
-    ioprio_set, io_setup, io_submit, kcmp, kexec_load, keyctl, kill,
+    ioprio_set, io_setup, io_submit,
+    io_uring_enter, io_uring_register, io_uring_setup, kcmp, kexec_load, keyctl, kill,
     lgetxattr, linkat, listen, listxattr, llistxattr, lookup_dcookie,
     lremovexattr, lseek, lsetxattr, madvise, mbind, memfd_create,
     mincore, mkdirat, mknodat, mlock, mlockall, mmap, mount, move_pages,
     mprotect, mq_getsetattr, mq_open, mq_timedreceive, mq_timedsend, mq_unlink, mremap,
     msgctl, msgget, msgrcv, msgsnd, msync, munlock, munlockall, munmap, name_to_handle_at,
     nanosleep, openat, open_by_handle_at, perf_event_open, personality,
-    pipe2, pivot_root, ppoll, prctl, pread, preadv, prlimit, process_vm_readv,
+    pidfd_getfd, pidfd_open, pidfd_send_signal, pipe2, pivot_root, ppoll, prctl, pread,
+    preadv, prlimit, process_vm_readv,
     process_vm_writev, pselect6, ptrace, pwrite, pwritev, quotactl, read, readahead,
     readlinkat, readv, reboot, recvfrom, recvmmsg, recvmsg, remap_file_pages,
     removexattr, renameat, renameat2, request_key, restart_syscall,
@@ -44,7 +47,7 @@ pub use ::arch::{
     sethostname, setitimer, set_mempolicy, setns, setpgid, setpriority, setregid,
     setresgid, setresuid, setreuid, setrlimit, set_robust_list, setsid, setsockopt,
     set_tid_address, settimeofday, setuid, setxattr, shmat, shmctl, shmdt, shmget,
-    shutdown, sigaltstack, signalfd4, socket, socketpair, splice, statfs,
+    shutdown, sigaltstack, signalfd4, socket, socketpair, splice, statfs, statx,
     swapoff, swapon, symlinkat, sync, sync_file_range, syncfs, sysinfo,
     syslog, tee, tgkill, timer_delete, timerfd_create, timerfd_gettime,
     timerfd_settime, timer_getoverrun, timer_gettime, timer_settime, times, tkill,
@@ -90,6 +93,9 @@ macro_rules! call {
 
 mod common;
 
+#[cfg(any(target_arch = "x86", target_arch = "arm"))]
+mod time32;
+
 #[cfg(target_arch = "x86_64")]
 #[path = "x86_64/mod.rs"]
 pub mod arch;
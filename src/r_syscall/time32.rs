@@ -0,0 +1,405 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// Time system calls for 32 bit architectures.
+//
+// The legacy time system calls on these architectures use a 32 bit time_t which
+// overflows in 2038. The functions in this file use the *_time64 system calls that
+// were added in Linux 5.1 and take the same arguments as the legacy system calls on
+// 64 bit architectures. If the kernel doesn't know the *_time64 call, they fall back to
+// the legacy call and convert the arguments. Times that don't fit into 32 bits cause
+// EOVERFLOW in this case.
+//
+// clock_adjtime is not included because the time64 version also uses a different
+// layout of struct timex.
+
+use cty::{
+    self, k_int, k_uint, k_long, c_char, c_void, clockid_t, timespec, itimerspec,
+    old_timespec32, old_itimerspec32, timer_t, aio_context_t, io_event, mqd_t, size_t,
+    ssize_t, pollfd, sigset_t, fd_set, siginfo_t, pid_t, sembuf, mmsghdr, CLOCK_REALTIME,
+    FUTEX_CMD_MASK, FUTEX_WAIT, FUTEX_LOCK_PI, FUTEX_WAIT_BITSET, FUTEX_WAIT_REQUEUE_PI,
+    ENOSYS, EOVERFLOW, EINTR,
+};
+use ::arch::{SCT};
+use ::common;
+
+const ZERO: old_timespec32 = old_timespec32 { tv_sec: 0, tv_nsec: 0 };
+const ZERO_IT: old_itimerspec32 = old_itimerspec32 { it_interval: ZERO, it_value: ZERO };
+
+fn to_old(t: &timespec) -> Result<old_timespec32, k_int> {
+    let sec = t.tv_sec as i32;
+    if sec as i64 != t.tv_sec {
+        return Err(-EOVERFLOW);
+    }
+    Ok(old_timespec32 { tv_sec: sec, tv_nsec: t.tv_nsec as i32 })
+}
+
+fn from_old(t: &old_timespec32) -> timespec {
+    timespec { tv_sec: t.tv_sec as i64, tv_nsec: t.tv_nsec as i64 }
+}
+
+fn it_to_old(t: &itimerspec) -> Result<old_itimerspec32, k_int> {
+    Ok(old_itimerspec32 {
+        it_interval: try!(to_old(&t.it_interval)),
+        it_value:    try!(to_old(&t.it_value)),
+    })
+}
+
+fn it_from_old(t: &old_itimerspec32) -> itimerspec {
+    itimerspec {
+        it_interval: from_old(&t.it_interval),
+        it_value:    from_old(&t.it_value),
+    }
+}
+
+/// Converts an optional timespec argument.
+///
+/// Returns a pointer to `buf` or null if `t` is null.
+unsafe fn arg(t: *const timespec,
+              buf: &mut old_timespec32) -> Result<*mut old_timespec32, k_int> {
+    if t.is_null() {
+        return Ok(0 as *mut old_timespec32);
+    }
+    *buf = try!(to_old(&*t));
+    Ok(buf as *mut old_timespec32)
+}
+
+/// Copies a timespec that was written by the kernel back to the caller.
+unsafe fn ret(t: *mut timespec, buf: &old_timespec32) {
+    if !t.is_null() {
+        *t = from_old(buf);
+    }
+}
+
+pub unsafe fn clock_getres(which_clock: clockid_t, tp: *mut timespec) -> k_int {
+    let rv = common::clock_getres_time64(which_clock, tp);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let mut old = ZERO;
+    let old_tp: *mut old_timespec32 = if tp.is_null() { 0 as *mut _ } else { &mut old };
+    let rv = call!(cty::__NR_clock_getres, which_clock, old_tp) as k_int;
+    if rv == 0 {
+        ret(tp, &old);
+    }
+    rv
+}
+
+pub unsafe fn clock_gettime(which_clock: clockid_t, tp: *mut timespec) -> k_int {
+    let rv = common::clock_gettime64(which_clock, tp);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let mut old = ZERO;
+    let old_tp: *mut old_timespec32 = &mut old;
+    let rv = call!(cty::__NR_clock_gettime, which_clock, old_tp) as k_int;
+    if rv == 0 {
+        ret(tp, &old);
+    }
+    rv
+}
+
+pub unsafe fn clock_settime(which_clock: clockid_t, tp: *const timespec) -> k_int {
+    let rv = common::clock_settime64(which_clock, tp);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let old = match to_old(&*tp) {
+        Ok(o) => o,
+        Err(e) => return e,
+    };
+    let old_tp: *const old_timespec32 = &old;
+    call!(cty::__NR_clock_settime, which_clock, old_tp) as k_int
+}
+
+pub unsafe fn clock_nanosleep(which_clock: clockid_t, flags: k_int, rqtp: *const timespec,
+                              rmtp: *mut timespec) -> k_int {
+    let rv = common::clock_nanosleep_time64(which_clock, flags, rqtp, rmtp);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let old_rq = match to_old(&*rqtp) {
+        Ok(o) => o,
+        Err(e) => return e,
+    };
+    let mut old_rm = ZERO;
+    let old_rqtp: *const old_timespec32 = &old_rq;
+    let old_rmtp: *mut old_timespec32 = &mut old_rm;
+    let rv = call!(cty::__NR_clock_nanosleep, which_clock, flags, old_rqtp,
+                   old_rmtp) as k_int;
+    if rv == -EINTR {
+        ret(rmtp, &old_rm);
+    }
+    rv
+}
+
+pub unsafe fn nanosleep(rqtp: *mut timespec, rmtp: *mut timespec) -> k_int {
+    clock_nanosleep(CLOCK_REALTIME, 0, rqtp, rmtp)
+}
+
+pub unsafe fn timer_gettime(timer_id: timer_t, setting: *mut itimerspec) -> k_int {
+    let rv = common::timer_gettime64(timer_id, setting);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let mut old = ZERO_IT;
+    let old_ptr: *mut old_itimerspec32 = &mut old;
+    let rv = call!(cty::__NR_timer_gettime, timer_id, old_ptr) as k_int;
+    if rv == 0 {
+        *setting = it_from_old(&old);
+    }
+    rv
+}
+
+pub unsafe fn timer_settime(timer_id: timer_t, flags: k_int,
+                            new_setting: *const itimerspec,
+                            old_setting: *mut itimerspec) -> k_int {
+    let rv = common::timer_settime64(timer_id, flags, new_setting, old_setting);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let new = match it_to_old(&*new_setting) {
+        Ok(n) => n,
+        Err(e) => return e,
+    };
+    let mut old = ZERO_IT;
+    let new_ptr: *const old_itimerspec32 = &new;
+    let old_ptr: *mut old_itimerspec32 = if old_setting.is_null() {
+        0 as *mut _
+    } else {
+        &mut old
+    };
+    let rv = call!(cty::__NR_timer_settime, timer_id, flags, new_ptr, old_ptr) as k_int;
+    if rv == 0 && !old_setting.is_null() {
+        *old_setting = it_from_old(&old);
+    }
+    rv
+}
+
+pub unsafe fn timerfd_gettime(ufd: k_int, otmr: *mut itimerspec) -> k_int {
+    let rv = common::timerfd_gettime64(ufd, otmr);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let mut old = ZERO_IT;
+    let old_ptr: *mut old_itimerspec32 = &mut old;
+    let rv = call!(cty::__NR_timerfd_gettime, ufd, old_ptr) as k_int;
+    if rv == 0 {
+        *otmr = it_from_old(&old);
+    }
+    rv
+}
+
+pub unsafe fn timerfd_settime(ufd: k_int, flags: k_int, utmr: *const itimerspec,
+                              otmr: *mut itimerspec) -> k_int {
+    let rv = common::timerfd_settime64(ufd, flags, utmr, otmr);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let new = match it_to_old(&*utmr) {
+        Ok(n) => n,
+        Err(e) => return e,
+    };
+    let mut old = ZERO_IT;
+    let new_ptr: *const old_itimerspec32 = &new;
+    let old_ptr: *mut old_itimerspec32 = if otmr.is_null() {
+        0 as *mut _
+    } else {
+        &mut old
+    };
+    let rv = call!(cty::__NR_timerfd_settime, ufd, flags, new_ptr, old_ptr) as k_int;
+    if rv == 0 && !otmr.is_null() {
+        *otmr = it_from_old(&old);
+    }
+    rv
+}
+
+pub unsafe fn utimensat(dfd: k_int, filename: *const c_char, utimes: *const timespec,
+                        flags: k_int) -> k_int {
+    let rv = common::utimensat_time64(dfd, filename, utimes, flags);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let mut old = [ZERO, ZERO];
+    let old_ptr = if utimes.is_null() {
+        0 as *const old_timespec32
+    } else {
+        // UTIME_NOW and UTIME_OMIT are stored in tv_nsec and survive the conversion.
+        for i in 0..2 {
+            old[i] = match to_old(&*utimes.add(i)) {
+                Ok(o) => o,
+                Err(e) => return e,
+            };
+        }
+        old.as_ptr()
+    };
+    call!(cty::__NR_utimensat, dfd, filename, old_ptr, flags) as k_int
+}
+
+pub unsafe fn pselect6(n: k_int, inp: *mut fd_set, outp: *mut fd_set, exp: *mut fd_set,
+                       tsp: *mut timespec, sig: *mut c_void) -> k_int {
+    let rv = common::pselect6_time64(n, inp, outp, exp, tsp, sig);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let mut old = ZERO;
+    let old_tsp = match arg(tsp, &mut old) {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    let rv = call!(cty::__NR_pselect6, n, inp, outp, exp, old_tsp, sig) as k_int;
+    // The kernel updates the timeout with the remaining time.
+    ret(tsp, &old);
+    rv
+}
+
+pub unsafe fn ppoll(ufds: *mut pollfd, nfds: k_uint, tsp: *mut timespec,
+                    sigmask: *const sigset_t, sigsetsize: size_t) -> k_int {
+    let rv = common::ppoll_time64(ufds, nfds, tsp, sigmask, sigsetsize);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let mut old = ZERO;
+    let old_tsp = match arg(tsp, &mut old) {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    let rv = call!(cty::__NR_ppoll, ufds, nfds, old_tsp, sigmask, sigsetsize) as k_int;
+    ret(tsp, &old);
+    rv
+}
+
+pub unsafe fn io_getevents(ctx_id: aio_context_t, min_nr: k_long, nr: k_long,
+                           events: *mut io_event, timeout: *mut timespec) -> k_int {
+    // There is no io_getevents_time64. io_pgetevents without a signal mask does the
+    // same thing.
+    let rv = common::io_pgetevents_time64(ctx_id, min_nr, nr, events, timeout,
+                                          0 as *const c_void);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let mut old = ZERO;
+    let old_timeout = match arg(timeout, &mut old) {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    call!(cty::__NR_io_getevents, ctx_id, min_nr, nr, events, old_timeout) as k_int
+}
+
+pub unsafe fn recvmmsg(fd: k_int, mmsg: *mut mmsghdr, vlen: k_uint, flags: k_uint,
+                       timeout: *mut timespec) -> ssize_t {
+    let rv = common::recvmmsg_time64(fd, mmsg, vlen, flags, timeout);
+    if rv != -ENOSYS as ssize_t {
+        return rv;
+    }
+    let mut old = ZERO;
+    let old_timeout = match arg(timeout, &mut old) {
+        Ok(p) => p,
+        Err(e) => return e as ssize_t,
+    };
+    // The legacy call goes through socketcall on x86 and interprets the pointer as a
+    // pointer to an old_timespec32.
+    let rv = common::recvmmsg(fd, mmsg, vlen, flags, old_timeout as *mut timespec);
+    ret(timeout, &old);
+    rv
+}
+
+pub unsafe fn mq_timedsend(mqdes: mqd_t, u_msg_ptr: *const c_char, msg_len: size_t,
+                           msg_prio: k_uint, u_abs_timeout: *const timespec) -> k_int {
+    let rv = common::mq_timedsend_time64(mqdes, u_msg_ptr, msg_len, msg_prio,
+                                         u_abs_timeout);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let mut old = ZERO;
+    let old_timeout = match arg(u_abs_timeout, &mut old) {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    call!(cty::__NR_mq_timedsend, mqdes, u_msg_ptr, msg_len, msg_prio,
+          old_timeout) as k_int
+}
+
+pub unsafe fn mq_timedreceive(mqdes: mqd_t, u_msg_ptr: *mut c_char, msg_len: size_t,
+                              u_msg_prio: *mut k_uint,
+                              u_abs_timeout: *const timespec) -> ssize_t {
+    let rv = common::mq_timedreceive_time64(mqdes, u_msg_ptr, msg_len, u_msg_prio,
+                                            u_abs_timeout);
+    if rv != -ENOSYS as ssize_t {
+        return rv;
+    }
+    let mut old = ZERO;
+    let old_timeout = match arg(u_abs_timeout, &mut old) {
+        Ok(p) => p,
+        Err(e) => return e as ssize_t,
+    };
+    call!(cty::__NR_mq_timedreceive, mqdes, u_msg_ptr, msg_len, u_msg_prio,
+          old_timeout) as ssize_t
+}
+
+pub unsafe fn semtimedop(semid: k_int, tsops: *mut sembuf, nsops: k_uint,
+                         timeout: *const timespec) -> k_int {
+    let rv = common::semtimedop_time64(semid, tsops, nsops, timeout);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let mut old = ZERO;
+    let old_timeout = match arg(timeout, &mut old) {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    // The legacy call goes through ipc on x86 and interprets the pointer as a pointer to
+    // an old_timespec32.
+    common::semtimedop(semid, tsops, nsops, old_timeout as *const timespec)
+}
+
+pub unsafe fn rt_sigtimedwait(uthese: *const sigset_t, uinfo: *mut siginfo_t,
+                              uts: *const timespec, sigsetsize: size_t) -> k_int {
+    let rv = common::rt_sigtimedwait_time64(uthese, uinfo, uts, sigsetsize);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let mut old = ZERO;
+    let old_uts = match arg(uts, &mut old) {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    call!(cty::__NR_rt_sigtimedwait, uthese, uinfo, old_uts, sigsetsize) as k_int
+}
+
+pub unsafe fn futex(uaddr: *mut u32, op: k_int, val: u32, utime: *mut timespec,
+                    uaddr2: *mut u32, val3: u32) -> k_int {
+    let rv = common::futex_time64(uaddr, op, val, utime, uaddr2, val3);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    // Most operations use the fourth argument as an integer instead of a timeout.
+    let cmd = op & FUTEX_CMD_MASK;
+    let uses_timeout = cmd == FUTEX_WAIT || cmd == FUTEX_LOCK_PI ||
+                       cmd == FUTEX_WAIT_BITSET || cmd == FUTEX_WAIT_REQUEUE_PI;
+    let mut old = ZERO;
+    let old_utime = if uses_timeout {
+        match arg(utime, &mut old) {
+            Ok(p) => p as *mut c_void,
+            Err(e) => return e,
+        }
+    } else {
+        utime as *mut c_void
+    };
+    call!(cty::__NR_futex, uaddr, op, val, old_utime, uaddr2, val3) as k_int
+}
+
+pub unsafe fn sched_rr_get_interval(pid: pid_t, k_interval: *mut timespec) -> k_int {
+    let rv = common::sched_rr_get_interval_time64(pid, k_interval);
+    if rv != -ENOSYS {
+        return rv;
+    }
+    let mut old = ZERO;
+    let old_ptr: *mut old_timespec32 = &mut old;
+    let rv = call!(cty::__NR_sched_rr_get_interval, pid, old_ptr) as k_int;
+    if rv == 0 {
+        ret(k_interval, &old);
+    }
+    rv
+}
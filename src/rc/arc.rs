@@ -13,10 +13,15 @@ use atomic::{Atomic};
 use fmt::{Debug, Write};
 use alloc::{self, MemPool};
 
+/// The value of `weak` while `Arc::as_mut` checks the strong count.
+const WEAK_LOCKED: usize = !0;
+
 struct Inner<T: ?Sized, H = alloc::Heap>
     where H: MemPool,
 {
     count: Atomic<usize>,
+    /// The number of weak references plus one if `count` is not zero.
+    weak: Atomic<usize>,
     pool: H,
     val: T,
 }
@@ -128,16 +133,59 @@ impl<T: ?Sized, H = alloc::Heap> Arc<T, H>
             let data_ptr = try!(alloc::alloc::<Inner<T, H>, _>(&mut pool));
             ptr::write(&mut (*data_ptr).pool, pool);
             (*data_ptr).count.store(1);
+            (*data_ptr).weak.store(1);
             Ok(ArcBuf { data: NoAliasMemPtr::new(data_ptr) })
         }
     }
 
     /// Returns a mutable reference to the contained data if this is the only reference.
+    ///
+    /// = Remarks
+    ///
+    /// Weak references count as references for the purpose of this function.
     pub fn as_mut(&mut self) -> Option<&mut T> {
-        unsafe {
-            match self.data.count.load() {
-                1 => Some(&mut (*self.data.get()).val),
-                _ => None,
+        // Lock out weak references while the strong count is checked. Otherwise a weak
+        // reference could be upgraded and dropped between the two loads.
+        if self.data.weak.compare_exchange(1, WEAK_LOCKED) != 1 {
+            return None;
+        }
+        let unique = self.data.count.load() == 1;
+        self.data.weak.store_release(1);
+        match unique {
+            true => unsafe { Some(&mut (*self.data.get()).val) },
+            false => None,
+        }
+    }
+
+    /// Returns the number of strong references to the contained data.
+    pub fn strong_count(&self) -> usize {
+        self.data.count.load()
+    }
+
+    /// Returns the number of weak references to the contained data.
+    pub fn weak_count(&self) -> usize {
+        match self.data.weak.load() {
+            // `as_mut` is running which means that there are no weak references.
+            WEAK_LOCKED => 0,
+            n => n - 1,
+        }
+    }
+
+    /// Creates a weak reference to the contained data.
+    ///
+    /// = Remarks
+    ///
+    /// Weak references do not keep the contained data alive. The memory of the `Arc` is
+    /// freed once the last strong and the last weak reference have been dropped.
+    pub fn downgrade(&self) -> ArcWeak<T, H> {
+        loop {
+            let weak = self.data.weak.load();
+            // Wait until `as_mut` has released the weak count.
+            if weak == WEAK_LOCKED {
+                continue;
+            }
+            if self.data.weak.compare_exchange(weak, weak + 1) == weak {
+                return ArcWeak { data: self.data };
             }
         }
     }
@@ -193,8 +241,10 @@ impl<T: ?Sized, H> Drop for Arc<T, H>
                 if mem::needs_drop::<T>() {
                     ptr::drop(&mut (*self.data.get()).val);
                 }
-                let mut pool = ptr::read(&self.data.pool);
-                alloc::free(&mut pool, self.data.get());
+                if self.data.weak.sub(1) == 1 {
+                    let mut pool = ptr::read(&self.data.pool);
+                    alloc::free(&mut pool, self.data.get());
+                }
             }
         }
     }
@@ -237,3 +287,102 @@ impl<T: ?Sized, H> Debug for Arc<T, H>
         write!(w, "Arc {{ data: {:?} }}", self.deref())
     }
 }
+
+/// A weak reference to the contents of an `Arc`.
+pub struct ArcWeak<T: ?Sized, Heap = alloc::Heap>
+    where Heap: MemPool,
+          T: Leak,
+{
+    data: NoAliasObjPtr<Inner<T, Heap>>,
+}
+
+impl<T: ?Sized, H> ArcWeak<T, H>
+    where H: MemPool,
+          T: Leak,
+{
+    /// Tries to turn the weak reference into a strong reference.
+    ///
+    /// [return_value]
+    /// Returns a new `Arc` if the contained data is still alive.
+    pub fn upgrade(&self) -> Option<Arc<T, H>> {
+        loop {
+            let count = self.data.count.load();
+            if count == 0 {
+                return None;
+            }
+            if self.data.count.compare_exchange(count, count + 1) == count {
+                return Some(Arc { data: self.data });
+            }
+        }
+    }
+
+    /// Returns the number of strong references to the contained data.
+    pub fn strong_count(&self) -> usize {
+        self.data.count.load()
+    }
+
+    /// Returns the number of weak references to the contained data.
+    pub fn weak_count(&self) -> usize {
+        match self.data.count.load() {
+            0 => self.data.weak.load(),
+            _ => self.data.weak.load() - 1,
+        }
+    }
+
+    /// Adds a new weak reference, returning an `ArcWeak` that points to the same data.
+    pub fn add_ref(&self) -> ArcWeak<T, H> {
+        self.data.weak.add(1);
+        ArcWeak { data: self.data }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, H> CoerceUnsized<ArcWeak<U, H>> for ArcWeak<T, H>
+    where T: Unsize<U> + Leak,
+          U: Leak,
+          H: MemPool,
+{}
+
+unsafe impl<T: ?Sized, H> Send for ArcWeak<T, H>
+    where T: Sync+Send+Leak, H: Send+MemPool { }
+unsafe impl<T: ?Sized, H> Sync for ArcWeak<T, H> where T: Sync+Leak, H: MemPool { }
+
+impl<T: ?Sized, H> Drop for ArcWeak<T, H>
+    where H: MemPool,
+          T: Leak,
+{
+    fn drop(&mut self) {
+        unsafe {
+            if self.data.weak.sub(1) == 1 {
+                let mut pool = ptr::read(&self.data.pool);
+                alloc::free(&mut pool, self.data.get());
+            }
+        }
+    }
+}
+
+impl<T: ?Sized, H> To for ArcWeak<T, H>
+    where H: MemPool,
+          T: Leak,
+{
+    fn to(&self) -> ArcWeak<T, H> {
+        self.add_ref()
+    }
+}
+
+impl<T: ?Sized, H> TryTo for ArcWeak<T, H>
+    where H: MemPool,
+          T: Leak,
+{
+    fn try_to(&self) -> Result<ArcWeak<T, H>> {
+        Ok(self.add_ref())
+    }
+}
+
+impl<T: ?Sized, H> Debug for ArcWeak<T, H>
+    where H: MemPool,
+          T: Leak,
+{
+    fn fmt<W: Write+?Sized>(&self, mut w: &mut W) -> Result {
+        write!(w, "ArcWeak {{ strong: {:?} }}", self.strong_count())
+    }
+}
@@ -13,8 +13,8 @@ extern crate lrs_cell as cell;
 extern crate lrs_alloc as alloc;
 extern crate lrs_atomic as atomic;
 
-pub use rc::{Rc, RcBuf};
-pub use arc::{Arc, ArcBuf};
+pub use rc::{Rc, RcBuf, RcWeak};
+pub use arc::{Arc, ArcBuf, ArcWeak};
 
 pub mod std { pub use ::fmt::std::*; }
 
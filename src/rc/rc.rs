@@ -17,6 +17,8 @@ struct Inner<T: ?Sized, H = alloc::ThreadHeap>
     where H: MemPool,
 {
     count: Cell<usize>,
+    /// The number of weak references plus one if `count` is not zero.
+    weak: Cell<usize>,
     pool: H,
     val: T,
 }
@@ -128,20 +130,46 @@ impl<T: ?Sized, H = alloc::ThreadHeap> Rc<T, H>
             let data_ptr = try!(alloc::alloc::<Inner<T, H>, _>(&mut pool));
             ptr::write(&mut (*data_ptr).pool, pool);
             (*data_ptr).count.set(1);
+            (*data_ptr).weak.set(1);
             Ok(RcBuf { data: NoAliasMemPtr::new(data_ptr) })
         }
     }
 
     /// Returns a mutable reference to the contained data if this is the only reference.
+    ///
+    /// = Remarks
+    ///
+    /// Weak references count as references for the purpose of this function.
     pub fn as_mut(&mut self) -> Option<&mut T> {
         unsafe {
-            match self.data.count.get() {
-                1 => Some(&mut (*self.data.get()).val),
+            match (self.data.count.get(), self.data.weak.get()) {
+                (1, 1) => Some(&mut (*self.data.get()).val),
                 _ => None,
             }
         }
     }
 
+    /// Returns the number of strong references to the contained data.
+    pub fn strong_count(&self) -> usize {
+        self.data.count.get()
+    }
+
+    /// Returns the number of weak references to the contained data.
+    pub fn weak_count(&self) -> usize {
+        self.data.weak.get() - 1
+    }
+
+    /// Creates a weak reference to the contained data.
+    ///
+    /// = Remarks
+    ///
+    /// Weak references do not keep the contained data alive. The memory of the `Rc` is
+    /// freed once the last strong and the last weak reference have been dropped.
+    pub fn downgrade(&self) -> RcWeak<T, H> {
+        self.data.weak.set(self.data.weak.get() + 1);
+        RcWeak { data: self.data }
+    }
+
     /// Adds a new reference, returning an `Rc` that points to the same data.
     pub fn add_ref(&self) -> Rc<T, H> {
         self.data.count.set(self.data.count.get() + 1);
@@ -194,8 +222,12 @@ impl<T: ?Sized, H> Drop for Rc<T, H>
                 if mem::needs_drop::<T>() {
                     ptr::drop(&mut (*self.data.get()).val);
                 }
-                let mut pool = ptr::read(&self.data.pool);
-                alloc::free(&mut pool, self.data.get());
+                let weak = self.data.weak.get();
+                self.data.weak.set(weak - 1);
+                if weak == 1 {
+                    let mut pool = ptr::read(&self.data.pool);
+                    alloc::free(&mut pool, self.data.get());
+                }
             }
         }
     }
@@ -238,3 +270,100 @@ impl<T: ?Sized, H> Debug for Rc<T, H>
         write!(w, "Rc {{ data: {:?} }}", self.deref())
     }
 }
+
+/// A weak reference to the contents of an `Rc`.
+pub struct RcWeak<T: ?Sized, Heap = alloc::ThreadHeap>
+    where Heap: MemPool,
+          T: Leak,
+{
+    data: NoAliasObjPtr<Inner<T, Heap>>,
+}
+
+impl<T: ?Sized, H> RcWeak<T, H>
+    where H: MemPool,
+          T: Leak,
+{
+    /// Tries to turn the weak reference into a strong reference.
+    ///
+    /// [return_value]
+    /// Returns a new `Rc` if the contained data is still alive.
+    pub fn upgrade(&self) -> Option<Rc<T, H>> {
+        match self.data.count.get() {
+            0 => None,
+            count => {
+                self.data.count.set(count + 1);
+                Some(Rc { data: self.data })
+            },
+        }
+    }
+
+    /// Returns the number of strong references to the contained data.
+    pub fn strong_count(&self) -> usize {
+        self.data.count.get()
+    }
+
+    /// Returns the number of weak references to the contained data.
+    pub fn weak_count(&self) -> usize {
+        match self.data.count.get() {
+            0 => self.data.weak.get(),
+            _ => self.data.weak.get() - 1,
+        }
+    }
+
+    /// Adds a new weak reference, returning an `RcWeak` that points to the same data.
+    pub fn add_ref(&self) -> RcWeak<T, H> {
+        self.data.weak.set(self.data.weak.get() + 1);
+        RcWeak { data: self.data }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, H> CoerceUnsized<RcWeak<U, H>> for RcWeak<T, H>
+    where T: Unsize<U> + Leak,
+          U: Leak,
+          H: MemPool,
+{}
+
+impl<T: ?Sized, H> !Send for RcWeak<T, H> { }
+
+impl<T: ?Sized, H> Drop for RcWeak<T, H>
+    where H: MemPool,
+          T: Leak,
+{
+    fn drop(&mut self) {
+        unsafe {
+            let weak = self.data.weak.get();
+            self.data.weak.set(weak - 1);
+            if weak == 1 {
+                let mut pool = ptr::read(&self.data.pool);
+                alloc::free(&mut pool, self.data.get());
+            }
+        }
+    }
+}
+
+impl<T: ?Sized, H> From for RcWeak<T, H>
+    where H: MemPool,
+          T: Leak,
+{
+    fn from(t: &RcWeak<T, H>) -> Self {
+        t.add_ref()
+    }
+}
+
+impl<T: ?Sized, H> TryFrom for RcWeak<T, H>
+    where H: MemPool,
+          T: Leak,
+{
+    fn try_from(t: &RcWeak<T, H>) -> Result<Self> {
+        Ok(t.add_ref())
+    }
+}
+
+impl<T: ?Sized, H> Debug for RcWeak<T, H>
+    where H: MemPool,
+          T: Leak,
+{
+    fn fmt<W: Write+?Sized>(&self, mut w: &mut W) -> Result {
+        write!(w, "RcWeak {{ strong: {:?} }}", self.strong_count())
+    }
+}
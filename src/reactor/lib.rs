@@ -0,0 +1,588 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+#![crate_name = "lrs_reactor"]
+#![crate_type = "lib"]
+#![feature(custom_derive)]
+#![no_std]
+
+extern crate lrs_base      as base;
+extern crate lrs_cty       as cty;
+extern crate lrs_fd        as fd;
+extern crate lrs_alloc     as alloc;
+extern crate lrs_vec       as vec;
+extern crate lrs_poll      as poll;
+extern crate lrs_time_base as time_base;
+extern crate lrs_signal    as signal;
+extern crate lrs_event     as event;
+
+use base::prelude::*;
+mod std { pub use base::std::*; pub use {cty}; }
+
+use base::{error};
+use fd::{FdContainer};
+use alloc::{MemPool};
+use vec::{Vec};
+use poll::{Epoll, Event, PollFlags, EMPTY_EVENT, POLL_READ};
+use time_base::{Time};
+use time_base::clock::{MONO};
+use time_base::timer::{Timer};
+use signal::{Sigset};
+use signal::signals::{Signal};
+use signal::sigfd::{Sigfd, SigfdInfo};
+use signal::sigfd::flags::{SIGFD_DONT_BLOCK};
+use event::{Eventfd};
+use event::flags::{EFD_DONT_BLOCK};
+
+/// The epoll data used for the internal timerfd.
+const TIMER_TOKEN: u64 = !0;
+
+/// The epoll data used for the internal signalfd.
+const SIGNAL_TOKEN: u64 = !0 - 1;
+
+/// The epoll data used for the internal eventfd.
+const WAKE_TOKEN: u64 = !0 - 2;
+
+/// The smallest token that cannot be used for user sources.
+pub const MAX_TOKEN: u64 = WAKE_TOKEN;
+
+/// The identifier of a timer registered with a reactor.
+///
+/// [field, 1]
+/// The numeric identifier.
+#[derive(Pod, Eq)]
+pub struct TimerId(pub u64);
+
+/// A timer registered with a reactor.
+#[derive(Pod)]
+struct TimerEntry {
+    /// The absolute time (on the monotonic clock) at which the timer expires.
+    deadline: Time,
+    /// The interval of a repeating timer or zero for one-shot timers.
+    interval: Time,
+    /// The token passed to the handler.
+    token: u64,
+    /// The unique identifier of the timer.
+    id: u64,
+}
+
+impl TimerEntry {
+    fn before(&self, other: &TimerEntry) -> bool {
+        (self.deadline, self.id) < (other.deadline, other.id)
+    }
+}
+
+/// Objects that handle events dispatched by a reactor.
+///
+/// = Remarks
+///
+/// All methods receive a mutable reference to the reactor so that sources and timers
+/// can be added or removed from within the handler.
+pub trait Handler<Heap = alloc::Heap>
+    where Heap: MemPool,
+{
+    /// Handles readiness of a file descriptor.
+    ///
+    /// [argument, reactor]
+    /// The reactor that dispatched the event.
+    ///
+    /// [argument, token]
+    /// The token the file descriptor was registered with.
+    ///
+    /// [argument, event]
+    /// The event reported by epoll.
+    fn io(&mut self, reactor: &mut Reactor<Heap>, token: u64, event: Event);
+
+    /// Handles the expiration of a timer.
+    ///
+    /// [argument, reactor]
+    /// The reactor that dispatched the event.
+    ///
+    /// [argument, token]
+    /// The token the timer was registered with.
+    fn timer(&mut self, reactor: &mut Reactor<Heap>, token: u64) {
+        let _ = (reactor, token);
+    }
+
+    /// Handles a signal.
+    ///
+    /// [argument, reactor]
+    /// The reactor that dispatched the event.
+    ///
+    /// [argument, info]
+    /// Information about the signal.
+    fn signal(&mut self, reactor: &mut Reactor<Heap>, info: &SigfdInfo) {
+        let _ = (reactor, info);
+    }
+
+    /// Handles a wake-up from a `Waker`.
+    ///
+    /// [argument, reactor]
+    /// The reactor that dispatched the event.
+    ///
+    /// = Remarks
+    ///
+    /// Multiple wake-ups that happen before the reactor gets to dispatch them are
+    /// coalesced into a single call.
+    fn wake(&mut self, reactor: &mut Reactor<Heap>) {
+        let _ = reactor;
+    }
+}
+
+/// A handle that can wake a reactor from another thread.
+pub struct Waker {
+    fd: Eventfd,
+}
+
+impl Waker {
+    /// Wakes the reactor.
+    ///
+    /// = Remarks
+    ///
+    /// The reactor calls the `wake` method of its handler during its next iteration.
+    pub fn wake(&self) -> Result {
+        self.fd.add(1)
+    }
+}
+
+/// A single-threaded, readiness-based event loop.
+///
+/// = Remarks
+///
+/// The reactor multiplexes file descriptors, timers, signals, and wake-ups from other
+/// threads over a single epoll instance. Timers are kept in a binary heap and share a
+/// single timerfd on the monotonic clock.
+///
+/// Signals that are watched by a reactor are blocked in the thread that adds them so
+/// that they are delivered through the signalfd instead of a signal handler. If the
+/// process has more than one thread, the signals should be blocked in all threads.
+///
+/// = Examples
+///
+/// ----
+/// struct Echo;
+///
+/// impl Handler for Echo {
+///     fn io(&mut self, reactor: &mut Reactor, token: u64, event: Event) {
+///         // ...
+///     }
+///
+///     fn timer(&mut self, reactor: &mut Reactor, token: u64) {
+///         reactor.stop();
+///     }
+/// }
+///
+/// let mut reactor: Reactor = try!(Reactor::new());
+/// try!(reactor.add_timer(Time::seconds(10), 0));
+/// try!(reactor.run(&mut Echo));
+/// ----
+pub struct Reactor<Heap = alloc::Heap>
+    where Heap: MemPool,
+{
+    epoll: Epoll,
+    timer: Timer,
+    sigfd: Sigfd,
+    signals: Sigset,
+    unblock: Sigset,
+    wake: Eventfd,
+    timers: Vec<TimerEntry, Heap>,
+    next_timer_id: u64,
+    armed: Option<Time>,
+    stopped: bool,
+}
+
+impl<H = alloc::Heap> Reactor<H>
+    where H: MemPool,
+{
+    /// Creates a new reactor.
+    pub fn new() -> Result<Reactor<H>>
+        where H: OutOf,
+    {
+        Reactor::with_pool(H::out_of(()))
+    }
+
+    /// Creates a new reactor with a memory pool.
+    ///
+    /// [argument, pool]
+    /// The pool from which the timer heap is allocated.
+    pub fn with_pool(pool: H) -> Result<Reactor<H>> {
+        let epoll = try!(Epoll::new());
+        let timer = try!(MONO.timer_non_blocking());
+        let signals = Sigset::new();
+        let sigfd = try!(Sigfd::new(signals, SIGFD_DONT_BLOCK));
+        let wake = try!(Eventfd::new(EFD_DONT_BLOCK));
+        try!(epoll.add_data(&timer, POLL_READ, TIMER_TOKEN));
+        try!(epoll.add_data(&sigfd, POLL_READ, SIGNAL_TOKEN));
+        try!(epoll.add_data(&wake, POLL_READ, WAKE_TOKEN));
+        Ok(Reactor {
+            epoll: epoll,
+            timer: timer,
+            sigfd: sigfd,
+            signals: signals,
+            unblock: signals,
+            wake: wake,
+            timers: Vec::with_pool(pool),
+            next_timer_id: 0,
+            armed: None,
+            stopped: false,
+        })
+    }
+
+    /// Registers a file descriptor.
+    ///
+    /// [argument, fd]
+    /// The file descriptor to watch.
+    ///
+    /// [argument, token]
+    /// The token that will be passed to the handler.
+    ///
+    /// [argument, flags]
+    /// The events to watch for.
+    ///
+    /// = Remarks
+    ///
+    /// The token must be smaller than `MAX_TOKEN`. The file descriptor must stay open
+    /// until it is removed from the reactor.
+    pub fn add<F: FdContainer>(&self, fd: &F, token: u64, flags: PollFlags) -> Result {
+        if token >= MAX_TOKEN {
+            return Err(error::InvalidArgument);
+        }
+        self.epoll.add_data(fd, flags, token)
+    }
+
+    /// Modifies a registered file descriptor.
+    ///
+    /// [argument, fd]
+    /// The file descriptor to modify.
+    ///
+    /// [argument, token]
+    /// The new token.
+    ///
+    /// [argument, flags]
+    /// The new events to watch for.
+    pub fn modify<F: FdContainer>(&self, fd: &F, token: u64, flags: PollFlags) -> Result {
+        if token >= MAX_TOKEN {
+            return Err(error::InvalidArgument);
+        }
+        self.epoll.modify_data(fd, flags, token)
+    }
+
+    /// Unregisters a file descriptor.
+    ///
+    /// [argument, fd]
+    /// The file descriptor to remove.
+    pub fn remove<F: FdContainer>(&self, fd: &F) -> Result {
+        self.epoll.remove(fd)
+    }
+
+    /// Adds a one-shot timer.
+    ///
+    /// [argument, delay]
+    /// The time after which the timer expires.
+    ///
+    /// [argument, token]
+    /// The token that will be passed to the handler.
+    ///
+    /// [return_value]
+    /// Returns the identifier of the timer.
+    pub fn add_timer(&mut self, delay: Time, token: u64) -> Result<TimerId> {
+        let now = try!(MONO.get_time());
+        self.insert_timer(now + delay, Time::nanoseconds(0), token)
+    }
+
+    /// Adds a repeating timer.
+    ///
+    /// [argument, interval]
+    /// The interval at which the timer expires.
+    ///
+    /// [argument, token]
+    /// The token that will be passed to the handler.
+    ///
+    /// [return_value]
+    /// Returns the identifier of the timer.
+    ///
+    /// = Remarks
+    ///
+    /// The first expiration happens after one interval. If the reactor falls behind by
+    /// more than one interval, the missed expirations are dispatched only once.
+    pub fn add_repeating_timer(&mut self, interval: Time, token: u64) -> Result<TimerId> {
+        if interval <= Time::nanoseconds(0) {
+            return Err(error::InvalidArgument);
+        }
+        let now = try!(MONO.get_time());
+        self.insert_timer(now + interval, interval, token)
+    }
+
+    /// Removes a timer.
+    ///
+    /// [argument, id]
+    /// The identifier of the timer.
+    ///
+    /// [return_value]
+    /// Returns whether the timer was still registered.
+    pub fn cancel_timer(&mut self, id: TimerId) -> Result<bool> {
+        let pos = match self.timers.find(|t| t.id == id.0) {
+            Some(pos) => pos,
+            _ => return Ok(false),
+        };
+        self.heap_remove(pos);
+        try!(self.rearm());
+        Ok(true)
+    }
+
+    /// Watches a signal.
+    ///
+    /// [argument, sig]
+    /// The signal to watch.
+    ///
+    /// = Remarks
+    ///
+    /// The signal is blocked in the calling thread.
+    pub fn add_signal(&mut self, sig: Signal) -> Result {
+        if try!(self.signals.is_set(sig)) {
+            return Ok(());
+        }
+        let mut signals = self.signals;
+        try!(signals.set(sig));
+        let old = try!(signal::block_signal(sig));
+        if let Err(e) = self.sigfd.set_mask(signals) {
+            let _ = signal::set_blocked_signals(old);
+            return Err(e);
+        }
+        if !try!(old.is_set(sig)) {
+            try!(self.unblock.set(sig));
+        }
+        self.signals = signals;
+        Ok(())
+    }
+
+    /// Stops watching a signal.
+    ///
+    /// [argument, sig]
+    /// The signal to stop watching.
+    ///
+    /// = Remarks
+    ///
+    /// The signal is unblocked in the calling thread unless it was already blocked when
+    /// it was added.
+    pub fn remove_signal(&mut self, sig: Signal) -> Result {
+        let mut signals = self.signals;
+        try!(signals.unset(sig));
+        try!(self.sigfd.set_mask(signals));
+        self.signals = signals;
+        if try!(self.unblock.is_set(sig)) {
+            try!(self.unblock.unset(sig));
+            try!(signal::unblock_signal(sig));
+        }
+        Ok(())
+    }
+
+    /// Creates a handle that can wake the reactor from other threads.
+    pub fn waker(&self) -> Result<Waker> {
+        Ok(Waker { fd: try!(self.wake.duplicate()) })
+    }
+
+    /// Makes `run` return after the current iteration.
+    pub fn stop(&mut self) {
+        self.stopped = true;
+    }
+
+    /// Runs the reactor until `stop` is called.
+    ///
+    /// [argument, handler]
+    /// The handler to which events are dispatched.
+    pub fn run<T>(&mut self, handler: &mut T) -> Result
+        where T: Handler<H>,
+    {
+        self.stopped = false;
+        while !self.stopped {
+            try!(self.run_once(handler, None));
+        }
+        Ok(())
+    }
+
+    /// Waits for events and dispatches them once.
+    ///
+    /// [argument, handler]
+    /// The handler to which events are dispatched.
+    ///
+    /// [argument, timeout]
+    /// The maximum time to wait for events or `None` to wait indefinitely.
+    ///
+    /// = Remarks
+    ///
+    /// If the wait is interrupted by a signal that is not watched by the reactor, this
+    /// function returns without dispatching any events.
+    pub fn run_once<T>(&mut self, handler: &mut T, timeout: Option<Time>) -> Result
+        where T: Handler<H>,
+    {
+        let mut events = [EMPTY_EVENT; 32];
+        let res = match timeout {
+            Some(t) => self.epoll.wait_timeout(&mut events, t).map(|e| e.len()),
+            _ => self.epoll.wait(&mut events).map(|e| e.len()),
+        };
+        let num = match res {
+            Ok(num) => num,
+            Err(error::Interrupted) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        for i in 0..num {
+            let event = events[i];
+            match event.data() {
+                TIMER_TOKEN => try!(self.dispatch_timers(handler)),
+                SIGNAL_TOKEN => try!(self.dispatch_signals(handler)),
+                WAKE_TOKEN => {
+                    let _ = self.wake.get();
+                    handler.wake(self);
+                },
+                token => handler.io(self, token, event),
+            }
+        }
+        Ok(())
+    }
+
+    fn dispatch_timers<T>(&mut self, handler: &mut T) -> Result
+        where T: Handler<H>,
+    {
+        match self.timer.ticks() {
+            Ok(_) | Err(error::WouldBlock) => { },
+            Err(e) => return Err(e),
+        }
+        self.armed = None;
+        let now = try!(MONO.get_time());
+        while self.timers.len() > 0 && self.timers[0].deadline <= now {
+            let token = self.timers[0].token;
+            let interval = self.timers[0].interval;
+            if interval > Time::nanoseconds(0) {
+                // Repeating timers stay in the heap so that rescheduling them cannot
+                // fail.
+                let mut deadline = self.timers[0].deadline + interval;
+                if deadline <= now {
+                    deadline = now + interval;
+                }
+                self.timers[0].deadline = deadline;
+                self.sift_down(0);
+            } else {
+                self.heap_remove(0);
+            }
+            handler.timer(self, token);
+        }
+        self.rearm()
+    }
+
+    fn dispatch_signals<T>(&mut self, handler: &mut T) -> Result
+        where T: Handler<H>,
+    {
+        let mut buf = [SigfdInfo::new(); 8];
+        loop {
+            let num = match self.sigfd.read(&mut buf) {
+                Ok(infos) => infos.len(),
+                Err(error::WouldBlock) => return Ok(()),
+                Err(e) => return Err(e),
+            };
+            for i in 0..num {
+                handler.signal(self, &buf[i]);
+            }
+            if num < buf.len() {
+                return Ok(());
+            }
+        }
+    }
+
+    fn insert_timer(&mut self, deadline: Time, interval: Time,
+                    token: u64) -> Result<TimerId> {
+        let id = self.next_timer_id;
+        try!(self.heap_push(TimerEntry {
+            deadline: deadline,
+            interval: interval,
+            token: token,
+            id: id,
+        }));
+        self.next_timer_id += 1;
+        try!(self.rearm());
+        Ok(TimerId(id))
+    }
+
+    /// Arms the timerfd for the earliest deadline.
+    fn rearm(&mut self) -> Result {
+        let next = match self.timers.len() {
+            0 => None,
+            _ => Some(self.timers[0].deadline),
+        };
+        if next == self.armed {
+            return Ok(());
+        }
+        match next {
+            Some(deadline) => try!(self.timer.once_at(deadline)),
+            _ => try!(self.timer.disable()),
+        }
+        self.armed = next;
+        Ok(())
+    }
+
+    fn heap_push(&mut self, entry: TimerEntry) -> Result {
+        try!(self.timers.push(entry));
+        let pos = self.timers.len() - 1;
+        self.sift_up(pos);
+        Ok(())
+    }
+
+    fn heap_remove(&mut self, pos: usize) -> TimerEntry {
+        let last = self.timers.len() - 1;
+        self.heap_swap(pos, last);
+        let entry = self.timers.pop().unwrap();
+        if pos < self.timers.len() {
+            self.sift_up(pos);
+            self.sift_down(pos);
+        }
+        entry
+    }
+
+    fn sift_up(&mut self, mut pos: usize) {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if !self.timers[pos].before(&self.timers[parent]) {
+                break;
+            }
+            self.heap_swap(pos, parent);
+            pos = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut pos: usize) {
+        let len = self.timers.len();
+        loop {
+            let left = 2 * pos + 1;
+            let right = left + 1;
+            let mut min = pos;
+            if left < len && self.timers[left].before(&self.timers[min]) {
+                min = left;
+            }
+            if right < len && self.timers[right].before(&self.timers[min]) {
+                min = right;
+            }
+            if min == pos {
+                break;
+            }
+            self.heap_swap(pos, min);
+            pos = min;
+        }
+    }
+
+    fn heap_swap(&mut self, a: usize, b: usize) {
+        let tmp = self.timers[a];
+        self.timers[a] = self.timers[b];
+        self.timers[b] = tmp;
+    }
+}
+
+impl<H> Drop for Reactor<H>
+    where H: MemPool,
+{
+    fn drop(&mut self) {
+        if self.unblock != Sigset::new() {
+            let _ = signal::unblock_signals(self.unblock);
+        }
+    }
+}
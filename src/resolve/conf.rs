@@ -0,0 +1,103 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Parsing of resolv.conf.
+
+use base::prelude::*;
+use vec::{Vec};
+use parse::{Parse};
+use {IpAddr, Words};
+
+/// The maximum number of name servers that are used.
+pub const MAX_NAMESERVERS: usize = 3;
+
+/// The maximum number of search domains that are used.
+pub const MAX_SEARCH: usize = 6;
+
+/// The parsed contents of resolv.conf.
+pub struct Conf {
+    pub nameservers: Vec<IpAddr>,
+    pub search: Vec<Vec<u8>>,
+    pub ndots: u8,
+    pub timeout: u8,
+    pub attempts: u8,
+}
+
+impl Conf {
+    /// Creates a configuration with the default values.
+    pub fn new() -> Conf {
+        Conf {
+            nameservers: Vec::new(),
+            search: Vec::new(),
+            ndots: 1,
+            timeout: 5,
+            attempts: 2,
+        }
+    }
+
+    fn set_search(&mut self, words: Words) -> Result {
+        self.search.truncate(0);
+        for domain in words {
+            if self.search.len() == MAX_SEARCH {
+                break;
+            }
+            let mut d = Vec::new();
+            try!(d.push_all(domain));
+            try!(self.search.push(d));
+        }
+        Ok(())
+    }
+
+    fn set_option(&mut self, opt: &[u8]) {
+        let colon = match opt.find(|&b| b == b':') {
+            Some(c) => c,
+            _ => return,
+        };
+        let (name, val) = (&opt[..colon], &opt[colon + 1..]);
+        let val: u8 = match val.parse() {
+            Ok(v) => v,
+            _ => return,
+        };
+        if name == &b"ndots"[..] {
+            self.ndots = if val > 15 { 15 } else { val };
+        } else if name == &b"timeout"[..] {
+            self.timeout = if val > 30 { 30 } else { val };
+        } else if name == &b"attempts"[..] {
+            self.attempts = if val > 5 { 5 } else { val };
+        }
+    }
+
+    /// Parses the contents of resolv.conf.
+    ///
+    /// [argument, data]
+    /// The contents of the file.
+    ///
+    /// = Remarks
+    ///
+    /// Unknown keywords and malformed lines are ignored.
+    pub fn parse(&mut self, data: &[u8]) -> Result {
+        for line in data.split(|&b| b == b'\n') {
+            let mut words = Words::new(line, b"#;");
+            let keyword = match words.next() {
+                Some(k) => k,
+                _ => continue,
+            };
+            if keyword == &b"nameserver"[..] {
+                if self.nameservers.len() == MAX_NAMESERVERS {
+                    continue;
+                }
+                if let Some(addr) = words.next().chain(IpAddr::parse) {
+                    try!(self.nameservers.push(addr));
+                }
+            } else if keyword == &b"domain"[..] || keyword == &b"search"[..] {
+                try!(self.set_search(words));
+            } else if keyword == &b"options"[..] {
+                for opt in words {
+                    self.set_option(opt);
+                }
+            }
+        }
+        Ok(())
+    }
+}
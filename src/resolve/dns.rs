@@ -0,0 +1,291 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! DNS queries over UDP and TCP.
+
+use base::prelude::*;
+use base::{error};
+use vec::{Vec};
+use time_base::{Time};
+use socket::{Socket, Ipv4Addr, Ipv6Addr};
+use socket::flags::{SOCK_NONE};
+use socket::msg::{MSG_NONE};
+use {IpAddr, Endpoint};
+
+/// The record type of IPv4 addresses.
+pub const TYPE_A: u16 = 1;
+
+/// The record type of IPv6 addresses.
+pub const TYPE_AAAA: u16 = 28;
+
+const CLASS_IN: u16 = 1;
+
+const FLAG_RESPONSE: u16 = 0x8000;
+const FLAG_TRUNCATED: u16 = 0x0200;
+const FLAG_RECURSION_DESIRED: u16 = 0x0100;
+
+const RCODE_NO_ERROR: u16 = 0;
+const RCODE_NAME_ERROR: u16 = 3;
+
+const HEADER_SIZE: usize = 12;
+
+/// The maximum size of a UDP reply without EDNS.
+const MAX_UDP_SIZE: usize = 512;
+
+/// The result of a successful query.
+#[derive(Copy, Eq)]
+pub enum Answer {
+    /// The server answered the query. The records have been appended to the output.
+    Found,
+    /// The name does not exist.
+    NoSuchName,
+    /// The reply was truncated and has to be repeated over TCP.
+    Truncated,
+}
+
+fn get_u16(msg: &[u8], pos: usize) -> Result<u16> {
+    if pos + 2 > msg.len() {
+        return Err(error::InvalidSequence);
+    }
+    Ok((msg[pos] as u16) << 8 | msg[pos + 1] as u16)
+}
+
+fn push_u16(buf: &mut Vec<u8>, val: u16) -> Result {
+    try!(buf.push((val >> 8) as u8));
+    buf.push(val as u8)
+}
+
+/// Builds a query for a single name and record type.
+///
+/// [argument, buf]
+/// The buffer into which the query is written.
+///
+/// [argument, id]
+/// The identifier of the query.
+///
+/// [argument, name]
+/// The fully qualified name without the trailing dot.
+///
+/// [argument, qtype]
+/// The record type.
+pub fn build_query(buf: &mut Vec<u8>, id: u16, name: &[u8], qtype: u16) -> Result {
+    if name.len() == 0 || name.len() > 253 {
+        return Err(error::InvalidArgument);
+    }
+    buf.truncate(0);
+    try!(push_u16(buf, id));
+    try!(push_u16(buf, FLAG_RECURSION_DESIRED));
+    try!(push_u16(buf, 1));
+    try!(push_u16(buf, 0));
+    try!(push_u16(buf, 0));
+    try!(push_u16(buf, 0));
+    for label in name.split(|&b| b == b'.') {
+        if label.len() == 0 || label.len() > 63 {
+            return Err(error::InvalidArgument);
+        }
+        try!(buf.push(label.len() as u8));
+        try!(buf.push_all(label));
+    }
+    try!(buf.push(0));
+    try!(push_u16(buf, qtype));
+    push_u16(buf, CLASS_IN)
+}
+
+/// Returns the position after a possibly compressed name.
+fn skip_name(msg: &[u8], mut pos: usize) -> Result<usize> {
+    loop {
+        if pos >= msg.len() {
+            return Err(error::InvalidSequence);
+        }
+        let len = msg[pos] as usize;
+        if len & 0xC0 == 0xC0 {
+            return Ok(pos + 2);
+        }
+        if len & 0xC0 != 0 {
+            return Err(error::InvalidSequence);
+        }
+        pos += 1 + len;
+        if len == 0 {
+            return Ok(pos);
+        }
+    }
+}
+
+/// Parses a reply.
+///
+/// [argument, msg]
+/// The reply.
+///
+/// [argument, id]
+/// The identifier of the query.
+///
+/// [argument, qtype]
+/// The record type of the query.
+///
+/// [argument, out]
+/// The vector to which the addresses are appended.
+pub fn parse_reply(msg: &[u8], id: u16, qtype: u16,
+                   out: &mut Vec<IpAddr>) -> Result<Answer> {
+    if msg.len() < HEADER_SIZE || try!(get_u16(msg, 0)) != id {
+        return Err(error::InvalidSequence);
+    }
+    let flags = try!(get_u16(msg, 2));
+    if flags & FLAG_RESPONSE == 0 {
+        return Err(error::InvalidSequence);
+    }
+    if flags & FLAG_TRUNCATED != 0 {
+        return Ok(Answer::Truncated);
+    }
+    match flags & 0xF {
+        RCODE_NO_ERROR => { },
+        RCODE_NAME_ERROR => return Ok(Answer::NoSuchName),
+        _ => return Err(error::ProtocolError),
+    }
+    let questions = try!(get_u16(msg, 4));
+    let answers = try!(get_u16(msg, 6));
+
+    let mut pos = HEADER_SIZE;
+    for _ in 0..questions {
+        pos = try!(skip_name(msg, pos)) + 4;
+    }
+    for _ in 0..answers {
+        pos = try!(skip_name(msg, pos));
+        let ty = try!(get_u16(msg, pos));
+        let class = try!(get_u16(msg, pos + 2));
+        let len = try!(get_u16(msg, pos + 8)) as usize;
+        pos += 10;
+        if pos + len > msg.len() {
+            return Err(error::InvalidSequence);
+        }
+        let data = &msg[pos..pos + len];
+        pos += len;
+        if ty != qtype || class != CLASS_IN {
+            continue;
+        }
+        let addr = match (ty, len) {
+            (TYPE_A, 4) => {
+                IpAddr::V4(Ipv4Addr::from_bytes([data[0], data[1], data[2], data[3]]))
+            },
+            (TYPE_AAAA, 16) => {
+                let mut groups = [0; 8];
+                for i in 0..8 {
+                    groups[i] = (data[2 * i] as u16) << 8 | data[2 * i + 1] as u16;
+                }
+                IpAddr::V6(Ipv6Addr::from_bytes(groups))
+            },
+            _ => continue,
+        };
+        if !out.iter().any(|&a| a == addr) {
+            try!(out.push(addr));
+        }
+    }
+    Ok(Answer::Found)
+}
+
+fn socket(server: &IpAddr, stream: bool) -> Result<Socket> {
+    match (*server, stream) {
+        (IpAddr::V4(_), false) => Socket::ipv4_datagram(SOCK_NONE),
+        (IpAddr::V4(_), true) => Socket::ipv4_stream(SOCK_NONE),
+        (IpAddr::V6(_), false) => Socket::ipv6_datagram(SOCK_NONE),
+        (IpAddr::V6(_), true) => Socket::ipv6_stream(SOCK_NONE),
+    }
+}
+
+/// Sends a query over UDP and receives the reply.
+///
+/// [argument, server]
+/// The name server.
+///
+/// [argument, query]
+/// The query.
+///
+/// [argument, timeout]
+/// The time to wait for the reply.
+///
+/// [argument, reply]
+/// The buffer in which the reply is stored.
+pub fn query_udp(server: &Endpoint, query: &[u8], timeout: Time,
+                 reply: &mut Vec<u8>) -> Result {
+    let sock = try!(socket(&server.ip, false));
+    let mut addr = [0; 32];
+    try!(sock.connect(try!(server.to_sock_addr(&mut addr))));
+    try!(sock.set_recv_timeout(Some(timeout)));
+    try!(sock.send(query, MSG_NONE));
+    let mut buf = [0; MAX_UDP_SIZE];
+    loop {
+        let n = match sock.recv(&mut buf, MSG_NONE) {
+            Ok(n) => n,
+            Err(error::WouldBlock) => return Err(error::TimedOut),
+            Err(e) => return Err(e),
+        };
+        // Replies that do not belong to our query are ignored. Since the socket is
+        // connected, only the server can send replies.
+        if n >= 2 && buf[..2] == query[..2] {
+            reply.truncate(0);
+            return reply.push_all(&buf[..n]);
+        }
+    }
+}
+
+fn recv_exact(sock: &Socket, mut buf: &mut [u8]) -> Result {
+    while buf.len() > 0 {
+        let n = match sock.recv(buf, MSG_NONE) {
+            Ok(0) => return Err(error::ConnectionReset),
+            Ok(n) => n,
+            Err(error::WouldBlock) => return Err(error::TimedOut),
+            Err(e) => return Err(e),
+        };
+        buf = &mut {buf}[n..];
+    }
+    Ok(())
+}
+
+/// Sends a query over TCP and receives the reply.
+///
+/// [argument, server]
+/// The name server.
+///
+/// [argument, query]
+/// The query.
+///
+/// [argument, timeout]
+/// The time to wait for each part of the exchange.
+///
+/// [argument, reply]
+/// The buffer in which the reply is stored.
+pub fn query_tcp(server: &Endpoint, query: &[u8], timeout: Time,
+                 reply: &mut Vec<u8>) -> Result {
+    let sock = try!(socket(&server.ip, true));
+    try!(sock.set_recv_timeout(Some(timeout)));
+    try!(sock.set_send_timeout(Some(timeout)));
+    let mut addr = [0; 32];
+    try!(sock.connect(try!(server.to_sock_addr(&mut addr))));
+    let len = [(query.len() >> 8) as u8, query.len() as u8];
+    let mut bufs = [&len[..], query];
+    let mut total = len.len() + query.len();
+    while total > 0 {
+        let n = try!(sock.gather_send(&bufs, MSG_NONE));
+        total -= n;
+        // Skip the data that has been sent.
+        let mut n = n;
+        for buf in &mut bufs {
+            let skip = if n > buf.len() { buf.len() } else { n };
+            *buf = &buf[skip..];
+            n -= skip;
+        }
+    }
+    let mut len = [0; 2];
+    try!(recv_exact(&sock, &mut len));
+    let mut len = (len[0] as usize) << 8 | len[1] as usize;
+    reply.truncate(0);
+    try!(reply.reserve(len));
+    let mut buf = [0; MAX_UDP_SIZE];
+    while len > 0 {
+        let n = if len > buf.len() { buf.len() } else { len };
+        try!(recv_exact(&sock, &mut buf[..n]));
+        try!(reply.push_all(&buf[..n]));
+        len -= n;
+    }
+    Ok(())
+}
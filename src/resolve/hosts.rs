@@ -0,0 +1,38 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Parsing of the hosts file.
+
+use base::prelude::*;
+use vec::{Vec};
+use {IpAddr, Words, eq_ignore_case};
+
+/// Appends the addresses of a host in a hosts file to a vector.
+///
+/// [argument, data]
+/// The contents of the hosts file.
+///
+/// [argument, name]
+/// The name of the host.
+///
+/// [argument, out]
+/// The vector to which the addresses are appended.
+pub fn lookup(data: &[u8], name: &[u8], out: &mut Vec<IpAddr>) -> Result {
+    for line in data.split(|&b| b == b'\n') {
+        let mut words = Words::new(line, b"#");
+        let addr = match words.next().chain(IpAddr::parse) {
+            Some(addr) => addr,
+            _ => continue,
+        };
+        for alias in words {
+            if eq_ignore_case(alias, name) {
+                if !out.iter().any(|&a| a == addr) {
+                    try!(out.push(addr));
+                }
+                break;
+            }
+        }
+    }
+    Ok(())
+}
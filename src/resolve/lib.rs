@@ -0,0 +1,504 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+#![crate_name = "lrs_resolve"]
+#![crate_type = "lib"]
+#![feature(custom_derive, associated_consts)]
+#![no_std]
+
+extern crate lrs_base      as base;
+extern crate lrs_cty       as cty;
+extern crate lrs_fmt       as fmt;
+extern crate lrs_vec       as vec;
+extern crate lrs_io        as io;
+extern crate lrs_parse     as parse;
+extern crate lrs_file      as file;
+extern crate lrs_socket    as socket;
+extern crate lrs_time_base as time_base;
+extern crate lrs_rand      as rand;
+
+use base::prelude::*;
+mod std { pub use vec::std::*; pub use cty; }
+
+use base::{error};
+use fmt::{Debug, Write};
+use vec::{Vec};
+use io::{BufWrite};
+use parse::{Parse};
+use file::{File};
+use time_base::{Time};
+use rand::{Rng, GetUrandom};
+use socket::{Ipv4Addr, Ipv4SockAddr, Ipv6SockAddr, SockAddr};
+use dns::{Answer, TYPE_A, TYPE_AAAA};
+
+pub use socket::{IpAddr};
+
+mod hosts;
+mod services;
+mod nsswitch;
+mod conf;
+mod dns;
+
+/// The port on which name servers listen.
+pub const DNS_PORT: u16 = 53;
+
+/// An address and a port.
+#[derive(Copy, Eq)]
+pub struct Endpoint {
+    /// The address.
+    pub ip: IpAddr,
+    /// The port.
+    pub port: u16,
+}
+
+impl Endpoint {
+    /// Creates a socket address from the endpoint.
+    ///
+    /// [argument, buf]
+    /// The buffer in which the socket address is stored.
+    ///
+    /// = Remarks
+    ///
+    /// The buffer must be at least `IPV6_SOCK_ADDR_SIZE` bytes long to hold both kinds
+    /// of addresses.
+    pub fn to_sock_addr<'a>(&self, buf: &'a mut [u8]) -> Result<&'a mut SockAddr> {
+        match self.ip {
+            IpAddr::V4(a) => Ipv4SockAddr::from_addr_port(buf, a, self.port)
+                                                    .map(|a| a.as_mut()),
+            IpAddr::V6(a) => Ipv6SockAddr::from_addr_port(buf, a, self.port)
+                                                    .map(|a| a.as_mut()),
+        }
+    }
+}
+
+impl Debug for Endpoint {
+    fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+        match self.ip {
+            IpAddr::V4(ref a) => write!(w, "{:?}:{}", a, self.port),
+            IpAddr::V6(ref a) => write!(w, "[{:?}]:{}", a, self.port),
+        }
+    }
+}
+
+/// A source of host names.
+#[derive(Copy, Eq)]
+pub enum Source {
+    /// The hosts file.
+    Files,
+    /// The configured name servers.
+    Dns,
+}
+
+/// An iterator over the whitespace separated words of a configuration line.
+struct Words<'a> {
+    line: &'a [u8],
+}
+
+impl<'a> Words<'a> {
+    /// Creates a new iterator that stops at the first comment character.
+    fn new(line: &'a [u8], comment: &[u8]) -> Words<'a> {
+        let end = line.find(|&b| comment.iter().any(|&c| c == b)).unwrap_or(line.len());
+        Words { line: &line[..end] }
+    }
+}
+
+fn is_space(b: u8) -> bool {
+    b == b' ' || b == b'\t' || b == b'\r'
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = &'a [u8];
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let start = match self.line.find(|&b| !is_space(b)) {
+            Some(s) => s,
+            _ => return None,
+        };
+        let line = &self.line[start..];
+        let end = line.find(|&b| is_space(b)).unwrap_or(line.len());
+        self.line = &line[end..];
+        Some(&line[..end])
+    }
+}
+
+fn eq_ignore_case(a: &[u8], b: &[u8]) -> bool {
+    let lower = |c: u8| if b'A' <= c && c <= b'Z' { c + (b'a' - b'A') } else { c };
+    if a.len() != b.len() {
+        return false;
+    }
+    for i in 0..a.len() {
+        if lower(a[i]) != lower(b[i]) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Reads a configuration file. A missing file is treated like an empty one.
+fn read_file(path: &[u8], data: &mut Vec<u8>) -> Result {
+    data.truncate(0);
+    let mut file = match File::open_read(path) {
+        Ok(f) => f,
+        Err(error::DoesNotExist) => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    data.read_to_eof(&mut file).map(|_| ())
+}
+
+/// A host and service name resolver.
+///
+/// = Remarks
+///
+/// The resolver does not use libc. It consults the hosts file and the name servers in
+/// the order configured in `/etc/nsswitch.conf` and queries the name servers over UDP.
+/// Truncated replies are repeated over TCP.
+///
+/// = Examples
+///
+/// ----
+/// let resolver = try!(Resolver::new());
+/// for ep in &try!(resolver.resolve_addr(b"db.internal:5432")) {
+///     println!("{:?}", ep);
+/// }
+/// ----
+///
+/// = See also
+///
+/// * link:man:hosts(5)
+/// * link:man:services(5)
+/// * link:man:nsswitch.conf(5)
+/// * link:man:resolv.conf(5)
+pub struct Resolver {
+    hosts_path: Vec<u8>,
+    services_path: Vec<u8>,
+    sources: Vec<Source>,
+    nameservers: Vec<Endpoint>,
+    search: Vec<Vec<u8>>,
+    ndots: u8,
+    timeout: Time,
+    attempts: u8,
+}
+
+impl Resolver {
+    /// Creates a resolver without name servers and with the default options.
+    pub fn empty() -> Result<Resolver> {
+        let mut res = Resolver {
+            hosts_path: Vec::new(),
+            services_path: Vec::new(),
+            sources: Vec::new(),
+            nameservers: Vec::new(),
+            search: Vec::new(),
+            ndots: 1,
+            timeout: Time::seconds(5),
+            attempts: 2,
+        };
+        try!(res.hosts_path.push_all(b"/etc/hosts"));
+        try!(res.services_path.push_all(b"/etc/services"));
+        try!(res.sources.push_all(&[Source::Files, Source::Dns]));
+        Ok(res)
+    }
+
+    /// Creates a resolver from the system configuration.
+    ///
+    /// = Remarks
+    ///
+    /// The configuration is read from `/etc/resolv.conf` and `/etc/nsswitch.conf`. If
+    /// no name server is configured, the name server at `127.0.0.1` is used.
+    pub fn new() -> Result<Resolver> {
+        let mut res = try!(Resolver::empty());
+        let mut data = Vec::new();
+
+        try!(read_file(b"/etc/resolv.conf", &mut data));
+        try!(res.load_resolv_conf(&data));
+        if res.nameservers.len() == 0 {
+            try!(res.add_nameserver(IpAddr::V4(Ipv4Addr(127, 0, 0, 1)), DNS_PORT));
+        }
+
+        try!(read_file(b"/etc/nsswitch.conf", &mut data));
+        let mut sources = Vec::new();
+        if try!(nsswitch::parse_hosts(&data, &mut sources)) {
+            res.sources = sources;
+        }
+
+        Ok(res)
+    }
+
+    /// Replaces the name servers, search domains, and options by those in a
+    /// resolv.conf file.
+    ///
+    /// [argument, data]
+    /// The contents of the file.
+    pub fn load_resolv_conf(&mut self, data: &[u8]) -> Result {
+        let mut conf = conf::Conf::new();
+        try!(conf.parse(data));
+        self.nameservers.truncate(0);
+        for &ip in &conf.nameservers {
+            try!(self.add_nameserver(ip, DNS_PORT));
+        }
+        self.search = conf.search;
+        self.ndots = conf.ndots;
+        self.timeout = Time::seconds(conf.timeout as i64);
+        self.attempts = conf.attempts;
+        Ok(())
+    }
+
+    /// Sets the path of the hosts file.
+    ///
+    /// [argument, path]
+    /// The path of the file.
+    pub fn set_hosts_file(&mut self, path: &[u8]) -> Result {
+        self.hosts_path.truncate(0);
+        self.hosts_path.push_all(path)
+    }
+
+    /// Sets the path of the services file.
+    ///
+    /// [argument, path]
+    /// The path of the file.
+    pub fn set_services_file(&mut self, path: &[u8]) -> Result {
+        self.services_path.truncate(0);
+        self.services_path.push_all(path)
+    }
+
+    /// Sets the sources that are consulted for host names.
+    ///
+    /// [argument, sources]
+    /// The sources in the order in which they are consulted.
+    pub fn set_sources(&mut self, sources: &[Source]) -> Result {
+        self.sources.truncate(0);
+        self.sources.push_all(sources)
+    }
+
+    /// Removes all name servers.
+    pub fn clear_nameservers(&mut self) {
+        self.nameservers.truncate(0);
+    }
+
+    /// Adds a name server.
+    ///
+    /// [argument, ip]
+    /// The address of the name server.
+    ///
+    /// [argument, port]
+    /// The port of the name server, usually `DNS_PORT`.
+    pub fn add_nameserver(&mut self, ip: IpAddr, port: u16) -> Result {
+        self.nameservers.push(Endpoint { ip: ip, port: port })
+    }
+
+    /// Adds a domain to the search list.
+    ///
+    /// [argument, domain]
+    /// The domain.
+    pub fn add_search_domain(&mut self, domain: &[u8]) -> Result {
+        let mut d = Vec::new();
+        try!(d.push_all(domain));
+        self.search.push(d)
+    }
+
+    /// Sets the number of dots a name must contain before it is tried as an absolute
+    /// name first.
+    ///
+    /// [argument, ndots]
+    /// The number of dots.
+    pub fn set_ndots(&mut self, ndots: u8) {
+        self.ndots = ndots;
+    }
+
+    /// Sets the time to wait for a reply from a name server.
+    ///
+    /// [argument, timeout]
+    /// The timeout.
+    pub fn set_timeout(&mut self, timeout: Time) {
+        self.timeout = timeout;
+    }
+
+    /// Sets how often the list of name servers is tried.
+    ///
+    /// [argument, attempts]
+    /// The number of attempts.
+    pub fn set_attempts(&mut self, attempts: u8) {
+        self.attempts = attempts;
+    }
+
+    /// Looks up the addresses of a host.
+    ///
+    /// [argument, name]
+    /// The name of the host or an address in textual form.
+    ///
+    /// [return_value]
+    /// Returns the addresses of the host. IPv4 addresses come first.
+    ///
+    /// = Remarks
+    ///
+    /// If the host cannot be found, the error `DoesNotExist` is returned. If the DNS
+    /// lookup fails and no later source knows the host, the error of the DNS lookup is
+    /// returned.
+    pub fn lookup_host(&self, name: &[u8]) -> Result<Vec<IpAddr>> {
+        let mut out = Vec::new();
+        if let Some(ip) = IpAddr::parse(name) {
+            try!(out.push(ip));
+            return Ok(out);
+        }
+        if name.len() == 0 {
+            return Err(error::InvalidArgument);
+        }
+        let mut data = Vec::new();
+        let mut err = error::DoesNotExist;
+        for &source in &self.sources {
+            match source {
+                Source::Files => {
+                    try!(read_file(&self.hosts_path, &mut data));
+                    try!(hosts::lookup(&data, name, &mut out));
+                },
+                // A failing name server is treated like a host that could not be found
+                // so that the remaining sources are still consulted.
+                Source::Dns => if let Err(e) = self.lookup_dns(name, &mut out) {
+                    out.truncate(0);
+                    err = e;
+                },
+            }
+            if out.len() > 0 {
+                return Ok(out);
+            }
+        }
+        Err(err)
+    }
+
+    fn lookup_dns(&self, name: &[u8], out: &mut Vec<IpAddr>) -> Result {
+        let mut fqdn = Vec::new();
+        if name[name.len() - 1] == b'.' {
+            try!(fqdn.push_all(&name[..name.len() - 1]));
+            return self.query_name(&fqdn, out).map(|_| ());
+        }
+
+        // The name itself is tried first if it contains enough dots and last otherwise.
+        let mut dots = 0;
+        for &b in name {
+            if b == b'.' {
+                dots += 1;
+            }
+        }
+        let absolute_first = dots >= self.ndots as usize;
+        if absolute_first {
+            try!(fqdn.push_all(name));
+            if try!(self.query_name(&fqdn, out)) {
+                return Ok(());
+            }
+        }
+        for domain in &self.search {
+            fqdn.truncate(0);
+            try!(fqdn.push_all(name));
+            try!(fqdn.push(b'.'));
+            try!(fqdn.push_all(domain));
+            if try!(self.query_name(&fqdn, out)) {
+                return Ok(());
+            }
+        }
+        if !absolute_first {
+            fqdn.truncate(0);
+            try!(fqdn.push_all(name));
+            try!(self.query_name(&fqdn, out));
+        }
+        Ok(())
+    }
+
+    /// Queries the A and AAAA records of a name. Returns whether the name exists.
+    fn query_name(&self, name: &[u8], out: &mut Vec<IpAddr>) -> Result<bool> {
+        let v4 = try!(self.query(name, TYPE_A, out));
+        let v6 = try!(self.query(name, TYPE_AAAA, out));
+        Ok(v4 == Answer::Found || v6 == Answer::Found)
+    }
+
+    fn query(&self, name: &[u8], qtype: u16, out: &mut Vec<IpAddr>) -> Result<Answer> {
+        let id: u16 = try!(GetUrandom.gen());
+        let mut query = Vec::new();
+        try!(dns::build_query(&mut query, id, name, qtype));
+        let mut reply = Vec::new();
+        let mut err = error::TimedOut;
+        for _ in 0..self.attempts {
+            for server in &self.nameservers {
+                if let Err(e) = dns::query_udp(server, &query, self.timeout, &mut reply) {
+                    err = e;
+                    continue;
+                }
+                let mut answer = dns::parse_reply(&reply, id, qtype, out);
+                if let Ok(Answer::Truncated) = answer {
+                    answer = match dns::query_tcp(server, &query, self.timeout,
+                                                  &mut reply) {
+                        Ok(()) => dns::parse_reply(&reply, id, qtype, out),
+                        Err(e) => Err(e),
+                    };
+                }
+                match answer {
+                    Ok(Answer::Truncated) => err = error::InvalidSequence,
+                    Ok(a) => return Ok(a),
+                    Err(e) => err = e,
+                }
+            }
+        }
+        Err(err)
+    }
+
+    /// Looks up the port of a service.
+    ///
+    /// [argument, name]
+    /// The name of the service or a port number.
+    ///
+    /// [argument, proto]
+    /// The protocol, e.g., `tcp` or `udp`.
+    ///
+    /// = Remarks
+    ///
+    /// If the service cannot be found, the error `DoesNotExist` is returned.
+    pub fn lookup_service(&self, name: &[u8], proto: &[u8]) -> Result<u16> {
+        if let Ok(port) = name.parse() {
+            return Ok(port);
+        }
+        let mut data = Vec::new();
+        try!(read_file(&self.services_path, &mut data));
+        match services::lookup(&data, name, proto) {
+            Some(port) => Ok(port),
+            _ => Err(error::DoesNotExist),
+        }
+    }
+
+    /// Resolves a host and a service.
+    ///
+    /// [argument, host]
+    /// The name of the host or an address in textual form.
+    ///
+    /// [argument, service]
+    /// The name of a TCP service or a port number.
+    ///
+    /// [return_value]
+    /// Returns the endpoints of the service.
+    pub fn resolve(&self, host: &[u8], service: &[u8]) -> Result<Vec<Endpoint>> {
+        let port = try!(self.lookup_service(service, b"tcp"));
+        let addrs = try!(self.lookup_host(host));
+        let mut eps = try!(Vec::with_capacity(addrs.len()));
+        for &ip in &addrs {
+            try!(eps.push(Endpoint { ip: ip, port: port }));
+        }
+        Ok(eps)
+    }
+
+    /// Resolves an address of the form `host:service`.
+    ///
+    /// [argument, addr]
+    /// The address. IPv6 addresses have to be enclosed in brackets, e.g.,
+    /// `[::1]:80`.
+    ///
+    /// [return_value]
+    /// Returns the endpoints of the service.
+    pub fn resolve_addr(&self, addr: &[u8]) -> Result<Vec<Endpoint>> {
+        let colon = match addr.find_reverse(|&b| b == b':') {
+            Some(c) => c,
+            _ => return Err(error::InvalidArgument),
+        };
+        let (mut host, service) = (&addr[..colon], &addr[colon + 1..]);
+        if host.len() >= 2 && host[0] == b'[' && host[host.len() - 1] == b']' {
+            host = &host[1..host.len() - 1];
+        }
+        self.resolve(host, service)
+    }
+}
@@ -0,0 +1,63 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Parsing of the name service switch configuration.
+
+use base::prelude::*;
+use vec::{Vec};
+use {Source, Words};
+
+/// Parses the sources of the `hosts` database.
+///
+/// [argument, data]
+/// The contents of the nsswitch configuration file.
+///
+/// [argument, out]
+/// The vector to which the sources are appended.
+///
+/// [return_value]
+/// Returns whether the configuration contained a `hosts` entry.
+///
+/// = Remarks
+///
+/// Unknown sources and actions in brackets are ignored.
+pub fn parse_hosts(data: &[u8], out: &mut Vec<Source>) -> Result<bool> {
+    for line in data.split(|&b| b == b'\n') {
+        let mut words = Words::new(line, b"#");
+        let db = match words.next() {
+            Some(db) => db,
+            _ => continue,
+        };
+        if db == &b"hosts"[..] {
+            match words.next() {
+                Some(colon) if colon == &b":"[..] => { },
+                _ => continue,
+            }
+        } else if db != &b"hosts:"[..] {
+            continue;
+        }
+        let mut in_action = false;
+        for word in words {
+            if word[0] == b'[' {
+                in_action = true;
+            }
+            if in_action {
+                in_action = word[word.len() - 1] != b']';
+                continue;
+            }
+            let source = if word == &b"files"[..] {
+                Source::Files
+            } else if word == &b"dns"[..] {
+                Source::Dns
+            } else {
+                continue;
+            };
+            if !out.iter().any(|&s| s == source) {
+                try!(out.push(source));
+            }
+        }
+        return Ok(true);
+    }
+    Ok(false)
+}
@@ -0,0 +1,47 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Parsing of the services file.
+
+use base::prelude::*;
+use parse::{Parse};
+use {Words};
+
+/// Looks up the port of a service in a services file.
+///
+/// [argument, data]
+/// The contents of the services file.
+///
+/// [argument, name]
+/// The name of the service.
+///
+/// [argument, proto]
+/// The protocol of the service, e.g., `tcp`.
+pub fn lookup(data: &[u8], name: &[u8], proto: &[u8]) -> Option<u16> {
+    for line in data.split(|&b| b == b'\n') {
+        let mut words = Words::new(line, b"#");
+        let service = match words.next() {
+            Some(s) => s,
+            _ => continue,
+        };
+        let port_proto = match words.next() {
+            Some(p) => p,
+            _ => continue,
+        };
+        let slash = match port_proto.find(|&b| b == b'/') {
+            Some(s) => s,
+            _ => continue,
+        };
+        if &port_proto[slash + 1..] != proto {
+            continue;
+        }
+        if service != name && !words.any(|alias| alias == name) {
+            continue;
+        }
+        if let Ok(port) = port_proto[..slash].parse() {
+            return Some(port);
+        }
+    }
+    None
+}
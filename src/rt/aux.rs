@@ -2,8 +2,14 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use cty::{c_int, ElfPhdr, AUX_CNT, AT_PHDR, AT_EXECFD, AT_PHNUM, AT_PAGESZ, PAGE_SIZE};
+use cty::{
+    c_int, ElfPhdr, AUX_CNT, AT_PHDR, AT_EXECFD, AT_PHNUM, AT_PAGESZ, AT_SYSINFO_EHDR,
+    AT_HWCAP, AT_HWCAP2, AT_PLATFORM, AT_RANDOM, AT_SECURE, AT_MINSIGSTKSZ, AT_CLKTCK,
+    PAGE_SIZE,
+};
+use cty_base::types::{c_char};
 use core::{slice};
+use str_one::{CStr};
 
 static mut AUXV: [usize; AUX_CNT] = [0; AUX_CNT];
 
@@ -63,3 +69,107 @@ pub fn page_size() -> usize {
         n => n,
     }
 }
+
+/// The address of the vDSO.
+///
+/// [return_value]
+/// Returns the address of the ELF header of the vDSO if the kernel mapped one.
+pub fn vdso_address() -> Option<usize> {
+    match unsafe { AUXV[AT_SYSINFO_EHDR] } {
+        0 => None,
+        n => Some(n),
+    }
+}
+
+/// The hardware capabilities of the CPU.
+///
+/// [return_value]
+/// Returns the `AT_HWCAP` bit mask.
+///
+/// = Remarks
+///
+/// The meaning of the bits is architecture dependent. On x86 this only contains the
+/// EDX register of the first cpuid leaf. `lrs::cpu` provides a portable interface.
+pub fn hwcap() -> usize {
+    unsafe { AUXV[AT_HWCAP] }
+}
+
+/// Additional hardware capabilities of the CPU.
+///
+/// [return_value]
+/// Returns the `AT_HWCAP2` bit mask.
+pub fn hwcap2() -> usize {
+    unsafe { AUXV[AT_HWCAP2] }
+}
+
+/// The platform the process runs on.
+///
+/// [return_value]
+/// Returns a string identifying the platform, e.g., `x86_64` or `v7l`.
+pub fn platform() -> Option<&'static CStr> {
+    match unsafe { AUXV[AT_PLATFORM] } {
+        0 => None,
+        n => Some(unsafe { CStr::from_ptr(n as *const c_char) }),
+    }
+}
+
+/// Random bytes provided by the kernel.
+///
+/// [return_value]
+/// Returns 16 random bytes.
+///
+/// = Remarks
+///
+/// The libc might have used these bytes for stack protectors or pointer mangling.
+/// They should not be used to seed anything that has to stay secret from the libc.
+pub fn random_bytes() -> Option<&'static [u8; 16]> {
+    match unsafe { AUXV[AT_RANDOM] } {
+        0 => None,
+        n => Some(unsafe { &*(n as *const [u8; 16]) }),
+    }
+}
+
+/// Whether the process runs in secure mode.
+///
+/// [return_value]
+/// Returns whether the process gained privileges through its execution, e.g., via a
+/// set-user-ID binary or file capabilities.
+///
+/// = Remarks
+///
+/// Environment variables should not be trusted in secure mode.
+pub fn secure() -> bool {
+    unsafe { AUXV[AT_SECURE] != 0 }
+}
+
+/// The minimum size of a signal stack.
+///
+/// [return_value]
+/// Returns the size required by the kernel to deliver a signal if the kernel provides
+/// it.
+///
+/// = Remarks
+///
+/// This value depends on the register state of the CPU and can be larger than the
+/// `MINSIGSTKSZ` constant. It does not include space for the signal handler.
+pub fn min_signal_stack_size() -> Option<usize> {
+    match unsafe { AUXV[AT_MINSIGSTKSZ] } {
+        0 => None,
+        n => Some(n),
+    }
+}
+
+/// The frequency of the clock used by `times`.
+///
+/// [return_value]
+/// Returns the number of clock ticks per second.
+///
+/// = See also
+///
+/// * link:man:times(2)
+pub fn clock_ticks() -> usize {
+    match unsafe { AUXV[AT_CLKTCK] } {
+        0 => 100,
+        n => n,
+    }
+}
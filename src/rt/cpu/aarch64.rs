@@ -0,0 +1,24 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use aux;
+use cty::{
+    HWCAP_ASIMD, HWCAP_AES, HWCAP_PMULL, HWCAP_SHA1, HWCAP_SHA2, HWCAP_CRC32,
+    HWCAP_ATOMICS, HWCAP_SHA512, HWCAP_SHA3, HWCAP_SVE,
+};
+use super::{
+    Features, NEON, AES, CLMUL, SHA1, SHA2, CRC32, ATOMICS, SHA512, SHA3, SVE,
+};
+
+fn bit(reg: usize, mask: usize, f: Features) -> Features {
+    if reg & mask != 0 { f } else { Features(0) }
+}
+
+pub fn detect() -> Features {
+    let h = aux::hwcap();
+    bit(h, HWCAP_ASIMD, NEON) | bit(h, HWCAP_AES, AES) | bit(h, HWCAP_PMULL, CLMUL) |
+        bit(h, HWCAP_SHA1, SHA1) | bit(h, HWCAP_SHA2, SHA2) |
+        bit(h, HWCAP_CRC32, CRC32) | bit(h, HWCAP_ATOMICS, ATOMICS) |
+        bit(h, HWCAP_SHA512, SHA512) | bit(h, HWCAP_SHA3, SHA3) | bit(h, HWCAP_SVE, SVE)
+}
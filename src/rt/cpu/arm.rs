@@ -0,0 +1,22 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use aux;
+use cty::{
+    HWCAP_NEON, HWCAP_VFPv4, HWCAP2_AES, HWCAP2_PMULL, HWCAP2_SHA1, HWCAP2_SHA2,
+    HWCAP2_CRC32,
+};
+use super::{Features, NEON, VFPV4, AES, CLMUL, SHA1, SHA2, CRC32};
+
+fn bit(reg: usize, mask: usize, f: Features) -> Features {
+    if reg & mask != 0 { f } else { Features(0) }
+}
+
+pub fn detect() -> Features {
+    let h = aux::hwcap();
+    let h2 = aux::hwcap2();
+    bit(h, HWCAP_NEON, NEON) | bit(h, HWCAP_VFPv4, VFPV4) | bit(h2, HWCAP2_AES, AES) |
+        bit(h2, HWCAP2_PMULL, CLMUL) | bit(h2, HWCAP2_SHA1, SHA1) |
+        bit(h2, HWCAP2_SHA2, SHA2) | bit(h2, HWCAP2_CRC32, CRC32)
+}
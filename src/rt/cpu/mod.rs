@@ -0,0 +1,210 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! CPU feature detection.
+//!
+//! = Remarks
+//!
+//! On x86 and x86_64 the features are read with the `cpuid` instruction. Features that
+//! use the AVX registers are only reported if the kernel saves these registers. On ARM
+//! and AArch64 the features are read from the `AT_HWCAP` and `AT_HWCAP2` entries of
+//! the auxiliary vector. On other architectures no features are reported.
+
+use base::prelude::*;
+use core::ops::{BitOr, BitAnd, Not};
+use fmt::{Debug, Write};
+
+#[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
+#[path = "x86.rs"] mod arch;
+#[cfg(target_arch = "arm")] #[path = "arm.rs"] mod arch;
+#[cfg(target_arch = "aarch64")] #[path = "aarch64.rs"] mod arch;
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "x86", target_arch = "arm",
+              target_arch = "aarch64")))]
+mod arch {
+    use super::{Features};
+
+    pub fn detect() -> Features {
+        Features(0)
+    }
+}
+
+/// A set of CPU features.
+#[derive(Pod, Eq)]
+pub struct Features(pub u64);
+
+impl BitAnd for Features {
+    type Output = Features;
+    fn bitand(self, rhs: Features) -> Features { Features(self.0 & rhs.0) }
+}
+
+impl BitOr for Features {
+    type Output = Features;
+    fn bitor(self, rhs: Features) -> Features { Features(self.0 | rhs.0) }
+}
+
+impl Not for Features {
+    type Output = Features;
+    fn not(self) -> Features { Features(!self.0) }
+}
+
+impl Features {
+    /// Returns whether all features in a set are contained in this set.
+    ///
+    /// [argument, other]
+    /// The features to check.
+    pub fn contains(self, other: Features) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// Dummy feature set without any features.
+pub const NONE: Features = Features(0);
+
+macro_rules! create {
+    ($($(#[$meta:meta])* feature $name:ident = $val:expr;)*) => {
+        $($(#[$meta])* pub const $name: Features = Features(1 << $val);)*
+
+        /// = Remarks
+        ///
+        /// This prints the features as a comma-separated list or `NONE` if the set is
+        /// empty.
+        impl Debug for Features {
+            fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+                let raw = self.0;
+                if raw == 0 {
+                    return w.write_all(b"NONE").ignore_ok();
+                }
+                const KNOWN: u64 = 0 $(| 1 << $val)*;
+                if raw & !KNOWN != 0 {
+                    return write!(w, "0x{:x}", raw);
+                }
+                let mut first = true;
+                $(
+                    if raw & (1 << $val) != 0 {
+                        if !first { try!(w.write(b",")); }
+                        first = false;
+                        try!(w.write_all(stringify!($name).as_bytes()));
+                    }
+                )*
+                let _ = first;
+                Ok(())
+            }
+        }
+    }
+}
+
+create! {
+    #[doc = "The SSE2 instructions (x86)."]
+    feature SSE2 = 0;
+
+    #[doc = "The SSE3 instructions (x86)."]
+    feature SSE3 = 1;
+
+    #[doc = "The SSSE3 instructions (x86)."]
+    feature SSSE3 = 2;
+
+    #[doc = "The SSE4.1 instructions (x86)."]
+    feature SSE4_1 = 3;
+
+    #[doc = "The SSE4.2 instructions (x86)."]
+    feature SSE4_2 = 4;
+
+    #[doc = "The `popcnt` instruction (x86)."]
+    feature POPCNT = 5;
+
+    #[doc = "The AVX instructions (x86)."]
+    feature AVX = 6;
+
+    #[doc = "The AVX2 instructions (x86)."]
+    feature AVX2 = 7;
+
+    #[doc = "The FMA3 instructions (x86)."]
+    feature FMA = 8;
+
+    #[doc = "The BMI1 instructions (x86)."]
+    feature BMI1 = 9;
+
+    #[doc = "The BMI2 instructions (x86)."]
+    feature BMI2 = 10;
+
+    #[doc = "The AVX-512 foundation instructions (x86)."]
+    feature AVX512F = 11;
+
+    #[doc = "The `rdrand` instruction (x86)."]
+    feature RDRAND = 12;
+
+    #[doc = "The `rdseed` instruction (x86)."]
+    feature RDSEED = 13;
+
+    #[doc = "AES instructions (AES-NI on x86, the crypto extension on ARM)."]
+    feature AES = 14;
+
+    #[doc = "Carry-less multiplication (`pclmulqdq` on x86, `pmull` on ARM)."]
+    feature CLMUL = 15;
+
+    #[doc = "SHA-1 instructions (the SHA extension on x86, the crypto extension on \
+             ARM)."]
+    feature SHA1 = 16;
+
+    #[doc = "SHA-256 instructions (the SHA extension on x86, the crypto extension \
+             on ARM)."]
+    feature SHA2 = 17;
+
+    #[doc = "CRC-32 instructions.\n"]
+    #[doc = "= Remarks\n"]
+    #[doc = "On x86 this is part of SSE4.2 and only supports the Castagnoli \
+             polynomial. On ARM both the Castagnoli and the IEEE polynomial are \
+             supported."]
+    feature CRC32 = 18;
+
+    #[doc = "The NEON (Advanced SIMD) instructions (ARM)."]
+    feature NEON = 19;
+
+    #[doc = "The VFPv4 instructions (ARM)."]
+    feature VFPV4 = 20;
+
+    #[doc = "The large system extension atomics (AArch64)."]
+    feature ATOMICS = 21;
+
+    #[doc = "SHA-512 instructions (AArch64)."]
+    feature SHA512 = 22;
+
+    #[doc = "SHA-3 instructions (AArch64)."]
+    feature SHA3 = 23;
+
+    #[doc = "The scalable vector extension (AArch64)."]
+    feature SVE = 24;
+}
+
+/// Set once the features have been detected.
+const DETECTED: u64 = 1 << 63;
+
+static mut FEATURES: u64 = 0;
+
+/// Returns the features of the CPU.
+///
+/// = Remarks
+///
+/// The CPU is inspected on the first call. Concurrent first calls store the same
+/// value. The runtime makes the first call while it is being initialized.
+pub fn features() -> Features {
+    unsafe {
+        if FEATURES & DETECTED == 0 {
+            FEATURES = arch::detect().0 | DETECTED;
+        }
+        Features(FEATURES & !DETECTED)
+    }
+}
+
+/// Returns whether the CPU supports a set of features.
+///
+/// [argument, f]
+/// The features to check.
+///
+/// [return_value]
+/// Returns whether all of the features are supported.
+pub fn has(f: Features) -> bool {
+    features().contains(f)
+}
@@ -0,0 +1,86 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::{
+    Features, SSE2, SSE3, SSSE3, SSE4_1, SSE4_2, POPCNT, AVX, AVX2, FMA, BMI1, BMI2,
+    AVX512F, RDRAND, RDSEED, AES, CLMUL, SHA1, SHA2, CRC32,
+};
+
+#[cfg(target_arch = "x86_64")]
+unsafe fn cpuid(leaf: u32, sub: u32) -> (u32, u32, u32, u32) {
+    let a: u32;
+    let b: u32;
+    let c: u32;
+    let d: u32;
+    // rbx might be reserved by LLVM.
+    asm!("xchgq %rbx, ${1:q}
+          cpuid
+          xchgq %rbx, ${1:q}"
+         : "={eax}"(a), "=&r"(b), "={ecx}"(c), "={edx}"(d)
+         : "{eax}"(leaf), "{ecx}"(sub));
+    (a, b, c, d)
+}
+
+#[cfg(target_arch = "x86")]
+unsafe fn cpuid(leaf: u32, sub: u32) -> (u32, u32, u32, u32) {
+    let a: u32;
+    let b: u32;
+    let c: u32;
+    let d: u32;
+    // ebx is the PIC register.
+    asm!("xchgl %ebx, $1
+          cpuid
+          xchgl %ebx, $1"
+         : "={eax}"(a), "=&r"(b), "={ecx}"(c), "={edx}"(d)
+         : "{eax}"(leaf), "{ecx}"(sub));
+    (a, b, c, d)
+}
+
+unsafe fn xgetbv(reg: u32) -> u32 {
+    let lo: u32;
+    let _hi: u32;
+    asm!("xgetbv" : "={eax}"(lo), "={edx}"(_hi) : "{ecx}"(reg));
+    lo
+}
+
+fn bit(reg: u32, n: u32, f: Features) -> Features {
+    if reg & (1 << n) != 0 { f } else { Features(0) }
+}
+
+pub fn detect() -> Features {
+    unsafe {
+        let (max, _, _, _) = cpuid(0, 0);
+        if max < 1 {
+            return Features(0);
+        }
+
+        let (_, _, c, d) = cpuid(1, 0);
+        let mut f = bit(d, 26, SSE2) | bit(c, 0, SSE3) | bit(c, 1, CLMUL) |
+                    bit(c, 9, SSSE3) | bit(c, 19, SSE4_1) | bit(c, 20, SSE4_2) |
+                    bit(c, 20, CRC32) | bit(c, 23, POPCNT) | bit(c, 25, AES) |
+                    bit(c, 30, RDRAND);
+
+        // OSXSAVE and the kernel saves the SSE and AVX registers.
+        let xcr0 = if c & (1 << 27) != 0 { xgetbv(0) } else { 0 };
+        let avx = xcr0 & 0b110 == 0b110;
+        let avx512 = xcr0 & 0b1110_0110 == 0b1110_0110;
+        if avx {
+            f = f | bit(c, 28, AVX) | bit(c, 12, FMA);
+        }
+
+        if max >= 7 {
+            let (_, b, _, _) = cpuid(7, 0);
+            f = f | bit(b, 3, BMI1) | bit(b, 8, BMI2) | bit(b, 18, RDSEED) |
+                    bit(b, 29, SHA1) | bit(b, 29, SHA2);
+            if avx {
+                f = f | bit(b, 5, AVX2);
+            }
+            if avx512 {
+                f = f | bit(b, 16, AVX512F);
+            }
+        }
+
+        f
+    }
+}
@@ -13,10 +13,12 @@ extern crate lrs_cty_base as cty_base;
 extern crate lrs_cty as cty;
 extern crate lrs_lock as lock;
 extern crate lrs_str_one as str_one;
+extern crate lrs_fmt as fmt;
 #[cfg(not(no_libc))] extern crate lrs_libc as libc;
 extern crate lrs_syscall as syscall;
 extern crate lrs_r_syscall as r_syscall;
 extern crate lrs_atomic as atomic;
+extern crate lrs_arch_fns as arch_fns;
 
 use base::prelude::*;
 use core::{mem};
@@ -26,6 +28,7 @@ use lock::{SingleThreadMutex};
 
 mod std { pub use base::std::*; pub use cty; }
 pub mod aux;
+pub mod cpu;
 #[cfg(no_libc)] #[path = "no_libc/mod.rs"] pub mod imp;
 #[cfg(not(no_libc))] #[path = "libc/mod.rs"]  pub mod imp;
 
@@ -80,7 +83,10 @@ unsafe fn init_rt(argc: isize, argv: *const *const u8) {
     ARGV = argv;
     ENVP = argv.offset(argc + 1);
     aux::init(ENVP as *const _);
+    // The features are detected here because they depend on the auxiliary vector.
+    arch_fns::select_kernels(cpu::has(cpu::AVX2));
     imp::tls::init();
+    imp::vdso::init();
 }
 
 /// Returns the number of command line arguments.
@@ -117,7 +123,11 @@ pub fn raw_env() -> *const *const c_char {
     unsafe { ENVP as *const _ }
 }
 
-/// Returns an iterator over the environment variables.
+/// Returns an iterator over the environment variables passed to the process.
+///
+/// = Remarks
+///
+/// Modifications made with `lrs::env::set_var` are not visible through this iterator.
 pub fn env() -> EnvIter {
     ArgsIter { argv: raw_env() as *const _ }
 }
@@ -18,3 +18,9 @@ pub mod tls {
         &AT_EXIT
     }
 }
+
+pub mod vdso {
+    pub unsafe fn init() {
+        // Only used without libc
+    }
+}
@@ -4,3 +4,4 @@
 
 pub mod tls;
 pub mod crt;
+pub mod vdso;
@@ -0,0 +1,258 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Symbol lookup in the vDSO.
+//!
+//! = Remarks
+//!
+//! The vDSO is a small shared object that the kernel maps into every process. It
+//! provides implementations of some system calls that do not have to enter the kernel.
+//! We look up these functions in the dynamic symbol table of the vDSO and store their
+//! addresses in the table in `lrs_syscall::vdso`.
+
+use base::prelude::*;
+use core::{mem, slice};
+use str_one::{CStr};
+use cty_base::types::{c_char};
+use cty::{
+    ElfEhdr, ElfPhdr, ElfDyn, ElfSym, PT_LOAD, PT_DYNAMIC, DT_NULL, DT_HASH, DT_STRTAB,
+    DT_SYMTAB, DT_VERSYM, DT_VERDEF, STT_FUNC, STB_GLOBAL, STB_WEAK, SHN_UNDEF,
+};
+use syscall::vdso::{self, Vdso};
+use aux;
+
+const DT_GNU_HASH: u32 = 0x6ffffef5;
+const VER_FLG_BASE: u16 = 0x1;
+
+#[repr(C)]
+#[allow(dead_code)]
+struct Verdef {
+    vd_version: u16,
+    vd_flags: u16,
+    vd_ndx: u16,
+    vd_cnt: u16,
+    vd_hash: u32,
+    vd_aux: u32,
+    vd_next: u32,
+}
+
+#[repr(C)]
+#[allow(dead_code)]
+struct Verdaux {
+    vda_name: u32,
+    vda_next: u32,
+}
+
+#[cfg(target_arch = "aarch64")]
+mod names {
+    pub const VERSION: &'static [u8] = b"LINUX_2.6.39";
+    pub const CLOCK_GETTIME: &'static [u8] = b"__kernel_clock_gettime";
+    pub const CLOCK_GETRES: &'static [u8] = b"__kernel_clock_getres";
+    pub const GETTIMEOFDAY: &'static [u8] = b"__kernel_gettimeofday";
+    pub const TIME: &'static [u8] = b"__kernel_time";
+    pub const GETCPU: &'static [u8] = b"__kernel_getcpu";
+}
+
+#[cfg(target_arch = "x86_64")]
+mod names {
+    pub const VERSION: &'static [u8] = b"LINUX_2.6";
+    pub const CLOCK_GETTIME: &'static [u8] = b"__vdso_clock_gettime";
+    pub const CLOCK_GETRES: &'static [u8] = b"__vdso_clock_getres";
+    pub const GETTIMEOFDAY: &'static [u8] = b"__vdso_gettimeofday";
+    pub const TIME: &'static [u8] = b"__vdso_time";
+    pub const GETCPU: &'static [u8] = b"__vdso_getcpu";
+}
+
+// The clock functions have to use the 64 bit timespec. Older kernels don't provide
+// these and the system calls are used instead. `gettimeofday` and `time` only return
+// 32 bit seconds and are therefore not used.
+#[cfg(any(target_arch = "x86", target_arch = "arm"))]
+mod names {
+    pub const VERSION: &'static [u8] = b"LINUX_2.6";
+    pub const CLOCK_GETTIME: &'static [u8] = b"__vdso_clock_gettime64";
+    pub const CLOCK_GETRES: &'static [u8] = b"__vdso_clock_getres_time64";
+    pub const GETCPU: &'static [u8] = b"__vdso_getcpu";
+}
+
+/// The parsed dynamic section of the vDSO.
+struct Image {
+    /// The difference between the runtime and link-time addresses.
+    load_offset: usize,
+    strtab: *const c_char,
+    symtab: &'static [ElfSym],
+    versym: *const u16,
+    verdef: *const Verdef,
+}
+
+impl Image {
+    unsafe fn new(base: usize) -> Option<Image> {
+        let ehdr = &*(base as *const ElfEhdr);
+        if &ehdr.e_ident[..4] != &b"\x7fELF"[..] {
+            return None;
+        }
+        let phdrs = (base + ehdr.e_phoff as usize) as *const ElfPhdr;
+        let phdrs = slice::from_ptr(phdrs, ehdr.e_phnum as usize);
+
+        let mut load_offset = None;
+        let mut dynamic = None;
+        for phdr in phdrs {
+            if phdr.p_type == PT_LOAD && load_offset.is_none() {
+                load_offset = Some(base + phdr.p_offset as usize - phdr.p_vaddr as usize);
+            } else if phdr.p_type == PT_DYNAMIC {
+                dynamic = Some((base + phdr.p_offset as usize) as *const ElfDyn);
+            }
+        }
+        let (load_offset, mut dynamic) = match (load_offset, dynamic) {
+            (Some(l), Some(d)) => (l, d),
+            _ => return None,
+        };
+
+        let mut strtab = 0;
+        let mut symtab = 0;
+        let mut hash = 0;
+        let mut gnu_hash = 0;
+        let mut versym = 0;
+        let mut verdef = 0;
+        while (*dynamic).d_tag as u32 != DT_NULL {
+            let val = (*dynamic).d_val as usize + load_offset;
+            match (*dynamic).d_tag as u32 {
+                DT_STRTAB => strtab = val,
+                DT_SYMTAB => symtab = val,
+                DT_HASH => hash = val,
+                DT_GNU_HASH => gnu_hash = val,
+                DT_VERSYM => versym = val,
+                DT_VERDEF => verdef = val,
+                _ => { },
+            }
+            dynamic = dynamic.add(1);
+        }
+        if strtab == 0 || symtab == 0 {
+            return None;
+        }
+
+        let num_syms = if hash != 0 {
+            // nchain is the number of symbols.
+            *(hash as *const u32).add(1) as usize
+        } else if gnu_hash != 0 {
+            gnu_num_syms(gnu_hash as *const u32)
+        } else {
+            return None;
+        };
+
+        // The version information is only useful if both tables are present.
+        if versym == 0 || verdef == 0 {
+            versym = 0;
+            verdef = 0;
+        }
+
+        Some(Image {
+            load_offset: load_offset,
+            strtab: strtab as *const c_char,
+            symtab: slice::from_ptr(symtab as *const ElfSym, num_syms),
+            versym: versym as *const u16,
+            verdef: verdef as *const Verdef,
+        })
+    }
+
+    unsafe fn string(&self, offset: u32) -> &'static [u8] {
+        CStr::from_ptr(self.strtab.add(offset as usize)).as_ref()
+    }
+
+    /// Returns whether the version of the symbol at index `idx` is `name`.
+    unsafe fn version_matches(&self, idx: usize, name: &[u8]) -> bool {
+        if self.versym.is_null() {
+            return true;
+        }
+        let ver = *self.versym.add(idx) & 0x7fff;
+        let mut def = self.verdef;
+        loop {
+            if (*def).vd_flags & VER_FLG_BASE == 0 && (*def).vd_ndx == ver {
+                let aux = (def as usize + (*def).vd_aux as usize) as *const Verdaux;
+                return self.string((*aux).vda_name) == name;
+            }
+            if (*def).vd_next == 0 {
+                return false;
+            }
+            def = (def as usize + (*def).vd_next as usize) as *const Verdef;
+        }
+    }
+
+    /// Returns the address of a function in the vDSO or `0` if it doesn't exist.
+    unsafe fn lookup(&self, name: &[u8], version: &[u8]) -> usize {
+        for i in 0..self.symtab.len() {
+            let sym = &self.symtab[i];
+            let bind = sym.st_bind() as u16;
+            if sym.st_type() as u32 != STT_FUNC ||
+                    (bind != STB_GLOBAL && bind != STB_WEAK) ||
+                    sym.st_shndx as u32 == SHN_UNDEF {
+                continue;
+            }
+            if self.string(sym.st_name) != name || !self.version_matches(i, version) {
+                continue;
+            }
+            return sym.st_value as usize + self.load_offset;
+        }
+        0
+    }
+}
+
+/// Calculates the number of symbols from a GNU hash table.
+///
+/// The number of symbols is one more than the largest index in the hash chains.
+unsafe fn gnu_num_syms(table: *const u32) -> usize {
+    let nbuckets = *table as usize;
+    let symoffset = *table.add(1) as usize;
+    let bloom_size = *table.add(2) as usize;
+    let words_per_bloom = mem::size_of::<usize>() / 4;
+    let buckets = table.add(4 + bloom_size * words_per_bloom);
+    let chains = buckets.add(nbuckets);
+
+    let mut max = 0;
+    for i in 0..nbuckets {
+        let b = *buckets.add(i) as usize;
+        if b > max {
+            max = b;
+        }
+    }
+    if max < symoffset {
+        return symoffset;
+    }
+    // The last entry of each chain has the lowest bit set.
+    while *chains.add(max - symoffset) & 1 == 0 {
+        max += 1;
+    }
+    max + 1
+}
+
+/// Looks up the functions of the vDSO and stores them in the `lrs_syscall` table.
+///
+/// = Remarks
+///
+/// Functions that cannot be found are left unset so that the real system calls are
+/// used instead.
+pub unsafe fn init() {
+    let base = match aux::vdso_address() {
+        Some(b) => b,
+        _ => return,
+    };
+    let image = match Image::new(base) {
+        Some(i) => i,
+        _ => return,
+    };
+    let mut table = Vdso::new();
+    table.clock_gettime = image.lookup(names::CLOCK_GETTIME, names::VERSION);
+    table.clock_getres = image.lookup(names::CLOCK_GETRES, names::VERSION);
+    lookup_time(&image, &mut table);
+    table.getcpu = image.lookup(names::GETCPU, names::VERSION);
+    vdso::set(table);
+}
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+unsafe fn lookup_time(image: &Image, table: &mut Vdso) {
+    table.gettimeofday = image.lookup(names::GETTIMEOFDAY, names::VERSION);
+    table.time = image.lookup(names::TIME, names::VERSION);
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "arm"))]
+unsafe fn lookup_time(_: &Image, _: &mut Vdso) { }
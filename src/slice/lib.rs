@@ -8,13 +8,64 @@
 #![no_std]
 
 extern crate lrs_base as base;
+extern crate lrs_alloc as alloc;
 
 use base::prelude::*;
+use core::{mem, sort};
+use core::ops::{Ordering};
+use alloc::{MemPool};
 
 /// Extensions for slices.
 pub trait SliceExt<T> {
     fn last_to<U>(&self) -> Option<U>
         where T: To<U>;
+
+    /// Sorts the slice in-place with a stable sort.
+    ///
+    /// = Remarks
+    ///
+    /// The scratch buffer is allocated from the heap.
+    fn sort_stable(&mut self) -> Result
+        where T: Ord;
+
+    /// Sorts the slice in-place with a stable sort and a comparison function.
+    ///
+    /// [argument, f]
+    /// The comparison function.
+    ///
+    /// = Remarks
+    ///
+    /// The scratch buffer is allocated from the heap.
+    fn sort_stable_by<F>(&mut self, f: F) -> Result
+        where F: FnMut(&T, &T) -> Ordering;
+
+    /// Sorts the slice in-place with a stable sort and a key extraction function.
+    ///
+    /// [argument, f]
+    /// The function that returns the key of an element.
+    ///
+    /// = Remarks
+    ///
+    /// The scratch buffer is allocated from the heap.
+    fn sort_stable_by_key<K, F>(&mut self, f: F) -> Result
+        where K: Ord,
+              F: FnMut(&T) -> K;
+
+    /// Sorts the slice in-place with a stable sort and a comparison function.
+    ///
+    /// [argument, pool]
+    /// The pool from which the scratch buffer is allocated.
+    ///
+    /// [argument, f]
+    /// The comparison function.
+    ///
+    /// = Remarks
+    ///
+    /// The scratch buffer has space for half the elements of the slice. Short slices
+    /// don't need a scratch buffer.
+    fn sort_stable_with_pool<P, F>(&mut self, pool: &mut P, f: F) -> Result
+        where P: MemPool + ?Sized,
+              F: FnMut(&T, &T) -> Ordering;
 }
 
 impl<T> SliceExt<T> for [T] {
@@ -26,4 +77,41 @@ impl<T> SliceExt<T> for [T] {
             _ => None,
         }
     }
+
+    fn sort_stable(&mut self) -> Result
+        where T: Ord,
+    {
+        self.sort_stable_by(|one, two| one.cmp(two))
+    }
+
+    fn sort_stable_by<F>(&mut self, f: F) -> Result
+        where F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut pool = alloc::Heap::out_of(());
+        self.sort_stable_with_pool(&mut pool, f)
+    }
+
+    fn sort_stable_by_key<K, F>(&mut self, mut f: F) -> Result
+        where K: Ord,
+              F: FnMut(&T) -> K,
+    {
+        self.sort_stable_by(|one, two| f(one).cmp(&f(two)))
+    }
+
+    fn sort_stable_with_pool<P, F>(&mut self, pool: &mut P, mut f: F) -> Result
+        where P: MemPool + ?Sized,
+              F: FnMut(&T, &T) -> Ordering,
+    {
+        let len = sort::stable_buf_len(self.len());
+        if len == 0 || mem::size_of::<T>() == 0 {
+            unsafe { sort::sort_stable(self, 0 as *mut T, &mut f); }
+            return Ok(());
+        }
+        unsafe {
+            let (buf, num) = try!(alloc::alloc_array::<T, _>(pool, len));
+            sort::sort_stable(self, buf, &mut f);
+            alloc::free_array(pool, buf, num);
+        }
+        Ok(())
+    }
 }
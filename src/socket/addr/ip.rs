@@ -0,0 +1,284 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! IP addresses of either family.
+
+use base::prelude::*;
+use base::{error};
+use fmt::{Debug, Display, Write};
+use parse::{Parsable};
+use addr::ipv4::{Ipv4Addr};
+use addr::ipv6::{Ipv6Addr};
+use socket::{Socket};
+use flags::{SOCK_CLOSE_ON_EXEC};
+
+/// An IPv4 or IPv6 address.
+#[derive(Copy, Eq)]
+pub enum IpAddr {
+    /// An IPv4 address.
+    V4(Ipv4Addr),
+    /// An IPv6 address.
+    V6(Ipv6Addr),
+}
+
+impl IpAddr {
+    /// Parses an address in textual form.
+    ///
+    /// [argument, s]
+    /// The address in dotted-decimal or IPv6 notation.
+    ///
+    /// = Remarks
+    ///
+    /// A zone suffix of an IPv6 address, e.g., `%eth0`, is ignored.
+    pub fn parse(s: &[u8]) -> Option<IpAddr> {
+        match IpAddr::parse_bytes_init(s) {
+            Ok((a, len)) if len == s.len() => Some(a),
+            Ok((a @ IpAddr::V6(..), len)) if s[len] == b'%' => Some(a),
+            _ => None,
+        }
+    }
+}
+
+impl Debug for IpAddr {
+    fn fmt<W: Write>(&self, w: &mut W) -> Result {
+        match *self {
+            IpAddr::V4(ref a) => Debug::fmt(a, w),
+            IpAddr::V6(ref a) => Debug::fmt(a, w),
+        }
+    }
+}
+
+impl Display for IpAddr {
+    fn fmt<W: Write>(&self, w: &mut W) -> Result {
+        match *self {
+            IpAddr::V4(ref a) => Display::fmt(a, w),
+            IpAddr::V6(ref a) => Display::fmt(a, w),
+        }
+    }
+}
+
+impl Parsable for Ipv4Addr {
+    fn parse_bytes_init(s: &[u8]) -> Result<(Ipv4Addr, usize)> {
+        let len = prefix_len(s, |b| b == b'.' || is_digit(b));
+        match parse_ipv4(&s[..len]) {
+            Some(a) => Ok((a, len)),
+            _ => Err(error::InvalidSequence),
+        }
+    }
+}
+
+impl Parsable for Ipv6Addr {
+    fn parse_bytes_init(s: &[u8]) -> Result<(Ipv6Addr, usize)> {
+        let len = prefix_len(s, |b| b == b':' || b == b'.' || hex_digit(b).is_some());
+        match parse_ipv6(&s[..len]) {
+            Some(a) => Ok((a, len)),
+            _ => Err(error::InvalidSequence),
+        }
+    }
+}
+
+impl Parsable for IpAddr {
+    fn parse_bytes_init(s: &[u8]) -> Result<(IpAddr, usize)> {
+        // No IPv6 address starts with an IPv4 address.
+        match Ipv4Addr::parse_bytes_init(s) {
+            Ok((a, len)) => Ok((IpAddr::V4(a), len)),
+            _ => Ipv6Addr::parse_bytes_init(s).map(|(a, len)| (IpAddr::V6(a), len)),
+        }
+    }
+}
+
+fn is_digit(b: u8) -> bool {
+    b'0' <= b && b <= b'9'
+}
+
+/// Returns the length of the longest prefix whose bytes satisfy a predicate.
+fn prefix_len<F>(s: &[u8], f: F) -> usize
+    where F: Fn(u8) -> bool,
+{
+    s.find(|&b| !f(b)).unwrap_or(s.len())
+}
+
+/// Parses a `:port` suffix.
+fn parse_port(s: &[u8]) -> Result<(u16, usize)> {
+    if s.len() < 2 || s[0] != b':' {
+        return Err(error::InvalidSequence);
+    }
+    let len = prefix_len(&s[1..], is_digit);
+    if len == 0 || len > 5 {
+        return Err(error::InvalidSequence);
+    }
+    let mut port = 0u32;
+    for &b in &s[1..len + 1] {
+        port = port * 10 + (b - b'0') as u32;
+    }
+    if port > 65535 {
+        return Err(error::InvalidSequence);
+    }
+    Ok((port as u16, len + 1))
+}
+
+/// Parses the scope id of an IPv6 address.
+///
+/// The scope id is either a number or the name of a network interface.
+fn parse_scope_id(s: &[u8]) -> Result<u32> {
+    if s.len() == 0 {
+        return Err(error::InvalidSequence);
+    }
+    if prefix_len(s, is_digit) == s.len() {
+        return u32::parse_bytes(s);
+    }
+    let sock = try!(Socket::unix_datagram(SOCK_CLOSE_ON_EXEC));
+    sock.interface_index(s)
+}
+
+/// Parses an IPv4 socket address of the form `a.b.c.d:port`.
+///
+/// Returns the address, the port, and the number of bytes consumed.
+pub fn parse_ipv4_sock_addr(s: &[u8]) -> Result<(Ipv4Addr, u16, usize)> {
+    let (addr, len) = try!(Ipv4Addr::parse_bytes_init(s));
+    let (port, plen) = try!(parse_port(&s[len..]));
+    Ok((addr, port, len + plen))
+}
+
+/// Parses an IPv6 socket address of the form `[addr]:port` or `[addr%scope]:port`.
+///
+/// Returns the address, the scope id, the port, and the number of bytes consumed.
+pub fn parse_ipv6_sock_addr(s: &[u8]) -> Result<(Ipv6Addr, u32, u16, usize)> {
+    if s.len() == 0 || s[0] != b'[' {
+        return Err(error::InvalidSequence);
+    }
+    let (addr, len) = try!(Ipv6Addr::parse_bytes_init(&s[1..]));
+    let mut pos = len + 1;
+    let mut scope_id = 0;
+    if pos < s.len() && s[pos] == b'%' {
+        let end = match s[pos..].find(|&b| b == b']') {
+            Some(e) => pos + e,
+            _ => return Err(error::InvalidSequence),
+        };
+        scope_id = try!(parse_scope_id(&s[pos + 1..end]));
+        pos = end;
+    }
+    if pos == s.len() || s[pos] != b']' {
+        return Err(error::InvalidSequence);
+    }
+    let (port, plen) = try!(parse_port(&s[pos + 1..]));
+    Ok((addr, scope_id, port, pos + 1 + plen))
+}
+
+macro_rules! opt {
+    ($e:expr) => { match $e { Some(v) => v, _ => return None } }
+}
+
+fn hex_digit(b: u8) -> Option<u16> {
+    match b {
+        b'0'...b'9' => Some((b - b'0') as u16),
+        b'a'...b'f' => Some((b - b'a' + 10) as u16),
+        b'A'...b'F' => Some((b - b'A' + 10) as u16),
+        _ => None,
+    }
+}
+
+/// Parses an address in dotted-decimal notation.
+fn parse_ipv4(s: &[u8]) -> Option<Ipv4Addr> {
+    let mut bytes = [0u8; 4];
+    let mut num = 0;
+    for part in s.split(|&b| b == b'.') {
+        if num == 4 || part.len() == 0 || part.len() > 3 {
+            return None;
+        }
+        let mut val = 0u16;
+        for &b in part {
+            if b < b'0' || b > b'9' {
+                return None;
+            }
+            val = val * 10 + (b - b'0') as u16;
+        }
+        if val > 255 {
+            return None;
+        }
+        bytes[num] = val as u8;
+        num += 1;
+    }
+    if num != 4 || s[s.len() - 1] == b'.' {
+        return None;
+    }
+    Some(Ipv4Addr::from_bytes(bytes))
+}
+
+/// Parses a group of up to four hexadecimal digits.
+fn parse_group(s: &[u8]) -> Option<u16> {
+    if s.len() == 0 || s.len() > 4 {
+        return None;
+    }
+    let mut val = 0;
+    for &b in s {
+        val = val * 16 + opt!(hex_digit(b));
+    }
+    Some(val)
+}
+
+/// Parses the groups of one side of a `::`.
+///
+/// Returns the number of 16 bit groups that were stored. An embedded IPv4 address is
+/// only accepted at the end if `allow_v4` is set.
+fn parse_groups(s: &[u8], out: &mut [u16], allow_v4: bool) -> Option<usize> {
+    if s.len() == 0 {
+        return Some(0);
+    }
+    let mut num = 0;
+    let mut parts = s.split(|&b| b == b':');
+    loop {
+        let part = match parts.next() {
+            Some(p) => p,
+            _ => break,
+        };
+        if part.find(|&b| b == b'.').is_some() {
+            // Embedded IPv4 address. Must be the last part of the address.
+            if !allow_v4 || parts.next().is_some() || num + 2 > out.len() {
+                return None;
+            }
+            let v4 = opt!(parse_ipv4(part)).to_bytes();
+            out[num] = (v4[0] as u16) << 8 | v4[1] as u16;
+            out[num + 1] = (v4[2] as u16) << 8 | v4[3] as u16;
+            return Some(num + 2);
+        }
+        if num == out.len() {
+            return None;
+        }
+        out[num] = opt!(parse_group(part));
+        num += 1;
+    }
+    Some(num)
+}
+
+/// Parses an address in the notation of RFC 4291.
+fn parse_ipv6(s: &[u8]) -> Option<Ipv6Addr> {
+    let mut groups = [0u16; 8];
+    let mut pos = None;
+    let mut i = 0;
+    while i + 1 < s.len() {
+        if s[i] == b':' && s[i + 1] == b':' {
+            pos = Some(i);
+            break;
+        }
+        i += 1;
+    }
+    match pos {
+        Some(pos) => {
+            let (left, right) = (&s[..pos], &s[pos + 2..]);
+            let mut tail = [0u16; 8];
+            let l = opt!(parse_groups(left, &mut groups[..7], false));
+            let r = opt!(parse_groups(right, &mut tail[..7 - l], true));
+            for j in 0..r {
+                groups[8 - r + j] = tail[j];
+            }
+        },
+        _ => {
+            if opt!(parse_groups(s, &mut groups, true)) != 8 {
+                return None;
+            }
+        },
+    }
+    Some(Ipv6Addr::from_bytes(groups))
+}
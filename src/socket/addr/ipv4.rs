@@ -9,7 +9,8 @@ use cty::{
     AF_INET, sa_family_t, c_int, sockaddr_in, in_addr,
 };
 use addr::{SockAddr};
-use fmt::{Debug, Write};
+use fmt::{Debug, Display, Write};
+use addr::ip::{parse_ipv4_sock_addr};
 
 /// The size of an Ipv4 socket address.
 pub const IPV4_SOCK_ADDR_SIZE: usize = 16; // == size_of::<sockaddr_in>()
@@ -177,6 +178,33 @@ impl Ipv4SockAddr {
         Ok(unsafe { mem::cast(&mut bytes[..IPV4_SOCK_ADDR_SIZE]) })
     }
 
+    /// Creates a new Ipv4 socket address from its textual representation.
+    ///
+    /// [argument, bytes]
+    /// The buffer in which the address will be stored.
+    ///
+    /// [argument, s]
+    /// The textual representation of the form `a.b.c.d:port`.
+    ///
+    /// = Remarks
+    ///
+    /// Socket addresses are unsized and therefore don't implement `Parsable`.
+    ///
+    /// = Examples
+    ///
+    /// ----
+    /// let mut buf = [0; IPV4_SOCK_ADDR_SIZE];
+    /// let addr = Ipv4SockAddr::parse(&mut buf, b"10.0.0.1:8080").unwrap();
+    /// assert!(addr.port() == 8080);
+    /// ----
+    pub fn parse<'a>(bytes: &'a mut [u8], s: &[u8]) -> Result<&'a mut Ipv4SockAddr> {
+        let (addr, port, len) = try!(parse_ipv4_sock_addr(s));
+        if len != s.len() {
+            return Err(error::InvalidSequence);
+        }
+        Ipv4SockAddr::from_addr_port(bytes, addr, port)
+    }
+
     /// Returns the Ipv4 address of the socket address.
     pub fn addr(&self) -> Ipv4Addr {
         let mut addr = 0;
@@ -230,6 +258,12 @@ impl Debug for Ipv4Addr {
     }
 }
 
+impl Display for Ipv4Addr {
+    fn fmt<W: Write>(&self, w: &mut W) -> Result {
+        Debug::fmt(self, w)
+    }
+}
+
 impl Debug for Ipv4SockAddr {
     fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
         let addr = self.addr();
@@ -237,3 +271,9 @@ impl Debug for Ipv4SockAddr {
         write!(w, "Ipv4SockAddr {{ {:?}:{} }}", addr, port)
     }
 }
+
+impl Display for Ipv4SockAddr {
+    fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+        write!(w, "{}:{}", self.addr(), self.port())
+    }
+}
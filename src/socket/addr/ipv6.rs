@@ -9,7 +9,8 @@ use cty::{
 };
 use base::{error};
 use addr::{SockAddr};
-use fmt::{Debug, Write};
+use fmt::{Debug, Display, Write};
+use addr::ip::{parse_ipv6_sock_addr};
 
 /// The size of an Ipv6 socket address in bytes.
 pub const IPV6_SOCK_ADDR_SIZE: usize = 28; // == size_of::<sockaddr_in6>().
@@ -286,7 +287,7 @@ impl Ipv6SockAddr {
         }
         let addr = sockaddr_in6 {
             sin6_family: AF_INET6 as sa_family_t,
-            sin6_port: port,
+            sin6_port: port.to_be(),
             sin6_addr: in6_addr { u6_addr16: addr.to_be_bytes() },
             .. mem::zeroed()
         };
@@ -294,6 +295,38 @@ impl Ipv6SockAddr {
         Ok(unsafe { mem::cast(&mut bytes[..IPV6_SOCK_ADDR_SIZE]) })
     }
 
+    /// Creates a new Ipv6 socket address from its textual representation.
+    ///
+    /// [argument, bytes]
+    /// The buffer in which the address will be stored.
+    ///
+    /// [argument, s]
+    /// The textual representation of the form `[addr]:port` or `[addr%scope]:port`.
+    ///
+    /// = Remarks
+    ///
+    /// The scope is either a numeric scope id or the name of a network interface. In
+    /// the latter case, the interface is looked up in the current network namespace.
+    ///
+    /// Socket addresses are unsized and therefore don't implement `Parsable`.
+    ///
+    /// = Examples
+    ///
+    /// ----
+    /// let mut buf = [0; IPV6_SOCK_ADDR_SIZE];
+    /// let addr = Ipv6SockAddr::parse(&mut buf, b"[fe80::1%lo]:53").unwrap();
+    /// assert!(addr.port() == 53);
+    /// ----
+    pub fn parse<'a>(bytes: &'a mut [u8], s: &[u8]) -> Result<&'a mut Ipv6SockAddr> {
+        let (addr, scope_id, port, len) = try!(parse_ipv6_sock_addr(s));
+        if len != s.len() {
+            return Err(error::InvalidSequence);
+        }
+        let sock_addr = try!(Ipv6SockAddr::from_addr_port(bytes, addr, port));
+        sock_addr.set_scope_id(scope_id);
+        Ok(sock_addr)
+    }
+
     /// Returns the Ipv6 address of an Ipv6 socket address.
     pub fn addr(&self) -> Ipv6Addr {
         let mut addr = [0; 8];
@@ -311,9 +344,9 @@ impl Ipv6SockAddr {
 
     /// Returns the port of an Ipv6 socket address.
     pub fn port(&self) -> u16 {
-        let mut port = 0;
+        let mut port: u16 = 0;
         mem::copy(port.as_mut(), &self.data[PORT_OFF..]);
-        port
+        port.from_be()
     }
 
     /// Sets the port of an Ipv6 socket address.
@@ -343,7 +376,7 @@ impl Ipv6SockAddr {
     pub fn scope_id(&self) -> u32 {
         let mut id: u32 = 0;
         mem::copy(id.as_mut(), &self.data[SCOP_OFF..]);
-        id.from_be()
+        id
     }
 
     /// Sets the scope id of an Ipv6 socket address.
@@ -351,7 +384,7 @@ impl Ipv6SockAddr {
     /// [argument, id]
     /// The new scope id.
     pub fn set_scope_id(&mut self, id: u32) {
-        mem::copy(&mut self.data[SCOP_OFF..], id.to_be().as_ref());
+        mem::copy(&mut self.data[SCOP_OFF..], id.as_ref());
     }
 }
 
@@ -377,77 +410,50 @@ impl AsMut<SockAddr> for Ipv6SockAddr {
 impl_try_as_mut!(SockAddr, Ipv6SockAddr);
 
 impl Debug for Ipv6Addr {
+    fn fmt<W: Write>(&self, w: &mut W) -> Result {
+        Display::fmt(self, w)
+    }
+}
+
+/// = Remarks
+///
+/// The address is formatted as recommended by RFC 5952.
+impl Display for Ipv6Addr {
     fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
-        let is_ipv4 = if self.is_ipv4_compatible() {
-            try!(write!(w, "::"));
-            true
-        } else if self.is_ipv4_mapped() {
-            try!(write!(w, "::ffff:"));
-            true
-        } else if self.is_ipv4_translated() {
-            try!(write!(w, "::ffff:0:"));
-            true
-        } else {
-            false
-        };
+        let segs = self.to_bytes();
 
-        if is_ipv4 {
-            let ab: [u8; 2] = unsafe { mem::cast(self.6.to_be()) };
-            let cd: [u8; 2] = unsafe { mem::cast(self.6.to_be()) };
-            return write!(w, "{}.{}.{}.{}", ab[0], ab[1], cd[0], cd[1]);
+        if self.is_ipv4_mapped() {
+            return write!(w, "::ffff:{}.{}.{}.{}", segs[6] >> 8, segs[6] & 0xff,
+                          segs[7] >> 8, segs[7] & 0xff);
         }
 
-        let bytes = self.to_bytes();
-
-        // Find the longest sequence of zeros, favoring sequences at the end and start.
-        let (start, end) = {
-            let mut start = 0;
-            let mut end = 0;
-            let mut cur = 8;
-            for i in 0..8usize {
-                match bytes[i] {
-                    0 if cur == 8 => cur = i,
-                    0 => { },
-                    _ => {
-                        if cur != 8 && i - cur > end - start {
-                            start = cur;
-                            end = i;
-                        }
-                        cur = 8;
-                    },
-                }
-            }
-            if (8 - cur > end - start) || ((8 - cur == end - start) && start > 0) {
-                start = cur;
-                end = 8;
+        // Find the longest run of at least two zero segments, favoring the first.
+        let mut start = 8;
+        let mut len = 0;
+        let mut i = 0;
+        while i < 8 {
+            let mut j = i;
+            while j < 8 && segs[j] == 0 {
+                j += 1;
             }
-            if end == start + 1 {
-                (0, 0)
-            } else {
-                (start, end)
+            if j - i > 1 && j - i > len {
+                start = i;
+                len = j - i;
             }
-        };
+            i = if j > i { j } else { i + 1 };
+        }
 
-        if start == end {
-            for i in 0..7usize {
-                try!(write!(w, "{:x}:", bytes[i]))
+        for i in 0..8 {
+            if i == start {
+                try!(write!(w, "::"));
             }
-            try!(write!(w, "{:x}", bytes[7]))
-        } else {
-            if start == 0 {
-                try!(write!(w, ":"));
-            } else {
-                for i in 0..start {
-                    try!(write!(w, "{:x}:", bytes[i]))
-                }
+            if i >= start && i < start + len {
+                continue;
             }
-            if end == 8 {
+            if i > 0 && i != start + len {
                 try!(write!(w, ":"));
-            } else {
-                for i in end..8 {
-                    try!(write!(w, ":{:x}", bytes[i]))
-                }
             }
+            try!(write!(w, "{:x}", segs[i]));
         }
         Ok(())
     }
@@ -463,3 +469,12 @@ impl Debug for Ipv6SockAddr {
                addr, port, flow, scope)
     }
 }
+
+impl Display for Ipv6SockAddr {
+    fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+        match self.scope_id() {
+            0 => write!(w, "[{}]:{}", self.addr(), self.port()),
+            id => write!(w, "[{}%{}]:{}", self.addr(), id, self.port()),
+        }
+    }
+}
@@ -13,6 +13,8 @@ use fmt::{Debug, Write};
 pub mod unix;
 pub mod ipv4;
 pub mod ipv6;
+pub mod ip;
+pub mod net;
 
 /// A socket address.
 pub struct SockAddr { data: [u8] }
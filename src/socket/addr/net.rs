@@ -0,0 +1,414 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Network prefixes.
+
+use base::prelude::*;
+use base::{error};
+use fmt::{Debug, Display, Write};
+use parse::{Parsable};
+use addr::ipv4::{Ipv4Addr};
+use addr::ipv6::{Ipv6Addr};
+
+fn ipv4_to_u32(addr: Ipv4Addr) -> u32 {
+    (addr.0 as u32) << 24 | (addr.1 as u32) << 16 | (addr.2 as u32) << 8 | addr.3 as u32
+}
+
+fn ipv4_from_u32(val: u32) -> Ipv4Addr {
+    Ipv4Addr((val >> 24) as u8, (val >> 16) as u8, (val >> 8) as u8, val as u8)
+}
+
+fn ipv4_mask(prefix: u8) -> u32 {
+    match prefix {
+        0 => 0,
+        _ => !0 << (32 - prefix as u32),
+    }
+}
+
+fn ipv6_mask(prefix: u8) -> [u16; 8] {
+    let mut mask = [0; 8];
+    for i in 0..8 {
+        let bits = (prefix as usize).saturating_sub(16 * i);
+        mask[i] = match bits {
+            0 => 0,
+            1...15 => !0 << (16 - bits),
+            _ => !0,
+        };
+    }
+    mask
+}
+
+fn ipv6_and(addr: [u16; 8], mask: [u16; 8]) -> [u16; 8] {
+    let mut res = [0; 8];
+    for i in 0..8 {
+        res[i] = addr[i] & mask[i];
+    }
+    res
+}
+
+/// Parses the `/prefix` suffix of a network.
+fn parse_prefix(s: &[u8], max: u8) -> Result<(u8, usize)> {
+    if s.len() < 2 || s[0] != b'/' {
+        return Err(error::InvalidSequence);
+    }
+    let len = s[1..].find(|&b| b < b'0' || b > b'9').unwrap_or(s.len() - 1);
+    if len == 0 || len > 3 {
+        return Err(error::InvalidSequence);
+    }
+    let mut prefix = 0u32;
+    for &b in &s[1..len + 1] {
+        prefix = prefix * 10 + (b - b'0') as u32;
+    }
+    if prefix > max as u32 {
+        return Err(error::InvalidSequence);
+    }
+    Ok((prefix as u8, len + 1))
+}
+
+/// An Ipv4 network.
+///
+/// = Remarks
+///
+/// The network consists of an address and the length of the network prefix. The host
+/// bits of the address are not cleared, i.e., `10.1.2.3/8` and `10.0.0.0/8` are
+/// different objects that describe the same network.
+///
+/// = Examples
+///
+/// ----
+/// let net: Ipv4Net = "192.168.0.0/16".parse().unwrap();
+/// assert!(net.contains(Ipv4Addr(192, 168, 1, 1)));
+/// assert!(net.broadcast() == Ipv4Addr(192, 168, 255, 255));
+/// ----
+#[derive(Copy, Eq)]
+pub struct Ipv4Net {
+    addr: Ipv4Addr,
+    prefix: u8,
+}
+
+impl Ipv4Net {
+    /// Creates a new network.
+    ///
+    /// [argument, addr]
+    /// An address in the network.
+    ///
+    /// [argument, prefix]
+    /// The length of the network prefix.
+    ///
+    /// = Remarks
+    ///
+    /// If the prefix is longer than 32 bits, an error is returned.
+    pub fn new(addr: Ipv4Addr, prefix: u8) -> Result<Ipv4Net> {
+        if prefix > 32 {
+            return Err(error::InvalidArgument);
+        }
+        Ok(Ipv4Net { addr: addr, prefix: prefix })
+    }
+
+    /// Returns the address the network was created with.
+    pub fn addr(&self) -> Ipv4Addr {
+        self.addr
+    }
+
+    /// Returns the length of the network prefix.
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Returns the netmask of the network, e.g., `255.255.0.0` for `/16`.
+    pub fn netmask(&self) -> Ipv4Addr {
+        ipv4_from_u32(ipv4_mask(self.prefix))
+    }
+
+    /// Returns the hostmask of the network, e.g., `0.0.255.255` for `/16`.
+    pub fn hostmask(&self) -> Ipv4Addr {
+        ipv4_from_u32(!ipv4_mask(self.prefix))
+    }
+
+    /// Returns the first address of the network.
+    pub fn network(&self) -> Ipv4Addr {
+        ipv4_from_u32(ipv4_to_u32(self.addr) & ipv4_mask(self.prefix))
+    }
+
+    /// Returns the last address of the network.
+    pub fn broadcast(&self) -> Ipv4Addr {
+        ipv4_from_u32(ipv4_to_u32(self.addr) | !ipv4_mask(self.prefix))
+    }
+
+    /// Checks whether the network contains an address.
+    ///
+    /// [argument, addr]
+    /// The address to check.
+    pub fn contains(&self, addr: Ipv4Addr) -> bool {
+        let mask = ipv4_mask(self.prefix);
+        ipv4_to_u32(addr) & mask == ipv4_to_u32(self.addr) & mask
+    }
+
+    /// Checks whether the network contains another network.
+    ///
+    /// [argument, other]
+    /// The network to check.
+    pub fn contains_net(&self, other: &Ipv4Net) -> bool {
+        other.prefix >= self.prefix && self.contains(other.addr)
+    }
+
+    /// Returns an iterator over the subnets of the network.
+    ///
+    /// [argument, prefix]
+    /// The length of the prefix of the subnets.
+    ///
+    /// = Remarks
+    ///
+    /// If the prefix is shorter than the prefix of this network or longer than 32
+    /// bits, an error is returned.
+    ///
+    /// = Examples
+    ///
+    /// ----
+    /// let net = Ipv4Net::new(Ipv4Addr(10, 0, 0, 0), 8).unwrap();
+    /// let mut subnets = net.subnets(9).unwrap();
+    /// assert!(subnets.next().unwrap().network() == Ipv4Addr(10, 0, 0, 0));
+    /// assert!(subnets.next().unwrap().network() == Ipv4Addr(10, 128, 0, 0));
+    /// assert!(subnets.next().is_none());
+    /// ----
+    pub fn subnets(&self, prefix: u8) -> Result<Ipv4Subnets> {
+        if prefix < self.prefix || prefix > 32 {
+            return Err(error::InvalidArgument);
+        }
+        let start = (ipv4_to_u32(self.addr) & ipv4_mask(self.prefix)) as u64;
+        Ok(Ipv4Subnets {
+            next: start,
+            end: start + (1 << (32 - self.prefix as u64)),
+            step: 1 << (32 - prefix as u64),
+            prefix: prefix,
+        })
+    }
+}
+
+impl Parsable for Ipv4Net {
+    fn parse_bytes_init(s: &[u8]) -> Result<(Ipv4Net, usize)> {
+        let (addr, len) = try!(Ipv4Addr::parse_bytes_init(s));
+        let (prefix, plen) = try!(parse_prefix(&s[len..], 32));
+        Ok((Ipv4Net { addr: addr, prefix: prefix }, len + plen))
+    }
+}
+
+impl Debug for Ipv4Net {
+    fn fmt<W: Write>(&self, w: &mut W) -> Result {
+        Display::fmt(self, w)
+    }
+}
+
+impl Display for Ipv4Net {
+    fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+        write!(w, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+/// An iterator over the subnets of an Ipv4 network.
+///
+/// = See also
+///
+/// * link:lrs::socket::ip::Ipv4Net::subnets
+pub struct Ipv4Subnets {
+    next: u64,
+    end: u64,
+    step: u64,
+    prefix: u8,
+}
+
+impl Iterator for Ipv4Subnets {
+    type Item = Ipv4Net;
+    fn next(&mut self) -> Option<Ipv4Net> {
+        if self.next >= self.end {
+            return None;
+        }
+        let net = Ipv4Net { addr: ipv4_from_u32(self.next as u32), prefix: self.prefix };
+        self.next += self.step;
+        Some(net)
+    }
+}
+
+/// An Ipv6 network.
+///
+/// = Remarks
+///
+/// The network consists of an address and the length of the network prefix. The host
+/// bits of the address are not cleared.
+///
+/// = Examples
+///
+/// ----
+/// let net: Ipv6Net = "2001:db8::/32".parse().unwrap();
+/// assert!(net.contains("2001:db8::1".parse().unwrap()));
+/// ----
+#[derive(Copy, Eq)]
+pub struct Ipv6Net {
+    addr: Ipv6Addr,
+    prefix: u8,
+}
+
+impl Ipv6Net {
+    /// Creates a new network.
+    ///
+    /// [argument, addr]
+    /// An address in the network.
+    ///
+    /// [argument, prefix]
+    /// The length of the network prefix.
+    ///
+    /// = Remarks
+    ///
+    /// If the prefix is longer than 128 bits, an error is returned.
+    pub fn new(addr: Ipv6Addr, prefix: u8) -> Result<Ipv6Net> {
+        if prefix > 128 {
+            return Err(error::InvalidArgument);
+        }
+        Ok(Ipv6Net { addr: addr, prefix: prefix })
+    }
+
+    /// Returns the address the network was created with.
+    pub fn addr(&self) -> Ipv6Addr {
+        self.addr
+    }
+
+    /// Returns the length of the network prefix.
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Returns the netmask of the network, e.g., `ffff:ffff::` for `/32`.
+    pub fn netmask(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bytes(ipv6_mask(self.prefix))
+    }
+
+    /// Returns the hostmask of the network, e.g., `::ffff:ffff` for `/96`.
+    pub fn hostmask(&self) -> Ipv6Addr {
+        let mut mask = ipv6_mask(self.prefix);
+        for i in 0..8 {
+            mask[i] = !mask[i];
+        }
+        Ipv6Addr::from_bytes(mask)
+    }
+
+    /// Returns the first address of the network.
+    pub fn network(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bytes(ipv6_and(self.addr.to_bytes(), ipv6_mask(self.prefix)))
+    }
+
+    /// Checks whether the network contains an address.
+    ///
+    /// [argument, addr]
+    /// The address to check.
+    pub fn contains(&self, addr: Ipv6Addr) -> bool {
+        let mask = ipv6_mask(self.prefix);
+        ipv6_and(addr.to_bytes(), mask) == ipv6_and(self.addr.to_bytes(), mask)
+    }
+
+    /// Checks whether the network contains another network.
+    ///
+    /// [argument, other]
+    /// The network to check.
+    pub fn contains_net(&self, other: &Ipv6Net) -> bool {
+        other.prefix >= self.prefix && self.contains(other.addr)
+    }
+
+    /// Returns an iterator over the subnets of the network.
+    ///
+    /// [argument, prefix]
+    /// The length of the prefix of the subnets.
+    ///
+    /// = Remarks
+    ///
+    /// If the prefix is shorter than the prefix of this network or longer than 128
+    /// bits, an error is returned.
+    pub fn subnets(&self, prefix: u8) -> Result<Ipv6Subnets> {
+        if prefix < self.prefix || prefix > 128 {
+            return Err(error::InvalidArgument);
+        }
+        let network = ipv6_and(self.addr.to_bytes(), ipv6_mask(self.prefix));
+        Ok(Ipv6Subnets {
+            next: network,
+            network: network,
+            parent_prefix: self.prefix,
+            prefix: prefix,
+            done: false,
+        })
+    }
+}
+
+impl Parsable for Ipv6Net {
+    fn parse_bytes_init(s: &[u8]) -> Result<(Ipv6Net, usize)> {
+        let (addr, len) = try!(Ipv6Addr::parse_bytes_init(s));
+        let (prefix, plen) = try!(parse_prefix(&s[len..], 128));
+        Ok((Ipv6Net { addr: addr, prefix: prefix }, len + plen))
+    }
+}
+
+impl Debug for Ipv6Net {
+    fn fmt<W: Write>(&self, w: &mut W) -> Result {
+        Display::fmt(self, w)
+    }
+}
+
+impl Display for Ipv6Net {
+    fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+        write!(w, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+/// An iterator over the subnets of an Ipv6 network.
+///
+/// = See also
+///
+/// * link:lrs::socket::ip::Ipv6Net::subnets
+pub struct Ipv6Subnets {
+    next: [u16; 8],
+    network: [u16; 8],
+    parent_prefix: u8,
+    prefix: u8,
+    done: bool,
+}
+
+impl Ipv6Subnets {
+    /// Advances `next` to the following subnet and returns whether it is still part
+    /// of the network.
+    fn advance(&mut self) -> bool {
+        if self.prefix == 0 {
+            return false;
+        }
+        let bit = self.prefix as usize - 1;
+        let mut i = bit / 16;
+        let mut add = 1 << (15 - bit % 16);
+        loop {
+            match self.next[i].checked_add(add) {
+                Some(v) => {
+                    self.next[i] = v;
+                    break;
+                },
+                _ => {
+                    self.next[i] = self.next[i].wrapping_add(add);
+                    if i == 0 {
+                        return false;
+                    }
+                    i -= 1;
+                    add = 1;
+                },
+            }
+        }
+        ipv6_and(self.next, ipv6_mask(self.parent_prefix)) == self.network
+    }
+}
+
+impl Iterator for Ipv6Subnets {
+    type Item = Ipv6Net;
+    fn next(&mut self) -> Option<Ipv6Net> {
+        if self.done {
+            return None;
+        }
+        let net = Ipv6Net { addr: Ipv6Addr::from_bytes(self.next), prefix: self.prefix };
+        self.done = !self.advance();
+        Some(net)
+    }
+}
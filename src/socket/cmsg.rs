@@ -8,12 +8,13 @@ use core::{mem, slice, ptr};
 use core::ptr::{NoAliasMemPtr};
 use cty::{
     cmsghdr, c_int, SCM_RIGHTS, SCM_CREDENTIALS, SOL_SOCKET, user_size_t,
-    SO_TIMESTAMPNS, timespec, IPPROTO_IP, IP_OPTIONS,
+    SCM_TIMESTAMPNS, SCM_TIMESTAMPNS_NEW, __kernel_old_timespec, __kernel_timespec,
+    IPPROTO_IP, IP_OPTIONS,
 };
 use cty::alias::{ProcessId, UserId, GroupId};
 use io::{BufRead};
 use fmt::{Debug, Write};
-use time_base::{self, Time};
+use time_base::{Time};
 use alloc::{self, Dummy, MemPool};
 
 const PTR_MASK: usize = usize::bytes() - 1;
@@ -68,7 +69,7 @@ impl Debug for Credentials {
 ///
 /// = Remarks
 ///
-/// The timestamp has nanosecond precision.
+/// The timestamp has nanosecond precision and 64 bit seconds on all architectures.
 ///
 /// = See also
 ///
@@ -76,13 +77,16 @@ impl Debug for Credentials {
 #[repr(C)]
 #[derive(Pod, Eq)]
 pub struct Timestamp {
-    data: timespec,
+    data: __kernel_timespec,
 }
 
 impl Timestamp {
     /// Turns the timestamp into a `Time` object.
     pub fn to_time(&self) -> Time {
-        time_base::time_from_timespec(self.data)
+        Time {
+            seconds: self.data.tv_sec,
+            nanoseconds: self.data.tv_nsec,
+        }
     }
 }
 
@@ -126,7 +130,7 @@ pub enum CMsg<'a> {
     ///
     /// [field, 1]
     /// The timestamp.
-    Timestamp(&'a Timestamp),
+    Timestamp(Timestamp),
 
     /// Ipv4 options.
     ///
@@ -326,13 +330,27 @@ impl<'a> CMsgIter<'a> {
     }
 
     fn timestamp(&self, len: usize) -> Option<CMsg<'a>> {
-        if len != mem::size_of::<Timestamp>() {
+        // The data is only aligned to the pointer size which is not sufficient for
+        // the 64 bit fields on 32 bit architectures.
+        if len != mem::size_of::<__kernel_timespec>() {
             return None;
         }
-        unsafe {
-            let ptr = self.data.as_ptr() as *const Timestamp;
-            Some(CMsg::Timestamp(&*ptr))
+        let mut data: __kernel_timespec = mem::zeroed();
+        mem::copy(data.as_mut(), &self.data[..len]);
+        Some(CMsg::Timestamp(Timestamp { data: data }))
+    }
+
+    fn old_timestamp(&self, len: usize) -> Option<CMsg<'a>> {
+        if len != mem::size_of::<__kernel_old_timespec>() {
+            return None;
         }
+        let mut old: __kernel_old_timespec = mem::zeroed();
+        mem::copy(old.as_mut(), &self.data[..len]);
+        let data = __kernel_timespec {
+            tv_sec: old.tv_sec as i64,
+            tv_nsec: old.tv_nsec as i64,
+        };
+        Some(CMsg::Timestamp(Timestamp { data: data }))
     }
 
     fn ipv4_options(&self, len: usize) -> Option<CMsg<'a>> {
@@ -368,7 +386,8 @@ impl<'a> Iterator for CMsgIter<'a> {
         let rv = match (level, ty) {
             (SOL_SOCKET, SCM_RIGHTS) => self.fds(data_len),
             (SOL_SOCKET, SCM_CREDENTIALS) => self.credentials(data_len),
-            (SOL_SOCKET, SO_TIMESTAMPNS) => self.timestamp(data_len),
+            (SOL_SOCKET, SCM_TIMESTAMPNS_NEW) => self.timestamp(data_len),
+            (SOL_SOCKET, SCM_TIMESTAMPNS) => self.old_timestamp(data_len),
             (IPPROTO_IP, IP_OPTIONS) => self.ipv4_options(data_len),
             _ => Some(CMsg::Unknown(&self.data[..len])),
         };
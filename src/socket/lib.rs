@@ -20,11 +20,14 @@ extern crate lrs_alloc      as alloc;
 extern crate lrs_saturating as saturating;
 extern crate lrs_io         as io;
 extern crate lrs_syscall    as syscall;
+extern crate lrs_parse      as parse;
 
 pub use addr::{SockAddr, AddrType};
 pub use addr::unix::{UnixSockAddr, UnixAddrType};
 pub use addr::ipv4::{Ipv4Addr, Ipv4SockAddr, IPV4_SOCK_ADDR_SIZE};
 pub use addr::ipv6::{Ipv6Addr, Ipv6SockAddr, IPV6_SOCK_ADDR_SIZE, Ipv6Scope};
+pub use addr::ip::{IpAddr};
+pub use addr::net::{Ipv4Net, Ipv6Net, Ipv4Subnets, Ipv6Subnets};
 
 mod std { pub use fmt::std::*; pub use cty; }
 
@@ -12,7 +12,7 @@ use cty::{
     SO_SNDTIMEO, SO_RCVTIMEO, SO_SNDBUF, SO_SNDBUFFORCE, SO_RCVBUF, SO_RCVBUFFORCE,
     timeval, SO_PRIORITY, SO_PEERCRED, SO_PEEK_OFF, SO_PASSCRED, SO_OOBINLINE,
     SO_MARK, SO_LINGER, SO_DONTROUTE, SO_KEEPALIVE, k_int, SO_ERROR, linger, INT_MAX,
-    SO_PROTOCOL, SO_TIMESTAMPNS, SO_TYPE,
+    SO_PROTOCOL, SO_TIMESTAMPNS, SO_TIMESTAMPNS_NEW, SO_TYPE,
     IPPROTO_IP, IP_ADD_MEMBERSHIP, IP_DROP_MEMBERSHIP, IP_ADD_SOURCE_MEMBERSHIP,
     IP_DROP_SOURCE_MEMBERSHIP, IP_MULTICAST_ALL, IP_MULTICAST_TTL, IP_BLOCK_SOURCE,
     IP_UNBLOCK_SOURCE, in_addr, IP_RECVOPTS, IP_RETOPTS, IP_MULTICAST_LOOP, ip_mreqn,
@@ -21,13 +21,14 @@ use cty::{
     IPV6_DROP_MEMBERSHIP, IPV6_UNICAST_HOPS, IPV6_V6ONLY, AF_INET, IPV6_ADDRFORM,
     ipv6_mreq, in6_addr, IPV6_MULTICAST_LOOP,
     IPPROTO_TCP, TCP_CORK, IPPROTO_UDP, UDP_CORK,
+    SOL_NETLINK, NETLINK_ADD_MEMBERSHIP, NETLINK_DROP_MEMBERSHIP, ifreq,
 };
 use time_base::{Time};
 use core::{slice, mem};
 use syscall::{
     socket, bind, getsockname, getpeername, connect, close, shutdown, listen, sendto,
     sendmsg, recvfrom, recvmsg, getsockopt, setsockopt, ioctl_siocgstampns, ioctl_siocinq,
-    ioctl_siocoutq, accept4,
+    ioctl_siocoutq, accept4, ioctl_siocgifindex,
 };
 use str_one::{CStr, NoNullStr};
 use fd::{FdContainer};
@@ -803,6 +804,36 @@ impl Socket {
         rv!(setsockopt(self.fd, SOL_SOCKET, SO_BINDTODEVICE, cstr))
     }
 
+    /// Retrieves the index of a network interface.
+    ///
+    /// [argument, name]
+    /// The name of the interface.
+    ///
+    /// [return_value]
+    /// Returns the index of the interface.
+    ///
+    /// = Remarks
+    ///
+    /// The interface is looked up in the network namespace of the socket.
+    ///
+    /// = See also
+    ///
+    /// * link:man:netdevice(7) and SIOCGIFINDEX therein
+    pub fn interface_index<N: ?Sized>(&self, name: &N) -> Result<u32>
+        where N: AsRef<[u8]>,
+    {
+        let name = name.as_ref();
+        let mut buf = [0u8; IFNAMSIZ];
+        if name.len() >= buf.len() || name.find(|&b| b == 0).is_some() {
+            return Err(error::InvalidArgument);
+        }
+        mem::copy(&mut buf, name);
+        let mut req: ifreq = mem::zeroed();
+        req.ifr_name = unsafe { mem::cast(buf) };
+        try!(rv!(ioctl_siocgifindex(self.fd, &mut req)));
+        Ok(req.ifr_ifindex() as u32)
+    }
+
     /// Retrieves the name of the device this socket is bound to, if any.
     ///
     /// [argumnet, buf]
@@ -1486,7 +1517,10 @@ impl Socket {
     /// * link:man:socket(7) and SO_TIMESTAMPNS therein
     /// * link:lrs::socket::Socket::set_timestamp
     pub fn is_timestamp(&self) -> Result<bool> {
-        self.get_bool(SOL_SOCKET, SO_TIMESTAMPNS)
+        match self.get_bool(SOL_SOCKET, SO_TIMESTAMPNS_NEW) {
+            Err(error::OpNotSupported) => self.get_bool(SOL_SOCKET, SO_TIMESTAMPNS),
+            r => r,
+        }
     }
 
     /// Sets whether this socket sends timestamp control messages.
@@ -1494,12 +1528,20 @@ impl Socket {
     /// [argument, val]
     /// Whether this socket sends timestamp control messages.
     ///
+    /// = Remarks
+    ///
+    /// The timestamps have 64 bit seconds on all architectures. Kernels before 5.1 don't
+    /// support this and the legacy timestamps are used instead.
+    ///
     /// = See also
     ///
     /// * link:man:socket(7) and SO_TIMESTAMPNS therein
     /// * link:lrs::socket::Socket::is_timestamp
     pub fn set_timestamp(&self, val: bool) -> Result {
-        self.set_bool(SOL_SOCKET, SO_TIMESTAMPNS, val)
+        match self.set_bool(SOL_SOCKET, SO_TIMESTAMPNS_NEW, val) {
+            Err(error::OpNotSupported) => self.set_bool(SOL_SOCKET, SO_TIMESTAMPNS, val),
+            r => r,
+        }
     }
 
     /// Retrieves the kind of the socket.
@@ -2355,6 +2397,34 @@ impl Socket {
     pub fn udp_set_cork(&self, val: bool) -> Result {
         self.set_bool(IPPROTO_UDP, UDP_CORK, val)
     }
+
+    /// Joins a netlink multicast group.
+    ///
+    /// [argument, group]
+    /// The number of the group to join.
+    ///
+    /// = See also
+    ///
+    /// * link:man:netlink(7) and NETLINK_ADD_MEMBERSHIP therein
+    /// * link:lrs::socket::Socket::netlink_leave_group
+    pub fn netlink_join_group(&self, group: u32) -> Result {
+        let group = group as c_int;
+        rv!(setsockopt(self.fd, SOL_NETLINK, NETLINK_ADD_MEMBERSHIP, group.as_ref()))
+    }
+
+    /// Leaves a netlink multicast group.
+    ///
+    /// [argument, group]
+    /// The number of the group to leave.
+    ///
+    /// = See also
+    ///
+    /// * link:man:netlink(7) and NETLINK_DROP_MEMBERSHIP therein
+    /// * link:lrs::socket::Socket::netlink_join_group
+    pub fn netlink_leave_group(&self, group: u32) -> Result {
+        let group = group as c_int;
+        rv!(setsockopt(self.fd, SOL_NETLINK, NETLINK_DROP_MEMBERSHIP, group.as_ref()))
+    }
 }
 
 unsafe impl UndefState for Socket {
@@ -14,6 +14,7 @@ extern crate lrs_alloc    as alloc;
 extern crate lrs_fmt      as fmt;
 extern crate lrs_vec      as vec;
 extern crate lrs_box      as bx;
+extern crate lrs_iter     as iter;
 
 pub use c_string::{CString};
 pub use string::{String};
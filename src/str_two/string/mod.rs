@@ -8,7 +8,9 @@ use core::marker::{Leak};
 use base::undef::{UndefState};
 use fmt::{Debug, Display, Write};
 use vec::{Vec};
+use core::iter::{IntoIterator};
 use alloc::{self, MemPool};
+use iter::{FromIter};
 
 /// An owned UTF-8 string.
 pub struct String<Heap: MemPool+?Sized = alloc::Heap>(Vec<u8, Heap>);
@@ -91,6 +93,34 @@ impl<H: ?Sized> String<H>
     }
 }
 
+impl<H> FromIter<char> for String<H>
+    where H: MemPool+OutOf,
+{
+    fn from_iter<I>(iter: I) -> Result<String<H>>
+        where I: IntoIterator<Item=char>,
+    {
+        let mut s = String::new();
+        for c in iter {
+            try!(s.push_char(c));
+        }
+        Ok(s)
+    }
+}
+
+impl<'a, H> FromIter<&'a str> for String<H>
+    where H: MemPool+OutOf,
+{
+    fn from_iter<I>(iter: I) -> Result<String<H>>
+        where I: IntoIterator<Item=&'a str>,
+    {
+        let mut s = String::new();
+        for part in iter {
+            try!(s.push(part));
+        }
+        Ok(s)
+    }
+}
+
 unsafe impl<H> UndefState for String<H>
     where H: MemPool,
 {
@@ -33,13 +33,17 @@ use cty::{
     TIOCGPGRP, TCFLSH, TIOCOUTQ, TCXONC, TCGETS2, termios2, TCSETS2, mq_attr, sched_attr,
     __user_cap_data_struct, __user_cap_header_struct, _LINUX_CAPABILITY_VERSION_3,
     PR_CAPBSET_READ, PR_CAPBSET_DROP, PR_GET_KEEPCAPS, PR_SET_KEEPCAPS,
-    SECCOMP_SET_MODE_STRICT,
+    SECCOMP_SET_MODE_STRICT, io_uring_params, PR_SET_NO_NEW_PRIVS,
+    SECCOMP_SET_MODE_FILTER, sock_fprog, SIOCGIFINDEX, ifreq, FAN_CLOEXEC, timeval,
+    timezone, CLOCK_REALTIME, statx, __kernel_sock_timeval,
 };
 
 pub use r::{StatType, StatfsType};
 
 mod std { pub use base::std::*; pub use cty; }
 
+pub mod vdso;
+
 // XXX: iovec _MUST_ be the same as &mut [d8]
 //      We now have a test for that.
 //
@@ -844,6 +848,38 @@ pub fn fstatat(dir: c_int, file: &CStr, buf: &mut StatType, flags: c_int) -> c_i
     unsafe { r::fstatat(dir, file.as_ptr(), buf, flags) }
 }
 
+/// Retrieves extended information about a file relative to a file descriptor.
+///
+/// [argument, dir]
+/// The directory relative to which relative paths will be interpreted.
+///
+/// [argument, file]
+/// The path of the file.
+///
+/// [argument, flags]
+/// Flags to use while retrieving the information.
+///
+/// [argument, mask]
+/// The fields that should be retrieved.
+///
+/// [argument, buf]
+/// Where the information will be stored.
+///
+/// [return_value]
+/// Returns success or an error value.
+///
+/// = Remarks
+///
+/// The timestamps have 64 bit seconds fields on all architectures.
+///
+/// = See also
+///
+/// * link:man:statx(2)
+pub fn statx(dir: c_int, file: &CStr, flags: c_int, mask: c_uint,
+             buf: &mut statx) -> c_int {
+    unsafe { r::statx(dir, file.as_ptr(), flags as k_uint, mask as k_uint, buf) }
+}
+
 /// Checks whether a file relative to a file descriptor can be accessed.
 ///
 /// [argument, dir]
@@ -1401,6 +1437,11 @@ pub fn flistxattr(fd: c_int, list: &mut [d8]) -> ssize_t {
 ///
 /// * link:man:clock_getres(2)
 pub fn clock_getres(clock: clockid_t, res: &mut timespec) -> c_int {
+    let f = vdso::get().clock_getres;
+    if f != 0 {
+        let f: extern fn(clockid_t, *mut timespec) -> c_int = unsafe { mem::cast(f) };
+        return f(clock, res);
+    }
     unsafe { r::clock_getres(clock, res) }
 }
 
@@ -1419,9 +1460,110 @@ pub fn clock_getres(clock: clockid_t, res: &mut timespec) -> c_int {
 ///
 /// * link:man:clock_gettime(2)
 pub fn clock_gettime(clock: clockid_t, res: &mut timespec) -> c_int {
+    let f = vdso::get().clock_gettime;
+    if f != 0 {
+        let f: extern fn(clockid_t, *mut timespec) -> c_int = unsafe { mem::cast(f) };
+        return f(clock, res);
+    }
     unsafe { r::clock_gettime(clock, res) }
 }
 
+/// Retrieves the time of the real-time clock in microseconds.
+///
+/// [argument, tv]
+/// The place in which the time will be stored.
+///
+/// [argument, tz]
+/// The place in which the obsolete timezone information will be stored.
+///
+/// [return_value]
+/// Returns success or an error value.
+///
+/// = Remarks
+///
+/// On 32 bit architectures, the time is retrieved with `clock_gettime` because the
+/// legacy function only returns 32 bit seconds.
+///
+/// = See also
+///
+/// * link:man:gettimeofday(2)
+#[cfg(target_pointer_width = "64")]
+pub fn gettimeofday(tv: &mut __kernel_sock_timeval, tz: Option<&mut timezone>) -> c_int {
+    // `timeval` has the same layout on 64 bit architectures.
+    let tv = tv as *mut _ as *mut timeval;
+    let tz = tz.map(|t| t as *mut _).unwrap_or(0 as *mut _);
+    let f = vdso::get().gettimeofday;
+    if f != 0 {
+        let f: extern fn(*mut timeval, *mut timezone) -> c_int = unsafe { mem::cast(f) };
+        return f(tv, tz);
+    }
+    unsafe { r::gettimeofday(tv, tz) }
+}
+
+/// Retrieves the time of the real-time clock in microseconds.
+///
+/// [argument, tv]
+/// The place in which the time will be stored.
+///
+/// [argument, tz]
+/// The place in which the obsolete timezone information will be stored.
+///
+/// [return_value]
+/// Returns success or an error value.
+///
+/// = Remarks
+///
+/// On 32 bit architectures, the time is retrieved with `clock_gettime` because the
+/// legacy function only returns 32 bit seconds.
+///
+/// = See also
+///
+/// * link:man:gettimeofday(2)
+#[cfg(target_pointer_width = "32")]
+pub fn gettimeofday(tv: &mut __kernel_sock_timeval, tz: Option<&mut timezone>) -> c_int {
+    if let Some(tz) = tz {
+        let res = unsafe { r::gettimeofday(0 as *mut timeval, tz) };
+        if res < 0 {
+            return res;
+        }
+    }
+    let mut ts: timespec = mem::zeroed();
+    let res = clock_gettime(CLOCK_REALTIME, &mut ts);
+    if res < 0 {
+        return res;
+    }
+    tv.tv_sec = ts.tv_sec;
+    tv.tv_usec = ts.tv_nsec / 1000;
+    0
+}
+
+/// Retrieves the time of the real-time clock in seconds.
+///
+/// [return_value]
+/// Returns the number of seconds since the epoch.
+///
+/// = Remarks
+///
+/// If the vDSO does not provide this function or the architecture has 32 bit pointers,
+/// the time is retrieved with `clock_gettime`.
+///
+/// = See also
+///
+/// * link:man:time(2)
+pub fn time() -> i64 {
+    if cfg!(target_pointer_width = "64") {
+        let f = vdso::get().time;
+        if f != 0 {
+            // `time_t` has 64 bits on 64 bit architectures.
+            let f: extern fn(*mut i64) -> i64 = unsafe { mem::cast(f) };
+            return f(0 as *mut _);
+        }
+    }
+    let mut ts: timespec = mem::zeroed();
+    clock_gettime(CLOCK_REALTIME, &mut ts);
+    ts.tv_sec
+}
+
 /// Sets the time of a clock.
 ///
 /// [argument, clock]
@@ -2454,10 +2596,52 @@ pub fn chdir(path: &CStr) -> c_int {
 ///
 /// * link:man:ioctl(2)
 /// * link:man:socket(7) and SIOCGSTAMP therein
+#[cfg(not(any(target_arch = "x86", target_arch = "arm")))]
 pub fn ioctl_siocgstampns(fd: c_int, time: &mut timespec) -> c_int {
     unsafe { r::ioctl(fd as k_uint, SIOCGSTAMPNS as k_uint, time as *mut _ as k_ulong) }
 }
 
+#[cfg(any(target_arch = "x86", target_arch = "arm"))]
+pub fn ioctl_siocgstampns(fd: c_int, time: &mut timespec) -> c_int {
+    use cty::{SIOCGSTAMPNS_NEW, old_timespec32, ENOTTY, EINVAL};
+
+    let rv = unsafe {
+        r::ioctl(fd as k_uint, SIOCGSTAMPNS_NEW as k_uint, time as *mut _ as k_ulong)
+    };
+    // Kernels before 5.1 only know the version with a 32 bit time_t.
+    if rv != -ENOTTY && rv != -EINVAL {
+        return rv;
+    }
+    let mut old = old_timespec32 { tv_sec: 0, tv_nsec: 0 };
+    let rv = unsafe {
+        r::ioctl(fd as k_uint, SIOCGSTAMPNS as k_uint, &mut old as *mut _ as k_ulong)
+    };
+    if rv == 0 {
+        time.tv_sec = old.tv_sec as i64;
+        time.tv_nsec = old.tv_nsec as i64;
+    }
+    rv
+}
+
+/// Executes ioctl with the SIOCGIFINDEX option.
+///
+/// [argument, fd]
+/// The file descriptor on which to operate.
+///
+/// [argument, req]
+/// The request containing the interface name and into which the index will be placed.
+///
+/// [return_value]
+/// Returns success or an error value.
+///
+/// = See also
+///
+/// * link:man:ioctl(2)
+/// * link:man:netdevice(7) and SIOCGIFINDEX therein
+pub fn ioctl_siocgifindex(fd: c_int, req: &mut ifreq) -> c_int {
+    unsafe { r::ioctl(fd as k_uint, SIOCGIFINDEX as k_uint, req as *mut _ as k_ulong) }
+}
+
 /// Executes ioctl with the SIOCINQ option.
 ///
 /// [argument, fd]
@@ -2823,6 +3007,59 @@ pub fn inotify_rm_watch(fd: c_int, wd: c_int) -> c_int {
     unsafe { r::inotify_rm_watch(fd, wd) }
 }
 
+/// Creates a new fanotify object.
+///
+/// [argument, flags]
+/// Flags to use when creating the object.
+///
+/// [argument, event_flags]
+/// The file status flags of the file descriptors created for events.
+///
+/// = Remarks
+///
+/// Unless lrs was compiled with the `no-auto-cloexec` flag, this function automatically
+/// adds the `FAN_CLOEXEC` flag to `flags` and the `O_CLOEXEC` flag to `event_flags`.
+///
+/// = See also
+///
+/// * link:man:fanotify_init(2)
+pub fn fanotify_init(mut flags: c_uint, mut event_flags: c_uint) -> c_int {
+    if cfg!(not(no_auto_cloexec)) {
+        flags |= FAN_CLOEXEC;
+        event_flags |= O_CLOEXEC as c_uint;
+    }
+    unsafe { r::fanotify_init(flags, event_flags) }
+}
+
+/// Adds, removes, or modifies a fanotify mark.
+///
+/// [argument, fd]
+/// The fd of the fanotify object.
+///
+/// [argument, flags]
+/// The operation to perform and the kind of the mark.
+///
+/// [argument, mask]
+/// The events to mark.
+///
+/// [argument, dir]
+/// The file descriptor relative to which relative paths are interpreted.
+///
+/// [argument, path]
+/// The path to mark. If this is `None`, `dir` itself is marked.
+///
+/// = See also
+///
+/// * link:man:fanotify_mark(2)
+pub fn fanotify_mark(fd: c_int, flags: c_uint, mask: u64, dir: c_int,
+                     path: Option<&CStr>) -> c_int {
+    let path = match path {
+        Some(p) => p.as_ptr(),
+        _ => 0 as *const c_char,
+    };
+    unsafe { r::fanotify_mark(fd, flags, mask, dir, path) }
+}
+
 /// Duplicates a file descriptor by replacing another one.
 ///
 /// [argument, oldfd]
@@ -3210,6 +3447,67 @@ pub fn tgkill(tgid: c_int, tid: c_int, sig: c_int) -> c_int {
     unsafe { r::tgkill(tgid, tid, sig) }
 }
 
+/// Creates a file descriptor that refers to a process.
+///
+/// [argument, pid]
+/// The process.
+///
+/// [argument, flags]
+/// Flags for the new file descriptor.
+///
+/// = Remarks
+///
+/// The file descriptor always has the close-on-exec flag set.
+///
+/// = See also
+///
+/// * link:man:pidfd_open(2)
+pub fn pidfd_open(pid: pid_t, flags: c_uint) -> c_int {
+    unsafe { r::pidfd_open(pid, flags) }
+}
+
+/// Sends a signal to a process referred to by a file descriptor.
+///
+/// [argument, pidfd]
+/// The file descriptor referring to the process.
+///
+/// [argument, sig]
+/// The signal to send.
+///
+/// [argument, info]
+/// Optional information that will be passed along with the signal.
+///
+/// [argument, flags]
+/// Reserved. Must be `0`.
+///
+/// = See also
+///
+/// * link:man:pidfd_send_signal(2)
+pub fn pidfd_send_signal(pidfd: c_int, sig: c_int, info: Option<&mut siginfo_t>,
+                         flags: c_uint) -> c_int {
+    let info = info.map(|i| i as *mut _).unwrap_or(0 as *mut _);
+    unsafe { r::pidfd_send_signal(pidfd, sig, info, flags) }
+}
+
+/// Duplicates a file descriptor of another process.
+///
+/// [argument, pidfd]
+/// The file descriptor referring to the other process.
+///
+/// [argument, fd]
+/// The file descriptor in the other process.
+///
+/// = Remarks
+///
+/// The new file descriptor always has the close-on-exec flag set.
+///
+/// = See also
+///
+/// * link:man:pidfd_getfd(2)
+pub fn pidfd_getfd(pidfd: c_int, fd: c_int) -> c_int {
+    unsafe { r::pidfd_getfd(pidfd, fd, 0) }
+}
+
 /// Returns the thread id of the calling thread.
 ///
 /// = See also
@@ -3976,6 +4274,15 @@ pub fn prctl_pr_set_keepcaps(keep: bool) -> c_int {
     unsafe { r::prctl(PR_SET_KEEPCAPS, keep as k_ulong, 0, 0, 0) }
 }
 
+/// Prevents this thread and its children from gaining privileges via execve.
+///
+/// = See also
+///
+/// * link:man:prctl(2) and PR_SET_NO_NEW_PRIVS therein
+pub fn prctl_pr_set_no_new_privs() -> c_int {
+    unsafe { r::prctl(PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) }
+}
+
 /// Disassociate parts of the thread's execution context.
 ///
 /// [argument, flags]
@@ -4002,6 +4309,12 @@ pub fn unshare(flags: c_int) -> c_int {
 pub fn getcpu(cpu: Option<&mut c_uint>, node: Option<&mut c_uint>) -> c_int {
     let cpu = cpu.map(|c| c as *mut _).unwrap_or(0 as *mut _);
     let node = node.map(|c| c as *mut _).unwrap_or(0 as *mut _);
+    let f = vdso::get().getcpu;
+    if f != 0 {
+        let f: extern fn(*mut c_uint, *mut c_uint, *mut c_void) -> c_int =
+                                                            unsafe { mem::cast(f) };
+        return f(cpu, node, 0 as *mut _);
+    }
     unsafe { r::getcpu(cpu, node, 0 as *mut _) }
 }
 
@@ -4029,6 +4342,21 @@ pub fn seccomp_seccomp_set_mode_strict() -> c_int {
     unsafe { r::seccomp(SECCOMP_SET_MODE_STRICT, 0, 0 as *mut _) }
 }
 
+/// Installs a seccomp filter for this thread.
+///
+/// [argument, prog]
+/// The filter program.
+///
+/// [argument, flags]
+/// Flags modifying the behavior of the call.
+///
+/// = See also
+///
+/// * link:man:seccomp(2) and SECCOMP_SET_MODE_FILTER therein
+pub fn seccomp_seccomp_set_mode_filter(prog: &sock_fprog, flags: c_uint) -> c_int {
+    unsafe { r::seccomp(SECCOMP_SET_MODE_FILTER, flags, prog as *const _ as *const _) }
+}
+
 /// Adds a swap file/device.
 ///
 /// [argument, path]
@@ -4105,3 +4433,92 @@ pub unsafe fn set_tid_address(tidptr: Option<&Atomic<c_int>>) -> c_int {
     let addr = tidptr.map(|t| t.as_ptr()).unwrap_or(0 as *mut _);
     r::set_tid_address(addr) as c_int
 }
+
+/// Creates an io_uring instance.
+///
+/// [argument, entries]
+/// The requested number of submission queue entries.
+///
+/// [argument, params]
+/// The setup parameters. Upon success, the kernel fills in the ring offsets.
+///
+/// [return_value]
+/// Returns the file descriptor of the instance or an error value.
+///
+/// = Remarks
+///
+/// The file descriptor always has the `close on exec` flag set.
+///
+/// = See also
+///
+/// * link:man:io_uring_setup(2)
+pub fn io_uring_setup(entries: u32, params: &mut io_uring_params) -> c_int {
+    unsafe { r::io_uring_setup(entries, params) }
+}
+
+/// Submits and/or waits for io_uring operations.
+///
+/// [argument, fd]
+/// The io_uring instance.
+///
+/// [argument, to_submit]
+/// The number of submission queue entries to submit.
+///
+/// [argument, min_complete]
+/// The minimum number of completions to wait for if `IORING_ENTER_GETEVENTS` is set.
+///
+/// [argument, flags]
+/// Flags for the operation.
+///
+/// [argument, sigmask]
+/// A set of signals that will be masked during the operation.
+///
+/// [return_value]
+/// Returns the number of submitted entries or an error value.
+///
+/// = Remarks
+///
+/// This is unsafe because the submitted entries contain raw pointers that the kernel will
+/// read from or write to.
+///
+/// = See also
+///
+/// * link:man:io_uring_enter(2)
+pub unsafe fn io_uring_enter(fd: c_int, to_submit: u32, min_complete: u32, flags: c_uint,
+                             sigmask: Option<&sigset_t>) -> c_int {
+    let (sigmask, size) = match sigmask {
+        Some(sigmask) => (sigmask as *const _, mem::size_of::<sigset_t>() as size_t),
+        _ => (0 as *const _, 0),
+    };
+    r::io_uring_enter(fd as k_uint, to_submit, min_complete, flags, sigmask, size)
+}
+
+/// Registers resources with an io_uring instance.
+///
+/// [argument, fd]
+/// The io_uring instance.
+///
+/// [argument, opcode]
+/// The operation to perform.
+///
+/// [argument, arg]
+/// The operation-specific argument.
+///
+/// [argument, nr_args]
+/// The number of elements pointed to by `arg`.
+///
+/// [return_value]
+/// Returns success or an error value.
+///
+/// = Remarks
+///
+/// This is unsafe because registered buffers can be accessed by the kernel until they are
+/// unregistered.
+///
+/// = See also
+///
+/// * link:man:io_uring_register(2)
+pub unsafe fn io_uring_register(fd: c_int, opcode: c_uint, arg: *mut c_void,
+                                nr_args: c_uint) -> c_int {
+    r::io_uring_register(fd as k_uint, opcode, arg, nr_args)
+}
@@ -0,0 +1,66 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Functions provided by the vDSO.
+//!
+//! = Remarks
+//!
+//! The table is filled in by the runtime before `main` is called. The wrappers in this
+//! crate use the functions in the table if they are available and fall back to real
+//! system calls otherwise.
+
+/// The addresses of the functions in the vDSO.
+///
+/// = Remarks
+///
+/// A value of `0` means that the function is not available. The clock functions must
+/// use the 64 bit `timespec` on all architectures.
+#[derive(Copy)]
+pub struct Vdso {
+    pub clock_gettime: usize,
+    pub clock_getres: usize,
+    pub gettimeofday: usize,
+    pub time: usize,
+    pub getcpu: usize,
+}
+
+impl Vdso {
+    /// Creates a table without any functions.
+    pub fn new() -> Vdso {
+        Vdso {
+            clock_gettime: 0,
+            clock_getres: 0,
+            gettimeofday: 0,
+            time: 0,
+            getcpu: 0,
+        }
+    }
+}
+
+static mut VDSO: Vdso = Vdso {
+    clock_gettime: 0,
+    clock_getres: 0,
+    gettimeofday: 0,
+    time: 0,
+    getcpu: 0,
+};
+
+/// Sets the vDSO function table.
+///
+/// [argument, vdso]
+/// The new table.
+///
+/// = Remarks
+///
+/// This must only be called before any other threads have been created and all
+/// addresses must point to functions with the signatures documented in
+/// link:man:vdso(7).
+pub unsafe fn set(vdso: Vdso) {
+    VDSO = vdso;
+}
+
+/// Returns the vDSO function table.
+pub fn get() -> &'static Vdso {
+    unsafe { &VDSO }
+}
@@ -43,6 +43,7 @@ pub use imp::{Builder, JoinGuard};
 pub mod ids;
 pub mod sched;
 pub mod cap;
+pub mod seccomp;
 pub mod at_exit_;
 
 /// Spawns a new thread.
@@ -0,0 +1,377 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Seccomp filters.
+//!
+//! = See also
+//!
+//! * link:man:seccomp(2)
+
+use base::prelude::*;
+use base::error::{self, Errno};
+use core::{cmp, mem};
+use fmt::{Debug, Write};
+use cty::{
+    sock_filter, sock_fprog, c_ushort, BPF_LD, BPF_W, BPF_ABS, BPF_JMP, BPF_JEQ, BPF_JGT,
+    BPF_JGE, BPF_K, BPF_RET, BPF_ALU, BPF_AND, BPF_MAXINSNS, SECCOMP_RET_ALLOW,
+    SECCOMP_RET_ERRNO, SECCOMP_RET_TRAP, SECCOMP_RET_KILL, SECCOMP_RET_DATA,
+};
+use syscall::{prctl_pr_set_no_new_privs, seccomp_seccomp_set_mode_filter};
+
+#[cfg(target_arch = "x86_64")]
+use cty::{__X32_SYSCALL_BIT, BPF_JSET};
+
+#[cfg(target_arch = "x86_64")] const ARCH: u32 = ::cty::AUDIT_ARCH_X86_64;
+#[cfg(target_arch = "x86")] const ARCH: u32 = ::cty::AUDIT_ARCH_I386;
+#[cfg(target_arch = "arm")] const ARCH: u32 = ::cty::AUDIT_ARCH_ARM;
+#[cfg(target_arch = "aarch64")] const ARCH: u32 = ::cty::AUDIT_ARCH_AARCH64;
+
+// Offsets in `seccomp_data`.
+const NR_OFF: u32 = 0;
+const ARCH_OFF: u32 = 4;
+const ARGS_OFF: u32 = 16;
+
+#[cfg(target_endian = "little")] const LO_OFF: u32 = 0;
+#[cfg(target_endian = "little")] const HI_OFF: u32 = 4;
+#[cfg(target_endian = "big")] const LO_OFF: u32 = 4;
+#[cfg(target_endian = "big")] const HI_OFF: u32 = 0;
+
+/// An action performed when a filter matches a system call.
+#[derive(Copy, Eq)]
+pub enum Action {
+    /// The system call is executed.
+    Allow,
+    /// The system call fails with an error.
+    ///
+    /// [field, 1]
+    /// The error returned by the system call.
+    Errno(Errno),
+    /// The thread receives a `SIGSYS` signal.
+    Trap,
+    /// The thread is killed.
+    Kill,
+}
+
+impl Action {
+    fn to_ret(self) -> u32 {
+        match self {
+            Action::Allow => SECCOMP_RET_ALLOW,
+            Action::Errno(e) => SECCOMP_RET_ERRNO | (e.0 as u32 & SECCOMP_RET_DATA),
+            Action::Trap => SECCOMP_RET_TRAP,
+            Action::Kill => SECCOMP_RET_KILL,
+        }
+    }
+}
+
+impl Debug for Action {
+    fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+        match *self {
+            Action::Allow => w.write_all(b"Allow").ignore_ok(),
+            Action::Errno(e) => write!(w, "Errno({:?})", e),
+            Action::Trap => w.write_all(b"Trap").ignore_ok(),
+            Action::Kill => w.write_all(b"Kill").ignore_ok(),
+        }
+    }
+}
+
+/// A comparison of a system call argument.
+///
+/// = Remarks
+///
+/// The arguments are compared as unsigned 64 bit integers.
+#[derive(Copy, Eq)]
+pub enum Cmp {
+    /// The argument is equal to the value.
+    Eq(u64),
+    /// The argument is not equal to the value.
+    Ne(u64),
+    /// The argument is less than the value.
+    Lt(u64),
+    /// The argument is less than or equal to the value.
+    Le(u64),
+    /// The argument is greater than the value.
+    Gt(u64),
+    /// The argument is greater than or equal to the value.
+    Ge(u64),
+    /// The argument is equal to the value after being masked.
+    ///
+    /// [field, 1]
+    /// The mask.
+    ///
+    /// [field, 2]
+    /// The value.
+    MaskedEq(u64, u64),
+}
+
+impl Cmp {
+    fn len(self) -> usize {
+        match self {
+            Cmp::Eq(..) | Cmp::Ne(..) => 4,
+            Cmp::Lt(..) | Cmp::Le(..) | Cmp::Gt(..) | Cmp::Ge(..) => 5,
+            Cmp::MaskedEq(..) => 6,
+        }
+    }
+}
+
+/// A comparison of a system call argument.
+///
+/// [field, 1]
+/// The index of the argument in [0, 6).
+///
+/// [field, 2]
+/// The comparison.
+#[derive(Copy, Eq)]
+pub struct ArgCmp(pub usize, pub Cmp);
+
+/// A BPF instruction.
+#[repr(C)]
+#[derive(Pod, Eq)]
+pub struct Instruction(sock_filter);
+
+impl Instruction {
+    /// Creates a new zeroed instruction.
+    pub fn new() -> Instruction {
+        mem::zeroed()
+    }
+}
+
+fn stmt(code: u8, k: u32) -> sock_filter {
+    sock_filter { code: code as u16, jt: 0, jf: 0, k: k }
+}
+
+fn hi(v: u64) -> u32 { (v >> 32) as u32 }
+fn lo(v: u64) -> u32 { v as u32 }
+
+/// A seccomp filter builder.
+///
+/// = Remarks
+///
+/// The filter first checks the architecture of the system call. System calls made via
+/// a different architecture, e.g., i686 system calls on x86_64, kill the thread. On x32,
+/// only x32 system calls are accepted and the `__X32_SYSCALL_BIT` is added to the
+/// system call numbers automatically.
+///
+/// The rules are checked in the order in which they were added and the action of the
+/// first matching rule is performed.
+///
+/// = Examples
+///
+/// ----
+/// let mut buf: [Instruction; 32] = mem::zeroed();
+/// let mut filter = Filter::new(&mut buf).unwrap();
+/// filter.add(cty::__NR_getppid, Action::Errno(error::NotPermitted)).unwrap();
+/// filter.install(Action::Allow).unwrap();
+/// ----
+///
+/// = See also
+///
+/// * link:man:seccomp(2) and SECCOMP_SET_MODE_FILTER therein
+pub struct Filter<'a> {
+    buf: &'a mut [Instruction],
+    len: usize,
+}
+
+impl<'a> Filter<'a> {
+    /// Creates a new filter.
+    ///
+    /// [argument, buf]
+    /// The buffer in which the instructions will be stored.
+    ///
+    /// = Remarks
+    ///
+    /// The buffer must have space for at least seven instructions.
+    pub fn new(buf: &'a mut [Instruction]) -> Result<Filter<'a>> {
+        let mut filter = Filter { buf: buf, len: 0 };
+        try!(filter.reserve(6));
+        filter.push(stmt(BPF_LD | BPF_W | BPF_ABS, ARCH_OFF));
+        filter.push(sock_filter {
+            code: (BPF_JMP | BPF_JEQ | BPF_K) as u16, jt: 1, jf: 0, k: ARCH,
+        });
+        filter.push(stmt(BPF_RET | BPF_K, SECCOMP_RET_KILL));
+        filter.push(stmt(BPF_LD | BPF_W | BPF_ABS, NR_OFF));
+        filter.arch_abi();
+        Ok(filter)
+    }
+
+    #[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
+    fn arch_abi(&mut self) {
+        self.push(sock_filter {
+            code: (BPF_JMP | BPF_JGE | BPF_K) as u16, jt: 0, jf: 1,
+            k: __X32_SYSCALL_BIT as u32,
+        });
+        self.push(stmt(BPF_RET | BPF_K, SECCOMP_RET_KILL));
+    }
+
+    #[cfg(all(target_arch = "x86_64", target_pointer_width = "32"))]
+    fn arch_abi(&mut self) {
+        self.push(sock_filter {
+            code: (BPF_JMP | BPF_JSET | BPF_K) as u16, jt: 1, jf: 0,
+            k: __X32_SYSCALL_BIT as u32,
+        });
+        self.push(stmt(BPF_RET | BPF_K, SECCOMP_RET_KILL));
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    fn arch_abi(&mut self) { }
+
+    #[cfg(all(target_arch = "x86_64", target_pointer_width = "32"))]
+    fn nr(nr: usize) -> u32 { nr as u32 | __X32_SYSCALL_BIT as u32 }
+
+    #[cfg(not(all(target_arch = "x86_64", target_pointer_width = "32")))]
+    fn nr(nr: usize) -> u32 { nr as u32 }
+
+    /// Checks that `n` instructions and the final return fit into the buffer.
+    fn reserve(&self, n: usize) -> Result {
+        let cap = cmp::min(self.buf.len(), BPF_MAXINSNS);
+        match self.len + n < cap {
+            true => Ok(()),
+            false => Err(error::NoMemory),
+        }
+    }
+
+    fn push(&mut self, insn: sock_filter) {
+        self.buf[self.len] = Instruction(insn);
+        self.len += 1;
+    }
+
+    /// Appends a conditional jump. `jt` and `jf` are the absolute positions of the
+    /// targets.
+    fn jump(&mut self, op: u8, k: u32, jt: usize, jf: usize) {
+        let pos = self.len + 1;
+        self.push(sock_filter {
+            code: (BPF_JMP | op | BPF_K) as u16,
+            jt: (jt - pos) as u8,
+            jf: (jf - pos) as u8,
+            k: k,
+        });
+    }
+
+    fn load_arg(&mut self, arg: usize, off: u32) {
+        self.push(stmt(BPF_LD | BPF_W | BPF_ABS, ARGS_OFF + 8 * arg as u32 + off));
+    }
+
+    /// Appends a comparison that jumps to `fail` if it does not hold.
+    fn cmp(&mut self, arg: ArgCmp, fail: usize) {
+        let ArgCmp(idx, cmp) = arg;
+        let here = self.len;
+        let pass = here + cmp.len();
+        match cmp {
+            Cmp::Eq(v) => {
+                self.load_arg(idx, HI_OFF);
+                self.jump(BPF_JEQ, hi(v), here + 2, fail);
+                self.load_arg(idx, LO_OFF);
+                self.jump(BPF_JEQ, lo(v), pass, fail);
+            },
+            Cmp::Ne(v) => {
+                self.load_arg(idx, HI_OFF);
+                self.jump(BPF_JEQ, hi(v), here + 2, pass);
+                self.load_arg(idx, LO_OFF);
+                self.jump(BPF_JEQ, lo(v), fail, pass);
+            },
+            Cmp::MaskedEq(mask, v) => {
+                self.load_arg(idx, HI_OFF);
+                self.push(stmt(BPF_ALU | BPF_AND | BPF_K, hi(mask)));
+                self.jump(BPF_JEQ, hi(v), here + 3, fail);
+                self.load_arg(idx, LO_OFF);
+                self.push(stmt(BPF_ALU | BPF_AND | BPF_K, lo(mask)));
+                self.jump(BPF_JEQ, lo(v), pass, fail);
+            },
+            Cmp::Lt(v) | Cmp::Le(v) | Cmp::Gt(v) | Cmp::Ge(v) => {
+                let (greater, less) = match cmp {
+                    Cmp::Gt(..) | Cmp::Ge(..) => (pass, fail),
+                    _ => (fail, pass),
+                };
+                let op = match cmp {
+                    Cmp::Gt(..) | Cmp::Le(..) => BPF_JGT,
+                    _ => BPF_JGE,
+                };
+                self.load_arg(idx, HI_OFF);
+                self.jump(BPF_JGT, hi(v), greater, here + 2);
+                self.jump(BPF_JEQ, hi(v), here + 3, less);
+                self.load_arg(idx, LO_OFF);
+                self.jump(op, lo(v), greater, less);
+            },
+        }
+    }
+
+    /// Adds a rule for a system call.
+    ///
+    /// [argument, nr]
+    /// The number of the system call, e.g., `cty::__NR_read`.
+    ///
+    /// [argument, action]
+    /// The action performed when the system call is made.
+    pub fn add(&mut self, nr: usize, action: Action) -> Result {
+        self.add_if(nr, &[], action)
+    }
+
+    /// Adds a rule for a system call with argument comparisons.
+    ///
+    /// [argument, nr]
+    /// The number of the system call, e.g., `cty::__NR_read`.
+    ///
+    /// [argument, args]
+    /// The comparisons that must all hold for the rule to match.
+    ///
+    /// [argument, action]
+    /// The action performed when the system call is made.
+    pub fn add_if(&mut self, nr: usize, args: &[ArgCmp], action: Action) -> Result {
+        let mut len = 2;
+        for &ArgCmp(idx, cmp) in args {
+            if idx >= 6 {
+                return Err(error::InvalidArgument);
+            }
+            len += cmp.len();
+        }
+        if args.len() > 0 {
+            // The number of the system call has to be reloaded after a failed
+            // comparison.
+            len += 1;
+        }
+        // Jump offsets are limited to 8 bits.
+        if len > 256 {
+            return Err(error::InvalidArgument);
+        }
+        try!(self.reserve(len));
+
+        let start = self.len;
+        let end = start + len;
+        self.jump(BPF_JEQ, Filter::nr(nr), start + 1, end);
+        for &arg in args {
+            self.cmp(arg, end - 1);
+        }
+        self.push(stmt(BPF_RET | BPF_K, action.to_ret()));
+        if args.len() > 0 {
+            self.push(stmt(BPF_LD | BPF_W | BPF_ABS, NR_OFF));
+        }
+        Ok(())
+    }
+
+    /// Installs the filter for this thread.
+    ///
+    /// [argument, default]
+    /// The action performed for system calls that don't match any rule.
+    ///
+    /// = Remarks
+    ///
+    /// :execve: link:man:execve(2)
+    ///
+    /// This also prevents the thread from gaining privileges via {execve}. The filter is
+    /// inherited by child processes and threads created afterwards and cannot be
+    /// removed.
+    ///
+    /// = See also
+    ///
+    /// * link:man:seccomp(2) and SECCOMP_SET_MODE_FILTER therein
+    /// * link:man:prctl(2) and PR_SET_NO_NEW_PRIVS therein
+    pub fn install(mut self, default: Action) -> Result {
+        self.push(stmt(BPF_RET | BPF_K, default.to_ret()));
+        let prog = sock_fprog {
+            len: self.len as c_ushort,
+            filter: self.buf.as_mut_ptr() as *mut sock_filter,
+        };
+        try!(rv!(prctl_pr_set_no_new_privs()));
+        rv!(seccomp_seccomp_set_mode_filter(&prog, 0))
+    }
+}
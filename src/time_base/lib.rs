@@ -20,7 +20,7 @@ mod std { pub use fmt::std::*; pub use {cty}; }
 
 use core::ops::{Add, Sub, PartialOrd, Ordering};
 use core::cmp::{Ord};
-use cty::{timespec, time_t, k_long};
+use cty::{timespec};
 use fmt::{Debug, Write};
 
 #[cfg(not(freestanding))] pub mod clock;
@@ -28,15 +28,15 @@ use fmt::{Debug, Write};
 
 pub fn time_from_timespec(t: timespec) -> Time {
     Time {
-        seconds:     t.tv_sec  as i64,
-        nanoseconds: t.tv_nsec as i64,
+        seconds:     t.tv_sec,
+        nanoseconds: t.tv_nsec,
     }
 }
 
 pub fn time_to_timespec(d: Time) -> timespec {
     timespec {
-        tv_sec:  d.seconds     as time_t,
-        tv_nsec: d.nanoseconds as k_long,
+        tv_sec:  d.seconds,
+        tv_nsec: d.nanoseconds,
     }
 }
 
@@ -7,7 +7,9 @@ use core::ops::{Ordering};
 use core::cmp::{Ord};
 
 use super::{Zone, DateTime, Weekday, Time};
+use tz::{Rule};
 
+const NANOS_PER_SEC:        i64 = 1_000_000_000;
 const SECS_PER_MIN:         i64 = 60;
 const MINS_PER_HOUR:        i64 = 60;
 const HOURS_PER_DAY:        i64 = 24;
@@ -44,8 +46,74 @@ fn leap_years_to(year: i64) -> i64 {
     }
 }
 
+/// Returns the number of days between the epoch and the first day of a year.
+pub fn year_start(year: i64) -> i64 {
+    (year - EPOCH_YEAR) * DAYS_PER_NORMAL_YEAR + leap_years_to(year - 1) -
+        leap_years_to(EPOCH_YEAR - 1)
+}
+
+/// Returns the year that contains a day since the epoch.
+pub fn year_of(days: i64) -> i64 {
+    // 146097 is the number of days in 400 years.
+    let mut year = EPOCH_YEAR + days / 146097 * 400 + days % 146097 * 400 / 146097;
+    while year_start(year) > days {
+        year -= 1;
+    }
+    while year_start(year + 1) <= days {
+        year += 1;
+    }
+    year
+}
+
+/// Returns the day in the year of the first day of a month. Both start at 0.
+pub fn month_start(year: i64, month: usize) -> i64 {
+    let mut days = 0;
+    for m in 0..month {
+        days += DAYS_PER_MONTH[is_leap(year)][m] as i64;
+    }
+    days
+}
+
+/// Returns the weekday of a day in a year as a number in [0, 7) where 0 is Monday.
+/// `days` starts at 0.
+pub fn weekday_num(year: i64, days: i64) -> i64 {
+    ((((EPOCH_WEEK_DAY as i64) + ((year - EPOCH_YEAR) % DAYS_PER_WEEK) *
+       (DAYS_PER_NORMAL_YEAR % DAYS_PER_WEEK) + leap_years_to(year - 1) -
+       leap_years_to(EPOCH_YEAR - 1) + days) % DAYS_PER_WEEK) + DAYS_PER_WEEK) %
+       DAYS_PER_WEEK
+}
+
+/// Returns the weekday of a day in a year. `days` starts at 0.
+fn weekday(year: i64, days: i64) -> Weekday {
+    match weekday_num(year, days) {
+        0 => Weekday::Monday,
+        1 => Weekday::Tuesday,
+        2 => Weekday::Wednesday,
+        3 => Weekday::Thursday,
+        4 => Weekday::Friday,
+        5 => Weekday::Saturday,
+        _ => Weekday::Sunday,
+    }
+}
+
+/// Returns the number of days in a month. `month` starts at 1.
+pub fn days_in_month(year: i64, month: i8) -> i8 {
+    DAYS_PER_MONTH[is_leap(year)][month as usize - 1]
+}
+
+/// Calculates `day_in_year` and `weekday` from `year`, `month`, and `day`.
+pub fn set_day_fields(date: &mut DateTime) {
+    let days = month_start(date.year, date.month as usize - 1) + date.day as i64 - 1;
+    date.day_in_year = days as i16 + 1;
+    date.weekday = weekday(date.year, days);
+}
+
 pub fn explode(zone: &Zone, time: i64) -> DateTime {
-    let state = if zone.transitions.len() == 0 || time < zone.transitions[0].0 {
+    let num = zone.transitions.len();
+    let state = if zone.rule != Rule::None
+                    && (num == 0 || time > zone.transitions[num - 1].0) {
+        zone.rule.state(time)
+    } else if num == 0 || time < zone.transitions[0].0 {
         match zone.states.find(|s| !s.1) {
             Some(i) => zone.states[i],
             _ => zone.states[0],
@@ -98,19 +166,7 @@ pub fn explode(zone: &Zone, time: i64) -> DateTime {
         year -= 1;
     }
 
-    let weekday = ((((EPOCH_WEEK_DAY as i64) + ((year - EPOCH_YEAR) % DAYS_PER_WEEK) *
-                    (DAYS_PER_NORMAL_YEAR % DAYS_PER_WEEK) + leap_years_to(year - 1) -
-                    leap_years_to(EPOCH_YEAR - 1) + days) % DAYS_PER_WEEK) +
-                    DAYS_PER_WEEK) % DAYS_PER_WEEK;
-    let weekday = match weekday {
-        0 => Weekday::Monday,
-        1 => Weekday::Tuesday,
-        2 => Weekday::Wednesday,
-        3 => Weekday::Thursday,
-        4 => Weekday::Friday,
-        5 => Weekday::Saturday,
-        _ => Weekday::Sunday,
-    };
+    let weekday = weekday(year, days);
 
     let (hour, secs) = secs.div_rem(SECS_PER_HOUR);
     let (min, mut secs) = secs.div_rem(SECS_PER_MIN);
@@ -135,6 +191,7 @@ pub fn explode(zone: &Zone, time: i64) -> DateTime {
         hour:        hour as i8,
         minute:      min as i8,
         second:      secs as i8,
+        nanosecond:  0,
         weekday:     weekday,
         summer_time: state.1,
     }
@@ -145,6 +202,14 @@ pub fn compact(zone: &Zone, mut date: DateTime) -> (DateTime, Time) {
 
     let transition = find_transition(zone, &date);
 
+    let after_last = match transition {
+        Some(n) => n + 1 == zone.transitions.len(),
+        _ => zone.transitions.len() == 0,
+    };
+    if after_last && zone.rule != Rule::None {
+        return compact_rule(zone, date);
+    }
+
     let (state, mut lo, mut hi) = match transition {
         Some(n) => {
             let (time, idx) = zone.transitions[n];
@@ -183,7 +248,41 @@ pub fn compact(zone: &Zone, mut date: DateTime) -> (DateTime, Time) {
         }
     }
 
-    (res, Time { seconds: secs, nanoseconds: 0 })
+    res.nanosecond = date.nanosecond;
+    (res, Time { seconds: secs, nanoseconds: date.nanosecond as i64 })
+}
+
+/// Compacts a normalized date that falls after the last transition of a zone with a
+/// rule.
+fn compact_rule(zone: &Zone, date: DateTime) -> (DateTime, Time) {
+    let days = year_start(date.year) + month_start(date.year, date.month as usize - 1)
+                    + date.day as i64 - 1;
+    let local = days * SECS_PER_DAY + date.hour as i64 * SECS_PER_HOUR
+                    + date.minute as i64 * SECS_PER_MIN + date.second as i64;
+
+    let (std, summer) = match zone.rule {
+        Rule::Summer(std, summer) => (std, summer.offset),
+        Rule::Fixed(std) => (std, std),
+        Rule::None => (0, 0),
+    };
+
+    // Summer time is tried first so that dates that occur twice resolve to the earlier
+    // time. Dates that fall into the hole at the start of summer time are interpreted
+    // in standard time.
+    let mut secs = local - std;
+    for &offset in [summer, std].iter() {
+        if zone.rule.state(local - offset).0 == offset {
+            secs = local - offset;
+            break;
+        }
+    }
+    if let Some(i) = zone.leap_seconds.find_reverse(|l| secs >= l.0) {
+        secs += zone.leap_seconds[i].1;
+    }
+
+    let mut res = explode(zone, secs);
+    res.nanosecond = date.nanosecond;
+    (res, Time { seconds: secs, nanoseconds: date.nanosecond as i64 })
 }
 
 /// Find the largest transition such that the transition time expands to a DateTime which
@@ -218,9 +317,16 @@ fn dt_cmp(one: &DateTime, two: &DateTime) -> Ordering {
     Ordering::Equal
 }
 
-/// Normalizes `second`, `minute`, `hour`, `day`, `month`, and `year`.
+/// Normalizes `nanosecond`, `second`, `minute`, `hour`, `day`, `month`, and `year`.
 fn normalize(date: &mut DateTime) {
-    let (mut min, mut sec) = (date.second as i64).div_rem(SECS_PER_MIN);
+    let (mut sec, mut nano) = (date.nanosecond as i64).div_rem(NANOS_PER_SEC);
+    if nano < 0 {
+        sec -= 1;
+        nano += NANOS_PER_SEC;
+    }
+    date.nanosecond = nano as i32;
+
+    let (mut min, mut sec) = (date.second as i64 + sec).div_rem(SECS_PER_MIN);
     if sec < 0 {
         min -= 1;
         sec += SECS_PER_MIN;
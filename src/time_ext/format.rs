@@ -0,0 +1,170 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use base::{error};
+use fmt::{Write};
+
+use super::{DateTime, Weekday};
+
+pub static WEEKDAYS: [&'static str; 7] = [
+    "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday",
+];
+
+pub static MONTHS: [&'static str; 12] = [
+    "January", "February", "March", "April", "May", "June", "July", "August",
+    "September", "October", "November", "December",
+];
+
+/// Returns the weekday as a number in [0, 7) starting at Monday.
+pub fn weekday_num(weekday: Weekday) -> usize {
+    match weekday {
+        Weekday::Monday    => 0,
+        Weekday::Tuesday   => 1,
+        Weekday::Wednesday => 2,
+        Weekday::Thursday  => 3,
+        Weekday::Friday    => 4,
+        Weekday::Saturday  => 5,
+        Weekday::Sunday    => 6,
+    }
+}
+
+fn write_str<W: Write+?Sized>(w: &mut W, s: &str) -> Result {
+    w.write_all(s.as_ref()).ignore_ok()
+}
+
+/// Writes a number that is padded to `width` digits.
+fn write_num<W: Write+?Sized>(w: &mut W, num: i64, width: usize, pad: u8) -> Result {
+    let mut buf = [pad; 21];
+    let mut pos = buf.len();
+    let mut n = num.abs() as u64;
+    loop {
+        pos -= 1;
+        buf[pos] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    if buf.len() - pos < width {
+        pos = buf.len() - width;
+    }
+    if num < 0 {
+        pos -= 1;
+        buf[pos] = b'-';
+    }
+    w.write_all(&buf[pos..]).ignore_ok()
+}
+
+/// Writes the first `digits` digits of the nanoseconds.
+fn write_fraction<W: Write+?Sized>(w: &mut W, nanosecond: i32, digits: usize) -> Result {
+    let mut buf = [0; 9];
+    let mut n = nanosecond;
+    for i in 0..9 {
+        buf[8 - i] = b'0' + (n % 10) as u8;
+        n /= 10;
+    }
+    w.write_all(&buf[..digits]).ignore_ok()
+}
+
+fn write_offset<W: Write+?Sized>(w: &mut W, offset: i64, colon: bool) -> Result {
+    let minutes = offset.abs() / 60;
+    try!(write_str(w, if offset < 0 { "-" } else { "+" }));
+    try!(write_num(w, minutes / 60, 2, b'0'));
+    if colon {
+        try!(write_str(w, ":"));
+    }
+    write_num(w, minutes % 60, 2, b'0')
+}
+
+fn write_time<W: Write+?Sized>(w: &mut W, date: &DateTime, seconds: bool) -> Result {
+    try!(write_num(w, date.hour as i64, 2, b'0'));
+    try!(write_str(w, ":"));
+    try!(write_num(w, date.minute as i64, 2, b'0'));
+    if seconds {
+        try!(write_str(w, ":"));
+        try!(write_num(w, date.second as i64, 2, b'0'));
+    }
+    Ok(())
+}
+
+fn hour12(hour: i8) -> i64 {
+    match hour % 12 {
+        0 => 12,
+        h => h as i64,
+    }
+}
+
+pub fn format<W: Write+?Sized>(date: &DateTime, w: &mut W, fmt: &[u8]) -> Result {
+    let weekday = weekday_num(date.weekday);
+    let month = (date.month as usize).wrapping_sub(1) % 12;
+
+    let mut pos = 0;
+    while pos < fmt.len() {
+        let start = pos;
+        while pos < fmt.len() && fmt[pos] != b'%' {
+            pos += 1;
+        }
+        if start < pos {
+            try!(w.write_all(&fmt[start..pos]));
+        }
+        if pos == fmt.len() {
+            break;
+        }
+        pos += 1;
+
+        // Optional number of fractional digits or the colon of `%:z`.
+        let mut digits = 9;
+        let mut colon = false;
+        if pos < fmt.len() && b'1' <= fmt[pos] && fmt[pos] <= b'9' {
+            digits = (fmt[pos] - b'0') as usize;
+            pos += 1;
+        } else if pos < fmt.len() && fmt[pos] == b':' {
+            colon = true;
+            pos += 1;
+        }
+        if pos == fmt.len() {
+            return Err(error::InvalidArgument);
+        }
+        let spec = fmt[pos];
+        pos += 1;
+        if (digits != 9 && spec != b'f') || (colon && spec != b'z') {
+            return Err(error::InvalidArgument);
+        }
+
+        try!(match spec {
+            b'a' => write_str(w, &WEEKDAYS[weekday][..3]),
+            b'A' => write_str(w, WEEKDAYS[weekday]),
+            b'b' | b'h' => write_str(w, &MONTHS[month][..3]),
+            b'B' => write_str(w, MONTHS[month]),
+            b'C' => write_num(w, date.year / 100, 2, b'0'),
+            b'd' => write_num(w, date.day as i64, 2, b'0'),
+            b'D' => format(date, w, b"%m/%d/%y"),
+            b'e' => write_num(w, date.day as i64, 2, b' '),
+            b'f' => write_fraction(w, date.nanosecond, digits),
+            b'F' => format(date, w, b"%Y-%m-%d"),
+            b'H' => write_num(w, date.hour as i64, 2, b'0'),
+            b'I' => write_num(w, hour12(date.hour), 2, b'0'),
+            b'j' => write_num(w, date.day_in_year as i64, 3, b'0'),
+            b'k' => write_num(w, date.hour as i64, 2, b' '),
+            b'l' => write_num(w, hour12(date.hour), 2, b' '),
+            b'm' => write_num(w, date.month as i64, 2, b'0'),
+            b'M' => write_num(w, date.minute as i64, 2, b'0'),
+            b'n' => write_str(w, "\n"),
+            b'p' => write_str(w, if date.hour < 12 { "AM" } else { "PM" }),
+            b'R' => write_time(w, date, false),
+            b'S' => write_num(w, date.second as i64, 2, b'0'),
+            b't' => write_str(w, "\t"),
+            b'T' => write_time(w, date, true),
+            b'u' => write_num(w, weekday as i64 + 1, 1, b'0'),
+            b'w' => write_num(w, (weekday as i64 + 1) % 7, 1, b'0'),
+            b'y' => write_num(w, (date.year % 100).abs(), 2, b'0'),
+            b'Y' => write_num(w, date.year, 4, b'0'),
+            b'z' => write_offset(w, date.offset, colon),
+            b'%' => write_str(w, "%"),
+            _ => Err(error::InvalidArgument),
+        });
+    }
+    Ok(())
+}
@@ -14,12 +14,14 @@ extern crate lrs_time_base as time_base;
 extern crate lrs_io as io;
 extern crate lrs_vec as vec;
 #[cfg(not(freestanding))] extern crate lrs_file as file;
+#[cfg(not(freestanding))] extern crate lrs_env as env;
 
 use base::prelude::*;
 mod std { pub use vec::std::*; }
 
 pub use time_base::{Time};
 
+use base::{error};
 use fmt::{Debug, Write};
 use vec::{Vec};
 
@@ -29,6 +31,28 @@ use vec::{Vec};
 
 mod parse;
 mod convert;
+mod format;
+mod rfc;
+mod tz;
+
+/// The format of RFC 3339 dates, e.g., `1985-04-12T23:20:50.52+01:00`.
+///
+/// = Remarks
+///
+/// Fractional seconds are not written. Use `%Y-%m-%dT%H:%M:%S.%3f%:z` to write
+/// milliseconds.
+pub const RFC3339: &'static [u8] = b"%Y-%m-%dT%H:%M:%S%:z";
+
+/// The format of RFC 2822 dates, e.g., `Fri, 21 Nov 1997 09:55:06 -0600`.
+pub const RFC2822: &'static [u8] = b"%a, %d %b %Y %H:%M:%S %z";
+
+/// The format of HTTP dates, e.g., `Sun, 06 Nov 1994 08:49:37 GMT`.
+///
+/// = Remarks
+///
+/// HTTP dates are always in UTC. The date must be expanded in the UTC zone before it
+/// is formatted with this format.
+pub const HTTP_DATE: &'static [u8] = b"%a, %d %b %Y %H:%M:%S GMT";
 
 /// A weekday.
 #[derive(Copy, Eq)]
@@ -68,20 +92,163 @@ pub struct DateTime {
     pub minute:      i8,
     /// The second.
     pub second:      i8,
+    /// The nanosecond.
+    pub nanosecond:  i32,
     /// The weekday.
     pub weekday:     Weekday,
     /// Whether the date falls into summer time.
     pub summer_time: bool,
 }
 
+impl DateTime {
+    /// Formats the date.
+    ///
+    /// [argument, w]
+    /// The writer into which the date is written.
+    ///
+    /// [argument, fmt]
+    /// The format.
+    ///
+    /// = Remarks
+    ///
+    /// The format is similar to the one used by `strftime`. The following conversion
+    /// specifications are supported:
+    ///
+    /// |===
+    /// | *Specification* | *Meaning*
+    ///
+    /// | `%a` | The abbreviated weekday name, e.g., `Mon`.
+    ///
+    /// | `%A` | The full weekday name, e.g., `Monday`.
+    ///
+    /// | `%b`, `%h` | The abbreviated month name, e.g., `Jan`.
+    ///
+    /// | `%B` | The full month name, e.g., `January`.
+    ///
+    /// | `%C` | The century as a two digit number.
+    ///
+    /// | `%d` | The day in the month as a two digit number.
+    ///
+    /// | `%D` | Equivalent to `%m/%d/%y`.
+    ///
+    /// | `%e` | The day in the month padded with a space.
+    ///
+    /// | `%f` | The nanoseconds as a nine digit number. `%Nf` writes only the first
+    /// `N` digits, e.g., `%3f` writes milliseconds.
+    ///
+    /// | `%F` | Equivalent to `%Y-%m-%d`.
+    ///
+    /// | `%H` | The hour as a two digit number.
+    ///
+    /// | `%I` | The hour on a 12-hour clock as a two digit number.
+    ///
+    /// | `%j` | The day in the year as a three digit number.
+    ///
+    /// | `%k` | The hour padded with a space.
+    ///
+    /// | `%l` | The hour on a 12-hour clock padded with a space.
+    ///
+    /// | `%m` | The month as a two digit number.
+    ///
+    /// | `%M` | The minute as a two digit number.
+    ///
+    /// | `%n` | A newline.
+    ///
+    /// | `%p` | `AM` or `PM`.
+    ///
+    /// | `%R` | Equivalent to `%H:%M`.
+    ///
+    /// | `%S` | The second as a two digit number.
+    ///
+    /// | `%t` | A tab.
+    ///
+    /// | `%T` | Equivalent to `%H:%M:%S`.
+    ///
+    /// | `%u` | The weekday as a number where Monday is 1 and Sunday is 7.
+    ///
+    /// | `%w` | The weekday as a number where Sunday is 0 and Saturday is 6.
+    ///
+    /// | `%y` | The year without the century as a two digit number.
+    ///
+    /// | `%Y` | The year as an at least four digit number.
+    ///
+    /// | `%z` | The offset from UTC, e.g., `+0130`.
+    ///
+    /// | `%:z` | The offset from UTC with a colon, e.g., `+01:30`.
+    ///
+    /// | `%%` | A `%` character.
+    ///
+    /// |===
+    ///
+    /// If the format contains an unknown conversion specification, the error
+    /// `InvalidArgument` is returned.
+    ///
+    /// = Examples
+    ///
+    /// ----
+    /// let zone = try!(Zone::local());
+    /// let date = zone.expand(try!(time::REAL.get_time()));
+    /// try!(date.format(&mut fd::STDOUT, b"%A, %d %B %Y %H:%M:%S %z\n"));
+    /// ----
+    pub fn format<W: Write+?Sized>(&self, w: &mut W, fmt: &[u8]) -> Result {
+        format::format(self, w, fmt)
+    }
+
+    /// Parses an RFC 3339 date.
+    ///
+    /// [argument, s]
+    /// The date, e.g., `1985-04-12T23:20:50.52+01:00`.
+    ///
+    /// = Remarks
+    ///
+    /// The fields of the returned date are in the time zone given by its offset. Use
+    /// `Zone::fixed` to convert the date to a `Time`.
+    ///
+    /// = See also
+    ///
+    /// * link:lrs::time::Zone::fixed
+    pub fn parse_rfc3339(s: &[u8]) -> Result<DateTime> {
+        rfc::rfc3339(s)
+    }
+
+    /// Parses an RFC 2822 date.
+    ///
+    /// [argument, s]
+    /// The date, e.g., `Fri, 21 Nov 1997 09:55:06 -0600`.
+    ///
+    /// = Remarks
+    ///
+    /// The obsolete zone names and two digit years are accepted. Comments are not. If
+    /// the date contains a weekday, it must agree with the date.
+    ///
+    /// = See also
+    ///
+    /// * link:lrs::time::DateTime::parse_rfc3339
+    pub fn parse_rfc2822(s: &[u8]) -> Result<DateTime> {
+        rfc::rfc2822(s)
+    }
+
+    /// Parses an HTTP date.
+    ///
+    /// [argument, s]
+    /// The date, e.g., `Sun, 06 Nov 1994 08:49:37 GMT`.
+    ///
+    /// = Remarks
+    ///
+    /// The obsolete RFC 850 and asctime formats are accepted as well. The returned date
+    /// is always in UTC.
+    ///
+    /// = See also
+    ///
+    /// * link:lrs::time::DateTime::parse_rfc3339
+    pub fn parse_http_date(s: &[u8]) -> Result<DateTime> {
+        rfc::http_date(s)
+    }
+}
+
 impl Debug for DateTime {
-    fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
-        let offset_minutes = self.offset / 60;
-        let offset_hours = offset_minutes / 60;
-        let offset_minutes = offset_minutes % 60;
-        write!(w, "{}-{:02}-{:02}T{:02}:{:02}:{:02}+{:02}:{:02}", self.year, self.month,
-               self.day, self.hour, self.minute, self.second, offset_hours,
-               offset_minutes)
+    fn fmt<W: Write>(&self, w: &mut W) -> Result {
+        self.format(w, RFC3339)
     }
 }
 
@@ -94,6 +261,8 @@ pub struct Zone {
     transitions: Vec<(i64, usize)>,
     /// (leap second time, number of leap seconds)
     leap_seconds: Vec<(i64, i64)>,
+    /// The rule that applies after the last transition
+    rule: tz::Rule,
 }
 
 impl Zone {
@@ -126,15 +295,85 @@ impl Zone {
     pub fn load<S: ?Sized>(zone: &S) -> Result<Zone>
         where S: TryAsRef<NoNullStr>,
     {
+        let path: &NoNullStr = try!(zone.try_as_ref());
+        Zone::load_name(path.as_ref())
+    }
+
+    #[cfg(not(freestanding))]
+    fn load_name(name: &[u8]) -> Result<Zone> {
         const PREFIX: &'static [u8] = b"/usr/share/zoneinfo/";
-        let path = try!(zone.try_as_ref());
-        let mut vec: Vec<u8> = try!(Vec::with_capacity(PREFIX.len() + path.len() + 1));
-        vec.push_all(PREFIX);
-        vec.push_all(path.as_ref());
+        let prefix = match name.len() > 0 && name[0] == b'/' {
+            true => &b""[..],
+            false => PREFIX,
+        };
+        let mut vec: Vec<u8> = try!(Vec::with_capacity(prefix.len() + name.len() + 1));
+        vec.push_all(prefix);
+        vec.push_all(name);
         vec.push(0);
         Zone::load_from(&vec)
     }
 
+    /// Creates a time zone from a POSIX TZ rule.
+    ///
+    /// [argument, rule]
+    /// The rule, e.g., `CET-1CEST,M3.5.0,M10.5.0/3`.
+    ///
+    /// = Remarks
+    ///
+    /// The zone has no transitions and no leap seconds. All times are expanded according
+    /// to the rule. If the rule has summer time but does not specify when summer time
+    /// starts and ends, the rules of the United States are used.
+    ///
+    /// = See also
+    ///
+    /// * link:man:tzset(3)
+    pub fn from_rule(rule: &[u8]) -> Result<Zone> {
+        let rule = try!(tz::parse(rule));
+        let mut states = Vec::new();
+        match rule {
+            tz::Rule::Summer(std, summer) => {
+                try!(states.push((std, false)));
+                try!(states.push((summer.offset, true)));
+            },
+            tz::Rule::Fixed(std) => try!(states.push((std, false))),
+            tz::Rule::None => return Err(error::InvalidArgument),
+        }
+        Ok(Zone {
+            states: states,
+            transitions: Vec::new(),
+            leap_seconds: Vec::new(),
+            rule: rule,
+        })
+    }
+
+    /// Creates a time zone with a fixed offset from UTC.
+    ///
+    /// [argument, offset]
+    /// The offset in seconds.
+    ///
+    /// = Remarks
+    ///
+    /// The zone has no transitions and no leap seconds. `compact` converts dates that
+    /// have been parsed from text to a `Time` when it is used with the offset of the
+    /// date.
+    ///
+    /// = Examples
+    ///
+    /// ----
+    /// let date = try!(DateTime::parse_rfc3339(b"1985-04-12T23:20:50.52+01:00"));
+    /// let (_, time) = try!(Zone::fixed(date.offset)).compact(date);
+    /// ----
+    pub fn fixed(offset: i64) -> Result<Zone> {
+        let mut states = Vec::new();
+        try!(states.push((offset, false)));
+        Ok(Zone {
+            states: states,
+            transitions: Vec::new(),
+            leap_seconds: Vec::new(),
+            rule: tz::Rule::None,
+        })
+    }
+
     /// Loads the UTC time zone.
     #[cfg(not(freestanding))]
     pub fn utc() -> Result<Zone> {
@@ -142,9 +381,52 @@ impl Zone {
     }
 
     /// Loads the local time zone.
+    ///
+    /// = Remarks
+    ///
+    /// If the `TZ` environment variable is not set, the zone is loaded from
+    /// `/etc/localtime`. Otherwise, the variable contains either the name of a zone, the
+    /// absolute path of a zone file, or a POSIX TZ rule. If the name starts with a `:`,
+    /// it is always interpreted as a name or a path. If the variable is empty, UTC is
+    /// used.
+    ///
+    /// Relative names containing `..` are never loaded from the file system. If the zone
+    /// cannot be loaded and the variable is not a valid rule, UTC is used.
+    ///
+    /// = See also
+    ///
+    /// * link:man:tzset(3)
     #[cfg(not(freestanding))]
     pub fn local() -> Result<Zone> {
-        Zone::load_from(b"/etc/localtime\0")
+        let tz: &[u8] = match env::var("TZ") {
+            Ok(tz) => tz.as_ref(),
+            _ => return Zone::load_from(b"/etc/localtime\0"),
+        };
+        if tz.len() == 0 {
+            return Zone::from_rule(b"UTC0");
+        }
+        if tz[0] == b':' {
+            let name = &tz[1..];
+            if name.len() > 0 && name[0] != b'/' && has_parent_ref(name) {
+                return Err(error::InvalidArgument);
+            }
+            return Zone::load_name(name);
+        }
+        if tz[0] == b'/' {
+            return Zone::load_name(tz);
+        }
+        // Relative names must not leave the zoneinfo directory.
+        if !has_parent_ref(tz) {
+            if let Ok(zone) = Zone::load_name(tz) {
+                return Ok(zone);
+            }
+        }
+        // Like glibc, fall back to UTC if the variable is neither a zone nor a rule.
+        // This also covers `TZ=UTC` on systems without a zoneinfo directory.
+        match Zone::from_rule(tz) {
+            Ok(zone) => Ok(zone),
+            _ => Zone::from_rule(b"UTC0"),
+        }
     }
 
     /// Expands a time since the epoch to a `DateTime` in the given time zone.
@@ -152,7 +434,10 @@ impl Zone {
     /// [argument, time]
     /// The time to expand.
     pub fn expand(&self, time: Time) -> DateTime {
-        convert::explode(self, time.seconds)
+        let time = time.normalize();
+        let mut date = convert::explode(self, time.seconds);
+        date.nanosecond = time.nanoseconds as i32;
+        date
     }
 
     /// Returns a normalized version of a `DateTime` and a time that expands to the
@@ -164,15 +449,17 @@ impl Zone {
     /// = Remarks
     ///
     /// This function looks at the following fields: year, month, day, hour, minute,
-    /// second. All other fields will be calculated from these fields. Note that, if the
-    /// date cannot be represented as a time in the given timezone (e.g. because the year
-    /// is too large or the time falls into the one hour hole between winter and summer
-    /// time) then the return value will not agree with the given date in the fields
-    /// mentioned above but the property that the returned time expands to the returned
-    /// `DateTime` still holds.
+    /// second, nanosecond. All other fields will be calculated from these fields. Note
+    /// that, if the date cannot be represented as a time in the given timezone (e.g.
+    /// because the year is too large or the time falls into the one hour hole between
+    /// winter and summer time) then the return value will not agree with the given date
+    /// in the fields mentioned above but the property that the returned time expands to
+    /// the returned `DateTime` still holds.
     ///
     /// The fields mentioned above are normalized according to the following algorithm:
     ///
+    /// * nanosecond is reduced so that it is in the [0, 1_000_000_000) range and second
+    ///   is adjusted accordingly.
     /// * second is reduced so that it is in the [0, 60) range and minute is adjusted
     ///   accordingly (this means that times that coincide with leap seconds will not be
     ///   normalized correctly.)
@@ -187,3 +474,14 @@ impl Zone {
         convert::compact(self, date)
     }
 }
+
+/// Returns whether a zone name contains `..`.
+#[cfg(not(freestanding))]
+fn has_parent_ref(name: &[u8]) -> bool {
+    for i in 1..name.len() {
+        if name[i - 1] == b'.' && name[i] == b'.' {
+            return true;
+        }
+    }
+    false
+}
@@ -8,6 +8,7 @@ use core::{mem, cmp};
 use base::{error};
 
 use super::{Zone};
+use tz::{self, Rule};
 
 macro_rules! rd {
     ($ip:expr, $t:ty) => {
@@ -61,12 +62,31 @@ pub fn parse(ip: &mut &[u8]) -> Result<Zone> {
     };
     if version > 1 {
         try!(discard::<T32Reader>(ip));
-        parse_::<T64Reader>(ip)
+        let mut zone = try!(parse_::<T64Reader>(ip));
+        zone.rule = footer(ip);
+        Ok(zone)
     } else {
         parse_::<T32Reader>(ip)
     }
 }
 
+/// Parses the POSIX TZ rule that follows the version 2 data.
+fn footer(ip: &[u8]) -> Rule {
+    if ip.len() < 2 || ip[0] != b'\n' {
+        return Rule::None;
+    }
+    let ip = &ip[1..];
+    let rule = match ip.find(|&b| b == b'\n') {
+        Some(n) => &ip[..n],
+        _ => return Rule::None,
+    };
+    // An invalid rule is not fatal since the transitions can still be used.
+    match tz::parse(rule) {
+        Ok(rule) => rule,
+        _ => Rule::None,
+    }
+}
+
 fn consume(buf: &mut &[u8], n: usize) {
     let min = cmp::min(buf.len(), n);
     *buf = &buf[min..];
@@ -120,6 +140,7 @@ fn parse_<T: TReader>(ip: &mut &[u8]) -> Result<Zone> {
         transitions:  transitions,
         states:       states,
         leap_seconds: leap_seconds,
+        rule:         Rule::None,
     })
 }
 
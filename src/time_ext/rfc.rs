@@ -0,0 +1,324 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use base::{error};
+
+use super::{DateTime, Weekday};
+use format::{WEEKDAYS, MONTHS, weekday_num};
+use convert::{days_in_month, set_day_fields};
+
+struct Input<'a> {
+    s: &'a [u8],
+}
+
+impl<'a> Input<'a> {
+    fn peek(&self) -> Option<u8> {
+        match self.s.len() {
+            0 => None,
+            _ => Some(self.s[0]),
+        }
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        match self.peek() {
+            Some(c) if c == b => {
+                self.s = &self.s[1..];
+                true
+            },
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, b: u8) -> Result {
+        match self.eat(b) {
+            true => Ok(()),
+            false => Err(error::InvalidArgument),
+        }
+    }
+
+    fn skip_space(&mut self) -> bool {
+        let len = self.s.len();
+        while self.eat(b' ') || self.eat(b'\t') { }
+        self.s.len() < len
+    }
+
+    fn expect_space(&mut self) -> Result {
+        match self.skip_space() {
+            true => Ok(()),
+            false => Err(error::InvalidArgument),
+        }
+    }
+
+    /// Parses a number with `min` to `max` digits.
+    fn num(&mut self, min: usize, max: usize) -> Result<i64> {
+        let mut n = 0;
+        let mut digits = 0;
+        while digits < max {
+            match self.peek() {
+                Some(b) if b'0' <= b && b <= b'9' => n = 10 * n + (b - b'0') as i64,
+                _ => break,
+            }
+            self.s = &self.s[1..];
+            digits += 1;
+        }
+        match digits < min {
+            true => Err(error::InvalidArgument),
+            false => Ok(n),
+        }
+    }
+
+    /// Parses a run of ASCII letters.
+    fn word(&mut self) -> &'a [u8] {
+        let len = self.s.find(|&b| !(b'a' <= b | 0x20 && b | 0x20 <= b'z'))
+                        .unwrap_or(self.s.len());
+        let (word, rest) = self.s.split_at(len);
+        self.s = rest;
+        word
+    }
+
+    fn end(&self) -> Result {
+        match self.s.len() {
+            0 => Ok(()),
+            _ => Err(error::InvalidArgument),
+        }
+    }
+}
+
+fn eq_ignore_case(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    for i in 0..a.len() {
+        if a[i] | 0x20 != b[i] | 0x20 {
+            return false;
+        }
+    }
+    true
+}
+
+/// Finds a name in a list of names. Both the full names and the names abbreviated to
+/// three letters are accepted.
+fn find_name(names: &[&'static str], word: &[u8]) -> Result<usize> {
+    for i in 0..names.len() {
+        let name: &[u8] = names[i].as_ref();
+        if eq_ignore_case(word, name) || eq_ignore_case(word, &name[..3]) {
+            return Ok(i);
+        }
+    }
+    Err(error::InvalidArgument)
+}
+
+fn weekday(input: &mut Input) -> Result<Weekday> {
+    const DAYS: [Weekday; 7] = [
+        Weekday::Monday, Weekday::Tuesday, Weekday::Wednesday, Weekday::Thursday,
+        Weekday::Friday, Weekday::Saturday, Weekday::Sunday,
+    ];
+    find_name(&WEEKDAYS, input.word()).map(|i| DAYS[i])
+}
+
+fn month(input: &mut Input) -> Result<i8> {
+    find_name(&MONTHS, input.word()).map(|i| i as i8 + 1)
+}
+
+fn new_date() -> DateTime {
+    DateTime {
+        offset:      0,
+        year:        0,
+        day_in_year: 0,
+        month:       0,
+        day:         0,
+        hour:        0,
+        minute:      0,
+        second:      0,
+        nanosecond:  0,
+        weekday:     Weekday::Monday,
+        summer_time: false,
+    }
+}
+
+/// Parses `hh:mm[:ss]`.
+fn time(input: &mut Input, date: &mut DateTime, seconds_optional: bool) -> Result {
+    date.hour = try!(input.num(2, 2)) as i8;
+    try!(input.expect(b':'));
+    date.minute = try!(input.num(2, 2)) as i8;
+    if input.eat(b':') {
+        date.second = try!(input.num(2, 2)) as i8;
+    } else if !seconds_optional {
+        return Err(error::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Checks the ranges of the fields and calculates the weekday and the day in the year.
+///
+/// If `weekday` is given, it must agree with the date.
+fn finish(mut date: DateTime, weekday: Option<Weekday>) -> Result<DateTime> {
+    if date.month < 1 || date.month > 12 || date.day < 1
+            || date.day > days_in_month(date.year, date.month) || date.hour > 23
+            || date.minute > 59 || date.second > 60 {
+        return Err(error::InvalidArgument);
+    }
+    set_day_fields(&mut date);
+    if let Some(weekday) = weekday {
+        if weekday_num(weekday) != weekday_num(date.weekday) {
+            return Err(error::InvalidArgument);
+        }
+    }
+    Ok(date)
+}
+
+pub fn rfc3339(s: &[u8]) -> Result<DateTime> {
+    let mut input = Input { s: s };
+    let mut date = new_date();
+
+    date.year = try!(input.num(4, 4));
+    try!(input.expect(b'-'));
+    date.month = try!(input.num(2, 2)) as i8;
+    try!(input.expect(b'-'));
+    date.day = try!(input.num(2, 2)) as i8;
+    if !input.eat(b'T') && !input.eat(b't') && !input.eat(b' ') {
+        return Err(error::InvalidArgument);
+    }
+    try!(time(&mut input, &mut date, false));
+
+    if input.eat(b'.') {
+        let len = input.s.len();
+        let mut nano = try!(input.num(1, 9));
+        for _ in 0..9 - (len - input.s.len()) {
+            nano *= 10;
+        }
+        date.nanosecond = nano as i32;
+        // Digits beyond nanosecond precision are ignored.
+        let _ = input.num(0, !0);
+    }
+
+    if !input.eat(b'Z') && !input.eat(b'z') {
+        let sign = match input.peek() {
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            _ => return Err(error::InvalidArgument),
+        };
+        input.s = &input.s[1..];
+        let hours = try!(input.num(2, 2));
+        try!(input.expect(b':'));
+        let minutes = try!(input.num(2, 2));
+        if hours > 23 || minutes > 59 {
+            return Err(error::InvalidArgument);
+        }
+        date.offset = sign * (hours * 60 + minutes) * 60;
+    }
+    try!(input.end());
+
+    finish(date, None)
+}
+
+/// Parses the zone of an RFC 2822 date.
+fn rfc2822_zone(input: &mut Input) -> Result<i64> {
+    let sign = match input.peek() {
+        Some(b'+') => 1,
+        Some(b'-') => -1,
+        _ => {
+            // Obsolete zone names.
+            let word = input.word();
+            let hours = match word {
+                _ if eq_ignore_case(word, b"UT") || eq_ignore_case(word, b"GMT") => 0,
+                _ if eq_ignore_case(word, b"EDT") => -4,
+                _ if eq_ignore_case(word, b"EST") || eq_ignore_case(word, b"CDT") => -5,
+                _ if eq_ignore_case(word, b"CST") || eq_ignore_case(word, b"MDT") => -6,
+                _ if eq_ignore_case(word, b"MST") || eq_ignore_case(word, b"PDT") => -7,
+                _ if eq_ignore_case(word, b"PST") => -8,
+                // Military zones carry no information. See RFC 2822, section 4.3.
+                _ if word.len() == 1 && word[0] | 0x20 != b'j' => 0,
+                _ => return Err(error::InvalidArgument),
+            };
+            return Ok(hours * 60 * 60);
+        },
+    };
+    input.s = &input.s[1..];
+    let hours = try!(input.num(2, 2));
+    let minutes = try!(input.num(2, 2));
+    if hours > 23 || minutes > 59 {
+        return Err(error::InvalidArgument);
+    }
+    Ok(sign * (hours * 60 + minutes) * 60)
+}
+
+pub fn rfc2822(s: &[u8]) -> Result<DateTime> {
+    let mut input = Input { s: s };
+    let mut date = new_date();
+
+    input.skip_space();
+    let mut wd = None;
+    if input.peek().map(|b| b > b'9').unwrap_or(false) {
+        wd = Some(try!(weekday(&mut input)));
+        input.skip_space();
+        try!(input.expect(b','));
+        input.skip_space();
+    }
+    date.day = try!(input.num(1, 2)) as i8;
+    try!(input.expect_space());
+    date.month = try!(month(&mut input));
+    try!(input.expect_space());
+    let len = input.s.len();
+    date.year = try!(input.num(2, 9));
+    // Obsolete two and three digit years. See RFC 2822, section 4.3.
+    match len - input.s.len() {
+        2 if date.year < 50 => date.year += 2000,
+        2 | 3 => date.year += 1900,
+        _ => { },
+    }
+    try!(input.expect_space());
+    try!(time(&mut input, &mut date, true));
+    try!(input.expect_space());
+    date.offset = try!(rfc2822_zone(&mut input));
+    input.skip_space();
+    try!(input.end());
+
+    finish(date, wd)
+}
+
+pub fn http_date(s: &[u8]) -> Result<DateTime> {
+    let mut input = Input { s: s };
+    let mut date = new_date();
+
+    let wd = try!(weekday(&mut input));
+    if input.eat(b',') {
+        try!(input.expect_space());
+        date.day = try!(input.num(2, 2)) as i8;
+        if input.eat(b'-') {
+            // RFC 850: Sunday, 06-Nov-94 08:49:37 GMT
+            date.month = try!(month(&mut input));
+            try!(input.expect(b'-'));
+            date.year = try!(input.num(2, 2));
+            // See RFC 7231, section 7.1.1.1.
+            date.year += if date.year < 70 { 2000 } else { 1900 };
+        } else {
+            // IMF-fixdate: Sun, 06 Nov 1994 08:49:37 GMT
+            try!(input.expect_space());
+            date.month = try!(month(&mut input));
+            try!(input.expect_space());
+            date.year = try!(input.num(4, 4));
+        }
+        try!(input.expect_space());
+        try!(time(&mut input, &mut date, false));
+        try!(input.expect_space());
+        if input.word() != &b"GMT"[..] {
+            return Err(error::InvalidArgument);
+        }
+    } else {
+        // asctime: Sun Nov  6 08:49:37 1994
+        try!(input.expect_space());
+        date.month = try!(month(&mut input));
+        try!(input.expect_space());
+        date.day = try!(input.num(1, 2)) as i8;
+        try!(input.expect_space());
+        try!(time(&mut input, &mut date, false));
+        try!(input.expect_space());
+        date.year = try!(input.num(4, 4));
+    }
+    try!(input.end());
+
+    finish(date, Some(wd))
+}
@@ -0,0 +1,259 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! POSIX TZ rules.
+//!
+//! = See also
+//!
+//! * link:man:tzset(3)
+
+use base::prelude::*;
+use base::{error};
+
+use convert::{year_start, year_of, month_start, weekday_num, days_in_month};
+
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// The day on which summer time starts or ends.
+#[derive(Copy, Eq)]
+pub enum Day {
+    /// `Jn`: The day in the year in [1, 365]. February 29 is never counted.
+    Julian(i64),
+    /// `n`: The day in the year in [0, 365]. February 29 is counted in leap years.
+    Zero(i64),
+    /// `Mm.w.d`: Day `d` (0 is Sunday) of week `w` (5 is the last week) of month `m`.
+    Month(i64, i64, i64),
+}
+
+impl Day {
+    /// Returns the day in the year, starting at 0.
+    fn day_in_year(self, year: i64) -> i64 {
+        match self {
+            Day::Julian(n) => {
+                let leap = days_in_month(year, 2) == 29;
+                if leap && n >= 60 { n } else { n - 1 }
+            },
+            Day::Zero(n) => n,
+            Day::Month(m, w, d) => {
+                let first = month_start(year, m as usize - 1);
+                // weekday_num starts at Monday, `d` at Sunday.
+                let first_weekday = (weekday_num(year, first) + 1) % 7;
+                let mut day = (d - first_weekday + 7) % 7 + 7 * (w - 1);
+                while day >= days_in_month(year, m as i8) as i64 {
+                    day -= 7;
+                }
+                first + day
+            },
+        }
+    }
+}
+
+/// A rule with summer time.
+#[derive(Copy, Eq)]
+pub struct Summer {
+    /// The offset from UTC during summer time.
+    pub offset: i64,
+    /// The day on which summer time starts.
+    pub start: Day,
+    /// The local time, in standard time, at which summer time starts.
+    pub start_time: i64,
+    /// The day on which summer time ends.
+    pub end: Day,
+    /// The local time, in summer time, at which summer time ends.
+    pub end_time: i64,
+}
+
+/// A POSIX TZ rule.
+#[derive(Copy, Eq)]
+pub enum Rule {
+    /// There is no rule.
+    None,
+    /// The zone has a fixed offset from UTC.
+    Fixed(i64),
+    /// The zone has a standard offset and summer time.
+    Summer(i64, Summer),
+}
+
+impl Rule {
+    /// Returns the offset from UTC and whether summer time applies at a time.
+    pub fn state(&self, time: i64) -> (i64, bool) {
+        let (std, summer) = match *self {
+            Rule::Summer(std, summer) => (std, summer),
+            Rule::Fixed(std) => return (std, false),
+            Rule::None => return (0, false),
+        };
+        let (mut days, secs) = (time + std).div_rem(SECS_PER_DAY);
+        if secs < 0 {
+            days -= 1;
+        }
+        let year = year_of(days);
+        let start = (year_start(year) + summer.start.day_in_year(year)) * SECS_PER_DAY
+                        + summer.start_time - std;
+        let end = (year_start(year) + summer.end.day_in_year(year)) * SECS_PER_DAY
+                        + summer.end_time - summer.offset;
+        let is_summer = if start < end {
+            start <= time && time < end
+        } else {
+            // Southern hemisphere.
+            time < end || start <= time
+        };
+        match is_summer {
+            true => (summer.offset, true),
+            false => (std, false),
+        }
+    }
+}
+
+struct Input<'a> {
+    s: &'a [u8],
+}
+
+impl<'a> Input<'a> {
+    fn peek(&self) -> u8 {
+        match self.s.len() {
+            0 => 0,
+            _ => self.s[0],
+        }
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        if self.s.len() > 0 && self.s[0] == b {
+            self.s = &self.s[1..];
+            true
+        } else {
+            false
+        }
+    }
+
+    fn num(&mut self, max: i64) -> Result<i64> {
+        let mut n = 0;
+        let mut digits = 0;
+        while b'0' <= self.peek() && self.peek() <= b'9' {
+            n = 10 * n + (self.peek() - b'0') as i64;
+            if n > max {
+                return Err(error::InvalidArgument);
+            }
+            self.s = &self.s[1..];
+            digits += 1;
+        }
+        match digits {
+            0 => Err(error::InvalidArgument),
+            _ => Ok(n),
+        }
+    }
+}
+
+/// Parses a zone name. The name is not used.
+fn name(input: &mut Input) -> Result {
+    let len = if input.eat(b'<') {
+        match input.s.find(|&b| b == b'>') {
+            Some(n) => {
+                input.s = &input.s[n + 1..];
+                n
+            },
+            _ => return Err(error::InvalidArgument),
+        }
+    } else {
+        let n = input.s.find(|&b| !(b'a' <= b | 0x20 && b | 0x20 <= b'z'))
+                        .unwrap_or(input.s.len());
+        input.s = &input.s[n..];
+        n
+    };
+    match len < 3 {
+        true => Err(error::InvalidArgument),
+        false => Ok(()),
+    }
+}
+
+/// Parses `[+-]hh[:mm[:ss]]` and returns the number of seconds.
+fn time(input: &mut Input, max_hours: i64) -> Result<i64> {
+    let sign = match input.peek() {
+        b'-' => -1,
+        _ => 1,
+    };
+    if !input.eat(b'-') {
+        input.eat(b'+');
+    }
+    let mut secs = try!(input.num(max_hours)) * 60 * 60;
+    if input.eat(b':') {
+        secs += try!(input.num(59)) * 60;
+        if input.eat(b':') {
+            secs += try!(input.num(59));
+        }
+    }
+    Ok(sign * secs)
+}
+
+fn day(input: &mut Input) -> Result<Day> {
+    if input.eat(b'J') {
+        let n = try!(input.num(365));
+        match n {
+            0 => Err(error::InvalidArgument),
+            _ => Ok(Day::Julian(n)),
+        }
+    } else if input.eat(b'M') {
+        let m = try!(input.num(12));
+        if m == 0 || !input.eat(b'.') {
+            return Err(error::InvalidArgument);
+        }
+        let w = try!(input.num(5));
+        if w == 0 || !input.eat(b'.') {
+            return Err(error::InvalidArgument);
+        }
+        let d = try!(input.num(6));
+        Ok(Day::Month(m, w, d))
+    } else {
+        input.num(365).map(|n| Day::Zero(n))
+    }
+}
+
+/// Parses `day[/time]`.
+fn transition(input: &mut Input) -> Result<(Day, i64)> {
+    let day = try!(day(input));
+    // RFC 8536 extends the range of the hours to [-167, 167].
+    let time = match input.eat(b'/') {
+        true => try!(time(input, 167)),
+        false => 2 * 60 * 60,
+    };
+    Ok((day, time))
+}
+
+/// Parses a POSIX TZ rule such as `CET-1CEST,M3.5.0,M10.5.0/3`.
+pub fn parse(s: &[u8]) -> Result<Rule> {
+    let mut input = Input { s: s };
+
+    try!(name(&mut input));
+    // POSIX offsets are positive west of Greenwich.
+    let std = -try!(time(&mut input, 24));
+    if input.s.len() == 0 {
+        return Ok(Rule::Fixed(std));
+    }
+
+    try!(name(&mut input));
+    let offset = match input.peek() {
+        b',' | 0 => std + 60 * 60,
+        _ => -try!(time(&mut input, 24)),
+    };
+    let ((start, start_time), (end, end_time)) = if input.eat(b',') {
+        let start = try!(transition(&mut input));
+        if !input.eat(b',') {
+            return Err(error::InvalidArgument);
+        }
+        (start, try!(transition(&mut input)))
+    } else {
+        // The default rules of the United States.
+        ((Day::Month(3, 2, 0), 2 * 60 * 60), (Day::Month(11, 1, 0), 2 * 60 * 60))
+    };
+    if input.s.len() > 0 {
+        return Err(error::InvalidArgument);
+    }
+
+    Ok(Rule::Summer(std, Summer {
+        offset: offset,
+        start: start,
+        start_time: start_time,
+        end: end,
+        end_time: end_time,
+    }))
+}
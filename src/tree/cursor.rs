@@ -0,0 +1,161 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use core::cmp::{Ordering};
+
+use {Node, Entree, get_owner};
+use path::{Path};
+
+/// A cursor for in-order traversal of a tree.
+///
+/// = Remarks
+///
+/// The cursor either points to an element in the tree or to no element. Once the cursor
+/// has been moved past either end of the tree, it no longer points to an element.
+pub struct Cursor<'a, E>
+    where E: Entree + 'a,
+{
+    /// The ancestors of `cur`. `Greater` means that the path continues to the left,
+    /// `Less` that it continues to the right.
+    path: Path<'a, Ordering>,
+    cur: Option<&'a Node>,
+    _marker: PhantomData<E>,
+}
+
+impl<'a, E> Cursor<'a, E>
+    where E: Entree,
+{
+    /// Returns the element the cursor points to, if any.
+    pub fn get(&self) -> Option<&'a E::Owner> {
+        self.cur.map(|n| get_owner::<E>(n))
+    }
+
+    /// Moves the cursor to the next larger element.
+    pub fn move_next(&mut self) {
+        let cur = match self.cur {
+            Some(c) => c,
+            _ => return,
+        };
+        if let Some(right) = cur.right() {
+            unsafe { self.path.push(cur, Ordering::Less); }
+            self.cur = Some(self.leftmost(right));
+            return;
+        }
+        while let Some((node, ord)) = self.path.pop() {
+            if ord == Ordering::Greater {
+                self.cur = Some(node);
+                return;
+            }
+        }
+        self.cur = None;
+    }
+
+    /// Moves the cursor to the next smaller element.
+    pub fn move_prev(&mut self) {
+        let cur = match self.cur {
+            Some(c) => c,
+            _ => return,
+        };
+        if let Some(left) = cur.left() {
+            unsafe { self.path.push(cur, Ordering::Greater); }
+            self.cur = Some(self.rightmost(left));
+            return;
+        }
+        while let Some((node, ord)) = self.path.pop() {
+            if ord == Ordering::Less {
+                self.cur = Some(node);
+                return;
+            }
+        }
+        self.cur = None;
+    }
+
+    fn leftmost(&mut self, mut node: &'a Node) -> &'a Node {
+        while let Some(left) = node.left() {
+            unsafe { self.path.push(node, Ordering::Greater); }
+            node = left;
+        }
+        node
+    }
+
+    fn rightmost(&mut self, mut node: &'a Node) -> &'a Node {
+        while let Some(right) = node.right() {
+            unsafe { self.path.push(node, Ordering::Less); }
+            node = right;
+        }
+        node
+    }
+}
+
+fn empty<'a, E>() -> Cursor<'a, E>
+    where E: Entree,
+{
+    let mut path = unsafe { Path::new() };
+    path.reset();
+    Cursor {
+        path: path,
+        cur: None,
+        _marker: PhantomData,
+    }
+}
+
+/// Creates a cursor pointing to the smallest element.
+pub fn first<'a, E>(root: Option<&'a Node>) -> Cursor<'a, E>
+    where E: Entree,
+{
+    let mut cursor = empty();
+    if let Some(root) = root {
+        cursor.cur = Some(cursor.leftmost(root));
+    }
+    cursor
+}
+
+/// Creates a cursor pointing to the largest element.
+pub fn last<'a, E>(root: Option<&'a Node>) -> Cursor<'a, E>
+    where E: Entree,
+{
+    let mut cursor = empty();
+    if let Some(root) = root {
+        cursor.cur = Some(cursor.rightmost(root));
+    }
+    cursor
+}
+
+/// Creates a cursor pointing to the smallest element that is larger than the key, or
+/// not smaller if `strict` is `false`.
+///
+/// [argument, f]
+/// Compares an element to the key.
+pub fn bound<'a, E, F>(root: Option<&'a Node>, mut f: F, strict: bool) -> Cursor<'a, E>
+    where E: Entree,
+          F: FnMut(&E::Owner) -> Ordering,
+{
+    let mut cursor = empty();
+    let mut found = None;
+    let mut next = root;
+    while let Some(node) = next {
+        let go_left = match f(get_owner::<E>(node)) {
+            Ordering::Less => false,
+            Ordering::Equal => !strict,
+            Ordering::Greater => true,
+        };
+        if go_left {
+            found = Some((node, cursor.path.len()));
+            unsafe { cursor.path.push(node, Ordering::Greater); }
+            next = node.left();
+        } else {
+            unsafe { cursor.path.push(node, Ordering::Less); }
+            next = node.right();
+        }
+    }
+    match found {
+        Some((node, len)) => {
+            cursor.path.truncate(len);
+            cursor.cur = Some(node);
+        },
+        _ => cursor.path.reset(),
+    }
+    cursor
+}
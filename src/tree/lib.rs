@@ -17,9 +17,12 @@ use core::marker::{Leak};
 use core::cmp::{Ordering};
 use cell::{Cell};
 
+pub use cursor::{Cursor};
+
 mod std { pub use ::fmt::std::*; }
 
 mod path;
+mod cursor;
 mod insert;
 mod remove;
 mod test;
@@ -338,6 +341,93 @@ impl<E> Tree<E>
         found.is_some()
     }
 
+    /// Searches for an element.
+    ///
+    /// [argument, f]
+    /// {
+    /// Compares an element to the element that is being searched for.
+    ///
+    /// The function returns `Less` if the element is smaller than the element being
+    /// searched for, etc.
+    ///
+    /// }
+    ///
+    /// [return_value]
+    /// Returns a new reference to the element, if any.
+    pub fn find<F>(&self, mut f: F) -> Option<E::Ref>
+        where F: FnMut(&E::Owner) -> Ordering,
+    {
+        let mut next = self.root();
+        while let Some(node) = next {
+            let owner = get_owner::<E>(node);
+            next = match f(owner) {
+                Ordering::Less => node.right(),
+                Ordering::Greater => node.left(),
+                Ordering::Equal => return Some(new_ref::<E>(owner)),
+            };
+        }
+        None
+    }
+
+    /// Returns a new reference to the smallest element, if any.
+    pub fn first(&self) -> Option<E::Ref> {
+        let cursor = cursor::first::<E>(self.root());
+        cursor.get().map(|owner| new_ref::<E>(owner))
+    }
+
+    /// Returns a new reference to the largest element, if any.
+    pub fn last(&self) -> Option<E::Ref> {
+        let cursor = cursor::last::<E>(self.root());
+        cursor.get().map(|owner| new_ref::<E>(owner))
+    }
+
+    /// Creates a cursor pointing to the smallest element.
+    ///
+    /// = Remarks
+    ///
+    /// This is unsafe because the tree must not be modified while the cursor exists.
+    pub unsafe fn first_cursor(&self) -> Cursor<E> {
+        cursor::first(self.root())
+    }
+
+    /// Creates a cursor pointing to the largest element.
+    ///
+    /// = Remarks
+    ///
+    /// This is unsafe because the tree must not be modified while the cursor exists.
+    pub unsafe fn last_cursor(&self) -> Cursor<E> {
+        cursor::last(self.root())
+    }
+
+    /// Creates a cursor pointing to the smallest element that is not smaller than a
+    /// key.
+    ///
+    /// [argument, f]
+    /// Compares an element to the key.
+    ///
+    /// = Remarks
+    ///
+    /// This is unsafe because the tree must not be modified while the cursor exists.
+    pub unsafe fn lower_bound<F>(&self, f: F) -> Cursor<E>
+        where F: FnMut(&E::Owner) -> Ordering,
+    {
+        cursor::bound(self.root(), f, false)
+    }
+
+    /// Creates a cursor pointing to the smallest element that is larger than a key.
+    ///
+    /// [argument, f]
+    /// Compares an element to the key.
+    ///
+    /// = Remarks
+    ///
+    /// This is unsafe because the tree must not be modified while the cursor exists.
+    pub unsafe fn upper_bound<F>(&self, f: F) -> Cursor<E>
+        where F: FnMut(&E::Owner) -> Ordering,
+    {
+        cursor::bound(self.root(), f, true)
+    }
+
     /// Tests that the tree is in a valid state.
     ///
     /// [return_value]
@@ -395,6 +485,16 @@ fn get_owner<E>(node: &Node) -> &E::Owner
     }
 }
 
+/// Adds a reference to an owner and returns it as a reference type.
+fn new_ref<E>(el: &E::Owner) -> E::Ref
+    where E: Entree
+{
+    unsafe {
+        E::add_ref(el);
+        E::get_ref(el)
+    }
+}
+
 /// Returns the node in an owner.
 fn get_node<E>(el: &E::Owner) -> &Node
     where E: Entree
@@ -31,6 +31,18 @@ impl<'a, U: Copy> Path<'a, U> {
         self.pos = 0;
     }
 
+    /// Returns the length of the path.
+    pub fn len(&self) -> usize {
+        self.pos
+    }
+
+    /// Shortens the path to `len` elements.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.pos {
+            self.pos = len;
+        }
+    }
+
     /// Adds a node to the path.
     ///
     /// = Remarks
@@ -0,0 +1,20 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+#![crate_name = "lrs_treemap"]
+#![crate_type = "lib"]
+#![no_std]
+
+extern crate lrs_base as base;
+extern crate lrs_alloc as alloc;
+extern crate lrs_tree as tree;
+extern crate lrs_fmt as fmt;
+
+pub use map::{TreeMap, Entry, VacantEntry, OccupiedEntry, Iter, RevIter, Range, Keys};
+pub use set::{TreeSet};
+
+mod std { pub use fmt::std::*; }
+
+mod map;
+mod set;
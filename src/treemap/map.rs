@@ -0,0 +1,561 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use core::{ptr, intrinsics};
+use core::cmp::{Ord, Ordering};
+use core::iter::{IntoIterator};
+use alloc::{self, MemPool};
+use tree::{Tree, Node, GetNode, Entree, Cursor};
+use fmt::{Debug, Write};
+
+struct MapNode<Key, Value> {
+    node: Node,
+    key: Key,
+    value: Value,
+}
+
+/// The recipe used to store `MapNode`s in the intrusive tree.
+///
+/// = Remarks
+///
+/// The map owns the nodes, so the reference type is a raw pointer and no reference
+/// counting takes place.
+struct NodeEntree<Key, Value> {
+    _marker: PhantomData<(Key, Value)>,
+}
+
+unsafe impl<Key, Value> GetNode for NodeEntree<Key, Value>
+    where Key: Ord,
+{
+    type Owner = MapNode<Key, Value>;
+
+    unsafe fn get_node(el: *const MapNode<Key, Value>) -> *const Node {
+        &(*el).node
+    }
+
+    fn cmp(left: &MapNode<Key, Value>, right: &MapNode<Key, Value>) -> Ordering {
+        left.key.cmp(&right.key)
+    }
+}
+
+unsafe impl<Key, Value> Entree for NodeEntree<Key, Value>
+    where Key: Ord,
+{
+    type Ref = *const MapNode<Key, Value>;
+
+    unsafe fn get_owner(el: &*const MapNode<Key, Value>) -> &MapNode<Key, Value> {
+        &**el
+    }
+
+    unsafe fn add_ref(_: &MapNode<Key, Value>) { }
+
+    unsafe fn del_ref(_: &MapNode<Key, Value>) { }
+
+    unsafe fn get_ref(el: &MapNode<Key, Value>) -> *const MapNode<Key, Value> {
+        el
+    }
+}
+
+/// An ordered map implemented as a red-black tree.
+///
+/// = Remarks
+///
+/// Every entry is stored in its own node which is allocated from the pool of the map.
+pub struct TreeMap<Key, Value, Allocator: ?Sized = alloc::Heap>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    tree: Tree<NodeEntree<Key, Value>>,
+    size: usize,
+    pool: Allocator,
+}
+
+impl<Key, Value, Allocator> TreeMap<Key, Value, Allocator>
+    where Allocator: MemPool + OutOf,
+          Key: Ord,
+{
+    /// Creates a new, empty map.
+    pub fn new() -> Self {
+        Self::with_pool(Allocator::out_of(()))
+    }
+}
+
+impl<Key, Value, Allocator> TreeMap<Key, Value, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    /// Creates a new, empty map with a memory pool.
+    ///
+    /// [argument, pool]
+    /// The pool from which the nodes will be allocated.
+    pub fn with_pool(pool: Allocator) -> Self {
+        TreeMap {
+            tree: Tree::new(),
+            size: 0,
+            pool: pool,
+        }
+    }
+}
+
+impl<Key, Value, Allocator: ?Sized> TreeMap<Key, Value, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    /// Searches for the node of a key.
+    fn search<Q: ?Sized>(&self, key: &Q) -> Option<*mut MapNode<Key, Value>>
+        where Key: Ord<Q>,
+    {
+        self.tree.find(|n| <Key as Ord<Q>>::cmp(&n.key, key)).map(|n| n as *mut _)
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Retrieves a reference to the value stored for a key.
+    ///
+    /// [argument, key]
+    /// The key to search for.
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&Value>
+        where Key: Ord<Q>,
+    {
+        self.search(key).map(|n| unsafe { &(*n).value })
+    }
+
+    /// Retrieves a mutable reference to the value stored for a key.
+    ///
+    /// [argument, key]
+    /// The key to search for.
+    pub fn get_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut Value>
+        where Key: Ord<Q>,
+    {
+        self.search(key).map(|n| unsafe { &mut (*n).value })
+    }
+
+    /// Returns the entry with the smallest key, if any.
+    pub fn first(&self) -> Option<(&Key, &Value)> {
+        self.tree.first().map(|n| unsafe { (&(*n).key, &(*n).value) })
+    }
+
+    /// Returns the entry with the largest key, if any.
+    pub fn last(&self) -> Option<(&Key, &Value)> {
+        self.tree.last().map(|n| unsafe { (&(*n).key, &(*n).value) })
+    }
+
+    /// Sets a value in the map.
+    ///
+    /// [argument, key]
+    /// The key to set.
+    ///
+    /// [argument, value]
+    /// The value to store.
+    ///
+    /// = Remarks
+    ///
+    /// If the key is already in the map, the passed key will be dropped. If the key is
+    /// not in the map and allocating a node fails, the process will be aborted.
+    ///
+    /// :entry: link:lrs::treemap::TreeMap::entry[entry]
+    ///
+    /// See {entry} for a more robust API.
+    ///
+    /// = See also
+    ///
+    /// * {entry}
+    pub fn set(&mut self, key: Key, value: Value) {
+        match self.entry(&key).unwrap() {
+            Entry::Occupied(mut e) => *e = value,
+            Entry::Vacant(v) => { v.set(key, value); },
+        }
+    }
+
+    /// Removes an entry from the map.
+    ///
+    /// [argument, key]
+    /// The key to remove.
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<(Key, Value)>
+        where Key: Ord<Q>,
+    {
+        let node = match self.search(key) {
+            Some(node) => node,
+            _ => return None,
+        };
+        unsafe {
+            self.tree.remove(&(node as *const _));
+            self.size -= 1;
+            let key = ptr::read(&(*node).key);
+            let value = ptr::read(&(*node).value);
+            alloc::free(&mut self.pool, node);
+            Some((key, value))
+        }
+    }
+
+    /// Returns an entry of the map.
+    ///
+    /// [argument, key]
+    /// The key that identifies the entry.
+    ///
+    /// = Remarks
+    ///
+    /// If the key is not in the map, memory for a new node is allocated and owned by the
+    /// vacant entry until the entry is set or dropped.
+    pub fn entry<'a, Q: ?Sized>(&'a mut self,
+                                key: &Q) -> Result<Entry<'a, Key, Value, Allocator>>
+        where Key: Ord<Q>,
+    {
+        if let Some(node) = self.search(key) {
+            return Ok(Entry::Occupied(OccupiedEntry { map: self, node: node }));
+        }
+        let node = unsafe { try!(alloc::alloc(&mut self.pool)) };
+        Ok(Entry::Vacant(VacantEntry { map: self, node: node }))
+    }
+
+    /// Returns an iterator over the entries in descending order.
+    pub fn iter_rev<'a>(&'a self) -> RevIter<'a, Key, Value> {
+        RevIter { cursor: unsafe { self.tree.last_cursor() } }
+    }
+
+    /// Returns an iterator over the entries whose keys are not smaller than a key.
+    ///
+    /// [argument, key]
+    /// The lower bound of the keys.
+    pub fn lower_bound<'a, Q: ?Sized>(&'a self, key: &Q) -> Iter<'a, Key, Value>
+        where Key: Ord<Q>,
+    {
+        let cmp = |n: &MapNode<Key, Value>| <Key as Ord<Q>>::cmp(&n.key, key);
+        Iter { cursor: unsafe { self.tree.lower_bound(cmp) } }
+    }
+
+    /// Returns an iterator over the entries whose keys are larger than a key.
+    ///
+    /// [argument, key]
+    /// The strict lower bound of the keys.
+    pub fn upper_bound<'a, Q: ?Sized>(&'a self, key: &Q) -> Iter<'a, Key, Value>
+        where Key: Ord<Q>,
+    {
+        let cmp = |n: &MapNode<Key, Value>| <Key as Ord<Q>>::cmp(&n.key, key);
+        Iter { cursor: unsafe { self.tree.upper_bound(cmp) } }
+    }
+
+    /// Returns an iterator over the entries whose keys are in a range.
+    ///
+    /// [argument, start]
+    /// The inclusive lower bound of the keys.
+    ///
+    /// [argument, end]
+    /// The exclusive upper bound of the keys.
+    pub fn range<'a, Q: ?Sized, R: ?Sized>(&'a self, start: &Q,
+                                           end: &'a R) -> Range<'a, Key, Value, R>
+        where Key: Ord<Q> + Ord<R>,
+    {
+        Range { iter: self.lower_bound(start), end: end }
+    }
+
+    /// Returns an iterator over the keys in ascending order.
+    pub fn keys<'a>(&'a self) -> Keys<Iter<'a, Key, Value>> {
+        Keys { iter: self.into_iter() }
+    }
+}
+
+unsafe impl<Key, Value, Allocator: ?Sized> Send for TreeMap<Key, Value, Allocator>
+    where Allocator: MemPool + Send,
+          Key: Ord + Send,
+          Value: Send,
+{ }
+
+unsafe impl<Key, Value, Allocator: ?Sized> Sync for TreeMap<Key, Value, Allocator>
+    where Allocator: MemPool + Sync,
+          Key: Ord + Sync,
+          Value: Sync,
+{ }
+
+impl<Key, Value, Allocator: ?Sized> Drop for TreeMap<Key, Value, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    fn drop(&mut self) {
+        while let Some(node) = self.tree.remove_min() {
+            unsafe {
+                let node = node as *mut MapNode<Key, Value>;
+                ptr::drop(node);
+                alloc::free(&mut self.pool, node);
+            }
+        }
+    }
+}
+
+impl<'a, Key, Value, Allocator: ?Sized>
+    IntoIterator for &'a TreeMap<Key, Value, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    type Item = (&'a Key, &'a Value);
+    type IntoIter = Iter<'a, Key, Value>;
+    fn into_iter(self) -> Iter<'a, Key, Value> {
+        Iter { cursor: unsafe { self.tree.first_cursor() } }
+    }
+}
+
+impl<Key, Value, Allocator: ?Sized> Debug for TreeMap<Key, Value, Allocator>
+    where Allocator: MemPool,
+          Key: Ord + Debug,
+          Value: Debug,
+{
+    fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+        try!(write!(w, "{{ "));
+        for (key, value) in self {
+            try!(write!(w, "{:?}: {:?}, ", key, value));
+        }
+        write!(w, "}}")
+    }
+}
+
+/// An iterator over the entries of a map in ascending order.
+pub struct Iter<'a, Key: 'a, Value: 'a>
+    where Key: Ord,
+{
+    cursor: Cursor<'a, NodeEntree<Key, Value>>,
+}
+
+impl<'a, Key, Value> Iterator for Iter<'a, Key, Value>
+    where Key: Ord,
+{
+    type Item = (&'a Key, &'a Value);
+    fn next(&mut self) -> Option<(&'a Key, &'a Value)> {
+        let node = match self.cursor.get() {
+            Some(n) => n,
+            _ => return None,
+        };
+        self.cursor.move_next();
+        Some((&node.key, &node.value))
+    }
+}
+
+/// An iterator over the entries of a map in descending order.
+pub struct RevIter<'a, Key: 'a, Value: 'a>
+    where Key: Ord,
+{
+    cursor: Cursor<'a, NodeEntree<Key, Value>>,
+}
+
+impl<'a, Key, Value> Iterator for RevIter<'a, Key, Value>
+    where Key: Ord,
+{
+    type Item = (&'a Key, &'a Value);
+    fn next(&mut self) -> Option<(&'a Key, &'a Value)> {
+        let node = match self.cursor.get() {
+            Some(n) => n,
+            _ => return None,
+        };
+        self.cursor.move_prev();
+        Some((&node.key, &node.value))
+    }
+}
+
+/// An iterator over the entries of a map whose keys are in a range.
+pub struct Range<'a, Key: 'a, Value: 'a, End: ?Sized + 'a>
+    where Key: Ord,
+{
+    iter: Iter<'a, Key, Value>,
+    end: &'a End,
+}
+
+impl<'a, Key, Value, End: ?Sized> Iterator for Range<'a, Key, Value, End>
+    where Key: Ord + Ord<End>,
+{
+    type Item = (&'a Key, &'a Value);
+    fn next(&mut self) -> Option<(&'a Key, &'a Value)> {
+        match self.iter.next() {
+            Some((key, value)) => match <Key as Ord<End>>::cmp(key, self.end) {
+                Ordering::Less => Some((key, value)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// An iterator over the keys of a map.
+pub struct Keys<I> {
+    iter: I,
+}
+
+impl<'a, Key: 'a, Value: 'a, I> Iterator for Keys<I>
+    where I: Iterator<Item = (&'a Key, &'a Value)>,
+{
+    type Item = &'a Key;
+    fn next(&mut self) -> Option<&'a Key> {
+        self.iter.next().map(|(key, _)| key)
+    }
+}
+
+/// Creates a `Keys` iterator from an iterator over entries.
+pub fn keys<I>(iter: I) -> Keys<I> {
+    Keys { iter: iter }
+}
+
+/// An entry in a map.
+pub enum Entry<'a, Key: 'a, Value: 'a, Allocator: ?Sized + 'a>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    Occupied(OccupiedEntry<'a, Key, Value, Allocator>),
+    Vacant(VacantEntry<'a, Key, Value, Allocator>),
+}
+
+impl<'a, Key, Value, Allocator: ?Sized> Entry<'a, Key, Value, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    /// Returns the occupied entry or inserts a new key.
+    ///
+    /// [argument, key]
+    /// {
+    /// The key that will be inserted.
+    ///
+    /// This key should compare equal to the key that was used to retrieve this entry.
+    ///
+    /// }
+    ///
+    /// [argument, value]
+    /// The value that will be inserted.
+    pub fn or_insert(self, key: Key,
+                     value: Value) -> OccupiedEntry<'a, Key, Value, Allocator> {
+        self.or_insert_with(|| (key, value))
+    }
+
+    /// Returns the occupied entry or inserts the result of a function.
+    ///
+    /// [argument, f]
+    /// The function that will be called if the entry is vacant.
+    pub fn or_insert_with<F>(self, f: F) -> OccupiedEntry<'a, Key, Value, Allocator>
+        where F: FnOnce() -> (Key, Value),
+    {
+        match self {
+            Entry::Occupied(e) => e,
+            Entry::Vacant(v) => {
+                let (key, value) = f();
+                v.set(key, value)
+            },
+        }
+    }
+}
+
+/// A vacant entry in a map.
+///
+/// = Remarks
+///
+/// The entry owns the memory of the node that will be inserted into the map.
+pub struct VacantEntry<'a, Key: 'a, Value: 'a, Allocator: ?Sized + 'a>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    map: &'a mut TreeMap<Key, Value, Allocator>,
+    node: *mut MapNode<Key, Value>,
+}
+
+impl<'a, Key, Value, Allocator: ?Sized> VacantEntry<'a, Key, Value, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    /// Sets the content of the entry.
+    ///
+    /// [argument, key]
+    /// {
+    /// The key that will be inserted.
+    ///
+    /// This key should compare equal to the key that was used to retrieve this entry.
+    /// If the key is already in the map, the process is aborted.
+    ///
+    /// }
+    ///
+    /// [argument, value]
+    /// The value that will be inserted.
+    pub fn set(self, key: Key, value: Value) -> OccupiedEntry<'a, Key, Value, Allocator> {
+        unsafe {
+            let map = ptr::read(&self.map);
+            let node = self.node;
+            intrinsics::forget(self);
+            ptr::write(node, MapNode {
+                node: Node::new(),
+                key: key,
+                value: value,
+            });
+            map.tree.insert(&(node as *const _));
+            map.size += 1;
+            OccupiedEntry { map: map, node: node }
+        }
+    }
+}
+
+impl<'a, Key, Value, Allocator: ?Sized> Drop for VacantEntry<'a, Key, Value, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    fn drop(&mut self) {
+        unsafe { alloc::free(&mut self.map.pool, self.node); }
+    }
+}
+
+/// An occupied entry in a map.
+pub struct OccupiedEntry<'a, Key: 'a, Value: 'a, Allocator: ?Sized + 'a>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    map: &'a mut TreeMap<Key, Value, Allocator>,
+    node: *mut MapNode<Key, Value>,
+}
+
+impl<'a, Key, Value, Allocator: ?Sized> OccupiedEntry<'a, Key, Value, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    /// Returns a reference to the stored key.
+    pub fn key(&self) -> &Key {
+        unsafe { &(*self.node).key }
+    }
+
+    /// Returns the contained mutable reference to the stored value.
+    pub fn into_mut(self) -> &'a mut Value {
+        unsafe { &mut (*self.node).value }
+    }
+
+    /// Removes the contained value and key.
+    ///
+    /// = Remarks
+    ///
+    /// The memory of the node is reused by the returned vacant entry.
+    pub fn remove(self) -> (VacantEntry<'a, Key, Value, Allocator>, Key, Value) {
+        unsafe {
+            self.map.tree.remove(&(self.node as *const _));
+            self.map.size -= 1;
+            let key = ptr::read(&(*self.node).key);
+            let value = ptr::read(&(*self.node).value);
+            let entry = VacantEntry { map: self.map, node: self.node };
+            (entry, key, value)
+        }
+    }
+}
+
+impl<'a, Key, Value, Allocator: ?Sized> Deref for OccupiedEntry<'a, Key, Value, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    type Target = Value;
+    fn deref(&self) -> &Value {
+        unsafe { &(*self.node).value }
+    }
+}
+
+impl<'a, Key, Value, Allocator: ?Sized> DerefMut
+    for OccupiedEntry<'a, Key, Value, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    fn deref_mut(&mut self) -> &mut Value {
+        unsafe { &mut (*self.node).value }
+    }
+}
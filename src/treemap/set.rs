@@ -0,0 +1,165 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use core::cmp::{Ord};
+use core::iter::{IntoIterator};
+use alloc::{self, MemPool};
+use fmt::{Debug, Write};
+use map::{self, TreeMap, Entry, Keys, Iter, RevIter, Range};
+
+/// An ordered set implemented as a red-black tree.
+pub struct TreeSet<Key, Allocator: ?Sized = alloc::Heap>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    map: TreeMap<Key, (), Allocator>,
+}
+
+impl<Key, Allocator> TreeSet<Key, Allocator>
+    where Allocator: MemPool + OutOf,
+          Key: Ord,
+{
+    /// Creates a new, empty set.
+    pub fn new() -> Self {
+        TreeSet { map: TreeMap::new() }
+    }
+}
+
+impl<Key, Allocator> TreeSet<Key, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    /// Creates a new, empty set with a memory pool.
+    ///
+    /// [argument, pool]
+    /// The pool from which the nodes will be allocated.
+    pub fn with_pool(pool: Allocator) -> Self {
+        TreeSet { map: TreeMap::with_pool(pool) }
+    }
+}
+
+impl<Key, Allocator: ?Sized> TreeSet<Key, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    /// Returns the number of keys in the set.
+    pub fn size(&self) -> usize {
+        self.map.size()
+    }
+
+    /// Returns whether the set contains a key.
+    ///
+    /// [argument, key]
+    /// The key to search for.
+    pub fn contains<Q: ?Sized>(&self, key: &Q) -> bool
+        where Key: Ord<Q>,
+    {
+        self.map.get(key).is_some()
+    }
+
+    /// Returns the smallest key, if any.
+    pub fn first(&self) -> Option<&Key> {
+        self.map.first().map(|(key, _)| key)
+    }
+
+    /// Returns the largest key, if any.
+    pub fn last(&self) -> Option<&Key> {
+        self.map.last().map(|(key, _)| key)
+    }
+
+    /// Inserts a key into the set.
+    ///
+    /// [argument, key]
+    /// The key to insert.
+    ///
+    /// [return_value]
+    /// Returns whether the key was inserted.
+    ///
+    /// = Remarks
+    ///
+    /// If the key is already in the set, the passed key will be dropped.
+    pub fn insert(&mut self, key: Key) -> Result<bool> {
+        match try!(self.map.entry(&key)) {
+            Entry::Occupied(_) => Ok(false),
+            Entry::Vacant(v) => {
+                v.set(key, ());
+                Ok(true)
+            },
+        }
+    }
+
+    /// Removes a key from the set.
+    ///
+    /// [argument, key]
+    /// The key to remove.
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<Key>
+        where Key: Ord<Q>,
+    {
+        self.map.remove(key).map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the keys in descending order.
+    pub fn iter_rev<'a>(&'a self) -> Keys<RevIter<'a, Key, ()>> {
+        map::keys(self.map.iter_rev())
+    }
+
+    /// Returns an iterator over the keys that are not smaller than a key.
+    ///
+    /// [argument, key]
+    /// The lower bound of the keys.
+    pub fn lower_bound<'a, Q: ?Sized>(&'a self, key: &Q) -> Keys<Iter<'a, Key, ()>>
+        where Key: Ord<Q>,
+    {
+        map::keys(self.map.lower_bound(key))
+    }
+
+    /// Returns an iterator over the keys that are larger than a key.
+    ///
+    /// [argument, key]
+    /// The strict lower bound of the keys.
+    pub fn upper_bound<'a, Q: ?Sized>(&'a self, key: &Q) -> Keys<Iter<'a, Key, ()>>
+        where Key: Ord<Q>,
+    {
+        map::keys(self.map.upper_bound(key))
+    }
+
+    /// Returns an iterator over the keys in a range.
+    ///
+    /// [argument, start]
+    /// The inclusive lower bound of the keys.
+    ///
+    /// [argument, end]
+    /// The exclusive upper bound of the keys.
+    pub fn range<'a, Q: ?Sized, R: ?Sized>(&'a self, start: &Q,
+                                           end: &'a R) -> Keys<Range<'a, Key, (), R>>
+        where Key: Ord<Q> + Ord<R>,
+    {
+        map::keys(self.map.range(start, end))
+    }
+}
+
+impl<'a, Key, Allocator: ?Sized> IntoIterator for &'a TreeSet<Key, Allocator>
+    where Allocator: MemPool,
+          Key: Ord,
+{
+    type Item = &'a Key;
+    type IntoIter = Keys<Iter<'a, Key, ()>>;
+    fn into_iter(self) -> Keys<Iter<'a, Key, ()>> {
+        self.map.keys()
+    }
+}
+
+impl<Key, Allocator: ?Sized> Debug for TreeSet<Key, Allocator>
+    where Allocator: MemPool,
+          Key: Ord + Debug,
+{
+    fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+        try!(write!(w, "{{ "));
+        for key in self {
+            try!(write!(w, "{:?}, ", key));
+        }
+        write!(w, "}}")
+    }
+}
@@ -0,0 +1,440 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use core::{mem};
+use cty::{
+    c_int, io_uring_sqe, __kernel_timespec, IORING_OP_NOP, IORING_OP_READ,
+    IORING_OP_WRITE, IORING_OP_READV, IORING_OP_WRITEV, IORING_OP_FSYNC,
+    IORING_OP_ACCEPT, IORING_OP_CONNECT, IORING_OP_SEND, IORING_OP_RECV,
+    IORING_OP_TIMEOUT, IORING_OP_OPENAT, IORING_OP_CLOSE, IORING_FSYNC_DATASYNC,
+    IORING_TIMEOUT_ABS, IOSQE_IO_LINK, IOSQE_IO_HARDLINK, IOSQE_IO_DRAIN, IOSQE_ASYNC,
+    MSG_NOSIGNAL, SOCK_CLOEXEC, O_CLOEXEC, O_LARGEFILE,
+};
+use fd::{FdContainer};
+use str_one::{CStr};
+use time_base::{Time};
+use file::flags::{FileFlags, Mode};
+use socket::flags::{SockFlags};
+use socket::msg::{MsgFlags};
+
+/// The offset that makes reads and writes use the current file position.
+pub const CURRENT_POSITION: u64 = !0;
+
+/// A timeout that can be used with an io_uring.
+///
+/// = Remarks
+///
+/// The kernel reads the timeout when the entry is processed. The object must therefore
+/// stay alive until the corresponding completion has been received.
+#[derive(Pod, Eq)]
+pub struct Timeout {
+    ts: __kernel_timespec,
+}
+
+impl Timeout {
+    /// Creates a new timeout.
+    ///
+    /// [argument, time]
+    /// The duration of the timeout or, for absolute timeouts, the point in time on the
+    /// monotonic clock at which the timeout expires.
+    pub fn new(time: Time) -> Timeout {
+        Timeout {
+            ts: __kernel_timespec {
+                tv_sec: time.seconds,
+                tv_nsec: time.nanoseconds,
+            },
+        }
+    }
+}
+
+/// An entry in the submission queue.
+///
+/// = Remarks
+///
+/// The constructors of this type store raw pointers to their arguments. The buffers,
+/// paths, addresses, and timeouts used to create an entry must stay alive and must not be
+/// moved until the corresponding completion has been received.
+///
+/// = See also
+///
+/// * link:lrs::uring::Uring::push
+#[derive(Pod, Eq)]
+pub struct Entry {
+    sqe: io_uring_sqe,
+}
+
+impl Entry {
+    fn new(opcode: u8, fd: c_int) -> Entry {
+        let mut sqe: io_uring_sqe = mem::zeroed();
+        sqe.opcode = opcode;
+        sqe.fd = fd;
+        Entry { sqe: sqe }
+    }
+
+    /// Creates an entry that does nothing.
+    pub fn nop() -> Entry {
+        Entry::new(IORING_OP_NOP, -1)
+    }
+
+    /// Creates an entry that reads from a file descriptor.
+    ///
+    /// [argument, fd]
+    /// The file descriptor to read from.
+    ///
+    /// [argument, buf]
+    /// The buffer into which the data will be read.
+    ///
+    /// [argument, off]
+    /// The offset in the file or `CURRENT_POSITION`.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IORING_OP_READ therein
+    pub fn read<F>(fd: &F, buf: &mut [d8], off: u64) -> Entry
+        where F: FdContainer,
+    {
+        let mut e = Entry::new(IORING_OP_READ, fd.borrow());
+        e.sqe.addr = buf.as_ptr() as usize as u64;
+        e.sqe.len = buf.len() as u32;
+        e.sqe.off = off;
+        e
+    }
+
+    /// Creates an entry that writes to a file descriptor.
+    ///
+    /// [argument, fd]
+    /// The file descriptor to write to.
+    ///
+    /// [argument, buf]
+    /// The buffer that will be written.
+    ///
+    /// [argument, off]
+    /// The offset in the file or `CURRENT_POSITION`.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IORING_OP_WRITE therein
+    pub fn write<F>(fd: &F, buf: &[u8], off: u64) -> Entry
+        where F: FdContainer,
+    {
+        let mut e = Entry::new(IORING_OP_WRITE, fd.borrow());
+        e.sqe.addr = buf.as_ptr() as usize as u64;
+        e.sqe.len = buf.len() as u32;
+        e.sqe.off = off;
+        e
+    }
+
+    /// Creates an entry that reads from a file descriptor into multiple buffers.
+    ///
+    /// [argument, fd]
+    /// The file descriptor to read from.
+    ///
+    /// [argument, bufs]
+    /// The buffers into which the data will be read.
+    ///
+    /// [argument, off]
+    /// The offset in the file or `CURRENT_POSITION`.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IORING_OP_READV therein
+    pub fn scatter_read<F>(fd: &F, bufs: &mut [&mut [d8]], off: u64) -> Entry
+        where F: FdContainer,
+    {
+        let mut e = Entry::new(IORING_OP_READV, fd.borrow());
+        e.sqe.addr = bufs.as_ptr() as usize as u64;
+        e.sqe.len = bufs.len() as u32;
+        e.sqe.off = off;
+        e
+    }
+
+    /// Creates an entry that writes multiple buffers to a file descriptor.
+    ///
+    /// [argument, fd]
+    /// The file descriptor to write to.
+    ///
+    /// [argument, bufs]
+    /// The buffers that will be written.
+    ///
+    /// [argument, off]
+    /// The offset in the file or `CURRENT_POSITION`.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IORING_OP_WRITEV therein
+    pub fn gather_write<F>(fd: &F, bufs: &[&[u8]], off: u64) -> Entry
+        where F: FdContainer,
+    {
+        let mut e = Entry::new(IORING_OP_WRITEV, fd.borrow());
+        e.sqe.addr = bufs.as_ptr() as usize as u64;
+        e.sqe.len = bufs.len() as u32;
+        e.sqe.off = off;
+        e
+    }
+
+    /// Creates an entry that flushes a file to the disk.
+    ///
+    /// [argument, fd]
+    /// The file descriptor to flush.
+    ///
+    /// [argument, data_only]
+    /// Whether only the data and not the metadata should be flushed.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IORING_OP_FSYNC therein
+    pub fn sync<F>(fd: &F, data_only: bool) -> Entry
+        where F: FdContainer,
+    {
+        let mut e = Entry::new(IORING_OP_FSYNC, fd.borrow());
+        if data_only {
+            e.sqe.op_flags = IORING_FSYNC_DATASYNC;
+        }
+        e
+    }
+
+    /// Creates an entry that accepts a connection on a socket.
+    ///
+    /// [argument, fd]
+    /// The listening socket.
+    ///
+    /// [argument, flags]
+    /// The flags that will be set on the new socket.
+    ///
+    /// = Remarks
+    ///
+    /// The result of the completion is the file descriptor of the new socket. Unless lrs
+    /// was compiled with the `no-auto-cloexec` flag, the `SOCK_CLOSE_ON_EXEC` flag is
+    /// added automatically.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IORING_OP_ACCEPT therein
+    pub fn accept<F>(fd: &F, flags: SockFlags) -> Entry
+        where F: FdContainer,
+    {
+        let mut e = Entry::new(IORING_OP_ACCEPT, fd.borrow());
+        let mut flags = flags.0;
+        if cfg!(not(no_auto_cloexec)) {
+            flags |= SOCK_CLOEXEC;
+        }
+        e.sqe.op_flags = flags as u32;
+        e
+    }
+
+    /// Creates an entry that connects a socket to an address.
+    ///
+    /// [argument, fd]
+    /// The socket.
+    ///
+    /// [argument, addr]
+    /// The address to connect to.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IORING_OP_CONNECT therein
+    pub fn connect<F, A: ?Sized>(fd: &F, addr: &A) -> Entry
+        where F: FdContainer,
+              A: AsRef<[u8]>,
+    {
+        let addr = addr.as_ref();
+        let mut e = Entry::new(IORING_OP_CONNECT, fd.borrow());
+        e.sqe.addr = addr.as_ptr() as usize as u64;
+        e.sqe.off = addr.len() as u64;
+        e
+    }
+
+    /// Creates an entry that sends data over a socket.
+    ///
+    /// [argument, fd]
+    /// The socket.
+    ///
+    /// [argument, buf]
+    /// The data to send.
+    ///
+    /// [argument, flags]
+    /// Flags to use while sending.
+    ///
+    /// = Remarks
+    ///
+    /// The `MSG_NO_SIGNAL` flag is added automatically.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IORING_OP_SEND therein
+    pub fn send<F>(fd: &F, buf: &[u8], flags: MsgFlags) -> Entry
+        where F: FdContainer,
+    {
+        let mut e = Entry::new(IORING_OP_SEND, fd.borrow());
+        e.sqe.addr = buf.as_ptr() as usize as u64;
+        e.sqe.len = buf.len() as u32;
+        e.sqe.op_flags = (flags.0 | MSG_NOSIGNAL) as u32;
+        e
+    }
+
+    /// Creates an entry that receives data from a socket.
+    ///
+    /// [argument, fd]
+    /// The socket.
+    ///
+    /// [argument, buf]
+    /// The buffer into which the data will be received.
+    ///
+    /// [argument, flags]
+    /// Flags to use while receiving.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IORING_OP_RECV therein
+    pub fn recv<F>(fd: &F, buf: &mut [d8], flags: MsgFlags) -> Entry
+        where F: FdContainer,
+    {
+        let mut e = Entry::new(IORING_OP_RECV, fd.borrow());
+        e.sqe.addr = buf.as_ptr() as usize as u64;
+        e.sqe.len = buf.len() as u32;
+        e.sqe.op_flags = flags.0 as u32;
+        e
+    }
+
+    /// Creates an entry that completes after a timeout.
+    ///
+    /// [argument, timeout]
+    /// The timeout.
+    ///
+    /// [argument, count]
+    /// The number of other completions after which the timeout completes early or `0`.
+    ///
+    /// [argument, absolute]
+    /// Whether the timeout is a point in time on the monotonic clock.
+    ///
+    /// = Remarks
+    ///
+    /// If the timeout expires, the result of the completion is the `TimerExpired` error.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IORING_OP_TIMEOUT therein
+    pub fn timeout(timeout: &Timeout, count: u32, absolute: bool) -> Entry {
+        let mut e = Entry::new(IORING_OP_TIMEOUT, -1);
+        e.sqe.addr = &timeout.ts as *const _ as usize as u64;
+        e.sqe.len = 1;
+        e.sqe.off = count as u64;
+        if absolute {
+            e.sqe.op_flags = IORING_TIMEOUT_ABS;
+        }
+        e
+    }
+
+    /// Creates an entry that opens a file relative to a directory.
+    ///
+    /// [argument, dir]
+    /// The directory relative to which relative paths are interpreted.
+    ///
+    /// [argument, path]
+    /// The path of the file.
+    ///
+    /// [argument, flags]
+    /// The flags used to open the file.
+    ///
+    /// [argument, mode]
+    /// The mode used to create new files.
+    ///
+    /// = Remarks
+    ///
+    /// The result of the completion is the new file descriptor. Unless lrs was compiled
+    /// with the `no-auto-cloexec` flag, the `FILE_CLOSE_ON_EXEC` flag is added
+    /// automatically. The `FILE_LARGE` flag is always added.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IORING_OP_OPENAT therein
+    pub fn open_at<F>(dir: &F, path: &CStr, flags: FileFlags, mode: Mode) -> Entry
+        where F: FdContainer,
+    {
+        let mut e = Entry::new(IORING_OP_OPENAT, dir.borrow());
+        let mut flags = flags.0 | O_LARGEFILE;
+        if cfg!(not(no_auto_cloexec)) {
+            flags |= O_CLOEXEC;
+        }
+        e.sqe.addr = path.as_ptr() as usize as u64;
+        e.sqe.len = mode.0 as u32;
+        e.sqe.op_flags = flags as u32;
+        e
+    }
+
+    /// Creates an entry that closes a file descriptor.
+    ///
+    /// [argument, fd]
+    /// The file descriptor to close.
+    ///
+    /// = Remarks
+    ///
+    /// The file descriptor should not be owned by an object that will close it again.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IORING_OP_CLOSE therein
+    pub fn close(fd: c_int) -> Entry {
+        Entry::new(IORING_OP_CLOSE, fd)
+    }
+
+    /// Sets the user data of the entry.
+    ///
+    /// [argument, data]
+    /// The data that will be returned in the completion of this entry.
+    pub fn set_user_data(&mut self, data: u64) {
+        self.sqe.user_data = data;
+    }
+
+    /// Returns the user data of the entry.
+    pub fn user_data(&self) -> u64 {
+        self.sqe.user_data
+    }
+
+    /// Links the next entry to this one.
+    ///
+    /// = Remarks
+    ///
+    /// The next entry will not be started before this one has completed successfully.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IOSQE_IO_LINK therein
+    pub fn link(&mut self) {
+        self.sqe.flags |= IOSQE_IO_LINK;
+    }
+
+    /// Links the next entry to this one regardless of the result of this entry.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IOSQE_IO_HARDLINK therein
+    pub fn hard_link(&mut self) {
+        self.sqe.flags |= IOSQE_IO_HARDLINK;
+    }
+
+    /// Makes the entry wait for all previously submitted entries.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IOSQE_IO_DRAIN therein
+    pub fn drain(&mut self) {
+        self.sqe.flags |= IOSQE_IO_DRAIN;
+    }
+
+    /// Makes the kernel execute the entry asynchronously from the start.
+    ///
+    /// = See also
+    ///
+    /// * link:man:io_uring_enter(2) and IOSQE_ASYNC therein
+    pub fn force_async(&mut self) {
+        self.sqe.flags |= IOSQE_ASYNC;
+    }
+
+    /// Returns the raw submission queue entry.
+    pub fn as_sqe(&self) -> &io_uring_sqe {
+        &self.sqe
+    }
+}
@@ -0,0 +1,114 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use base::prelude::*;
+use core::ops::{BitOr, Not, BitAnd};
+use fmt::{Debug, Write};
+use cty::{
+    self,
+};
+
+/// Flags for creating an io_uring.
+///
+/// [field, 1]
+/// The numeric representation of the flags.
+#[derive(Pod, Eq)]
+pub struct UringFlags(pub u32);
+
+impl BitOr for UringFlags {
+    type Output = UringFlags;
+    fn bitor(self, other: UringFlags) -> UringFlags {
+        UringFlags(self.0 | other.0)
+    }
+}
+
+impl BitAnd for UringFlags {
+    type Output = UringFlags;
+    fn bitand(self, other: UringFlags) -> UringFlags {
+        UringFlags(self.0 & other.0)
+    }
+}
+
+impl Not for UringFlags {
+    type Output = UringFlags;
+    fn not(self) -> UringFlags {
+        UringFlags(!self.0)
+    }
+}
+
+/// Dummy flag with all flags unset.
+pub const URING_NONE: UringFlags = UringFlags(0);
+
+macro_rules! create_flags {
+    ($($(#[$meta:meta])* flag $name:ident = $val:ident;)*) => {
+        $($(#[$meta])* pub const $name: UringFlags = UringFlags(cty::$val);)*
+
+        impl Debug for UringFlags {
+            fn fmt<W: Write>(&self, mut w: &mut W) -> Result {
+                let raw = self.0;
+                const KNOWN_FLAGS: u32 = 0 $(| cty::$val)*;
+                if raw & !KNOWN_FLAGS != 0 {
+                    return write!(w, "0x{:x}", raw);
+                }
+                let mut first = true;
+                $(
+                    if raw & cty::$val != 0 {
+                        if !first { try!(w.write(b"|")); }
+                        first = false;
+                        try!(w.write_all(stringify!($name).as_bytes()));
+                    }
+                )*
+                if first {
+                    try!(w.write_all("URING_NONE".as_bytes()));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+create_flags! {
+    #[doc = "Busy-poll for completions instead of waiting for interrupts.\n"]
+    #[doc = "= Remarks"]
+    #[doc = "This only works with files opened with `FILE_BYPASS_BUFFER`.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:io_uring_setup(2) and IORING_SETUP_IOPOLL therein"]
+    flag URING_IO_POLL = IORING_SETUP_IOPOLL;
+
+    #[doc = "Let a kernel thread poll the submission queue.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:io_uring_setup(2) and IORING_SETUP_SQPOLL therein"]
+    flag URING_SQ_POLL = IORING_SETUP_SQPOLL;
+
+    #[doc = "Clamp the number of entries to the maximum instead of failing.\n"]
+    #[doc = "= See also"]
+    #[doc = "* link:man:io_uring_setup(2) and IORING_SETUP_CLAMP therein"]
+    flag URING_CLAMP = IORING_SETUP_CLAMP;
+}
+
+impl UringFlags {
+    /// Sets a flag.
+    ///
+    /// [argument, flag]
+    /// The flag to be set.
+    pub fn set(&mut self, flag: UringFlags) {
+        self.0 |= flag.0
+    }
+
+    /// Clears a flag.
+    ///
+    /// [argument, flag]
+    /// The flag to be cleared.
+    pub fn unset(&mut self, flag: UringFlags) {
+        self.0 &= !flag.0
+    }
+
+    /// Returns whether a flag is set.
+    ///
+    /// [argument, flag]
+    /// The flag to be checked.
+    pub fn is_set(&self, flag: UringFlags) -> bool {
+        self.0 & flag.0 != 0
+    }
+}
@@ -0,0 +1,324 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+#![crate_name = "lrs_uring"]
+#![crate_type = "lib"]
+#![feature(custom_derive, associated_consts)]
+#![no_std]
+
+extern crate lrs_base      as base;
+extern crate lrs_cty       as cty;
+extern crate lrs_fmt       as fmt;
+extern crate lrs_syscall   as syscall;
+extern crate lrs_rv        as rv;
+extern crate lrs_fd        as fd;
+extern crate lrs_atomic    as atomic;
+extern crate lrs_mem       as mem_map;
+extern crate lrs_str_one   as str_one;
+extern crate lrs_time_base as time_base;
+extern crate lrs_file      as file;
+extern crate lrs_socket    as socket;
+
+use base::prelude::*;
+mod std { pub use fmt::std::*; pub use cty; }
+
+use core::{mem};
+use base::{error};
+use cty::{
+    c_int, io_uring_params, io_uring_sqe, io_uring_cqe, IORING_OFF_SQ_RING,
+    IORING_OFF_CQ_RING, IORING_OFF_SQES, IORING_ENTER_GETEVENTS, IORING_ENTER_SQ_WAKEUP,
+    IORING_SETUP_SQPOLL, IORING_SQ_NEED_WAKEUP,
+};
+use syscall::{close, io_uring_setup, io_uring_enter};
+use rv::{retry};
+use fd::{FdIo};
+use atomic::{Atomic};
+use mem_map::{MemMap};
+use mem_map::flags::{PROT_READ, PROT_WRITE, MMAP_POPULATE};
+use flags::{UringFlags, URING_NONE};
+use entry::{Entry};
+
+pub mod flags;
+pub mod entry;
+
+/// A completed operation.
+#[derive(Pod, Eq)]
+pub struct Completion {
+    cqe: io_uring_cqe,
+}
+
+impl Completion {
+    /// Returns the user data of the entry that produced this completion.
+    pub fn user_data(&self) -> u64 {
+        self.cqe.user_data
+    }
+
+    /// Returns the result of the operation.
+    ///
+    /// = Remarks
+    ///
+    /// The meaning of the value depends on the operation. For reads and writes, it is the
+    /// number of bytes transferred. For `accept` and `open_at`, it is the new file
+    /// descriptor.
+    pub fn result(&self) -> Result<u32> {
+        match self.cqe.res {
+            n if n < 0 => Err(error::Errno(-n as c_int)),
+            n => Ok(n as u32),
+        }
+    }
+}
+
+/// An io_uring instance.
+///
+/// = Remarks
+///
+/// Entries are added to the submission queue with `push` and handed to the kernel with
+/// `submit`. Completed operations are retrieved with `completions`.
+///
+/// = Examples
+///
+/// ----
+/// let mut ring = try!(Uring::new(32));
+/// let mut buf = [0; 128];
+/// let mut entry = Entry::read(&file, &mut buf, 0);
+/// entry.set_user_data(1);
+/// unsafe { try!(ring.push(&entry)); }
+/// try!(ring.submit_and_wait(1));
+/// for c in ring.completions() {
+///     println!("{}: {:?}", c.user_data(), c.result());
+/// }
+/// ----
+///
+/// = See also
+///
+/// * link:man:io_uring_setup(2)
+/// * link:man:io_uring_enter(2)
+pub struct Uring {
+    fd: c_int,
+    params: io_uring_params,
+    sq_ring: MemMap,
+    cq_ring: MemMap,
+    sqes: MemMap,
+    /// The tail of the submission queue that has not yet been published to the kernel.
+    sq_tail: u32,
+}
+
+impl Uring {
+    /// Creates a new io_uring instance.
+    ///
+    /// [argument, entries]
+    /// The requested number of submission queue entries.
+    ///
+    /// = Remarks
+    ///
+    /// The kernel rounds the number up to the next power of two. The completion queue
+    /// is twice as large as the submission queue.
+    pub fn new(entries: u32) -> Result<Uring> {
+        Uring::with_flags(entries, URING_NONE)
+    }
+
+    /// Creates a new io_uring instance with flags.
+    ///
+    /// [argument, entries]
+    /// The requested number of submission queue entries.
+    ///
+    /// [argument, flags]
+    /// The flags used to create the instance.
+    pub fn with_flags(entries: u32, flags: UringFlags) -> Result<Uring> {
+        let mut params: io_uring_params = mem::zeroed();
+        params.flags = flags.0;
+        let fd = try!(rv!(io_uring_setup(entries, &mut params), -> c_int));
+
+        let sq_len = params.sq_off.array as usize + 4 * params.sq_entries as usize;
+        let cq_len = params.cq_off.cqes as usize
+                        + mem::size_of::<io_uring_cqe>() * params.cq_entries as usize;
+        let sqes_len = mem::size_of::<io_uring_sqe>() * params.sq_entries as usize;
+
+        let prot = PROT_READ | PROT_WRITE;
+        let map = |len: usize, off: u64| MemMap::file(&fd, off, len, prot, true,
+                                                      MMAP_POPULATE);
+        let maps = map(sq_len, IORING_OFF_SQ_RING).chain(|sq| {
+            map(cq_len, IORING_OFF_CQ_RING).chain(|cq| {
+                map(sqes_len, IORING_OFF_SQES).map(|sqes| (sq, cq, sqes))
+            })
+        });
+        let (sq_ring, cq_ring, sqes) = match maps {
+            Ok(m) => m,
+            Err(e) => {
+                close(fd);
+                return Err(e);
+            },
+        };
+
+        let mut uring = Uring {
+            fd: fd,
+            params: params,
+            sq_ring: sq_ring,
+            cq_ring: cq_ring,
+            sqes: sqes,
+            sq_tail: 0,
+        };
+        uring.sq_tail = uring.sq_u32(params.sq_off.tail).load_unordered();
+        Ok(uring)
+    }
+
+    fn sq_u32(&self, off: u32) -> &Atomic<u32> {
+        unsafe { Atomic::wrap(self.sq_ring.as_ptr().offset(off as isize) as *mut u32) }
+    }
+
+    fn cq_u32(&self, off: u32) -> &Atomic<u32> {
+        unsafe { Atomic::wrap(self.cq_ring.as_ptr().offset(off as isize) as *mut u32) }
+    }
+
+    /// Returns the file descriptor of the instance.
+    ///
+    /// = Remarks
+    ///
+    /// The file descriptor becomes readable when completions are available. It can be
+    /// used to integrate the instance into an epoll loop.
+    pub fn as_fdio(&self) -> FdIo {
+        FdIo(self.fd)
+    }
+
+    /// Returns the number of entries in the submission queue.
+    pub fn sq_entries(&self) -> u32 {
+        self.params.sq_entries
+    }
+
+    /// Returns the number of entries in the completion queue.
+    pub fn cq_entries(&self) -> u32 {
+        self.params.cq_entries
+    }
+
+    /// Returns the number of entries that can be pushed before the submission queue is
+    /// full.
+    pub fn free_entries(&self) -> u32 {
+        let head = self.sq_u32(self.params.sq_off.head).load_acquire();
+        self.params.sq_entries - self.sq_tail.wrapping_sub(head)
+    }
+
+    /// Adds an entry to the submission queue.
+    ///
+    /// [argument, entry]
+    /// The entry to add.
+    ///
+    /// = Remarks
+    ///
+    /// If the submission queue is full, the error `WouldBlock` is returned. In this case,
+    /// the entries have to be submitted before more entries can be added.
+    ///
+    /// This is unsafe because the kernel will access the memory referenced by the entry
+    /// until the operation has completed. The caller must ensure that this memory stays
+    /// valid for that long.
+    pub unsafe fn push(&mut self, entry: &Entry) -> Result {
+        if self.free_entries() == 0 {
+            return Err(error::WouldBlock);
+        }
+        let mask = self.sq_u32(self.params.sq_off.ring_mask).load_unordered();
+        let idx = self.sq_tail & mask;
+        let sqes = self.sqes.as_ptr() as *mut io_uring_sqe;
+        *sqes.offset(idx as isize) = *entry.as_sqe();
+        let array = self.sq_ring.as_ptr().offset(self.params.sq_off.array as isize);
+        *(array as *mut u32).offset(idx as isize) = idx;
+        self.sq_tail = self.sq_tail.wrapping_add(1);
+        Ok(())
+    }
+
+    /// Publishes pushed entries to the kernel and returns how many have to be submitted.
+    fn flush(&self) -> u32 {
+        let tail = self.sq_u32(self.params.sq_off.tail);
+        let to_submit = self.sq_tail.wrapping_sub(tail.load_unordered());
+        tail.store_release(self.sq_tail);
+        to_submit
+    }
+
+    fn enter(&self, min_complete: u32) -> Result<usize> {
+        let to_submit = self.flush();
+        let mut flags = 0;
+        if min_complete > 0 {
+            flags |= IORING_ENTER_GETEVENTS;
+        }
+        if self.params.flags & IORING_SETUP_SQPOLL != 0 {
+            let sq_flags = self.sq_u32(self.params.sq_off.flags).load_acquire();
+            if sq_flags & IORING_SQ_NEED_WAKEUP != 0 {
+                flags |= IORING_ENTER_SQ_WAKEUP;
+            } else if min_complete == 0 {
+                return Ok(to_submit as usize);
+            }
+        }
+        retry(|| unsafe {
+            io_uring_enter(self.fd, to_submit, min_complete, flags, None)
+        }).map(|n| n as usize)
+    }
+
+    /// Submits all pushed entries to the kernel.
+    ///
+    /// [return_value]
+    /// Returns the number of submitted entries.
+    pub fn submit(&mut self) -> Result<usize> {
+        self.enter(0)
+    }
+
+    /// Submits all pushed entries to the kernel and waits for completions.
+    ///
+    /// [argument, min_complete]
+    /// The number of completions to wait for.
+    ///
+    /// [return_value]
+    /// Returns the number of submitted entries.
+    pub fn submit_and_wait(&mut self, min_complete: u32) -> Result<usize> {
+        self.enter(min_complete)
+    }
+
+    /// Returns an iterator over the available completions.
+    ///
+    /// = Remarks
+    ///
+    /// Each completion is removed from the completion queue when the iterator yields it.
+    /// The iterator does not wait for new completions.
+    pub fn completions(&mut self) -> Completions {
+        Completions { uring: self }
+    }
+
+    /// Retrieves the next available completion.
+    pub fn next_completion(&mut self) -> Option<Completion> {
+        let off = self.params.cq_off;
+        let head = self.cq_u32(off.head);
+        let cur = head.load_unordered();
+        if cur == self.cq_u32(off.tail).load_acquire() {
+            return None;
+        }
+        let mask = self.cq_u32(off.ring_mask).load_unordered();
+        let cqe = unsafe {
+            let cqes = self.cq_ring.as_ptr().offset(off.cqes as isize);
+            *(cqes as *const io_uring_cqe).offset((cur & mask) as isize)
+        };
+        head.store_release(cur.wrapping_add(1));
+        Some(Completion { cqe: cqe })
+    }
+
+    /// Returns the number of completions that were dropped because the completion queue
+    /// was full.
+    pub fn overflow(&self) -> u32 {
+        self.cq_u32(self.params.cq_off.overflow).load_acquire()
+    }
+}
+
+/// An iterator over the available completions of an io_uring.
+pub struct Completions<'a> {
+    uring: &'a mut Uring,
+}
+
+impl<'a> Iterator for Completions<'a> {
+    type Item = Completion;
+    fn next(&mut self) -> Option<Completion> {
+        self.uring.next_completion()
+    }
+}
+
+impl Drop for Uring {
+    fn drop(&mut self) {
+        close(self.fd);
+    }
+}
@@ -13,6 +13,7 @@ extern crate lrs_io as io;
 extern crate lrs_box as bx;
 extern crate lrs_fmt as fmt;
 extern crate lrs_alloc as alloc;
+extern crate lrs_iter as iter;
 
 pub mod std {
     pub use fmt::std::*;
@@ -27,6 +28,7 @@ use base::undef::{UndefState};
 use core::iter::{IntoIterator};
 use fmt::{Write, Debug};
 use alloc::{MemPool, empty_ptr};
+use iter::{FromIter};
 
 mod conv;
 mod cmp_;
@@ -410,6 +412,20 @@ impl<T, H: ?Sized> Debug for Vec<T, H>
     }
 }
 
+impl<T, H> FromIter<T> for Vec<T, H>
+    where H: MemPool+OutOf,
+{
+    fn from_iter<I>(iter: I) -> Result<Vec<T, H>>
+        where I: IntoIterator<Item=T>,
+    {
+        let mut vec = Vec::new();
+        for item in iter {
+            try!(vec.push(item));
+        }
+        Ok(vec)
+    }
+}
+
 impl<'a, T, H: ?Sized> IntoIterator for &'a Vec<T, H>
     where H: MemPool,
 {
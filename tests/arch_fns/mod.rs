@@ -1,3 +1,377 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::util::{memchr, memrchr, equal, strlen};
+use std::ptr::{memcpy, memmove};
+use std::cty::{c_int};
+
+extern {
+    fn memset(s: *mut u8, c: c_int, n: usize) -> *mut u8;
+    fn memcmp(s1: *const u8, s2: *const u8, n: usize) -> c_int;
+}
+
+const ALIGNS: usize = 33;
+const LENS: usize = 300;
+const SIZE: usize = ALIGNS + LENS + 64;
+
+fn naive_memchr(s: &[u8], c: u8) -> Option<usize> {
+    for i in 0..s.len() {
+        if s[i] == c {
+            return Some(i);
+        }
+    }
+    None
+}
+
+fn naive_memrchr(s: &[u8], c: u8) -> Option<usize> {
+    let mut i = s.len();
+    while i > 0 {
+        i -= 1;
+        if s[i] == c {
+            return Some(i);
+        }
+    }
+    None
+}
+
+fn naive_equal(a: &[u8], b: &[u8]) -> bool {
+    for i in 0..a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+    }
+    true
+}
+
+fn pattern(buf: &mut [u8]) {
+    for i in 0..buf.len() {
+        buf[i] = (i % 251) as u8 + 1;
+    }
+}
+
+/// Returns the needle positions that are tested for a slice of length `len`.
+fn positions(len: usize) -> Vec<usize> {
+    let mut pos = Vec::new();
+    if len <= 80 {
+        for i in 0..len {
+            pos.push(i);
+        }
+    } else {
+        for &i in &[0, 1, len / 3, len / 2, len - 17, len - 2, len - 1] {
+            pos.push(i);
+        }
+    }
+    pos
+}
+
+#[test]
+fn memchr_alignments() {
+    let mut buf = [0; SIZE];
+    for align in 0..ALIGNS {
+        for len in 0..LENS {
+            pattern(&mut buf);
+            // Needles right outside the slice must not be found.
+            buf[align + len] = 0;
+            if align > 0 {
+                buf[align - 1] = 0;
+            }
+            {
+                let s = &buf[align..align + len];
+                test!(memchr(s, 0) == None);
+                test!(memrchr(s, 0) == None);
+            }
+            for p in positions(len) {
+                let old = buf[align + p];
+                buf[align + p] = 0;
+                {
+                    let s = &buf[align..align + len];
+                    test!(memchr(s, 0) == naive_memchr(s, 0));
+                    test!(memrchr(s, 0) == naive_memrchr(s, 0));
+                }
+                buf[align + p] = old;
+            }
+        }
+    }
+}
+
+#[test]
+fn equal_alignments() {
+    let mut a = [0; SIZE];
+    let mut b = [0; SIZE];
+    pattern(&mut a);
+    pattern(&mut b);
+    for align_a in 0..ALIGNS {
+        for align_b in 0..ALIGNS {
+            for len in 0..LENS {
+                let sa = &a[align_a..align_a + len];
+                let sb = &b[align_b..align_b + len];
+                test!(equal(sa, sb) == naive_equal(sa, sb));
+            }
+        }
+    }
+    for align in 0..ALIGNS {
+        for len in 1..LENS {
+            for p in positions(len) {
+                b[align + p] ^= 0x80;
+                test!(!equal(&a[align..align + len], &b[align..align + len]));
+                b[align + p] ^= 0x80;
+                test!(equal(&a[align..align + len], &b[align..align + len]));
+            }
+        }
+    }
+}
+
+#[test]
+fn strlen_alignments() {
+    let mut buf = [0; SIZE];
+    for align in 0..ALIGNS {
+        for len in 0..LENS {
+            pattern(&mut buf);
+            buf[align + len] = 0;
+            test!(unsafe { strlen(buf[align..].as_ptr()) } == len);
+        }
+    }
+}
+
+#[test]
+fn memcpy_alignments() {
+    let mut src = [0; SIZE];
+    pattern(&mut src);
+    for align_d in 0..ALIGNS {
+        for align_s in 0..ALIGNS {
+            for len in 0..LENS {
+                let mut dst = [0; SIZE];
+                unsafe {
+                    memcpy(dst[align_d..].as_mut_ptr(), src[align_s..].as_ptr(), len);
+                }
+                test!(dst[..align_d].iter().all(|&b| b == 0));
+                test!(&dst[align_d..align_d + len] == &src[align_s..align_s + len]);
+                test!(dst[align_d + len..].iter().all(|&b| b == 0));
+            }
+        }
+    }
+}
+
+#[test]
+fn memmove_overlapping() {
+    let mut orig = [0; SIZE];
+    pattern(&mut orig);
+    for from in 0..ALIGNS {
+        for to in 0..ALIGNS {
+            for len in 0..LENS {
+                let mut buf = orig;
+                unsafe {
+                    let p = buf.as_mut_ptr();
+                    memmove(p.add(to), p.add(from), len);
+                }
+                let mut naive = orig;
+                for i in 0..len {
+                    naive[to + i] = orig[from + i];
+                }
+                test!(&buf[..] == &naive[..]);
+            }
+        }
+    }
+}
+
+#[test]
+fn memset_alignments() {
+    for align in 0..ALIGNS {
+        for len in 0..LENS {
+            let mut buf = [0; SIZE];
+            unsafe { memset(buf[align..].as_mut_ptr(), 0xab, len); }
+            test!(buf[..align].iter().all(|&b| b == 0));
+            test!(buf[align..align + len].iter().all(|&b| b == 0xab));
+            test!(buf[align + len..].iter().all(|&b| b == 0));
+        }
+    }
+}
+
+/// The number of cases tested by each randomized test.
+const ROUNDS: usize = 20000;
+/// The maximum alignment offset of the randomized tests.
+const MAX_ALIGN: usize = 64;
+/// The maximum length of the randomized tests.
+const MAX_LEN: usize = 4096;
+const RSIZE: usize = MAX_ALIGN + MAX_LEN + 64;
+
+/// A xorshift generator with a fixed seed so that failures can be reproduced.
+struct Rng(u64);
+
+impl Rng {
+    fn new() -> Rng {
+        Rng(0x2545f4914f6cdd1d)
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// Returns a length that is short in half of the cases.
+    fn len(&mut self) -> usize {
+        match self.below(2) {
+            0 => self.below(256),
+            _ => self.below(MAX_LEN + 1),
+        }
+    }
+
+    /// Fills a buffer with bytes below `n`.
+    fn fill(&mut self, buf: &mut [u8], n: usize) {
+        for b in buf {
+            *b = self.below(n) as u8;
+        }
+    }
+}
+
+fn naive_memcmp(a: &[u8], b: &[u8]) -> c_int {
+    for i in 0..a.len() {
+        if a[i] != b[i] {
+            return a[i] as c_int - b[i] as c_int;
+        }
+    }
+    0
+}
+
+fn sign(x: c_int) -> c_int {
+    if x < 0 { -1 } else if x > 0 { 1 } else { 0 }
+}
+
+#[test]
+fn memchr_random() {
+    let mut rng = Rng::new();
+    let mut buf = [0; RSIZE];
+    for _ in 0..ROUNDS {
+        // Small alphabets produce many matches, large ones few.
+        let alphabet = [2, 16, 256][rng.below(3)];
+        rng.fill(&mut buf, alphabet);
+        let c = rng.below(alphabet) as u8;
+        let align = rng.below(MAX_ALIGN);
+        let len = rng.len();
+        let s = &buf[align..align + len];
+        test!(memchr(s, c) == naive_memchr(s, c));
+        test!(memrchr(s, c) == naive_memrchr(s, c));
+    }
+}
+
+#[test]
+fn memcmp_random() {
+    let mut rng = Rng::new();
+    let mut a = [0; RSIZE];
+    let mut b = [0; RSIZE];
+    for _ in 0..ROUNDS {
+        let align_a = rng.below(MAX_ALIGN);
+        let align_b = rng.below(MAX_ALIGN);
+        let len = rng.len();
+        rng.fill(&mut a, 256);
+        for i in 0..len {
+            b[align_b + i] = a[align_a + i];
+        }
+        if len > 0 && rng.below(4) != 0 {
+            // The first difference decides the sign. A later difference with the
+            // opposite sign must not affect the result.
+            let p = rng.below(len);
+            let (x, y) = match rng.below(2) {
+                0 => (0x10, 0xf0),
+                _ => (0xf0, 0x10),
+            };
+            a[align_a + p] = x;
+            b[align_b + p] = y;
+            if p + 1 < len {
+                let q = p + 1 + rng.below(len - p - 1);
+                a[align_a + q] = y;
+                b[align_b + q] = x;
+            }
+        }
+        let sa = &a[align_a..align_a + len];
+        let sb = &b[align_b..align_b + len];
+        let res = unsafe { memcmp(sa.as_ptr(), sb.as_ptr(), len) };
+        test!(sign(res) == sign(naive_memcmp(sa, sb)));
+        test!(equal(sa, sb) == naive_equal(sa, sb));
+    }
+}
+
+#[test]
+fn strlen_random() {
+    let mut rng = Rng::new();
+    let mut buf = [0; RSIZE];
+    for _ in 0..ROUNDS {
+        rng.fill(&mut buf, 255);
+        for b in &mut buf[..] {
+            *b += 1;
+        }
+        let align = rng.below(MAX_ALIGN);
+        let len = rng.len();
+        buf[align + len] = 0;
+        test!(unsafe { strlen(buf[align..].as_ptr()) } == len);
+    }
+}
+
+#[test]
+fn memcpy_random() {
+    let mut rng = Rng::new();
+    let mut src = [0; RSIZE];
+    let mut dst = [0; RSIZE];
+    for _ in 0..ROUNDS {
+        rng.fill(&mut src, 256);
+        rng.fill(&mut dst, 256);
+        let orig = dst;
+        let align_d = rng.below(MAX_ALIGN);
+        let align_s = rng.below(MAX_ALIGN);
+        let len = rng.len();
+        unsafe {
+            memcpy(dst[align_d..].as_mut_ptr(), src[align_s..].as_ptr(), len);
+        }
+        test!(&dst[..align_d] == &orig[..align_d]);
+        test!(&dst[align_d..align_d + len] == &src[align_s..align_s + len]);
+        test!(&dst[align_d + len..] == &orig[align_d + len..]);
+    }
+}
+
+#[test]
+fn memmove_random() {
+    let mut rng = Rng::new();
+    let mut orig = [0; RSIZE];
+    for _ in 0..ROUNDS {
+        rng.fill(&mut orig, 256);
+        let len = rng.len();
+        let from = rng.below(RSIZE - len + 1);
+        let to = rng.below(RSIZE - len + 1);
+        let mut buf = orig;
+        unsafe {
+            let p = buf.as_mut_ptr();
+            memmove(p.add(to), p.add(from), len);
+        }
+        let mut naive = orig;
+        for i in 0..len {
+            naive[to + i] = orig[from + i];
+        }
+        test!(&buf[..] == &naive[..]);
+    }
+}
+
+#[test]
+fn memset_random() {
+    let mut rng = Rng::new();
+    let mut buf = [0; RSIZE];
+    for _ in 0..ROUNDS {
+        rng.fill(&mut buf, 256);
+        let orig = buf;
+        let c = rng.below(256) as u8;
+        let align = rng.below(MAX_ALIGN);
+        let len = rng.len();
+        unsafe { memset(buf[align..].as_mut_ptr(), c as c_int, len); }
+        test!(&buf[..align] == &orig[..align]);
+        test!(buf[align..align + len].iter().all(|&b| b == c));
+        test!(&buf[align + len..] == &orig[align + len..]);
+    }
+}
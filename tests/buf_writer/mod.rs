@@ -0,0 +1,151 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::io::{BufWriter, LineWriter, BufWrite, Write};
+use std::error::{DeviceFull};
+use std::{process, syscall, stdio};
+use std::fd::{FdContainer};
+use std::pipe::{Pipe};
+use std::pipe::flags::{PIPE_NONE};
+
+#[test]
+fn buffered() {
+    let mut buf: BufWriter<Vec<u8>> = BufWriter::new(Vec::new(), 8).unwrap();
+    test!(buf.write(b"abc").unwrap() == 3);
+    test!(buf.buffered() == 3);
+    test!(buf.get_ref().len() == 0);
+    test!(buf.gather_write(&[b"de", b"fg"]).unwrap() == 4);
+    test!(buf.get_ref().len() == 0);
+    test!(buf.write(b"hij").unwrap() == 3);
+    test!(buf.buffered() == 0);
+    test!(&buf.get_ref()[..] == &b"abcdefghij"[..]);
+}
+
+#[test]
+fn large_write() {
+    let mut buf: BufWriter<Vec<u8>> = BufWriter::new(Vec::new(), 4).unwrap();
+    test!(buf.write(b"a").unwrap() == 1);
+    test!(buf.write(b"bcdefgh").unwrap() == 7);
+    test!(buf.buffered() == 0);
+    test!(&buf.into_inner().unwrap()[..] == &b"abcdefgh"[..]);
+}
+
+#[test]
+fn into_inner_error() {
+    let mut dst = [0; 4];
+    {
+        let mut buf = BufWriter::<_>::new(&mut dst[..], 8).unwrap();
+        test!(buf.write(b"abcdef").unwrap() == 6);
+        match buf.into_inner() {
+            Err((rest, e)) => {
+                test!(rest.len() == 0);
+                test!(e == DeviceFull);
+            },
+            _ => test!(false),
+        }
+    }
+    test!(&dst[..] == &b"abcd"[..]);
+}
+
+#[test]
+fn read_to_eof() {
+    let mut buf: BufWriter<Vec<u8>> = BufWriter::new(Vec::new(), 4).unwrap();
+    test!(buf.read_to_eof(&b"Hello World"[..]).unwrap() == 11);
+    test!(&buf.into_inner().unwrap()[..] == &b"Hello World"[..]);
+}
+
+#[test]
+fn line_writer() {
+    let mut buf: LineWriter<Vec<u8>> = LineWriter::new(Vec::new(), 64).unwrap();
+    test!(buf.write(b"abc").unwrap() == 3);
+    test!(buf.get_ref().len() == 0);
+    test!(buf.write(b"d\nef").unwrap() == 4);
+    test!(&buf.get_ref()[..] == &b"abcd\n"[..]);
+    buf.set_line_buffered(false);
+    test!(buf.write(b"\n").unwrap() == 1);
+    test!(&buf.get_ref()[..] == &b"abcd\n"[..]);
+    test!(&buf.into_inner().unwrap()[..] == &b"abcd\nef\n"[..]);
+}
+
+/// A writer that accepts at most `limit` bytes per call and counts the calls.
+struct Limited {
+    data: Vec<u8>,
+    calls: usize,
+    limit: usize,
+}
+
+impl Write for Limited {
+    fn gather_write(&mut self, bufs: &[&[u8]]) -> Result<usize> {
+        self.calls += 1;
+        let mut n = 0;
+        for buf in bufs {
+            let m = if buf.len() > self.limit - n { self.limit - n } else { buf.len() };
+            self.data.push_all(&buf[..m]).unwrap();
+            n += m;
+        }
+        Ok(n)
+    }
+}
+
+fn limited(limit: usize) -> BufWriter<Limited> {
+    let w = Limited { data: Vec::new(), calls: 0, limit: limit };
+    BufWriter::new(w, 4).unwrap()
+}
+
+#[test]
+fn single_gather_write() {
+    let mut buf = limited(100);
+    test!(buf.write(b"abc").unwrap() == 3);
+    test!(buf.write(b"defghijk").unwrap() == 8);
+    test!(buf.get_ref().calls == 1);
+    test!(buf.buffered() == 0);
+    test!(&buf.get_ref().data[..] == &b"abcdefghijk"[..]);
+}
+
+#[test]
+fn partial_write_in_buffer() {
+    let mut buf = limited(2);
+    test!(buf.write(b"abc").unwrap() == 3);
+    // Only "ab" is written. "c" stays buffered and "def" still fits.
+    test!(buf.write(b"defghi").unwrap() == 3);
+    test!(buf.get_ref().calls == 1);
+    test!(buf.buffered() == 4);
+    test!(&buf.get_ref().data[..] == &b"ab"[..]);
+    buf.flush().unwrap();
+    test!(&buf.get_ref().data[..] == &b"abcdef"[..]);
+}
+
+#[test]
+fn partial_write_in_new_data() {
+    let mut buf = limited(5);
+    test!(buf.write(b"abc").unwrap() == 3);
+    // "abc" and "de" are written. "fghi" is buffered and "jk" is left over.
+    test!(buf.gather_write(&[b"def", b"ghijk"]).unwrap() == 6);
+    test!(buf.get_ref().calls == 1);
+    test!(buf.buffered() == 4);
+    test!(&buf.get_ref().data[..] == &b"abcde"[..]);
+    buf.flush().unwrap();
+    test!(&buf.get_ref().data[..] == &b"abcdefghi"[..]);
+}
+
+#[test]
+fn shared_stdout() {
+    let (write, read) = Pipe::new(PIPE_NONE).unwrap();
+    let child = process::fork(|| {
+        test!(syscall::dup3(write.borrow(), 1, 0) == 1);
+        let mut a = stdio::stdout().unwrap();
+        let mut b = stdio::stdout().unwrap();
+        a.write_all(b"a").unwrap();
+        b.write_all(b"b").unwrap();
+        // Dropping a handle flushes the shared buffer.
+        drop(b);
+        a.write_all(b"c").unwrap();
+    }).unwrap();
+    drop(write);
+    test!(process::wait_id(child, process::WAIT_EXITED).unwrap() ==
+          process::ChildStatus::Exited(0));
+    let mut buf = [0; 8];
+    test!(read.read(buf.as_mut()).unwrap() == 3);
+    test!(&buf[..3] == &b"abc"[..]);
+}
@@ -119,6 +119,58 @@ fn sort_by() {
     test!(x == [3, 2, 1, 0]);
 }
 
+/// Sorts `x` with a simple insertion sort to produce the expected result.
+fn insertion_sort(x: &mut [u32]) {
+    for i in 1..x.len() {
+        let mut j = i;
+        while j > 0 && x[j - 1] > x[j] {
+            let tmp = x[j - 1];
+            x[j - 1] = x[j];
+            x[j] = tmp;
+            j -= 1;
+        }
+    }
+}
+
+/// Sorts `x` and checks the result against the insertion sort.
+fn check_sort(x: &mut [u32; 1000]) {
+    let mut expected = *x;
+    insertion_sort(&mut expected);
+    x.sort();
+    test!(&x[..] == &expected[..]);
+}
+
+#[test]
+fn sort_patterns() {
+    let mut x = [0u32; 1000];
+    for i in 0..x.len() {
+        x[i] = i as u32;
+    }
+    check_sort(&mut x);
+
+    for i in 0..x.len() {
+        x[i] = (x.len() - i) as u32;
+    }
+    check_sort(&mut x);
+
+    for i in 0..x.len() {
+        x[i] = (i as u32).wrapping_mul(2654435761) % 7;
+    }
+    check_sort(&mut x);
+
+    for i in 0..x.len() {
+        x[i] = (i as u32).wrapping_mul(2654435761);
+    }
+    check_sort(&mut x);
+}
+
+#[test]
+fn sort_by_key() {
+    let mut x = [(1, 5), (0, 4), (2, 3)];
+    x.sort_by_key(|e| e.0);
+    test!(x == [(0, 4), (1, 5), (2, 3)]);
+}
+
 #[test]
 fn unchecked_slice() {
     let mut x = [0, 1, 2, 3];
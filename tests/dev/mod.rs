@@ -1,3 +1,25 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::file::{Device, DeviceType};
+
+#[test]
+fn major_minor() {
+    let dev = Device::from_major_minor(8, 1, DeviceType::Block);
+    test!(dev.id() == 0x801);
+    test!(dev.major() == 8);
+    test!(dev.minor() == 1);
+    test!(dev.ty() == DeviceType::Block);
+}
+
+#[test]
+fn major_minor_large() {
+    let dev = Device::from_major_minor(0x12345, 0x6789a, DeviceType::Character);
+    test!(dev.major() == 0x12345);
+    test!(dev.minor() == 0x6789a);
+
+    let dev = Device::from_major_minor(0xfffff, 0xfffff, DeviceType::Block);
+    test!(dev.major() == 0xfffff);
+    test!(dev.minor() == 0xfffff);
+}
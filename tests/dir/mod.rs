@@ -1,3 +1,75 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::dir::{self, WalkOptions, Order};
+use std::file::{File, Type, Mode};
+use std::file::flags::{FILE_ONLY_DIRECTORY};
+
+const ROOT: &'static str = "/tmp/lrs_dir_walk";
+
+fn remove_tree(tmp: &File) {
+    let mut opts = WalkOptions::new();
+    opts.order = Order::Post;
+    let mut walk = dir::walk(ROOT, opts, None);
+    while let Some(entry) = walk.next() {
+        walk.dir().unwrap().rel_remove(entry.name()).unwrap();
+    }
+    tmp.rel_remove("lrs_dir_walk").unwrap();
+}
+
+fn create_tree() -> File {
+    let tmp = File::open("/tmp", FILE_ONLY_DIRECTORY, Mode(0)).unwrap();
+    if tmp.rel_exists("lrs_dir_walk").unwrap() {
+        remove_tree(&tmp);
+    }
+    tmp.rel_create_dir("lrs_dir_walk", Mode(0o755)).unwrap();
+    let root = tmp.rel_open("lrs_dir_walk", FILE_ONLY_DIRECTORY, Mode(0)).unwrap();
+    root.rel_create_dir("a", Mode(0o755)).unwrap();
+    root.rel_create_dir("a/b", Mode(0o755)).unwrap();
+    root.rel_create_dir("c", Mode(0o755)).unwrap();
+    root.rel_create_file("a/x", Type::File, Mode(0o644)).unwrap();
+    root.rel_create_file("a/b/y", Type::File, Mode(0o644)).unwrap();
+    tmp
+}
+
+fn paths(opts: WalkOptions, prune: Option<&str>) -> Vec<u8> {
+    let mut res = Ok(());
+    let mut paths = Vec::new();
+    {
+        let mut walk = dir::walk(ROOT, opts, Some(&mut res));
+        while let Some(entry) = walk.next() {
+            if let Some(p) = prune {
+                if entry.name() == p {
+                    walk.prune();
+                }
+            }
+            if paths.len() > 0 {
+                paths.push(b' ').unwrap();
+            }
+            paths.push_all(&entry.path).unwrap();
+        }
+    }
+    res.unwrap();
+    paths
+}
+
+#[test]
+fn walk() {
+    let tmp = create_tree();
+
+    let mut opts = WalkOptions::new();
+    opts.sorted = true;
+    test!(&paths(opts, None)[..] == "a a/b a/b/y a/x c");
+    test!(&paths(opts, Some("b"))[..] == "a a/b a/x c");
+
+    opts.order = Order::Post;
+    test!(&paths(opts, None)[..] == "a/b/y a/b a/x a c");
+
+    opts.order = Order::Pre;
+    opts.max_depth = Some(1);
+    test!(&paths(opts, None)[..] == "a c");
+
+    remove_tree(&tmp);
+    test!(!tmp.rel_exists("lrs_dir_walk").unwrap());
+}
@@ -1,3 +1,45 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::env::{self, Environment};
+use std::error::{InvalidArgument};
+
+#[test]
+fn set_var() {
+    env::set_var("LRS_TEST_SET_VAR", "abc").unwrap();
+    test!(env::var("LRS_TEST_SET_VAR").unwrap() == "abc");
+    let old = env::var("LRS_TEST_SET_VAR").unwrap();
+    env::set_var("LRS_TEST_SET_VAR", "def").unwrap();
+    test!(env::var("LRS_TEST_SET_VAR").unwrap() == "def");
+    test!(old == "abc");
+    env::remove_var("LRS_TEST_SET_VAR").unwrap();
+    test!(env::var("LRS_TEST_SET_VAR").is_err());
+    test!(env::set_var("A=B", "c") == Err(InvalidArgument));
+}
+
+#[test]
+fn environment() {
+    let mut vars: Environment = Environment::new();
+    test!(vars.len() == 0);
+    vars.set("A", "1").unwrap();
+    vars.set("B", "2").unwrap();
+    vars.set("A", "3").unwrap();
+    test!(vars.len() == 2);
+    test!(vars.get("A").unwrap() == "3");
+    vars.remove("B").unwrap();
+    test!(vars.get("B").is_none());
+
+    let mut envp = vars.to_envp().unwrap();
+    let envp = envp.finish().unwrap();
+    test!(envp.len() == 2);
+    test!(envp[1].is_null());
+}
+
+#[test]
+fn environment_current() {
+    env::set_var("LRS_TEST_CURRENT", "xyz").unwrap();
+    let vars: Environment = Environment::current().unwrap();
+    test!(vars.get("LRS_TEST_CURRENT").unwrap() == "xyz");
+    env::remove_var("LRS_TEST_CURRENT").unwrap();
+}
@@ -0,0 +1,126 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::{process, thread, fs};
+use std::process::clone::{CLONE_NEWMOUNT};
+use std::fs::flags::{MOUNT_NONE, MOUNT_REC, MOUNT_PRIVATE};
+use std::file::{File, Type, Mode};
+use std::file::flags::{FILE_ONLY_DIRECTORY, FILE_READ_ONLY};
+use std::fanotify::{Fanotify, Response, EventInfo, FileIdKind};
+use std::fanotify::flags::{
+    FANOTIFY_CLOSE_ON_EXEC, FANOTIFY_CLASS_CONTENT, FANOTIFY_REPORT_DIR_FID,
+    FANOTIFY_REPORT_NAME, FANOTIFY_REPORT_PIDFD, MARK_FILESYSTEM, MARK_MOUNT,
+};
+use std::fanotify::events::{FANEV_CREATE, FANEV_OPEN, FANEV_OPEN_PERM, FANEV_ON_CHILD};
+
+const DIR: &'static str = "/tmp/lrs_fanotify";
+
+/// Runs `f` in a child process with a private tmpfs mounted on `DIR`.
+///
+/// The test is skipped if the process lacks the privileges to set this up.
+fn on_tmpfs<F: FnOnce()>(name: &str, f: F) {
+    let tmp = File::open("/tmp", FILE_ONLY_DIRECTORY, Mode(0)).unwrap();
+    if !tmp.rel_exists("lrs_fanotify").unwrap() {
+        tmp.rel_create_dir("lrs_fanotify", Mode(0o755)).unwrap();
+    }
+    ::in_child(|| {
+        if thread::unshare(CLONE_NEWMOUNT).is_err() {
+            ::skip_child(name, "cannot create a mount namespace");
+        }
+        fs::mount("none", "/", "", MOUNT_REC | MOUNT_PRIVATE, "").unwrap();
+        fs::mount("tmpfs", DIR, "tmpfs", MOUNT_NONE, "").unwrap();
+        f();
+    });
+}
+
+#[test]
+fn dir_fid_name() {
+    on_tmpfs("dir_fid_name", || {
+        let flags = FANOTIFY_CLOSE_ON_EXEC | FANOTIFY_REPORT_DIR_FID |
+                        FANOTIFY_REPORT_NAME;
+        let fan = match Fanotify::new(flags, FILE_READ_ONLY) {
+            Ok(fan) => fan,
+            _ => ::skip_child("dir_fid_name", "cannot create a fanotify object"),
+        };
+        fan.add_mark(DIR, FANEV_CREATE | FANEV_ON_CHILD, MARK_FILESYSTEM).unwrap();
+
+        let dir = File::open(DIR, FILE_ONLY_DIRECTORY, Mode(0)).unwrap();
+        dir.rel_create_file("file", Type::File, Mode(0o644)).unwrap();
+
+        let mut buf = [0; 1024];
+        let mut events = fan.events(&mut buf).unwrap();
+        let event = events.next().unwrap();
+        test!(event.events == FANEV_CREATE);
+        test!(event.file.is_none());
+        test!(event.pid == process::process_id());
+        let mut found = false;
+        for info in event.info() {
+            if let EventInfo::FileId(fid) = info {
+                test!(fid.kind == FileIdKind::DirName);
+                test!(fid.handle.len() > 0);
+                let name: &[u8] = fid.name.unwrap().as_ref();
+                test!(name == "file");
+                found = true;
+            }
+        }
+        test!(found);
+    });
+}
+
+#[test]
+fn deny_open() {
+    on_tmpfs("deny_open", || {
+        let flags = FANOTIFY_CLOSE_ON_EXEC | FANOTIFY_CLASS_CONTENT;
+        let fan = match Fanotify::new(flags, FILE_READ_ONLY) {
+            Ok(fan) => fan,
+            _ => ::skip_child("deny_open", "cannot create a fanotify object"),
+        };
+        let dir = File::open(DIR, FILE_ONLY_DIRECTORY, Mode(0)).unwrap();
+        dir.rel_create_file("file", Type::File, Mode(0o644)).unwrap();
+        fan.add_mark(DIR, FANEV_OPEN_PERM, MARK_MOUNT).unwrap();
+
+        let child = process::fork(|| {
+            test!(dir.rel_open_read("file").is_err());
+        }).unwrap();
+
+        let mut buf = [0; 1024];
+        let mut events = fan.events(&mut buf).unwrap();
+        let event = events.next().unwrap();
+        test!(event.events == FANEV_OPEN_PERM);
+        test!(event.pid == child);
+        fan.respond(event.file.as_ref().unwrap(), Response::Deny, false).unwrap();
+
+        test!(process::wait_id(child, process::WAIT_EXITED).unwrap()
+                                            == process::ChildStatus::Exited(0));
+    });
+}
+
+#[test]
+fn report_pidfd() {
+    on_tmpfs("report_pidfd", || {
+        let flags = FANOTIFY_CLOSE_ON_EXEC | FANOTIFY_REPORT_PIDFD;
+        let fan = match Fanotify::new(flags, FILE_READ_ONLY) {
+            Ok(fan) => fan,
+            _ => ::skip_child("report_pidfd", "cannot create a fanotify object"),
+        };
+        let dir = File::open(DIR, FILE_ONLY_DIRECTORY, Mode(0)).unwrap();
+        dir.rel_create_file("file", Type::File, Mode(0o644)).unwrap();
+        fan.add_mark(DIR, FANEV_OPEN, MARK_MOUNT).unwrap();
+        dir.rel_open_read("file").unwrap();
+
+        let mut buf = [0; 1024];
+        let mut events = fan.events(&mut buf).unwrap();
+        let event = events.next().unwrap();
+        test!(event.events == FANEV_OPEN);
+        test!(event.pidfd.is_some());
+        let mut found = false;
+        for info in event.info() {
+            if let EventInfo::Pidfd(fd) = info {
+                test!(fd >= 0);
+                found = true;
+            }
+        }
+        test!(found);
+    });
+}
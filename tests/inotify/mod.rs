@@ -1,3 +1,143 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::inotify::{TreeWatcher, TreeEvent};
+use std::file::{File, Type, Mode};
+use std::file::flags::{FILE_ONLY_DIRECTORY, FILE_READ_ONLY, FILE_WRITE_ONLY};
+
+macro_rules! event {
+    ($w:expr, $p:pat => $e:expr) => {
+        match $w.next_event().unwrap() {
+            $p => test!($e),
+            _ => abort!(),
+        }
+    }
+}
+
+#[test]
+fn tree_watcher() {
+    let tmp = File::open("/tmp", FILE_ONLY_DIRECTORY, Mode(0)).unwrap();
+    if tmp.rel_exists("lrs_inotify_tree").unwrap() {
+        let _ = tmp.rel_remove("lrs_inotify_tree/b/f");
+        let _ = tmp.rel_remove("lrs_inotify_tree/b");
+        tmp.rel_remove("lrs_inotify_tree").unwrap();
+    }
+    tmp.rel_create_dir("lrs_inotify_tree", Mode(0o755)).unwrap();
+    let root = tmp.rel_open("lrs_inotify_tree", FILE_ONLY_DIRECTORY, Mode(0)).unwrap();
+
+    let mut watcher = TreeWatcher::new("/tmp/lrs_inotify_tree").unwrap();
+    test!(watcher.num_watches() == 1);
+
+    root.rel_create_dir("a", Mode(0o755)).unwrap();
+    event!(watcher, TreeEvent::Created(p) => &p[..] == "a");
+    test!(watcher.num_watches() == 2);
+
+    root.rel_create_file("a/f", Type::File, Mode(0o644)).unwrap();
+    event!(watcher, TreeEvent::Created(p) => &p[..] == "a/f");
+
+    root.rel_rename("a", "b", false).unwrap();
+    event!(watcher, TreeEvent::Renamed(f, t) => &f[..] == "a" && &t[..] == "b");
+
+    root.rel_remove("b/f").unwrap();
+    event!(watcher, TreeEvent::Removed(p) => &p[..] == "b/f");
+
+    root.rel_remove("b").unwrap();
+    event!(watcher, TreeEvent::Removed(p) => &p[..] == "b");
+
+    tmp.rel_remove("lrs_inotify_tree").unwrap();
+}
+
+/// Creates the empty directory `/tmp/<name>` after removing the listed leftovers of a
+/// previous run.
+fn fresh_dir(tmp: &File, name: &str, leftovers: &[&str]) -> File {
+    for path in leftovers {
+        let _ = tmp.rel_remove(*path);
+    }
+    if tmp.rel_exists(name).unwrap() {
+        tmp.rel_remove(name).unwrap();
+    }
+    tmp.rel_create_dir(name, Mode(0o755)).unwrap();
+    tmp.rel_open(name, FILE_ONLY_DIRECTORY, Mode(0)).unwrap()
+}
+
+#[test]
+fn tree_watcher_modify() {
+    let tmp = File::open("/tmp", FILE_ONLY_DIRECTORY, Mode(0)).unwrap();
+    let root = fresh_dir(&tmp, "lrs_inotify_modify", &["lrs_inotify_modify/f"]);
+    root.rel_create_file("f", Type::File, Mode(0o644)).unwrap();
+    let mut watcher = TreeWatcher::new("/tmp/lrs_inotify_modify").unwrap();
+
+    {
+        let file = root.rel_open("f", FILE_WRITE_ONLY, Mode(0)).unwrap();
+        file.write(b"x").unwrap();
+    }
+    event!(watcher, TreeEvent::Modified(p) => &p[..] == "f");
+
+    root.rel_change_mode("f", Mode(0o600)).unwrap();
+    event!(watcher, TreeEvent::AttribChanged(p) => &p[..] == "f");
+
+    root.rel_remove("f").unwrap();
+    tmp.rel_remove("lrs_inotify_modify").unwrap();
+}
+
+#[test]
+fn tree_watcher_move_out() {
+    let tmp = File::open("/tmp", FILE_ONLY_DIRECTORY, Mode(0)).unwrap();
+    let root = fresh_dir(&tmp, "lrs_inotify_move",
+                         &["lrs_inotify_move/f", "lrs_inotify_moved"]);
+    root.rel_create_file("f", Type::File, Mode(0o644)).unwrap();
+    let mut watcher = TreeWatcher::new("/tmp/lrs_inotify_move").unwrap();
+
+    // There is no matching IN_MOVED_TO event.
+    tmp.rel_rename("lrs_inotify_move/f", "lrs_inotify_moved", false).unwrap();
+    event!(watcher, TreeEvent::Removed(p) => &p[..] == "f");
+
+    tmp.rel_remove("lrs_inotify_moved").unwrap();
+    tmp.rel_remove("lrs_inotify_move").unwrap();
+}
+
+fn max_queued_events() -> usize {
+    let file = File::open("/proc/sys/fs/inotify/max_queued_events", FILE_READ_ONLY,
+                          Mode(0)).unwrap();
+    let mut buf = [0u8; 32];
+    let n = file.read(buf[..].as_mut()).unwrap();
+    buf[..n - 1].parse().unwrap()
+}
+
+#[test]
+fn tree_watcher_overflow() {
+    let tmp = File::open("/tmp", FILE_ONLY_DIRECTORY, Mode(0)).unwrap();
+    let root = fresh_dir(&tmp, "lrs_inotify_overflow",
+                         &["lrs_inotify_overflow/a", "lrs_inotify_overflow/b",
+                           "lrs_inotify_overflow/c"]);
+    root.rel_create_file("a", Type::File, Mode(0o644)).unwrap();
+    root.rel_create_file("b", Type::File, Mode(0o644)).unwrap();
+    let mut watcher = TreeWatcher::new("/tmp/lrs_inotify_overflow").unwrap();
+
+    // Identical consecutive events are merged by the kernel. Alternate between two
+    // files so that every change produces a new event.
+    let max = max_queued_events();
+    for _ in 0..max / 2 + 1 {
+        root.rel_change_mode("a", Mode(0o600)).unwrap();
+        root.rel_change_mode("b", Mode(0o600)).unwrap();
+    }
+    let mut rescanned = false;
+    for _ in 0..max + 2 {
+        match watcher.next_event().unwrap() {
+            TreeEvent::Rescanned => { rescanned = true; break; },
+            TreeEvent::AttribChanged(_) => { },
+            _ => abort!(),
+        }
+    }
+    test!(rescanned);
+    test!(watcher.num_watches() == 1);
+
+    root.rel_create_file("c", Type::File, Mode(0o644)).unwrap();
+    event!(watcher, TreeEvent::Created(p) => &p[..] == "c");
+
+    root.rel_remove("a").unwrap();
+    root.rel_remove("b").unwrap();
+    root.rel_remove("c").unwrap();
+    tmp.rel_remove("lrs_inotify_overflow").unwrap();
+}
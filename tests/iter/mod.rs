@@ -1,3 +1,65 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::iter::{IteratorExt};
+use std::string::{String};
+use std::hashmap::{HashMap};
+
+#[test]
+fn adapters() {
+    let a = [1, 2, 3, 4, 5, 6];
+    let mut buf = [0; 8];
+
+    let n = a.iter().zip(a.iter().skip(1)).map(|(x, y)| x * y).collect_into(&mut buf);
+    test!(&buf[..n] == &[2, 6, 12, 20, 30][..]);
+
+    let n = a.iter().take(2).chain(a.iter().rev().take(2)).map(|x| *x)
+                    .collect_into(&mut buf);
+    test!(&buf[..n] == &[1, 2, 6, 5][..]);
+
+    let n = a.iter().skip_while(|x| **x < 3).take_while(|x| **x < 6).map(|x| *x)
+                    .collect_into(&mut buf);
+    test!(&buf[..n] == &[3, 4, 5][..]);
+
+    let n = a.iter().step_by(2).flat_map(|x| 0..*x).collect_into(&mut buf);
+    test!(&buf[..n] == &[0, 0, 1, 2, 0, 1, 2, 3][..]);
+
+    let n = a.iter().filter_map(|x| if x % 2 == 0 { Some(x / 2) } else { None })
+                    .scan(0, |sum, x| { *sum += x; Some(*sum) })
+                    .collect_into(&mut buf);
+    test!(&buf[..n] == &[1, 3, 6][..]);
+
+    let mut peek = a.iter().peekable();
+    test!(*peek.peek().unwrap() == &1);
+    test!(*peek.next().unwrap() == 1);
+
+    let mut sum = 0;
+    a.iter().inspect(|x| sum += **x).count();
+    test!(sum == 21);
+}
+
+#[test]
+fn consumers() {
+    let a = [3, 1, 4, 1, 5];
+    test!(a.iter().fold(0, |acc, x| acc + x) == 14);
+    test!(a.iter().all(|x| *x > 0));
+    test!(!a.iter().all(|x| *x > 1));
+    test!(a.iter().position(|x| *x == 4).unwrap() == 2);
+    test!(*a.iter().min_by(|x, y| x.cmp(y)).unwrap() == 1);
+    test!(*a.iter().max_by(|x, y| x.cmp(y)).unwrap() == 5);
+    test!(a.iter().count() == 5);
+    test!(*a.iter().last().unwrap() == 5);
+}
+
+#[test]
+fn collect() {
+    let v: Vec<u32> = (0..5).map(|i| i * i).collect().unwrap();
+    test!(&v[..] == &[0, 1, 4, 9, 16][..]);
+
+    let s: String = "a1b2".filter(|c| *c != '1').collect().unwrap();
+    test!(&*s == "ab2");
+
+    let m: HashMap<u32, u32> = (0..4).map(|i| (i, i + 1)).collect().unwrap();
+    test!(*m.get(&3).unwrap() == 4);
+}
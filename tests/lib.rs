@@ -13,6 +13,33 @@ macro_rules! test {
     }
 }
 
+use std::process::{self, ChildStatus, WAIT_EXITED};
+
+/// The exit status of a child process that cannot run its test.
+const SKIPPED: u8 = 77;
+
+/// Reports that the test `name` has been skipped.
+fn skip(name: &str, reason: &str) {
+    let _ = errln!("{}: skipped: {}", name, reason);
+}
+
+/// Reports that the test `name` has been skipped and exits the child process that runs
+/// it.
+fn skip_child(name: &str, reason: &str) -> ! {
+    skip(name, reason);
+    process::exit(SKIPPED);
+}
+
+/// Runs a test in a child process and checks that it passed or was skipped.
+fn in_child<F: FnOnce()>(f: F) {
+    let child = process::fork(f).unwrap();
+    match process::wait_id(child, WAIT_EXITED).unwrap() {
+        ChildStatus::Exited(0) => { },
+        ChildStatus::Exited(n) if n == SKIPPED as i32 => { },
+        _ => test!(false),
+    }
+}
+
 mod core;
 mod libc;
 mod int;
@@ -44,20 +71,24 @@ mod event;
 mod signal;
 mod pipe;
 mod alloc;
+mod slice;
 mod queue;
 // mod box;
 mod c_ptr_ptr;
 mod buf_reader;
+mod buf_writer;
 mod rc;
 mod vec;
 mod ringbuf;
 mod hashmap;
+mod treemap;
 mod iter;
 mod str_two;
 mod rmo;
 mod str_three;
 mod swap;
 mod inotify;
+mod fanotify;
 mod env;
 mod fs;
 mod socket;
@@ -73,3 +104,6 @@ mod process;
 mod time_ext;
 mod dir;
 mod user_group;
+mod reactor;
+mod uring;
+mod resolve;
@@ -1,3 +1,87 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::{RwLock, Semaphore, Barrier, Mutex, Condvar};
+use std::atomic::{Atomic};
+use std::error::{TimedOut};
+use std::time::{Time, MONO, REAL};
+use std::thread::{self};
+
+#[test]
+fn rw_lock() {
+    let lock = RwLock::new(1);
+    {
+        let a = lock.read();
+        let b = lock.try_read().unwrap();
+        test!(*a + *b == 2);
+        test!(lock.try_write().is_err());
+        test!(lock.try_write_until(Time::milliseconds(10)).is_err());
+    }
+    {
+        let mut w = lock.write();
+        *w = 2;
+        test!(lock.try_read().is_err());
+        test!(lock.try_read_until(Time::milliseconds(10)).is_err());
+    }
+    test!(*lock.read() == 2);
+}
+
+#[test]
+fn semaphore() {
+    let sem = Semaphore::new(1);
+    sem.acquire();
+    test!(sem.try_acquire().is_err());
+    test!(sem.try_acquire_until(Time::milliseconds(10)).is_err());
+    {
+        let _guard = thread::scoped(|| sem.release()).unwrap();
+        sem.acquire();
+    }
+    test!(sem.value() == 0);
+}
+
+#[test]
+fn barrier() {
+    let barrier = Barrier::new(2);
+    let count: Atomic<i32> = Atomic::new(0);
+    {
+        let _guard = thread::scoped(|| {
+            count.add(1);
+            barrier.wait();
+            test!(count.load() == 2);
+            barrier.wait();
+        }).unwrap();
+        count.add(1);
+        barrier.wait();
+        barrier.wait();
+    }
+    test!(count.load() == 2);
+}
+
+#[test]
+fn condvar_timeout() {
+    let mutex = Mutex::new(false);
+    let condvar = Condvar::new();
+
+    let guard = mutex.lock();
+    let (guard, res) = condvar.wait_for(guard, Time::milliseconds(10));
+    test!(res == Err(TimedOut));
+    let deadline = REAL.get_time().unwrap() + Time::milliseconds(10);
+    let (guard, res) = condvar.wait_until(guard, REAL, deadline);
+    test!(res == Err(TimedOut));
+    drop(guard);
+
+    {
+        let _guard = thread::scoped(|| {
+            *mutex.lock() = true;
+            condvar.signal(1);
+        }).unwrap();
+        let deadline = MONO.get_time().unwrap() + Time::seconds(10);
+        let mut guard = mutex.lock();
+        while !*guard {
+            let (g, res) = condvar.wait_until(guard, MONO, deadline);
+            test!(res.is_ok());
+            guard = g;
+        }
+    }
+}
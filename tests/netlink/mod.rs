@@ -1,3 +1,144 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::{thread};
+use std::process::clone::{CLONE_NEWNET, CLONE_NEWUSER};
+use std::socket::{Socket};
+use std::socket::flags::{SOCK_NONE};
+use std::socket::msg::{MSG_NONE};
+use std::socket::ip::{IpAddr, Ipv4Addr, Ipv4SockAddr};
+use std::netlink::route::{group};
+use std::netlink::rtnl::{RtNetlink, Route, Neighbour, Event};
+
+/// Runs `f` in a child process in a new network namespace.
+///
+/// The test is skipped if the process lacks the privileges to create the namespace.
+fn in_netns<F: FnOnce()>(name: &str, f: F) {
+    ::in_child(|| {
+        if thread::unshare(CLONE_NEWNET).is_err() {
+            if thread::unshare(CLONE_NEWUSER | CLONE_NEWNET).is_err() {
+                ::skip_child(name, "cannot create a network namespace");
+            }
+        }
+        f();
+    });
+}
+
+/// Waits for an event that matches `f`.
+fn wait_for<F: Fn(&Event) -> bool>(rtnl: &mut RtNetlink, f: F) {
+    loop {
+        if f(&rtnl.next_event().unwrap()) {
+            return;
+        }
+    }
+}
+
+#[test]
+fn rtnl_namespace() {
+    in_netns("rtnl_namespace", || {
+        let mut rtnl = RtNetlink::new().unwrap();
+        let lo = rtnl.link_by_name("lo").unwrap();
+        test!(!lo.is_up());
+
+        rtnl.set_link_up(lo.index, true).unwrap();
+        test!(rtnl.link_by_name("lo").unwrap().is_up());
+
+        let addr = IpAddr::V4(Ipv4Addr(192, 0, 2, 1));
+        rtnl.add_addr(lo.index, addr, 24).unwrap();
+        test!(rtnl.addrs().unwrap().iter().any(|a| a.index == lo.index &&
+                                                   a.addr == addr));
+        test!(rtnl.add_addr(lo.index, addr, 24).is_err());
+
+        let mut route = Route::new(IpAddr::V4(Ipv4Addr(198, 51, 100, 0)), 24);
+        route.gateway = Some(IpAddr::V4(Ipv4Addr(192, 0, 2, 2)));
+        rtnl.add_route(&route).unwrap();
+        test!(rtnl.routes().unwrap().iter().any(|r| r.dst_len == 24 &&
+                                                    r.gateway.is_some()));
+        rtnl.del_route(&route).unwrap();
+
+        rtnl.del_addr(lo.index, addr, 24).unwrap();
+        test!(!rtnl.addrs().unwrap().iter().any(|a| a.addr == addr));
+    });
+}
+
+#[test]
+fn rtnl_link_stats() {
+    in_netns("rtnl_link_stats", || {
+        let mut rtnl = RtNetlink::new().unwrap();
+        let lo = rtnl.link_by_name("lo").unwrap();
+        test!(lo.address() == &[0, 0, 0, 0, 0, 0][..]);
+        test!(lo.mtu > 0);
+        test!(lo.stats.tx_packets == 0);
+        rtnl.set_link_up(lo.index, true).unwrap();
+
+        let sock = Socket::ipv4_datagram(SOCK_NONE).unwrap();
+        let mut addr = [0; 32];
+        let local = Ipv4SockAddr::from_addr_port(&mut addr, Ipv4Addr(127, 0, 0, 1),
+                                                 0).unwrap();
+        sock.bind(local).unwrap();
+        let mut buf = [0; 32];
+        let local = sock.get_addr(&mut buf).unwrap();
+        sock.send_to(b"x", &*local, MSG_NONE).unwrap();
+
+        let lo = rtnl.link_by_name("lo").unwrap();
+        test!(lo.stats.tx_packets >= 1);
+        test!(lo.stats.rx_packets >= 1);
+        test!(lo.stats.tx_bytes > 0);
+    });
+}
+
+#[test]
+fn rtnl_neighbours() {
+    in_netns("rtnl_neighbours", || {
+        let mut rtnl = RtNetlink::new().unwrap();
+        let lo = rtnl.link_by_name("lo").unwrap();
+        rtnl.set_link_up(lo.index, true).unwrap();
+
+        let addr = IpAddr::V4(Ipv4Addr(192, 0, 2, 5));
+        let lladdr = [2, 0, 0, 0, 0, 1];
+        let neigh = Neighbour::new(lo.index, addr, &lladdr);
+        rtnl.add_neigh(&neigh).unwrap();
+        test!(rtnl.neighbours().unwrap().iter().any(|n| n.index == lo.index &&
+                                                        n.addr == addr &&
+                                                        n.lladdr() == &lladdr[..]));
+
+        rtnl.del_neigh(&neigh).unwrap();
+        test!(!rtnl.neighbours().unwrap().iter().any(|n| n.addr == addr));
+    });
+}
+
+#[test]
+fn rtnl_events() {
+    in_netns("rtnl_events", || {
+        let mut rtnl = RtNetlink::new().unwrap();
+        let lo = rtnl.link_by_name("lo").unwrap();
+
+        // Requests discard notifications, so the events are read from another socket.
+        let mut events = RtNetlink::new().unwrap();
+        events.subscribe(group::Link).unwrap();
+        events.subscribe(group::Ipv4Addr).unwrap();
+
+        rtnl.set_link_up(lo.index, true).unwrap();
+        wait_for(&mut events, |e| match *e {
+            Event::NewLink(ref link) => link.index == lo.index && link.is_up(),
+            _ => false,
+        });
+
+        let addr = IpAddr::V4(Ipv4Addr(192, 0, 2, 1));
+        rtnl.add_addr(lo.index, addr, 24).unwrap();
+        wait_for(&mut events, |e| match *e {
+            Event::NewAddr(ref a) => a.index == lo.index && a.addr == addr,
+            _ => false,
+        });
+
+        rtnl.del_addr(lo.index, addr, 24).unwrap();
+        wait_for(&mut events, |e| match *e {
+            Event::DelAddr(ref a) => a.addr == addr,
+            _ => false,
+        });
+
+        events.unsubscribe(group::Link).unwrap();
+        events.unsubscribe(group::Ipv4Addr).unwrap();
+    });
+}
@@ -1,3 +1,197 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::process::{self, Command, Stdio, ChildStatus, PidFd, WAIT_EXITED};
+use std::pipe::{Pipe};
+use std::pipe::flags::{PIPE_NONE};
+use std::error::{DoesNotExist, NotImplemented, NotPermitted};
+use std::fd::{FdContainer};
+use std::env::{self};
+use std::poll::{Epoll, EMPTY_EVENT, POLL_READ};
+use std::signal::signals::{Kill};
+use std::time::{self, Time};
+
+#[test]
+fn command_output() {
+    let mut cmd: Command = Command::new("echo").unwrap();
+    cmd.arg("Hello World").unwrap();
+    let output = cmd.output().unwrap();
+    test!(output.status == ChildStatus::Exited(0));
+    test!(&output.stdout[..] == "Hello World\n");
+    test!(output.stderr.len() == 0);
+}
+
+#[test]
+fn command_env() {
+    let mut cmd: Command = Command::new("/bin/sh").unwrap();
+    cmd.arg("-c").unwrap();
+    cmd.arg("echo $LRS_TEST").unwrap();
+    cmd.clear_env();
+    cmd.set_env("LRS_TEST", "xyz").unwrap();
+    let output = cmd.output().unwrap();
+    test!(&output.stdout[..] == "xyz\n");
+}
+
+#[test]
+fn command_inherit_env() {
+    env::set_var("LRS_TEST_INHERIT", "abc").unwrap();
+    let mut cmd: Command = Command::new("/bin/sh").unwrap();
+    cmd.arg("-c").unwrap();
+    cmd.arg("echo $LRS_TEST_INHERIT").unwrap();
+    let output = cmd.output().unwrap();
+    env::remove_var("LRS_TEST_INHERIT").unwrap();
+    test!(&output.stdout[..] == "abc\n");
+}
+
+#[test]
+fn command_stdin() {
+    let mut cmd: Command = Command::new("cat").unwrap();
+    cmd.set_stdin(Stdio::Piped);
+    cmd.set_stdout(Stdio::Piped);
+    let mut child = cmd.spawn().unwrap();
+    child.stdin().unwrap().write(b"abc").unwrap();
+    let mut buf = [0; 4];
+    test!(child.stdout().unwrap().read(&mut buf).unwrap() == 3);
+    test!(&buf[..3] == "abc");
+    test!(child.wait().unwrap() == ChildStatus::Exited(0));
+}
+
+#[test]
+fn command_not_found() {
+    let mut cmd: Command = Command::new("/lrs/does/not/exist").unwrap();
+    match cmd.spawn() {
+        Err(e) => test!(e == DoesNotExist),
+        _ => test!(false),
+    }
+}
+
+#[test]
+fn command_null() {
+    let mut cmd: Command = Command::new("cat").unwrap();
+    cmd.set_stdin(Stdio::Null);
+    let output = cmd.output().unwrap();
+    test!(output.status == ChildStatus::Exited(0));
+    test!(output.stdout.len() == 0);
+}
+
+#[test]
+fn command_fd() {
+    let (write, mut read) = Pipe::new(PIPE_NONE).unwrap();
+    let mut cmd: Command = Command::new("echo").unwrap();
+    cmd.arg("abc").unwrap();
+    cmd.set_stdout(Stdio::from_fd(&write));
+    let mut child = cmd.spawn().unwrap();
+    test!(child.wait().unwrap() == ChildStatus::Exited(0));
+    drop(write);
+    let mut buf = [0; 8];
+    test!(read.read_all(&mut buf).unwrap() == 4);
+    test!(&buf[..4] == "abc\n");
+}
+
+#[test]
+fn command_cwd() {
+    let mut cmd: Command = Command::new("pwd").unwrap();
+    cmd.set_cwd("/").unwrap();
+    let output = cmd.output().unwrap();
+    test!(&output.stdout[..] == "/\n");
+}
+
+#[test]
+fn command_kill() {
+    let mut cmd: Command = Command::new("cat").unwrap();
+    cmd.set_stdin(Stdio::Piped);
+    let mut child = cmd.spawn().unwrap();
+    test!(child.try_wait().unwrap() == None);
+    child.kill().unwrap();
+    test!(child.wait().unwrap() == ChildStatus::Killed(Kill.0 as i32));
+    test!(child.try_wait().unwrap() == Some(ChildStatus::Killed(Kill.0 as i32)));
+    test!(child.kill().is_err());
+}
+
+#[test]
+fn pidfd_exit() {
+    let (_, pidfd) = match PidFd::fork(|| { process::exit(3); }) {
+        Ok(c) => c,
+        Err(NotImplemented) => {
+            ::skip("pidfd_exit", "process file descriptors are not supported");
+            return;
+        },
+        Err(_) => abort!(),
+    };
+    let epoll = Epoll::new().unwrap();
+    epoll.add(&pidfd, POLL_READ).unwrap();
+    let mut events = [EMPTY_EVENT; 1];
+    test!(epoll.wait(&mut events).unwrap().len() == 1);
+    test!(pidfd.wait(WAIT_EXITED).unwrap() == ChildStatus::Exited(3));
+}
+
+#[test]
+fn pidfd_send_signal() {
+    let child = PidFd::fork(|| { let _ = time::MONO.sleep_for(Time::seconds(10)); });
+    let (_, pidfd) = match child {
+        Ok(c) => c,
+        Err(NotImplemented) => {
+            ::skip("pidfd_send_signal", "process file descriptors are not supported");
+            return;
+        },
+        Err(_) => abort!(),
+    };
+    pidfd.send_signal(Kill).unwrap();
+    test!(pidfd.wait(WAIT_EXITED).unwrap() == ChildStatus::Killed(Kill.0 as i32));
+}
+
+#[test]
+fn pidfd_get_fd() {
+    let (write, read) = Pipe::new(PIPE_NONE).unwrap();
+    let child = PidFd::fork(|| { let _ = time::MONO.sleep_for(Time::seconds(10)); });
+    let (_, pidfd) = match child {
+        Ok(c) => c,
+        Err(NotImplemented) => {
+            ::skip("pidfd_get_fd", "process file descriptors are not supported");
+            return;
+        },
+        Err(_) => abort!(),
+    };
+    // Only the child has the write end open after this.
+    let fd = write.borrow();
+    drop(write);
+    let copy: Pipe = match pidfd.get_fd(fd) {
+        Ok(p) => p,
+        Err(e) if e == NotImplemented || e == NotPermitted => {
+            ::skip("pidfd_get_fd", e.desc());
+            pidfd.send_signal(Kill).unwrap();
+            pidfd.wait(WAIT_EXITED).unwrap();
+            return;
+        },
+        Err(_) => abort!(),
+    };
+    test!(copy.write(b"x").unwrap() == 1);
+    let mut buf = [0; 2];
+    test!(read.read(buf.as_mut()).unwrap() == 1);
+    test!(buf[0] == b'x');
+    pidfd.send_signal(Kill).unwrap();
+    test!(pidfd.wait(WAIT_EXITED).unwrap() == ChildStatus::Killed(Kill.0 as i32));
+    drop(copy);
+    test!(read.read(buf.as_mut()).unwrap() == 0);
+}
+
+#[test]
+fn pidfd_open() {
+    let mut cmd: Command = Command::new("cat").unwrap();
+    cmd.set_stdin(Stdio::Piped);
+    let mut child = cmd.spawn().unwrap();
+    let pidfd = match PidFd::open(child.id()) {
+        Ok(p) => p,
+        Err(NotImplemented) => {
+            ::skip("pidfd_open", "process file descriptors are not supported");
+            child.kill().unwrap();
+            child.wait().unwrap();
+            return;
+        },
+        Err(_) => abort!(),
+    };
+    pidfd.send_signal(Kill).unwrap();
+    test!(child.wait().unwrap() == ChildStatus::Killed(Kill.0 as i32));
+    test!(PidFd::open(process::process_id()).is_ok());
+}
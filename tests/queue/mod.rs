@@ -1,3 +1,58 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::{Queue};
+use std::error::{TimedOut, BrokenPipe};
+use std::time::{Time};
+use std::thread::{self};
+use std::atomic::{Atomic};
+
+#[test]
+fn timeout() {
+    let queue: Queue<u8> = Queue::new(1).unwrap();
+    test!(queue.pop_wait_timeout(Time::milliseconds(10)) == Err(TimedOut));
+    test!(queue.push_wait_timeout(1, Time::milliseconds(10)).is_ok());
+    test!(queue.push_wait_timeout(2, Time::milliseconds(10)) == Err((2, TimedOut)));
+    test!(queue.pop_wait_timeout(Time::milliseconds(10)).unwrap() == 1);
+}
+
+#[test]
+fn close() {
+    let queue: Queue<u8> = Queue::new(4).unwrap();
+    test!(queue.push_wait(1).is_ok());
+    {
+        let _guard = thread::scoped(|| {
+            test!(queue.pop_wait().unwrap() == 1);
+            test!(queue.pop_wait() == Err(BrokenPipe));
+        }).unwrap();
+        queue.close();
+    }
+    test!(queue.is_closed());
+    test!(queue.push(3) == Some(3));
+    test!(queue.push_wait(3) == Err((3, BrokenPipe)));
+    test!(queue.pop().is_none());
+}
+
+#[test]
+fn close_while_pushing() {
+    let queue: Queue<usize> = Queue::new(4).unwrap();
+    let pushed = Atomic::new(0);
+    let mut popped = 0;
+    {
+        let _guard = thread::scoped(|| {
+            while queue.push_wait(1).is_ok() {
+                pushed.add(1);
+            }
+        }).unwrap();
+        for _ in 0..100 {
+            popped += queue.pop_wait().unwrap();
+        }
+        queue.close();
+        // Every element whose push succeeded must still be returned.
+        while let Ok(n) = queue.pop_wait() {
+            popped += n;
+        }
+    }
+    test!(popped == pushed.load());
+}
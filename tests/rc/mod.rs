@@ -1,3 +1,75 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::rc::{Rc, Arc};
+use std::thread::{self};
+
+#[test]
+fn rc_weak() {
+    let mut rc: Rc<u8> = Rc::with_data(1).unwrap();
+    let weak = rc.downgrade();
+    test!(rc.weak_count() == 1);
+    test!(rc.as_mut().is_none());
+    {
+        let rc2 = weak.upgrade().unwrap();
+        test!(*rc2 == 1);
+        test!(weak.strong_count() == 2);
+    }
+    {
+        let _rc = rc;
+    }
+    test!(weak.strong_count() == 0);
+    test!(weak.weak_count() == 1);
+    test!(weak.upgrade().is_none());
+}
+
+#[test]
+fn arc_weak() {
+    let arc: Arc<u8> = Arc::with_data(1).unwrap();
+    let weak = arc.downgrade();
+    let weak2 = weak.add_ref();
+    test!(arc.weak_count() == 2);
+    test!(*weak2.upgrade().unwrap() == 1);
+    {
+        let _arc = arc;
+    }
+    test!(weak.upgrade().is_none());
+}
+
+#[test]
+fn arc_as_mut() {
+    let mut arc: Arc<u8> = Arc::with_data(1).unwrap();
+    *arc.as_mut().unwrap() = 2;
+    {
+        let _weak = arc.downgrade();
+        test!(arc.as_mut().is_none());
+    }
+    {
+        let _arc2 = arc.add_ref();
+        test!(arc.as_mut().is_none());
+    }
+    test!(arc.weak_count() == 0);
+    test!(*arc.as_mut().unwrap() == 2);
+}
+
+#[test]
+fn arc_as_mut_downgrade_race() {
+    let mut arc: Arc<u8> = Arc::with_data(1).unwrap();
+    let arc2 = arc.add_ref();
+    {
+        let _guard = thread::scoped(|| {
+            for _ in 0..10000 {
+                let weak = arc2.downgrade();
+                test!(weak.upgrade().is_some());
+            }
+        }).unwrap();
+        for _ in 0..10000 {
+            test!(arc.as_mut().is_none());
+        }
+    }
+    test!(arc.weak_count() == 0);
+    drop(arc2);
+    test!(arc.strong_count() == 1);
+    test!(*arc.as_mut().unwrap() == 1);
+}
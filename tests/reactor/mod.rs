@@ -0,0 +1,150 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::reactor::{Reactor, Handler};
+use std::poll::{Event, POLL_READ};
+use std::pipe::{Pipe};
+use std::pipe::flags::{PIPE_NONE};
+use std::time::{Time};
+use std::{signal, process, thread};
+use std::signal::signals::{User2};
+use std::signal::sigfd::{SigfdInfo};
+
+struct Counter {
+    io: usize,
+    timers: usize,
+    wakes: usize,
+    signals: usize,
+}
+
+impl Handler for Counter {
+    fn io(&mut self, reactor: &mut Reactor, token: u64, event: Event) {
+        test!(token == 1);
+        test!(event.is_read());
+        self.io += 1;
+        reactor.stop();
+    }
+
+    fn timer(&mut self, reactor: &mut Reactor, token: u64) {
+        test!(token == 2);
+        self.timers += 1;
+        if self.timers == 3 {
+            reactor.stop();
+        }
+    }
+
+    fn wake(&mut self, reactor: &mut Reactor) {
+        self.wakes += 1;
+        reactor.stop();
+    }
+
+    fn signal(&mut self, reactor: &mut Reactor, info: &SigfdInfo) {
+        test!(info.signal() == User2);
+        self.signals += 1;
+        reactor.stop();
+    }
+}
+
+fn counter() -> Counter {
+    Counter { io: 0, timers: 0, wakes: 0, signals: 0 }
+}
+
+#[test]
+fn io() {
+    let (write, read) = Pipe::new(PIPE_NONE).unwrap();
+    let mut reactor: Reactor = Reactor::new().unwrap();
+    reactor.add(&read, 1, POLL_READ).unwrap();
+    write.write(b"x").unwrap();
+    let mut handler = counter();
+    reactor.run(&mut handler).unwrap();
+    test!(handler.io == 1);
+}
+
+#[test]
+fn timers() {
+    let mut reactor: Reactor = Reactor::new().unwrap();
+    reactor.add_repeating_timer(Time::milliseconds(1), 2).unwrap();
+    let mut handler = counter();
+    reactor.run(&mut handler).unwrap();
+    test!(handler.timers == 3);
+}
+
+/// Counts the expirations of the timers with the tokens 0, 1, and 2.
+struct Timers {
+    fired: [usize; 3],
+}
+
+impl Handler for Timers {
+    fn io(&mut self, _: &mut Reactor, _: u64, _: Event) {
+        test!(false);
+    }
+
+    fn timer(&mut self, reactor: &mut Reactor, token: u64) {
+        self.fired[token as usize] += 1;
+        if self.fired[1] == 2 {
+            reactor.stop();
+        }
+    }
+}
+
+#[test]
+fn mixed_timers() {
+    let mut reactor: Reactor = Reactor::new().unwrap();
+    reactor.add_repeating_timer(Time::milliseconds(1), 0).unwrap();
+    reactor.add_repeating_timer(Time::milliseconds(5), 1).unwrap();
+    reactor.add_timer(Time::milliseconds(2), 2).unwrap();
+    let mut handler = Timers { fired: [0; 3] };
+    reactor.run(&mut handler).unwrap();
+    test!(handler.fired[0] >= 2);
+    test!(handler.fired[1] == 2);
+    test!(handler.fired[2] == 1);
+}
+
+#[test]
+fn cancel_timer() {
+    let mut reactor: Reactor = Reactor::new().unwrap();
+    let id = reactor.add_timer(Time::milliseconds(1), 2).unwrap();
+    test!(reactor.cancel_timer(id).unwrap());
+    test!(!reactor.cancel_timer(id).unwrap());
+    let mut handler = counter();
+    reactor.run_once(&mut handler, Some(Time::milliseconds(10))).unwrap();
+    test!(handler.timers == 0);
+}
+
+#[test]
+fn waker() {
+    let mut reactor: Reactor = Reactor::new().unwrap();
+    let waker = reactor.waker().unwrap();
+    waker.wake().unwrap();
+    waker.wake().unwrap();
+    let mut handler = counter();
+    reactor.run(&mut handler).unwrap();
+    test!(handler.wakes == 1);
+}
+
+#[test]
+fn signals() {
+    let mut reactor: Reactor = Reactor::new().unwrap();
+    reactor.add_signal(User2).unwrap();
+    signal::send_to_thread(process::process_id(), thread::thread_id(), User2).unwrap();
+    let mut handler = counter();
+    reactor.run(&mut handler).unwrap();
+    test!(handler.signals == 1);
+    reactor.remove_signal(User2).unwrap();
+    test!(!signal::blocked_signals().unwrap().is_set(User2).unwrap());
+}
+
+#[test]
+fn signal_mask_restored() {
+    let old = signal::block_signal(User2).unwrap();
+    {
+        let mut reactor: Reactor = Reactor::new().unwrap();
+        reactor.add_signal(User2).unwrap();
+        reactor.remove_signal(User2).unwrap();
+        test!(signal::blocked_signals().unwrap().is_set(User2).unwrap());
+        reactor.add_signal(User2).unwrap();
+    }
+    test!(signal::blocked_signals().unwrap().is_set(User2).unwrap());
+    signal::set_blocked_signals(old).unwrap();
+}
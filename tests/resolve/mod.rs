@@ -0,0 +1,160 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::resolve::{Resolver, IpAddr, Endpoint, Source};
+use std::socket::{Socket, Ipv4Addr, Ipv4SockAddr, Ipv6Addr};
+use std::socket::flags::{SOCK_NONE};
+use std::socket::msg::{MSG_NONE};
+use std::thread::{self};
+use std::time::{Time};
+use std::error::{DoesNotExist};
+
+#[test]
+fn literal() {
+    let res = Resolver::empty().unwrap();
+    let eps = res.resolve_addr(b"10.0.0.1:80").unwrap();
+    test!(eps.len() == 1);
+    test!(eps[0] == Endpoint { ip: IpAddr::V4(Ipv4Addr(10, 0, 0, 1)), port: 80 });
+    let eps = res.resolve_addr(b"[::1]:443").unwrap();
+    test!(eps[0] == Endpoint { ip: IpAddr::V6(Ipv6Addr(0, 0, 0, 0, 0, 0, 0, 1)),
+                               port: 443 });
+}
+
+/// Turns the query in `buf[..len]` into a reply that answers an A query with 10.1.2.3
+/// and an AAAA query without records. Returns the length of the reply.
+fn build_answer(buf: &mut [u8], len: usize, truncated: bool) -> usize {
+    let qtype = buf[len - 3];
+    buf[2] = if truncated { 0x83 } else { 0x81 };
+    buf[3] = 0x80;
+    let mut len = len;
+    if qtype == 1 && !truncated {
+        buf[7] = 1;
+        let rr = [0xC0, 12, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 10, 1, 2, 3];
+        for i in 0..rr.len() {
+            buf[len + i] = rr[i];
+        }
+        len += rr.len();
+    }
+    len
+}
+
+fn answer_udp(sock: &Socket, truncated: bool) {
+    let mut buf = [0; 512];
+    let mut addr = [0; 32];
+    let (len, from) = sock.recv_from(&mut buf, &mut addr, MSG_NONE).unwrap();
+    let from = from.unwrap();
+    let len = build_answer(&mut buf, len, truncated);
+    sock.send_to(&buf[..len], &*from, MSG_NONE).unwrap();
+}
+
+fn answer(sock: &Socket) {
+    answer_udp(sock, false);
+}
+
+fn recv_exact(sock: &Socket, buf: &mut [u8]) {
+    let mut pos = 0;
+    while pos < buf.len() {
+        let n = sock.recv(&mut buf[pos..], MSG_NONE).unwrap();
+        test!(n > 0);
+        pos += n;
+    }
+}
+
+fn answer_tcp(listener: &Socket) {
+    let conn = listener.accept(SOCK_NONE).unwrap();
+    let mut len = [0; 2];
+    recv_exact(&conn, &mut len);
+    let len = (len[0] as usize) << 8 | len[1] as usize;
+    let mut buf = [0; 514];
+    recv_exact(&conn, &mut buf[2..2 + len]);
+    let len = build_answer(&mut buf[2..], len, false);
+    buf[0] = (len >> 8) as u8;
+    buf[1] = len as u8;
+    conn.send(&buf[..2 + len], MSG_NONE).unwrap();
+}
+
+#[test]
+fn dns() {
+    let sock = Socket::ipv4_datagram(SOCK_NONE).unwrap();
+    let mut addr = [0; 32];
+    let local = Ipv4SockAddr::from_addr_port(&mut addr, Ipv4Addr(127, 0, 0, 1),
+                                             0).unwrap();
+    sock.bind(local).unwrap();
+    let mut buf = [0; 32];
+    let port = sock.get_addr(&mut buf).unwrap().as_ipv4().unwrap().port();
+
+    let mut res = Resolver::empty().unwrap();
+    res.set_sources(&[Source::Dns]).unwrap();
+    res.add_nameserver(IpAddr::V4(Ipv4Addr(127, 0, 0, 1)), port).unwrap();
+
+    let addrs = {
+        let _guard = thread::scoped(|| {
+            answer(&sock);
+            answer(&sock);
+        }).unwrap();
+        res.lookup_host(b"db.internal.").unwrap()
+    };
+    test!(addrs.len() == 1);
+    test!(addrs[0] == IpAddr::V4(Ipv4Addr(10, 1, 2, 3)));
+}
+
+#[test]
+fn dns_truncated() {
+    let listener = Socket::ipv4_stream(SOCK_NONE).unwrap();
+    let mut addr = [0; 32];
+    let local = Ipv4SockAddr::from_addr_port(&mut addr, Ipv4Addr(127, 0, 0, 1),
+                                             0).unwrap();
+    listener.bind(local).unwrap();
+    listener.listen(1).unwrap();
+    let mut buf = [0; 32];
+    let port = listener.get_addr(&mut buf).unwrap().as_ipv4().unwrap().port();
+
+    let sock = Socket::ipv4_datagram(SOCK_NONE).unwrap();
+    let local = Ipv4SockAddr::from_addr_port(&mut addr, Ipv4Addr(127, 0, 0, 1),
+                                             port).unwrap();
+    sock.bind(local).unwrap();
+
+    let mut res = Resolver::empty().unwrap();
+    res.set_sources(&[Source::Dns]).unwrap();
+    res.add_nameserver(IpAddr::V4(Ipv4Addr(127, 0, 0, 1)), port).unwrap();
+
+    let addrs = {
+        let _guard = thread::scoped(|| {
+            for _ in 0..2 {
+                answer_udp(&sock, true);
+                answer_tcp(&listener);
+            }
+        }).unwrap();
+        res.lookup_host(b"db.internal.").unwrap()
+    };
+    test!(addrs.len() == 1);
+    test!(addrs[0] == IpAddr::V4(Ipv4Addr(10, 1, 2, 3)));
+}
+
+#[test]
+fn dns_error_falls_through() {
+    // Nothing listens on this port so that the DNS lookup fails.
+    let sock = Socket::ipv4_datagram(SOCK_NONE).unwrap();
+    let mut addr = [0; 32];
+    let local = Ipv4SockAddr::from_addr_port(&mut addr, Ipv4Addr(127, 0, 0, 1),
+                                             0).unwrap();
+    sock.bind(local).unwrap();
+    let mut buf = [0; 32];
+    let port = sock.get_addr(&mut buf).unwrap().as_ipv4().unwrap().port();
+    drop(sock);
+
+    let mut res = Resolver::empty().unwrap();
+    res.set_sources(&[Source::Dns]).unwrap();
+    res.set_timeout(Time::milliseconds(50));
+    res.set_attempts(1);
+    res.add_nameserver(IpAddr::V4(Ipv4Addr(127, 0, 0, 1)), port).unwrap();
+    match res.lookup_host(b"db.internal.") {
+        Err(e) => test!(e != DoesNotExist),
+        Ok(_) => test!(false),
+    }
+
+    res.set_sources(&[Source::Dns, Source::Files]).unwrap();
+    let addrs = res.lookup_host(b"localhost").unwrap();
+    test!(addrs.len() > 0);
+}
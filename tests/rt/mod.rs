@@ -1,3 +1,42 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::env::aux;
+use std::alloc::{OncePool};
+use std::cpu::{self, NONE, SSE2, SSE3, NEON};
+
+#[test]
+fn aux_entries() {
+    test!(aux::page_size() > 0);
+    test!(aux::clock_ticks() > 0);
+    test!(aux::random_bytes().is_some());
+    test!(aux::platform().is_some());
+    test!(!aux::secure());
+}
+
+#[test]
+fn features() {
+    let f = cpu::features();
+    test!(f == cpu::features());
+    test!(f.contains(NONE));
+    test!(cpu::has(f));
+    if cfg!(target_arch = "x86_64") {
+        test!(cpu::has(SSE2));
+    }
+    if cfg!(target_arch = "aarch64") {
+        test!(cpu::has(NEON));
+    }
+}
+
+#[test]
+fn features_debug() {
+    let mut buf = [0; 64];
+    let mut buf = Vec::with_pool(OncePool::new(buf.as_mut()));
+    write!(&mut buf, "{:?}", NONE);
+    test!(&*buf == "NONE");
+
+    buf.truncate(0);
+    write!(&mut buf, "{:?}", SSE2 | SSE3);
+    test!(&*buf == "SSE2,SSE3");
+}
@@ -0,0 +1,68 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::alloc::{Heap, MemPool};
+
+#[test]
+fn sort_stable() {
+    let mut x = [0u32; 500];
+    for i in 0..x.len() {
+        x[i] = (i as u32).wrapping_mul(2654435761);
+    }
+    x.sort_stable().unwrap();
+    for i in 1..x.len() {
+        test!(x[i - 1] <= x[i]);
+    }
+}
+
+#[test]
+fn sort_stable_by_key() {
+    // The second field records the original position.
+    let mut x = [(0u32, 0u32); 300];
+    for i in 0..x.len() {
+        x[i] = ((i as u32).wrapping_mul(2654435761) % 5, i as u32);
+    }
+    x.sort_stable_by_key(|e| e.0).unwrap();
+    for i in 1..x.len() {
+        test!(x[i - 1].0 < x[i].0 || (x[i - 1].0 == x[i].0 && x[i - 1].1 < x[i].1));
+    }
+}
+
+/// A pool that counts its allocations.
+struct Counting {
+    heap: Heap,
+    allocs: usize,
+}
+
+impl MemPool for Counting {
+    unsafe fn alloc(&mut self, size: usize, alignment: usize) -> Result<*mut d8> {
+        self.allocs += 1;
+        self.heap.alloc(size, alignment)
+    }
+
+    unsafe fn realloc(&mut self, ptr: *mut d8, cur_size: usize, new_size: usize,
+                      alignment: usize) -> Result<*mut d8> {
+        self.heap.realloc(ptr, cur_size, new_size, alignment)
+    }
+
+    unsafe fn free(&mut self, ptr: *mut d8, size: usize, alignment: usize) {
+        self.heap.free(ptr, size, alignment)
+    }
+}
+
+#[test]
+fn sort_stable_with_pool() {
+    // More elements than are sorted by insertion sort so that the pool is used. The
+    // second field records the original position.
+    let mut x = [(0u32, 0u32); 100];
+    for i in 0..x.len() {
+        x[i] = ((i as u32).wrapping_mul(2654435761) % 7, i as u32);
+    }
+    let mut pool = Counting { heap: Heap::out_of(()), allocs: 0 };
+    x.sort_stable_with_pool(&mut pool, |a, b| a.0.cmp(&b.0)).unwrap();
+    test!(pool.allocs > 0);
+    for i in 1..x.len() {
+        test!(x[i - 1].0 < x[i].0 || (x[i - 1].0 == x[i].0 && x[i - 1].1 < x[i].1));
+    }
+}
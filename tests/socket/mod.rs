@@ -1,3 +1,183 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::alloc::{OncePool};
+use std::parse::{Parsable};
+use std::{mem};
+use std::socket::{Socket};
+use std::socket::cmsg::{CMsg};
+use std::socket::flags::{SOCK_NONE};
+use std::socket::msg::{MSG_NONE};
+use std::time::{REAL};
+use std::socket::ip::{
+    Ipv4Addr, Ipv6Addr, IpAddr, Ipv4SockAddr, Ipv6SockAddr, Ipv4Net, Ipv6Net,
+};
+
+macro_rules! fmt_eq {
+    ($val:expr, $res:expr) => {{
+        let mut buf = [0; 64];
+        let mut buf = Vec::with_pool(OncePool::new(buf.as_mut()));
+        write!(&mut buf, "{}", $val);
+        test!(&*buf == $res);
+    }}
+}
+
+#[test]
+fn parse_ipv4() {
+    test!(Ipv4Addr::parse_bytes(b"10.0.0.1").unwrap() == Ipv4Addr(10, 0, 0, 1));
+    test!(Ipv4Addr::parse_bytes_init(b"10.0.0.1:80").unwrap() ==
+          (Ipv4Addr(10, 0, 0, 1), 8));
+    test!(Ipv4Addr::parse_bytes(b"10.0.0").is_err());
+    test!(Ipv4Addr::parse_bytes(b"10.0.0.256").is_err());
+}
+
+#[test]
+fn parse_ipv6() {
+    test!(Ipv6Addr::parse_bytes(b"::").unwrap() == Ipv6Addr::unspecified());
+    test!(Ipv6Addr::parse_bytes(b"::1").unwrap() == Ipv6Addr::loopback());
+    test!(Ipv6Addr::parse_bytes(b"2001:DB8::8:800:200c:417a").unwrap() ==
+          Ipv6Addr(0x2001, 0xdb8, 0, 0, 0x8, 0x800, 0x200c, 0x417a));
+    test!(Ipv6Addr::parse_bytes(b"::ffff:192.0.2.1").unwrap() ==
+          Ipv6Addr(0, 0, 0, 0, 0, 0xffff, 0xc000, 0x0201));
+    test!(Ipv6Addr::parse_bytes(b"1::2::3").is_err());
+    test!(Ipv6Addr::parse_bytes(b"1:2:3:4:5:6:7:8:9").is_err());
+    test!(Ipv6Addr::parse_bytes(b"1.2.3.4::").is_err());
+    test!(Ipv6Addr::parse_bytes(b"::1.2.3.4:1").is_err());
+    test!(IpAddr::parse_bytes(b"fe80::1").unwrap() ==
+          IpAddr::V6(Ipv6Addr(0xfe80, 0, 0, 0, 0, 0, 0, 1)));
+}
+
+#[test]
+fn parse_ip() {
+    test!(IpAddr::parse(b"10.0.0.1") == Some(IpAddr::V4(Ipv4Addr(10, 0, 0, 1))));
+    test!(IpAddr::parse(b"fe80::1%eth0") ==
+          Some(IpAddr::V6(Ipv6Addr(0xfe80, 0, 0, 0, 0, 0, 0, 1))));
+    test!(IpAddr::parse(b"10.0.0.1%eth0").is_none());
+    test!(IpAddr::parse(b"10.0.0.1:80").is_none());
+    test!(IpAddr::parse(b"::1 ").is_none());
+}
+
+#[test]
+fn display_ipv6() {
+    fmt_eq!(Ipv6Addr::unspecified(), "::");
+    fmt_eq!(Ipv6Addr::loopback(), "::1");
+    fmt_eq!(Ipv6Addr(0x2001, 0xdb8, 0, 0, 1, 0, 0, 1), "2001:db8::1:0:0:1");
+    fmt_eq!(Ipv6Addr(0x2001, 0xdb8, 0, 1, 1, 1, 1, 1), "2001:db8:0:1:1:1:1:1");
+    fmt_eq!(Ipv6Addr(0x2001, 0, 0, 1, 0, 0, 0, 1), "2001:0:0:1::1");
+    fmt_eq!(Ipv6Addr(0, 0, 0, 0, 0, 0xffff, 0xc000, 0x0201), "::ffff:192.0.2.1");
+}
+
+#[test]
+fn parse_sock_addr() {
+    let mut buf = [0; 32];
+    {
+        let addr = Ipv4SockAddr::parse(&mut buf, b"10.0.0.1:8080").unwrap();
+        test!(addr.addr() == Ipv4Addr(10, 0, 0, 1));
+        test!(addr.port() == 8080);
+        fmt_eq!(addr, "10.0.0.1:8080");
+    }
+    test!(Ipv4SockAddr::parse(&mut buf, b"10.0.0.1:65536").is_err());
+    {
+        let addr = Ipv6SockAddr::parse(&mut buf, b"[fe80::1%1]:53").unwrap();
+        test!(addr.addr() == Ipv6Addr(0xfe80, 0, 0, 0, 0, 0, 0, 1));
+        test!(addr.scope_id() == 1);
+        test!(addr.port() == 53);
+        fmt_eq!(addr, "[fe80::1%1]:53");
+    }
+    test!(Ipv6SockAddr::parse(&mut buf, b"[::1]:53").unwrap().scope_id() == 0);
+    test!(Ipv6SockAddr::parse(&mut buf, b"::1:53").is_err());
+    test!(Ipv6SockAddr::parse(&mut buf, b"[::1%lo]:53").is_ok());
+}
+
+#[test]
+fn ipv4_net() {
+    let net = Ipv4Net::parse_bytes(b"192.168.1.7/16").unwrap();
+    test!(net.prefix() == 16);
+    test!(net.network() == Ipv4Addr(192, 168, 0, 0));
+    test!(net.netmask() == Ipv4Addr(255, 255, 0, 0));
+    test!(net.broadcast() == Ipv4Addr(192, 168, 255, 255));
+    test!(net.contains(Ipv4Addr(192, 168, 200, 1)));
+    test!(!net.contains(Ipv4Addr(192, 169, 0, 1)));
+    test!(net.contains_net(&Ipv4Net::new(Ipv4Addr(192, 168, 4, 0), 24).unwrap()));
+    fmt_eq!(net, "192.168.1.7/16");
+    test!(Ipv4Net::parse_bytes(b"10.0.0.0/33").is_err());
+
+    let mut subnets = net.subnets(18).unwrap();
+    test!(subnets.next().unwrap().network() == Ipv4Addr(192, 168, 0, 0));
+    test!(subnets.next().unwrap().network() == Ipv4Addr(192, 168, 64, 0));
+    test!(subnets.next().unwrap().network() == Ipv4Addr(192, 168, 128, 0));
+    test!(subnets.next().unwrap().network() == Ipv4Addr(192, 168, 192, 0));
+    test!(subnets.next().is_none());
+    test!(net.subnets(8).is_err());
+}
+
+#[test]
+fn ipv6_net() {
+    let net = Ipv6Net::parse_bytes(b"2001:db8::1/32").unwrap();
+    test!(net.network() == Ipv6Addr(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+    test!(net.netmask() == Ipv6Addr(0xffff, 0xffff, 0, 0, 0, 0, 0, 0));
+    test!(net.contains(Ipv6Addr(0x2001, 0xdb8, 0xffff, 0, 0, 0, 0, 1)));
+    test!(!net.contains(Ipv6Addr(0x2001, 0xdb9, 0, 0, 0, 0, 0, 1)));
+    fmt_eq!(net, "2001:db8::1/32");
+
+    let mut subnets = net.subnets(34).unwrap();
+    test!(subnets.next().unwrap().network() == Ipv6Addr(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+    test!(subnets.next().unwrap().network() ==
+          Ipv6Addr(0x2001, 0xdb8, 0x4000, 0, 0, 0, 0, 0));
+    subnets.next();
+    test!(subnets.next().unwrap().network() ==
+          Ipv6Addr(0x2001, 0xdb8, 0xc000, 0, 0, 0, 0, 0));
+    test!(subnets.next().is_none());
+}
+
+#[test]
+fn ipv6_sock_addr_port() {
+    let mut buf = [0; 32];
+    let addr = Ipv6SockAddr::from_addr_port(&mut buf, Ipv6Addr::loopback(),
+                                            0x1234).unwrap();
+    test!(addr.port() == 0x1234);
+    {
+        let bytes: &[u8] = addr.as_ref();
+        test!(bytes[2] == 0x12);
+        test!(bytes[3] == 0x34);
+    }
+    addr.set_port(0x5678);
+    test!(addr.port() == 0x5678);
+}
+
+#[test]
+fn timestamp() {
+    let sock = Socket::ipv4_datagram(SOCK_NONE).unwrap();
+    let mut addr = [0; 32];
+    let local = Ipv4SockAddr::from_addr_port(&mut addr, Ipv4Addr(127, 0, 0, 1),
+                                             0).unwrap();
+    sock.bind(local).unwrap();
+    sock.set_timestamp(true).unwrap();
+    test!(sock.is_timestamp().unwrap());
+    let mut buf = [0; 32];
+    let local = sock.get_addr(&mut buf).unwrap();
+
+    let before = REAL.get_time().unwrap();
+    let sender = Socket::ipv4_datagram(SOCK_NONE).unwrap();
+    sender.send_to(b"x", &*local, MSG_NONE).unwrap();
+
+    let mut data = [0; 8];
+    let mut ctrl = [0; 128];
+    let ctrl = mem::align_for_mut::<usize>(&mut ctrl);
+    let (len, _, cmsgs, _) = sock.recv_msg(&mut [&mut data[..]], &mut [], ctrl,
+                                           MSG_NONE).unwrap();
+    test!(len == 1);
+    let after = REAL.get_time().unwrap();
+
+    let mut found = false;
+    for cmsg in cmsgs {
+        if let CMsg::Timestamp(ts) = cmsg {
+            let time = ts.to_time();
+            test!(time.seconds >= before.seconds && time.seconds <= after.seconds);
+            test!(time.nanoseconds >= 0 && time.nanoseconds < 1_000_000_000);
+            found = true;
+        }
+    }
+    test!(found);
+}
@@ -184,6 +184,24 @@ fn timerfd_create_settime_gettime() {
     test!(time4.it_interval == time2.it_interval);
 }
 
+#[test]
+fn timerfd_after_2038() {
+    // The expiration lies after 2038 and the remaining time doesn't fit into 32 bits.
+    let timer = rv!(syscall::timerfd_create(cty::CLOCK_REALTIME, 0));
+    let zero = cty::timespec { tv_sec: 0, tv_nsec: 0 };
+    let when = cty::timespec { tv_sec: 5_000_000_000, tv_nsec: 0 };
+    let spec = cty::itimerspec { it_interval: zero, it_value: when };
+    let rv = syscall::timerfd_settime(timer, cty::TFD_TIMER_ABSTIME, &spec, None);
+    if rv == -cty::EOVERFLOW {
+        // Old kernel without the time64 system calls.
+        return;
+    }
+    rv!(rv);
+    let mut cur: cty::itimerspec = mem::zeroed();
+    rv!(syscall::timerfd_gettime(timer, &mut cur));
+    test!(cur.it_value.tv_sec > 1 << 31);
+}
+
 #[test]
 fn epoll_create() {
     // TODO
@@ -284,6 +302,23 @@ fn fstatat() {
     // TODO
 }
 
+#[test]
+fn statx() {
+    let root = "/\0".try_as_ref().unwrap():&CStr;
+    let mut stat = mem::zeroed();
+    rv!(syscall::fstatat(cty::AT_FDCWD, root, &mut stat, 0));
+    let mut stx: cty::statx = mem::zeroed();
+    let rv = syscall::statx(cty::AT_FDCWD, root, 0, cty::STATX_BASIC_STATS, &mut stx);
+    if rv == -cty::ENOSYS {
+        return;
+    }
+    rv!(rv);
+    test!(stx.stx_mask & cty::STATX_BASIC_STATS == cty::STATX_BASIC_STATS);
+    test!(stx.stx_ino == stat.st_ino as u64);
+    test!(stx.stx_mode as u32 == stat.st_mode as u32);
+    test!(stx.stx_mtime.tv_sec == stat.st_mtime as i64);
+}
+
 #[test]
 fn faccessat() {
     // TODO
@@ -411,7 +446,40 @@ fn clock_getres() {
 
 #[test]
 fn clock_gettime() {
-    // TODO
+    let mut a = mem::zeroed();
+    let mut b = mem::zeroed();
+    rv!(syscall::clock_gettime(cty::CLOCK_MONOTONIC, &mut a));
+    rv!(syscall::clock_gettime(cty::CLOCK_MONOTONIC, &mut b));
+    test!(b.tv_sec > a.tv_sec || (b.tv_sec == a.tv_sec && b.tv_nsec >= a.tv_nsec));
+    test!(syscall::clock_gettime(-1, &mut a) == -cty::EINVAL);
+}
+
+#[test]
+fn gettimeofday() {
+    let mut ts: cty::timespec = mem::zeroed();
+    let mut tv: cty::__kernel_sock_timeval = mem::zeroed();
+    let mut tz: cty::timezone = mem::zeroed();
+    rv!(syscall::clock_gettime(cty::CLOCK_REALTIME, &mut ts));
+    rv!(syscall::gettimeofday(&mut tv, Some(&mut tz)));
+    test!(tv.tv_sec >= ts.tv_sec && tv.tv_sec - ts.tv_sec <= 1);
+    test!(tv.tv_usec >= 0 && tv.tv_usec < 1_000_000);
+}
+
+#[cfg(all(no_libc, target_arch = "x86_64"))]
+#[test]
+fn vdso() {
+    // The x86_64 vDSO has always provided these functions.
+    let vdso = syscall::vdso::get();
+    test!(vdso.clock_gettime != 0);
+    test!(vdso.getcpu != 0);
+}
+
+#[test]
+fn time() {
+    let mut ts: cty::timespec = mem::zeroed();
+    rv!(syscall::clock_gettime(cty::CLOCK_REALTIME, &mut ts));
+    let t = syscall::time();
+    test!(t >= ts.tv_sec && t - ts.tv_sec <= 1);
 }
 
 #[test]
@@ -1081,7 +1149,10 @@ fn unshare() {
 
 #[test]
 fn getcpu() {
-    // TODO
+    let mut cpu = !0;
+    let mut node = !0;
+    rv!(syscall::getcpu(Some(&mut cpu), Some(&mut node)));
+    test!(cpu != !0 && node != !0);
 }
 
 #[test]
@@ -1,3 +1,27 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::{cty, syscall, process};
+use std::error::{NotPermitted, BadFileDesc};
+use std::thread::seccomp::{Filter, Action, Cmp, ArgCmp, Instruction};
+
+#[test]
+fn seccomp() {
+    let ppid = syscall::getpid();
+    let child = process::fork(|| {
+        let mut buf = [Instruction::new(); 32];
+        let mut filter = Filter::new(&mut buf).unwrap();
+        filter.add(cty::__NR_getppid, Action::Errno(NotPermitted)).unwrap();
+        filter.add_if(cty::__NR_close, &[ArgCmp(0, Cmp::Eq(1000))],
+                      Action::Errno(NotPermitted)).unwrap();
+        filter.install(Action::Allow).unwrap();
+
+        test!(syscall::getppid() == -NotPermitted.0);
+        test!(syscall::close(1000) == -NotPermitted.0);
+        test!(syscall::close(1001) == -BadFileDesc.0);
+        test!(syscall::getpid() != ppid);
+    }).unwrap();
+    test!(process::wait_id(child, process::WAIT_EXITED).unwrap()
+                                        == process::ChildStatus::Exited(0));
+}
@@ -1,3 +1,130 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::time::{DateTime, Weekday, Zone, Time, RFC3339, RFC2822, HTTP_DATE};
+use std::vec::{Vec};
+use std::{env, file, fs, thread};
+use std::process::clone::{CLONE_NEWMOUNT, CLONE_NEWUSER};
+use std::fs::flags::{MOUNT_NONE, MOUNT_REC, MOUNT_PRIVATE};
+
+fn format(date: &DateTime, fmt: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    date.format(&mut buf, fmt).unwrap();
+    buf
+}
+
+#[test]
+fn format_fixed() {
+    let zone = Zone::fixed(-(5 * 60 + 30) * 60).unwrap();
+    let date = zone.expand(Time { seconds: 784111777, nanoseconds: 123_456_789 });
+    test!(date.weekday == Weekday::Sunday);
+    test!(&format(&date, RFC3339)[..] == &b"1994-11-06T03:19:37-05:30"[..]);
+    test!(&format(&date, RFC2822)[..] == &b"Sun, 06 Nov 1994 03:19:37 -0530"[..]);
+    test!(&format(&date, b"%A %e %B %j %I%p %3f %%")[..] ==
+          &b"Sunday  6 November 310 03AM 123 %"[..]);
+    let mut buf: Vec<u8> = Vec::new();
+    test!(date.format(&mut buf, b"%Q").is_err());
+}
+
+#[test]
+fn parse_rfc3339() {
+    let date = DateTime::parse_rfc3339(b"1985-04-12T23:20:50.52+01:00").unwrap();
+    test!(date.year == 1985);
+    test!(date.month == 4);
+    test!(date.day == 12);
+    test!(date.nanosecond == 520_000_000);
+    test!(date.offset == 3600);
+    test!(date.weekday == Weekday::Friday);
+    let (_, time) = Zone::fixed(date.offset).unwrap().compact(date);
+    test!(time.seconds == 482192450);
+    test!(time.nanoseconds == 520_000_000);
+
+    test!(DateTime::parse_rfc3339(b"1985-02-29T00:00:00Z").is_err());
+    test!(DateTime::parse_rfc3339(b"1985-04-12T23:20:50").is_err());
+}
+
+#[test]
+fn parse_rfc2822() {
+    let date = DateTime::parse_rfc2822(b"Fri, 21 Nov 1997 09:55:06 -0600").unwrap();
+    test!(date.offset == -6 * 3600);
+    test!(&format(&date, RFC2822)[..] == &b"Fri, 21 Nov 1997 09:55:06 -0600"[..]);
+    let date = DateTime::parse_rfc2822(b"21 Nov 97 09:55 EST").unwrap();
+    test!(date.year == 1997);
+    test!(date.offset == -5 * 3600);
+    test!(DateTime::parse_rfc2822(b"Sat, 21 Nov 1997 09:55:06 -0600").is_err());
+    test!(DateTime::parse_rfc2822(b"Fri, 21 Nov 1997 09:55:06 +9959").is_err());
+    test!(DateTime::parse_rfc2822(b"Fri, 21 Nov 1997 09:55:06 +2360").is_err());
+}
+
+#[test]
+fn parse_http_date() {
+    let dates: [&[u8]; 3] = [
+        b"Sun, 06 Nov 1994 08:49:37 GMT",
+        b"Sunday, 06-Nov-94 08:49:37 GMT",
+        b"Sun Nov  6 08:49:37 1994",
+    ];
+    for i in 0..dates.len() {
+        let date = DateTime::parse_http_date(dates[i]).unwrap();
+        test!(&format(&date, HTTP_DATE)[..] == dates[0]);
+        let (_, time) = Zone::fixed(0).unwrap().compact(date);
+        test!(time.seconds == 784111777);
+    }
+}
+
+#[test]
+fn rule() {
+    let zone = Zone::from_rule(b"CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+    let date = zone.expand(Time { seconds: 1719835200, nanoseconds: 0 });
+    test!(date.offset == 7200);
+    test!(date.summer_time);
+    test!(date.hour == 14);
+    let (_, time) = zone.compact(date);
+    test!(time.seconds == 1719835200);
+
+    let date = zone.expand(Time { seconds: 1705320000, nanoseconds: 0 });
+    test!(date.offset == 3600);
+    test!(!date.summer_time);
+
+    // 2024-03-31T01:00:00Z
+    test!(zone.expand(Time { seconds: 1711846799, nanoseconds: 0 }).offset == 3600);
+    test!(zone.expand(Time { seconds: 1711846800, nanoseconds: 0 }).offset == 7200);
+
+    test!(Zone::from_rule(b"EST5").unwrap().expand(Time::seconds(0)).offset == -18000);
+    test!(Zone::from_rule(b"CET-1CEST,M3.5.0").is_err());
+    test!(Zone::from_rule(b"X1").is_err());
+}
+
+#[test]
+fn local_absolute_path() {
+    ::in_child(|| {
+        const TOKYO: &'static str = "/usr/share/zoneinfo/Asia/Tokyo";
+        if !file::exists(TOKYO).unwrap() {
+            ::skip_child("local_absolute_path", "Asia/Tokyo is not installed");
+        }
+        env::set_var("TZ", TOKYO).unwrap();
+        test!(Zone::local().unwrap().expand(Time::seconds(0)).offset == 9 * 3600);
+    });
+}
+
+#[test]
+fn local_utc_without_zoneinfo() {
+    ::in_child(|| {
+        if thread::unshare(CLONE_NEWMOUNT).is_err() {
+            if thread::unshare(CLONE_NEWUSER | CLONE_NEWMOUNT).is_err() {
+                ::skip_child("local_utc_without_zoneinfo",
+                             "cannot create a mount namespace");
+            }
+        }
+        // Hide the zoneinfo directory behind an empty tmpfs.
+        const ZONEINFO: &'static str = "/usr/share/zoneinfo";
+        if file::exists(ZONEINFO).unwrap() {
+            fs::mount("none", "/", "", MOUNT_REC | MOUNT_PRIVATE, "").unwrap();
+            fs::mount("tmpfs", ZONEINFO, "tmpfs", MOUNT_NONE, "").unwrap();
+        }
+        env::set_var("TZ", "UTC").unwrap();
+        let date = Zone::local().unwrap().expand(Time::seconds(3600));
+        test!(date.offset == 0);
+        test!(date.hour == 1);
+    });
+}
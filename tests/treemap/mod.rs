@@ -0,0 +1,64 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::treemap::{TreeMap, TreeSet, Entry};
+
+#[test]
+fn map() {
+    let mut map: TreeMap<u32, u32> = TreeMap::new();
+    for &i in [5, 1, 4, 2, 3].iter() {
+        map.set(i, 10 * i);
+    }
+    map.set(4, 0);
+    test!(map.size() == 5);
+    test!(map.get(&4).unwrap() == &0);
+    test!(map.get(&6).is_none());
+    test!(map.first().unwrap() == (&1, &10));
+    test!(map.last().unwrap() == (&5, &50));
+    test!(map.remove(&1).unwrap().1 == 10);
+    test!(map.size() == 4);
+
+    let mut keys = map.keys();
+    test!(keys.next().unwrap() == &2);
+    test!(keys.next().unwrap() == &3);
+
+    let mut rev = map.iter_rev();
+    test!(rev.next().unwrap() == (&5, &50));
+    test!(rev.next().unwrap() == (&4, &0));
+
+    let mut range = map.range(&3, &5);
+    test!(range.next().unwrap() == (&3, &30));
+    test!(range.next().unwrap() == (&4, &0));
+    test!(range.next().is_none());
+
+    test!(map.upper_bound(&3).next().unwrap() == (&4, &0));
+    test!(map.lower_bound(&6).next().is_none());
+}
+
+#[test]
+fn entry() {
+    let mut map: TreeMap<u32, u32> = TreeMap::new();
+    *map.entry(&1).unwrap().or_insert(1, 0) += 1;
+    *map.entry(&1).unwrap().or_insert(1, 0) += 1;
+    test!(map.get(&1).unwrap() == &2);
+    match map.entry(&1).unwrap() {
+        Entry::Occupied(e) => { e.remove(); },
+        _ => abort!(),
+    }
+    test!(map.size() == 0);
+}
+
+#[test]
+fn set() {
+    let mut set: TreeSet<u32> = TreeSet::new();
+    test!(set.insert(2).unwrap());
+    test!(set.insert(1).unwrap());
+    test!(!set.insert(2).unwrap());
+    test!(set.contains(&1));
+    test!(set.first().unwrap() == &1);
+    let mut iter = set.into_iter();
+    test!(iter.next().unwrap() == &1);
+    test!(iter.next().unwrap() == &2);
+    test!(iter.next().is_none());
+}
@@ -0,0 +1,100 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::uring::{Uring, Entry, Timeout, CURRENT_POSITION};
+use std::error::{TimerExpired, WouldBlock};
+use std::time::{Time};
+use std::pipe::{Pipe};
+use std::pipe::flags::{PIPE_NONE};
+
+#[test]
+fn nop() {
+    let mut ring = Uring::new(4).unwrap();
+    let mut entry = Entry::nop();
+    entry.set_user_data(7);
+    unsafe { ring.push(&entry).unwrap(); }
+    test!(ring.submit_and_wait(1).unwrap() == 1);
+    let c = ring.next_completion().unwrap();
+    test!(c.user_data() == 7);
+    test!(c.result().unwrap() == 0);
+    test!(ring.next_completion().is_none());
+}
+
+#[test]
+fn write_read() {
+    let (write, read) = Pipe::new(PIPE_NONE).unwrap();
+    let mut ring = Uring::new(4).unwrap();
+    let mut buf = [0; 5];
+    let mut w = Entry::write(&write, b"hello", CURRENT_POSITION);
+    w.set_user_data(1);
+    w.link();
+    let mut r = Entry::read(&read, &mut buf, CURRENT_POSITION);
+    r.set_user_data(2);
+    unsafe {
+        ring.push(&w).unwrap();
+        ring.push(&r).unwrap();
+    }
+    test!(ring.submit_and_wait(2).unwrap() == 2);
+    let mut n = 0;
+    for c in ring.completions() {
+        test!(c.result().unwrap() == 5);
+        n += 1;
+    }
+    test!(n == 2);
+    test!(&buf[..] == "hello");
+}
+
+#[test]
+fn timeout() {
+    let mut ring = Uring::new(4).unwrap();
+    let timeout = Timeout::new(Time::milliseconds(1));
+    let mut entry = Entry::timeout(&timeout, 0, false);
+    entry.set_user_data(3);
+    unsafe { ring.push(&entry).unwrap(); }
+    test!(ring.submit_and_wait(1).unwrap() == 1);
+    let c = ring.next_completion().unwrap();
+    test!(c.user_data() == 3);
+    test!(c.result() == Err(TimerExpired));
+}
+
+#[test]
+fn timeout_count() {
+    let mut ring = Uring::new(4).unwrap();
+    let timeout = Timeout::new(Time::seconds(10));
+    let mut t = Entry::timeout(&timeout, 1, false);
+    t.set_user_data(1);
+    let mut nop = Entry::nop();
+    nop.set_user_data(2);
+    unsafe {
+        ring.push(&t).unwrap();
+        ring.push(&nop).unwrap();
+    }
+    test!(ring.submit_and_wait(2).unwrap() == 2);
+    let mut n = 0;
+    for c in ring.completions() {
+        test!(c.result().unwrap() == 0);
+        n += 1;
+    }
+    test!(n == 2);
+}
+
+#[test]
+fn full_queue() {
+    let mut ring = Uring::new(2).unwrap();
+    let entries = ring.sq_entries();
+    let entry = Entry::nop();
+    for _ in 0..entries {
+        unsafe { ring.push(&entry).unwrap(); }
+    }
+    test!(ring.free_entries() == 0);
+    test!(unsafe { ring.push(&entry) } == Err(WouldBlock));
+    test!(ring.submit_and_wait(entries).unwrap() == entries as usize);
+    test!(ring.free_entries() == entries);
+    unsafe { ring.push(&entry).unwrap(); }
+    let mut n = 0;
+    for _ in ring.completions() {
+        n += 1;
+    }
+    test!(n == entries);
+}